@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AbiParam {
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<AbiParam>,
+    #[serde(default)]
+    pub indexed: bool,
+    #[serde(rename = "internalType", default, skip_serializing_if = "Option::is_none")]
+    pub internal_type: Option<String>,
+}
+
+impl AbiParam {
+    // Canonical type as used in signatures, expanding tuples: "(address,uint256)[]"
+    pub fn canonical_type(&self) -> String {
+        match self.kind.strip_prefix("tuple") {
+            Some(suffix) => {
+                let inner: Vec<String> = self.components.iter().map(AbiParam::canonical_type).collect();
+                format!("({}){}", inner.join(","), suffix)
+            }
+            None => self.kind.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AbiItem {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub inputs: Vec<AbiParam>,
+    #[serde(default)]
+    pub outputs: Vec<AbiParam>,
+    #[serde(rename = "stateMutability", default, skip_serializing_if = "Option::is_none")]
+    pub state_mutability: Option<String>,
+    #[serde(default)]
+    pub anonymous: bool,
+}
+
+impl AbiItem {
+    // "transfer(address,uint256)" — the string that gets hashed for selectors/topics
+    pub fn signature(&self) -> String {
+        let inputs: Vec<String> = self.inputs.iter().map(AbiParam::canonical_type).collect();
+        format!("{}({})", self.name, inputs.join(","))
+    }
+
+    pub fn is_function(&self) -> bool {
+        self.kind == "function"
+    }
+
+    pub fn is_event(&self) -> bool {
+        self.kind == "event"
+    }
+}
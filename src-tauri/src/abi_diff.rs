@@ -0,0 +1,323 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::abi::{AbiItem, AbiParam};
+use crate::artifacts;
+use crate::error::AppError;
+use crate::store;
+
+const SNAPSHOTS_FILE: &str = "abi-snapshots.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AbiSnapshot {
+    pub contract: String,
+    pub label: String,
+    pub created_at: u64,
+    pub abi: Vec<AbiItem>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AbiBaseline {
+    // Latest snapshot of the contract when no label is given
+    Snapshot { label: Option<String> },
+    // ABI Ignition stored alongside a deployment, e.g. future id "LockModule#Lock"
+    Deployment { deployment_id: String, future_id: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AbiChange {
+    pub item: String,
+    pub signature: String,
+    pub change: ChangeKind,
+    pub breaking: bool,
+    pub detail: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionBump {
+    None,
+    Minor,
+    Major,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AbiDiff {
+    pub contract: String,
+    pub baseline: String,
+    pub bump: VersionBump,
+    pub changes: Vec<AbiChange>,
+}
+
+fn load_snapshots(project_path: &Path) -> Result<Vec<AbiSnapshot>, String> {
+    store::load_json(project_path, SNAPSHOTS_FILE)
+}
+
+fn current_abi(project_path: &Path, contract: &str) -> Result<Vec<AbiItem>, String> {
+    artifacts::index(project_path)
+        .get(contract)
+        .map(|a| a.abi.clone())
+        .ok_or_else(|| format!("No compiled artifact found for {}", contract))
+}
+
+fn deployed_abi(project_path: &Path, deployment_id: &str, future_id: &str) -> Result<Vec<AbiItem>, String> {
+    #[derive(Deserialize)]
+    struct IgnitionArtifact {
+        abi: Vec<AbiItem>,
+    }
+
+    let path = project_path
+        .join("ignition")
+        .join("deployments")
+        .join(deployment_id)
+        .join("artifacts")
+        .join(format!("{}.json", future_id));
+    let content = fs::read_to_string(&path)
+        .map_err(|_| format!("No artifact recorded for {} in deployment {}", future_id, deployment_id))?;
+    serde_json::from_str::<IgnitionArtifact>(&content)
+        .map(|a| a.abi)
+        .map_err(|e| format!("Failed to parse deployed artifact: {}", e))
+}
+
+fn types(params: &[AbiParam]) -> Vec<String> {
+    params.iter().map(AbiParam::canonical_type).collect()
+}
+
+// Key under which the same item is matched across versions
+fn item_key(item: &AbiItem) -> String {
+    match item.kind.as_str() {
+        "constructor" | "fallback" | "receive" => item.kind.clone(),
+        _ => format!("{} {}", item.kind, item.signature()),
+    }
+}
+
+fn mutability(item: &AbiItem) -> &str {
+    item.state_mutability.as_deref().unwrap_or("nonpayable")
+}
+
+// Changes to an item that kept its signature
+fn compare_item(old: &AbiItem, new: &AbiItem) -> Option<(bool, String)> {
+    let mut notes = Vec::new();
+    let mut breaking = false;
+
+    if old.is_function() && types(&old.outputs) != types(&new.outputs) {
+        breaking = true;
+        notes.push(format!("returns ({}) instead of ({})", types(&new.outputs).join(","), types(&old.outputs).join(",")));
+    }
+
+    if old.is_event() {
+        let indexed = |item: &AbiItem| item.inputs.iter().map(|p| p.indexed).collect::<Vec<_>>();
+        if indexed(old) != indexed(new) || old.anonymous != new.anonymous {
+            // Topic layout changes break every log filter and decoder
+            breaking = true;
+            notes.push("indexed parameters changed".to_string());
+        }
+    }
+
+    let (before, after) = (mutability(old), mutability(new));
+    if before != after {
+        // Losing payable rejects calls that send value; gaining state writes breaks eth_call users
+        let tightened = (before == "payable" && after != "payable")
+            || (matches!(before, "view" | "pure") && matches!(after, "nonpayable" | "payable"));
+        breaking |= tightened && old.kind != "constructor";
+        notes.push(format!("state mutability {} -> {}", before, after));
+    }
+
+    if old.kind == "constructor" && types(&old.inputs) != types(&new.inputs) {
+        notes.push(format!("constructor arguments ({}) -> ({})", types(&old.inputs).join(","), types(&new.inputs).join(",")));
+    }
+
+    (!notes.is_empty()).then(|| (breaking, notes.join("; ")))
+}
+
+pub fn diff(old: &[AbiItem], new: &[AbiItem]) -> Vec<AbiChange> {
+    let old_items: BTreeMap<String, &AbiItem> = old.iter().map(|i| (item_key(i), i)).collect();
+    let new_items: BTreeMap<String, &AbiItem> = new.iter().map(|i| (item_key(i), i)).collect();
+    let mut changes = Vec::new();
+    let mut replaced = BTreeSet::new();
+
+    for (key, old_item) in &old_items {
+        match new_items.get(key) {
+            Some(new_item) => {
+                if let Some((breaking, detail)) = compare_item(old_item, new_item) {
+                    changes.push(AbiChange {
+                        item: old_item.kind.clone(),
+                        signature: old_item.signature(),
+                        change: ChangeKind::Changed,
+                        breaking,
+                        detail: Some(detail),
+                    });
+                }
+            }
+            None => {
+                // Same name with new parameter types reads better as a change than remove+add
+                let renamed = new.iter().find(|n| {
+                    n.kind == old_item.kind && n.name == old_item.name && !old_items.contains_key(&item_key(n))
+                });
+                if let Some(n) = renamed {
+                    replaced.insert(item_key(n));
+                }
+                changes.push(AbiChange {
+                    item: old_item.kind.clone(),
+                    signature: old_item.signature(),
+                    change: if renamed.is_some() { ChangeKind::Changed } else { ChangeKind::Removed },
+                    // Custom errors only affect revert decoding, not callers
+                    breaking: old_item.kind != "error",
+                    detail: renamed.map(|n| format!("now {}", n.signature())),
+                });
+            }
+        }
+    }
+
+    for (key, new_item) in &new_items {
+        if !old_items.contains_key(key) && !replaced.contains(key) {
+            changes.push(AbiChange {
+                item: new_item.kind.clone(),
+                signature: new_item.signature(),
+                change: ChangeKind::Added,
+                breaking: false,
+                detail: None,
+            });
+        }
+    }
+
+    changes
+}
+
+#[tauri::command]
+pub async fn snapshot_abi(project_path: String, contract: String, label: Option<String>) -> Result<AbiSnapshot, AppError> {
+    let root = Path::new(&project_path);
+    let abi = current_abi(root, &contract)?;
+    let created_at = store::timestamp_millis();
+    let snapshot = AbiSnapshot {
+        label: label.filter(|l| !l.trim().is_empty()).unwrap_or_else(|| created_at.to_string()),
+        contract,
+        created_at,
+        abi,
+    };
+
+    let mut snapshots = load_snapshots(root)?;
+    snapshots.retain(|s| !(s.contract == snapshot.contract && s.label == snapshot.label));
+    snapshots.push(snapshot.clone());
+    store::save_json(root, SNAPSHOTS_FILE, &snapshots)?;
+    Ok(snapshot)
+}
+
+#[tauri::command]
+pub async fn list_abi_snapshots(project_path: String, contract: Option<String>) -> Result<Vec<AbiSnapshot>, AppError> {
+    let mut snapshots = load_snapshots(Path::new(&project_path))?;
+    snapshots.retain(|s| contract.as_ref().is_none_or(|c| &s.contract == c));
+    snapshots.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+    Ok(snapshots)
+}
+
+#[tauri::command]
+pub async fn diff_abi(project_path: String, contract: String, baseline: AbiBaseline) -> Result<AbiDiff, AppError> {
+    let root = Path::new(&project_path);
+    let current = current_abi(root, &contract)?;
+
+    let (baseline_name, old) = match &baseline {
+        AbiBaseline::Snapshot { label } => {
+            let snapshot = load_snapshots(root)?
+                .into_iter()
+                .filter(|s| s.contract == contract && label.as_ref().is_none_or(|l| &s.label == l))
+                .max_by_key(|s| s.created_at)
+                .ok_or_else(|| format!("No ABI snapshot found for {}", contract))?;
+            (format!("snapshot {}", snapshot.label), snapshot.abi)
+        }
+        AbiBaseline::Deployment { deployment_id, future_id } => (
+            format!("deployment {} ({})", deployment_id, future_id),
+            deployed_abi(root, deployment_id, future_id)?,
+        ),
+    };
+
+    let changes = diff(&old, &current);
+    let bump = if changes.iter().any(|c| c.breaking) {
+        VersionBump::Major
+    } else if changes.is_empty() {
+        VersionBump::None
+    } else {
+        VersionBump::Minor
+    };
+
+    Ok(AbiDiff { contract, baseline: baseline_name, bump, changes })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn abi(items: serde_json::Value) -> Vec<AbiItem> {
+        serde_json::from_value(items).unwrap()
+    }
+
+    fn function(name: &str, inputs: &[&str], outputs: &[&str], mutability: &str) -> serde_json::Value {
+        let params = |types: &[&str]| types.iter().map(|t| json!({ "name": "", "type": t })).collect::<Vec<_>>();
+        json!({ "type": "function", "name": name, "inputs": params(inputs), "outputs": params(outputs), "stateMutability": mutability })
+    }
+
+    fn summary(changes: &[AbiChange]) -> Vec<(String, ChangeKind, bool)> {
+        changes.iter().map(|c| (c.signature.clone(), c.change, c.breaking)).collect()
+    }
+
+    #[test]
+    fn identical_abis_have_no_changes() {
+        let items = abi(json!([function("balanceOf", &["address"], &["uint256"], "view")]));
+        assert!(diff(&items, &items).is_empty());
+    }
+
+    #[test]
+    fn added_items_are_not_breaking_and_removed_ones_are() {
+        let old = abi(json!([function("pause", &[], &[], "nonpayable"), { "type": "error", "name": "Paused", "inputs": [] }]));
+        let new = abi(json!([function("unpause", &[], &[], "nonpayable")]));
+        assert_eq!(
+            summary(&diff(&old, &new)),
+            vec![
+                ("Paused()".to_string(), ChangeKind::Removed, false),
+                ("pause()".to_string(), ChangeKind::Removed, true),
+                ("unpause()".to_string(), ChangeKind::Added, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn new_parameter_types_are_a_breaking_change_of_the_same_item() {
+        let old = abi(json!([function("mint", &["uint256"], &[], "nonpayable")]));
+        let new = abi(json!([function("mint", &["address", "uint256"], &[], "nonpayable")]));
+        let changes = diff(&old, &new);
+        assert_eq!(summary(&changes), vec![("mint(uint256)".to_string(), ChangeKind::Changed, true)]);
+        assert_eq!(changes[0].detail.as_deref(), Some("now mint(address,uint256)"));
+    }
+
+    #[test]
+    fn mutability_changes_only_break_when_tightened() {
+        let old = abi(json!([function("deposit", &[], &[], "payable"), function("total", &[], &["uint256"], "nonpayable")]));
+        let new = abi(json!([function("deposit", &[], &[], "nonpayable"), function("total", &[], &["uint256"], "view")]));
+        assert_eq!(
+            summary(&diff(&old, &new)),
+            vec![("deposit()".to_string(), ChangeKind::Changed, true), ("total()".to_string(), ChangeKind::Changed, false)]
+        );
+    }
+
+    #[test]
+    fn event_indexing_changes_are_breaking() {
+        let event = |indexed: bool| json!({ "type": "event", "name": "Transfer", "anonymous": false, "inputs": [{ "name": "from", "type": "address", "indexed": indexed }] });
+        let changes = diff(&abi(json!([event(true)])), &abi(json!([event(false)])));
+        assert_eq!(summary(&changes), vec![("Transfer(address)".to_string(), ChangeKind::Changed, true)]);
+        assert_eq!(changes[0].detail.as_deref(), Some("indexed parameters changed"));
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::artifacts::{self, Artifact, BuildInfoView, Compilation};
+use crate::ast;
+use crate::error::AppError;
+use crate::findings::SourceLocation;
+
+// Modifiers that gate *when* a function runs rather than *who* may call it
+const NON_ACCESS_MODIFIERS: [&str; 4] = ["nonReentrant", "whenNotPaused", "whenPaused", "onlyInitializing"];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessKind {
+    Role,
+    Owner,
+    // A custom modifier that checks msg.sender
+    Modifier,
+    // require/if on msg.sender inside the function body
+    Inline,
+    // Callable once, by whoever gets there first
+    Initializer,
+    None,
+    // The ABI entry couldn't be matched to a function in the AST
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FunctionAccess {
+    pub signature: String,
+    pub selector: Option<String>,
+    pub state_mutability: String,
+    // Base contract the function is implemented in, when inherited
+    pub declared_in: Option<String>,
+    pub access: AccessKind,
+    // Modifier names, roles and inline checks, e.g. "onlyRole(MINTER_ROLE)"
+    pub guards: Vec<String>,
+    pub location: Option<SourceLocation>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ContractAccess {
+    pub contract: String,
+    pub source_name: String,
+    pub unprotected_count: usize,
+    pub functions: Vec<FunctionAccess>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AccessMatrix {
+    pub unprotected_count: usize,
+    pub contracts: Vec<ContractAccess>,
+}
+
+fn call_name(call: &Value) -> &str {
+    call.get("expression").map(|e| ast::str_field(e, "name")).unwrap_or_default()
+}
+
+fn is_sender(node: &Value) -> bool {
+    let msg_sender = ast::node_type(node) == "MemberAccess"
+        && ast::str_field(node, "memberName") == "sender"
+        && node.get("expression").is_some_and(|e| ast::str_field(e, "name") == "msg");
+    msg_sender || (ast::node_type(node) == "FunctionCall" && call_name(node) == "_msgSender")
+}
+
+fn mentions_sender(node: &Value) -> bool {
+    let mut found = false;
+    ast::walk(node, &mut |n| found |= is_sender(n));
+    found
+}
+
+// `MINTER_ROLE` / `Roles.MINTER` from the first argument of a role check
+fn role_name(call: &Value) -> &str {
+    call.pointer("/arguments/0")
+        .map(|arg| match ast::node_type(arg) {
+            "MemberAccess" => ast::str_field(arg, "memberName"),
+            _ => ast::str_field(arg, "name"),
+        })
+        .filter(|role| !role.is_empty())
+        .unwrap_or("role")
+}
+
+// Access checks made directly in the function body
+fn inline_guards(body: &Value, guards: &mut Vec<(AccessKind, String)>) {
+    ast::walk(body, &mut |node| match ast::node_type(node) {
+        "FunctionCall" => match call_name(node) {
+            "_checkOwner" => guards.push((AccessKind::Owner, "_checkOwner()".to_string())),
+            "_checkRole" | "hasRole" => guards.push((AccessKind::Role, format!("{}({})", call_name(node), role_name(node)))),
+            "require" if node.pointer("/arguments/0").is_some_and(mentions_sender) => {
+                guards.push((AccessKind::Inline, "require(msg.sender ...)".to_string()))
+            }
+            _ => {}
+        },
+        "IfStatement" if mentions_sender(&node["condition"]) => {
+            guards.push((AccessKind::Inline, "if (msg.sender ...) revert".to_string()))
+        }
+        _ => {}
+    });
+}
+
+fn classify(compilation: &Compilation, function: &Value) -> (AccessKind, Vec<String>) {
+    let mut guards: Vec<(AccessKind, String)> = Vec::new();
+
+    let modifiers = function.get("modifiers").and_then(Value::as_array).into_iter().flatten();
+    for invocation in modifiers.filter(|m| ast::str_field(m, "kind") != "baseConstructorSpecifier") {
+        let name = invocation.pointer("/modifierName/name").and_then(Value::as_str).unwrap_or_default();
+        if NON_ACCESS_MODIFIERS.contains(&name) {
+            continue;
+        }
+        if name == "initializer" || name == "reinitializer" {
+            guards.push((AccessKind::Initializer, name.to_string()));
+            continue;
+        }
+
+        let definition = invocation
+            .pointer("/modifierName/referencedDeclaration")
+            .and_then(Value::as_i64)
+            .and_then(|id| compilation.node(id));
+        let lower = name.to_lowercase();
+        if lower.contains("role") {
+            guards.push((AccessKind::Role, format!("{}({})", name, role_name(invocation))));
+        } else if lower.contains("owner") {
+            guards.push((AccessKind::Owner, name.to_string()));
+        } else if definition.is_some_and(mentions_sender) || lower.starts_with("only") {
+            guards.push((AccessKind::Modifier, name.to_string()));
+        } else if let Some(definition) = definition {
+            // Modifiers that delegate to _checkOwner()/_checkRole() instead of reading msg.sender
+            let mut nested = Vec::new();
+            inline_guards(&definition["body"], &mut nested);
+            if let Some((kind, _)) = nested.into_iter().find(|(kind, _)| *kind != AccessKind::Inline) {
+                guards.push((kind, name.to_string()));
+            }
+        }
+    }
+
+    if let Some(body) = function.get("body").filter(|b| !b.is_null()) {
+        inline_guards(body, &mut guards);
+    }
+
+    let priority = [AccessKind::Role, AccessKind::Owner, AccessKind::Modifier, AccessKind::Inline, AccessKind::Initializer];
+    let kind = priority
+        .into_iter()
+        .find(|kind| guards.iter().any(|(k, _)| k == kind))
+        .unwrap_or(AccessKind::None);
+
+    let mut seen = HashSet::new();
+    let names = guards.into_iter().map(|(_, g)| g).filter(|g| seen.insert(g.clone())).collect();
+    (kind, names)
+}
+
+// `outputs` is the same build-info's contracts view, for the method identifiers
+fn contract_access(compilation: &Compilation, outputs: &Value, artifact: &Artifact) -> Option<ContractAccess> {
+    let contract = ast::find_all(compilation.source_ast(&artifact.source_name)?, "ContractDefinition")
+        .into_iter()
+        .find(|c| ast::str_field(c, "name") == artifact.contract_name)?;
+    let method_ids = outputs
+        .pointer("/output/contracts")
+        .and_then(|c| c.get(&artifact.source_name))
+        .and_then(|c| c.get(&artifact.contract_name))
+        .and_then(|c| c.pointer("/evm/methodIdentifiers"));
+
+    // Most derived first, so overrides shadow the base implementation
+    let mut by_selector: HashMap<&str, (&Value, &Value)> = HashMap::new();
+    let mut by_name: HashMap<(&str, usize), (&Value, &Value)> = HashMap::new();
+    let bases = contract.get("linearizedBaseContracts").and_then(Value::as_array).into_iter().flatten();
+    for base in bases.filter_map(Value::as_i64).filter_map(|id| compilation.node(id)) {
+        let functions = base.get("nodes").and_then(Value::as_array).into_iter().flatten();
+        for function in functions.filter(|n| ast::node_type(n) == "FunctionDefinition") {
+            let selector = ast::str_field(function, "functionSelector");
+            if selector.is_empty() {
+                continue;
+            }
+            let arity = function.pointer("/parameters/parameters").and_then(Value::as_array).map_or(0, Vec::len);
+            by_selector.entry(selector).or_insert((base, function));
+            by_name.entry((ast::str_field(function, "name"), arity)).or_insert((base, function));
+        }
+    }
+
+    let mut functions = Vec::new();
+    for item in artifact.abi.iter().filter(|i| i.is_function()) {
+        let mutability = item.state_mutability.clone().unwrap_or_else(|| "nonpayable".to_string());
+        if mutability == "view" || mutability == "pure" {
+            continue;
+        }
+        let signature = item.signature();
+        let selector = method_ids.and_then(|ids| ids.get(&signature)).and_then(Value::as_str);
+        // Without methodIdentifiers in the output selection, overloads fall back to name + arity
+        let definition = match selector {
+            Some(selector) => by_selector.get(selector),
+            None => by_name.get(&(item.name.as_str(), item.inputs.len())),
+        };
+
+        let (access, guards, declared_in, location) = match definition {
+            Some((base, function)) => {
+                let (access, guards) = classify(compilation, function);
+                let base_name = ast::str_field(base, "name");
+                let declared_in = (base_name != artifact.contract_name).then(|| base_name.to_string());
+                (access, guards, declared_in, compilation.location(function))
+            }
+            None => (AccessKind::Unknown, Vec::new(), None, None),
+        };
+        functions.push(FunctionAccess {
+            signature,
+            selector: selector.map(|s| format!("0x{}", s)),
+            state_mutability: mutability,
+            declared_in,
+            access,
+            guards,
+            location,
+        });
+    }
+
+    functions.sort_by(|a, b| a.signature.cmp(&b.signature));
+    Some(ContractAccess {
+        contract: artifact.contract_name.clone(),
+        source_name: artifact.source_name.clone(),
+        unprotected_count: functions.iter().filter(|f| f.access == AccessKind::None).count(),
+        functions,
+    })
+}
+
+pub fn build_matrix(project_path: &Path) -> AccessMatrix {
+    // Deployable project contracts only; interfaces and libraries have nothing to guard
+    let mut pending: Vec<Artifact> = artifacts::load_artifacts(project_path)
+        .into_iter()
+        .filter(|a| !artifacts::is_dependency_source(&a.source_name))
+        .filter(|a| a.deployed_size > 0)
+        .collect();
+
+    let mut contracts = Vec::new();
+    for path in artifacts::build_info_files(project_path) {
+        if pending.is_empty() {
+            break;
+        }
+        let (Some(build_info), Some(outputs)) = (
+            artifacts::read_build_info(&path, BuildInfoView::Sources),
+            artifacts::read_build_info(&path, BuildInfoView::Contracts),
+        ) else {
+            continue;
+        };
+        let compilation = Compilation::new(&build_info);
+        pending.retain(|artifact| match contract_access(&compilation, &outputs, artifact) {
+            Some(access) => {
+                contracts.push(access);
+                false
+            }
+            None => true,
+        });
+    }
+
+    contracts.sort_by(|a, b| a.contract.cmp(&b.contract));
+    AccessMatrix {
+        unprotected_count: contracts.iter().map(|c| c.unprotected_count).sum(),
+        contracts,
+    }
+}
+
+#[tauri::command]
+pub async fn get_access_control_matrix(project_path: String) -> Result<AccessMatrix, AppError> {
+    let root = Path::new(&project_path);
+    if !root.join("artifacts").join("build-info").is_dir() {
+        return Err("No build info found. Compile the project first.".to_string().into());
+    }
+    Ok(build_matrix(root))
+}
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::deployments;
+use crate::error::AppError;
+use crate::external_abis;
+use crate::rpc;
+use crate::store;
+
+const ADDRESS_BOOK_FILE: &str = "address-book.json";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelSource {
+    User,
+    Deployment,
+    ExternalAbi,
+    Account,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AddressLabel {
+    pub address: String,
+    pub label: String,
+    pub source: LabelSource,
+}
+
+pub fn is_address(value: &str) -> bool {
+    value.len() == 42 && value.starts_with("0x") && value[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn load_user_labels(project_path: &Path) -> Result<Vec<AddressLabel>, String> {
+    store::load_json(project_path, ADDRESS_BOOK_FILE)
+}
+
+// Every label known for the project, keyed by lowercase address. User labels win over
+// deployment names, which win over imported ABI names and the node's dev accounts
+pub struct AddressBook {
+    labels: BTreeMap<String, AddressLabel>,
+}
+
+impl AddressBook {
+    pub async fn load(project_path: &Path, url: Option<&str>) -> Self {
+        let mut entries = load_user_labels(project_path).unwrap_or_default();
+        entries.extend(deployments::load_deployments(project_path).into_iter().map(|d| AddressLabel {
+            address: d.address,
+            label: d.contract_name,
+            source: LabelSource::Deployment,
+        }));
+        for abi in external_abis::load(project_path).unwrap_or_default() {
+            if let Some(address) = abi.address {
+                entries.push(AddressLabel { address, label: abi.name, source: LabelSource::ExternalAbi });
+            }
+        }
+        if let Some(url) = url {
+            let accounts = rpc::call(url, "eth_accounts", json!([])).await.unwrap_or(Value::Null);
+            let accounts = accounts.as_array().into_iter().flatten().filter_map(Value::as_str);
+            entries.extend(accounts.enumerate().map(|(i, address)| AddressLabel {
+                address: address.to_string(),
+                label: format!("Account #{}", i),
+                source: LabelSource::Account,
+            }));
+        }
+
+        let mut labels: BTreeMap<String, AddressLabel> = BTreeMap::new();
+        for mut entry in entries {
+            entry.address = entry.address.to_lowercase();
+            match labels.get(&entry.address) {
+                Some(existing) if existing.source <= entry.source => {}
+                _ => {
+                    labels.insert(entry.address.clone(), entry);
+                }
+            }
+        }
+        AddressBook { labels }
+    }
+
+    pub fn label(&self, address: &str) -> Option<&str> {
+        self.labels.get(&address.to_lowercase()).map(|l| l.label.as_str())
+    }
+
+    fn collect(&self, value: &Value, found: &mut BTreeMap<String, String>) {
+        match value {
+            Value::String(text) if is_address(text) => {
+                if let Some(label) = self.label(text) {
+                    found.insert(text.to_lowercase(), label.to_string());
+                }
+            }
+            Value::Array(items) => items.iter().for_each(|item| self.collect(item, found)),
+            Value::Object(fields) => fields.values().for_each(|field| self.collect(field, found)),
+            _ => {}
+        }
+    }
+
+    // Labels for every address anywhere in a response, including decoded arguments, so
+    // views can show names without resolving addresses themselves
+    pub fn labels_for<T: Serialize>(&self, response: &T) -> BTreeMap<String, String> {
+        let mut found = BTreeMap::new();
+        if let Ok(value) = serde_json::to_value(response) {
+            self.collect(&value, &mut found);
+        }
+        found
+    }
+}
+
+#[tauri::command]
+pub async fn list_address_labels(project_path: String, rpc_url: Option<String>) -> Result<Vec<AddressLabel>, AppError> {
+    let book = AddressBook::load(Path::new(&project_path), Some(rpc_url.unwrap_or_else(rpc::default_url).as_str())).await;
+    Ok(book.labels.into_values().collect())
+}
+
+#[tauri::command]
+pub async fn set_address_label(project_path: String, address: String, label: String) -> Result<AddressLabel, AppError> {
+    let root = Path::new(&project_path);
+    let address = address.trim().to_lowercase();
+    if !is_address(&address) {
+        return Err(format!("Invalid address: {}", address).into());
+    }
+    let label = label.trim().to_string();
+    if label.is_empty() {
+        return Err("Label cannot be empty".to_string().into());
+    }
+
+    let entry = AddressLabel { address, label, source: LabelSource::User };
+    let mut labels = load_user_labels(root)?;
+    labels.retain(|l| l.address != entry.address);
+    labels.push(entry.clone());
+    store::save_json(root, ADDRESS_BOOK_FILE, &labels)?;
+    Ok(entry)
+}
+
+#[tauri::command]
+pub async fn remove_address_label(project_path: String, address: String) -> Result<(), AppError> {
+    let root = Path::new(&project_path);
+    let address = address.trim().to_lowercase();
+    let mut labels = load_user_labels(root)?;
+    labels.retain(|l| l.address != address);
+    Ok(store::save_json(root, ADDRESS_BOOK_FILE, &labels)?)
+}
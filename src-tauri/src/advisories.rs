@@ -0,0 +1,150 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::AppError;
+use crate::health::Severity;
+use crate::project;
+use crate::solidity::{self, SolcVersion};
+
+struct Advisory {
+    id: &'static str,
+    packages: &'static [&'static str],
+    // Half-open [introduced, fixed) ranges
+    affected: &'static [(&'static str, &'static str)],
+    // Paths inside the package, e.g. "utils/cryptography/ECDSA.sol"
+    modules: &'static [&'static str],
+    severity: Severity,
+    title: &'static str,
+}
+
+const OZ: &[&str] = &["@openzeppelin/contracts", "@openzeppelin/contracts-upgradeable"];
+
+// Bundled subset of the OpenZeppelin Contracts security advisories. Kept offline on
+// purpose; extend this list when new advisories are published.
+const ADVISORIES: &[Advisory] = &[
+    Advisory {
+        id: "GHSA-4h98-2769-gh6h",
+        packages: OZ,
+        affected: &[("4.1.0", "4.7.3")],
+        modules: &["utils/cryptography/ECDSA.sol"],
+        severity: Severity::Error,
+        title: "ECDSA signature malleability via compact signatures",
+    },
+    Advisory {
+        id: "GHSA-g4vp-m682-qqmp",
+        packages: OZ,
+        affected: &[("4.0.0", "4.9.4"), ("5.0.0", "5.0.1")],
+        modules: &["metatx/ERC2771Context.sol", "utils/Multicall.sol"],
+        severity: Severity::Error,
+        title: "Address spoofing when ERC2771Context is combined with Multicall",
+    },
+    Advisory {
+        id: "GHSA-wprv-93r4-jj2p",
+        packages: OZ,
+        affected: &[("4.7.0", "4.9.2")],
+        modules: &["utils/cryptography/MerkleProof.sol"],
+        severity: Severity::Error,
+        title: "Invalid Merkle multiproofs can be accepted",
+    },
+    Advisory {
+        id: "GHSA-mx2q-35m2-x2rh",
+        packages: &["@openzeppelin/contracts-upgradeable"],
+        affected: &[("4.1.0", "4.3.2")],
+        modules: &["proxy/utils/UUPSUpgradeable.sol"],
+        severity: Severity::Error,
+        title: "Uninitialized UUPS implementation can be taken over and destroyed",
+    },
+    Advisory {
+        id: "GHSA-93hq-5wgc-jc82",
+        packages: OZ,
+        affected: &[("4.3.0", "4.8.3")],
+        modules: &["governance/compatibility/GovernorCompatibilityBravo.sol"],
+        severity: Severity::Warning,
+        title: "GovernorCompatibilityBravo may trim proposal calldata",
+    },
+    Advisory {
+        id: "GHSA-878m-3g6q-594q",
+        packages: OZ,
+        affected: &[("4.5.0", "4.9.6"), ("5.0.0", "5.0.2")],
+        modules: &["utils/Base64.sol"],
+        severity: Severity::Warning,
+        title: "Base64 encoding may read from dirty memory",
+    },
+];
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VulnerableDependency {
+    pub advisory: String,
+    pub package: String,
+    pub installed_version: String,
+    // Lowest release that fixes the advisory within the installed major line
+    pub minimum_safe_version: String,
+    pub severity: Severity,
+    pub title: String,
+    pub affected_modules: Vec<String>,
+    // Project contracts that import an affected module, directly or transitively
+    pub affected_contracts: Vec<String>,
+}
+
+pub fn installed_version(root: &Path, package: &str) -> Option<String> {
+    let content = fs::read_to_string(root.join("node_modules").join(package).join("package.json")).ok()?;
+    let manifest: Value = serde_json::from_str(&content).ok()?;
+    manifest.get("version")?.as_str().map(str::to_string)
+}
+
+#[tauri::command]
+pub async fn check_vulnerable_dependencies(project_path: String) -> Result<Vec<VulnerableDependency>, AppError> {
+    let root = Path::new(&project_path);
+
+    let contracts = project::collect_files(&root.join("contracts"), "sol");
+    let closures: Vec<_> = contracts
+        .iter()
+        .map(|contract| (contract, solidity::import_closure(root, contract)))
+        .collect();
+
+    let mut findings = Vec::new();
+    for advisory in ADVISORIES {
+        for package in advisory.packages {
+            let Some(version_text) = installed_version(root, package) else {
+                continue;
+            };
+            let Some(version) = SolcVersion::parse(&version_text) else {
+                continue;
+            };
+            let Some((_, fixed)) = advisory.affected.iter().find(|(introduced, fixed)| {
+                SolcVersion::parse(introduced).is_some_and(|v| version >= v)
+                    && SolcVersion::parse(fixed).is_some_and(|v| version < v)
+            }) else {
+                continue;
+            };
+
+            let package_dir = root.join("node_modules").join(package).canonicalize().ok();
+            let affected_modules: Vec<_> = advisory
+                .modules
+                .iter()
+                .filter_map(|module| package_dir.as_ref().map(|dir| dir.join(module)))
+                .collect();
+            let affected_contracts = closures
+                .iter()
+                .filter(|(_, closure)| closure.iter().any(|file| affected_modules.contains(file)))
+                .map(|(contract, _)| contract.strip_prefix(root).unwrap_or(contract).to_string_lossy().to_string())
+                .collect();
+
+            findings.push(VulnerableDependency {
+                advisory: advisory.id.to_string(),
+                package: package.to_string(),
+                installed_version: version_text,
+                minimum_safe_version: fixed.to_string(),
+                severity: advisory.severity,
+                title: advisory.title.to_string(),
+                affected_modules: advisory.modules.iter().map(|m| format!("{}/{}", package, m)).collect(),
+                affected_contracts,
+            });
+        }
+    }
+
+    Ok(findings)
+}
@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct TextStyle {
+    pub fg: Option<String>,
+    pub bg: Option<String>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StyledSegment {
+    pub text: String,
+    #[serde(flatten)]
+    pub style: TextStyle,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMode {
+    Segments,
+    Plain,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FormattedOutput {
+    pub text: String,
+    pub segments: Option<Vec<StyledSegment>>,
+}
+
+const COLOR_NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+// The 16 base colors keep their names so the frontend can theme them; extended
+// palette and truecolor values become hex
+fn palette_color(index: u16) -> String {
+    match index {
+        0..=7 => COLOR_NAMES[index as usize].to_string(),
+        8..=15 => format!("bright_{}", COLOR_NAMES[(index - 8) as usize]),
+        16..=231 => {
+            let i = index - 16;
+            let level = |v: u16| if v == 0 { 0 } else { 55 + v * 40 };
+            format!("#{:02x}{:02x}{:02x}", level(i / 36), level((i / 6) % 6), level(i % 6))
+        }
+        _ => {
+            let gray = 8 + (index.min(255) - 232) * 10;
+            format!("#{:02x}{:02x}{:02x}", gray, gray, gray)
+        }
+    }
+}
+
+// Consumes `38;5;n` / `38;2;r;g;b` style extended color codes
+fn extended_color(codes: &mut std::slice::Iter<'_, u16>) -> Option<String> {
+    match codes.next()? {
+        5 => codes.next().map(|n| palette_color(*n)),
+        2 => {
+            let r = *codes.next()?;
+            let g = *codes.next()?;
+            let b = *codes.next()?;
+            Some(format!("#{:02x}{:02x}{:02x}", r.min(255), g.min(255), b.min(255)))
+        }
+        _ => None,
+    }
+}
+
+fn apply_sgr(style: &mut TextStyle, params: &str) {
+    let codes: Vec<u16> = if params.is_empty() {
+        vec![0]
+    } else {
+        params.split(';').map(|p| p.parse().unwrap_or(0)).collect()
+    };
+
+    let mut codes = codes.iter();
+    while let Some(code) = codes.next() {
+        match code {
+            0 => *style = TextStyle::default(),
+            1 => style.bold = true,
+            2 => style.dim = true,
+            3 => style.italic = true,
+            4 => style.underline = true,
+            22 => {
+                style.bold = false;
+                style.dim = false;
+            }
+            23 => style.italic = false,
+            24 => style.underline = false,
+            30..=37 => style.fg = Some(palette_color(code - 30)),
+            38 => style.fg = extended_color(&mut codes),
+            39 => style.fg = None,
+            40..=47 => style.bg = Some(palette_color(code - 40)),
+            48 => style.bg = extended_color(&mut codes),
+            49 => style.bg = None,
+            90..=97 => style.fg = Some(palette_color(code - 90 + 8)),
+            100..=107 => style.bg = Some(palette_color(code - 100 + 8)),
+            _ => {}
+        }
+    }
+}
+
+enum Token<'a> {
+    Text(char),
+    Sgr(&'a str),
+    Other,
+}
+
+// Splits text into characters and escape sequences. Only SGR (`ESC[...m`) carries
+// styling; cursor movement, line clearing and OSC sequences are dropped.
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < text.len() {
+        if bytes[i] == 0x1b && i + 1 < text.len() {
+            match bytes[i + 1] {
+                b'[' => {
+                    let start = i + 2;
+                    let mut end = start;
+                    while end < text.len() && !(0x40..=0x7e).contains(&bytes[end]) {
+                        end += 1;
+                    }
+                    if end < text.len() && bytes[end] == b'm' {
+                        tokens.push(Token::Sgr(&text[start..end]));
+                    } else {
+                        tokens.push(Token::Other);
+                    }
+                    i = end + 1;
+                    continue;
+                }
+                b']' => {
+                    // OSC runs until BEL or ESC \
+                    let mut end = i + 2;
+                    while end < text.len() && bytes[end] != 0x07 && !(bytes[end] == 0x1b && bytes.get(end + 1) == Some(&b'\\')) {
+                        end += 1;
+                    }
+                    tokens.push(Token::Other);
+                    i = if bytes.get(end) == Some(&0x07) { end + 1 } else { end + 2 };
+                    continue;
+                }
+                _ => {
+                    // Two-character escapes like ESC 7 / ESC 8 (save/restore cursor)
+                    tokens.push(Token::Other);
+                    i += 1 + text[i + 1..].chars().next().map_or(1, char::len_utf8);
+                    continue;
+                }
+            }
+        }
+
+        let c = text[i..].chars().next().unwrap();
+        tokens.push(Token::Text(c));
+        i += c.len_utf8();
+    }
+
+    tokens
+}
+
+pub fn parse(text: &str) -> Vec<StyledSegment> {
+    let mut segments: Vec<StyledSegment> = Vec::new();
+    let mut style = TextStyle::default();
+    let mut current = String::new();
+
+    let mut flush = |current: &mut String, style: &TextStyle| {
+        if current.is_empty() {
+            return;
+        }
+        match segments.last_mut() {
+            Some(last) if last.style == *style => last.text.push_str(current),
+            _ => segments.push(StyledSegment { text: current.clone(), style: style.clone() }),
+        }
+        current.clear();
+    };
+
+    for token in tokenize(text) {
+        match token {
+            Token::Text(c) => current.push(c),
+            Token::Sgr(params) => {
+                flush(&mut current, &style);
+                apply_sgr(&mut style, params);
+            }
+            Token::Other => {}
+        }
+    }
+    flush(&mut current, &style);
+
+    segments
+}
+
+pub fn strip(text: &str) -> String {
+    tokenize(text)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Text(c) => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn format_output(text: String, mode: OutputMode) -> Result<FormattedOutput, AppError> {
+    Ok(FormattedOutput {
+        segments: matches!(mode, OutputMode::Segments).then(|| parse(&text)),
+        text: strip(&text),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_removes_escape_sequences() {
+        assert_eq!(strip("\x1b[32m✔\x1b[0m passes \x1b[1;31mfails\x1b[22;39m"), "✔ passes fails");
+        assert_eq!(strip("\x1b[2K\x1b[1Gdone\x1b7\x1b8"), "done");
+        assert_eq!(strip("\x1b]0;title\x07a\x1b]8;;https://x.y\x1b\\b"), "ab");
+        assert_eq!(strip("plain text"), "plain text");
+    }
+
+    #[test]
+    fn strip_tolerates_truncated_sequences() {
+        assert_eq!(strip("ok\x1b[31"), "ok");
+        assert_eq!(strip("ok\x1b]0;title"), "ok");
+    }
+
+    #[test]
+    fn parse_merges_segments_with_the_same_style() {
+        let segments = parse("\x1b[31mred\x1b[0m \x1b[38;5;196mx\x1b[39m\x1b[1mbold\x1b[1m!");
+        let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["red", " ", "x", "bold!"]);
+        assert_eq!(segments[0].style.fg.as_deref(), Some("red"));
+        assert_eq!(segments[2].style.fg.as_deref(), Some("#ff0000"));
+        assert!(segments[3].style.bold);
+    }
+}
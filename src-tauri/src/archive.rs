@@ -0,0 +1,183 @@
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::error::AppError;
+use crate::store;
+
+// Excluded wherever they appear in the tree
+const EXCLUDED_ANYWHERE: [&str; 3] = ["node_modules", ".git", ".DS_Store"];
+
+// Build outputs and secrets that only make sense at the project root
+const EXCLUDED_AT_ROOT: [&str; 7] = [
+    "artifacts",
+    "cache",
+    "coverage",
+    "coverage.json",
+    "typechain",
+    "typechain-types",
+    ".env",
+];
+
+#[derive(Serialize, Deserialize)]
+pub struct ArchiveSummary {
+    pub archive_path: String,
+    pub file_count: usize,
+    pub total_bytes: u64,
+    pub included_gui_data: bool,
+}
+
+#[tauri::command]
+pub async fn export_project_archive(
+    project_path: String,
+    destination: String,
+    include_gui_data: bool,
+) -> Result<ArchiveSummary, AppError> {
+    // Canonical paths on both sides, so a destination inside the project is recognised
+    // (and skipped) even when given relative or through a symlink
+    let root = fs::canonicalize(&project_path)
+        .ok()
+        .filter(|root| root.is_dir())
+        .ok_or_else(|| format!("Project directory not found: {}", project_path))?;
+    let root = root.as_path();
+    let destination = canonical_destination(PathBuf::from(destination));
+
+    let mut files = Vec::new();
+    collect_archive_files(root, root, include_gui_data, &destination, &mut files)
+        .map_err(|e| format!("Failed to read project files: {}", e))?;
+
+    let archive = File::create(&destination)
+        .map_err(|e| format!("Failed to create archive: {}", e))?;
+    let mut zip = ZipWriter::new(archive);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let mut total_bytes = 0;
+    for file in &files {
+        // Zip entries always use forward slashes, regardless of platform
+        let name = file
+            .strip_prefix(root)
+            .unwrap_or(file)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        zip.start_file(name, options)
+            .map_err(|e| format!("Failed to add {} to archive: {}", file.display(), e))?;
+        let mut source = File::open(file)
+            .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+        total_bytes += io::copy(&mut source, &mut zip)
+            .map_err(|e| format!("Failed to add {} to archive: {}", file.display(), e))?;
+    }
+
+    zip.finish().map_err(|e| format!("Failed to finalize archive: {}", e))?;
+
+    Ok(ArchiveSummary {
+        archive_path: destination.to_string_lossy().to_string(),
+        file_count: files.len(),
+        total_bytes,
+        included_gui_data: include_gui_data,
+    })
+}
+
+// The archive doesn't exist yet, so only its directory can be resolved
+fn canonical_destination(destination: PathBuf) -> PathBuf {
+    let Some(name) = destination.file_name() else {
+        return destination;
+    };
+    let parent = destination.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    match fs::canonicalize(parent) {
+        Ok(parent) => parent.join(name),
+        Err(_) => destination,
+    }
+}
+
+// Symlinks are skipped rather than followed: a linked directory can loop back into the
+// tree, and either kind can pull in files from outside the project
+fn collect_archive_files(
+    root: &Path,
+    dir: &Path,
+    include_gui_data: bool,
+    destination: &Path,
+    files: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+        let at_root = dir == root;
+
+        if EXCLUDED_ANYWHERE.contains(&name)
+            || (at_root && EXCLUDED_AT_ROOT.contains(&name))
+            || (at_root && name == store::STORE_DIR && !include_gui_data)
+            || path == destination
+            || file_type.is_symlink()
+        {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            collect_archive_files(root, &path, include_gui_data, destination, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(root: &Path, destination: &Path) -> Vec<String> {
+        let mut files = Vec::new();
+        collect_archive_files(root, root, false, destination, &mut files).unwrap();
+        let mut names: Vec<String> =
+            files.iter().map(|f| f.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/")).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn skips_build_outputs_and_the_archive_itself() {
+        let dir = std::env::temp_dir().join(format!("hardhat-gui-archive-{}", std::process::id()));
+        fs::create_dir_all(dir.join("contracts")).unwrap();
+        fs::create_dir_all(dir.join("artifacts")).unwrap();
+        fs::create_dir_all(dir.join("lib/node_modules")).unwrap();
+        fs::write(dir.join("contracts/Box.sol"), "").unwrap();
+        fs::write(dir.join("artifacts/Box.json"), "").unwrap();
+        fs::write(dir.join("lib/node_modules/x.js"), "").unwrap();
+        fs::write(dir.join(".env"), "").unwrap();
+        fs::write(dir.join("out.zip"), "").unwrap();
+        let root = fs::canonicalize(&dir).unwrap();
+
+        let destination = canonical_destination(dir.join("contracts/../out.zip"));
+        assert_eq!(collect(&root, &destination), ["contracts/Box.sol"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn does_not_follow_symlinks() {
+        let dir = std::env::temp_dir().join(format!("hardhat-gui-archive-links-{}", std::process::id()));
+        let outside = std::env::temp_dir().join(format!("hardhat-gui-archive-outside-{}", std::process::id()));
+        fs::create_dir_all(dir.join("contracts")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(dir.join("contracts/Box.sol"), "").unwrap();
+        fs::write(outside.join("secret.txt"), "").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("contracts/loop")).unwrap();
+        std::os::unix::fs::symlink(&outside, dir.join("outside")).unwrap();
+        std::os::unix::fs::symlink(outside.join("secret.txt"), dir.join("secret.txt")).unwrap();
+        let root = fs::canonicalize(&dir).unwrap();
+
+        assert_eq!(collect(&root, &root.join("out.zip")), ["contracts/Box.sol"]);
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+}
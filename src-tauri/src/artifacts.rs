@@ -0,0 +1,543 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use serde::de::{self, DeserializeOwned, Deserializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::abi::AbiItem;
+use crate::error::AppError;
+use crate::ast;
+use crate::findings::SourceLocation;
+use crate::project;
+
+// What the index keeps per artifact. Bytecode is most of an artifact's size and few
+// commands need it, so only its size is kept; `code()` reads it from `path` on demand
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Artifact {
+    pub contract_name: String,
+    pub source_name: String,
+    pub abi: Vec<AbiItem>,
+    // In bytes; 0 for abstract contracts and interfaces
+    pub bytecode_size: usize,
+    // Runtime code size, the number EIP-170's 24KiB limit applies to
+    pub deployed_size: usize,
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ArtifactCode {
+    // Creation code, empty for abstract contracts and interfaces
+    #[serde(default)]
+    pub bytecode: String,
+    #[serde(alias = "deployedBytecode", default)]
+    pub deployed_bytecode: String,
+}
+
+impl Artifact {
+    pub fn fully_qualified_name(&self) -> String {
+        format!("{}:{}", self.source_name, self.contract_name)
+    }
+
+    pub fn code(&self) -> Result<ArtifactCode, String> {
+        read_streamed(&self.path).map_err(|e| format!("Failed to read bytecode of {}: {}", self.contract_name, e))
+    }
+}
+
+// Length of a hex string in bytes, measured while streaming without keeping the string
+struct CodeSize(usize);
+
+impl<'de> Deserialize<'de> for CodeSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct HexVisitor;
+        impl de::Visitor<'_> for HexVisitor {
+            type Value = CodeSize;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a hex string")
+            }
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<CodeSize, E> {
+                Ok(CodeSize(value.trim_start_matches("0x").len() / 2))
+            }
+        }
+        deserializer.deserialize_str(HexVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactFile {
+    contract_name: String,
+    source_name: String,
+    abi: Vec<AbiItem>,
+    #[serde(default)]
+    bytecode: Option<CodeSize>,
+    #[serde(default)]
+    deployed_bytecode: Option<CodeSize>,
+}
+
+// Parses straight from the file instead of reading it into a string first
+fn read_streamed<T: DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let file = fs::File::open(path).map_err(|e| e.to_string())?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|e| e.to_string())
+}
+
+fn read_artifact(path: &Path) -> Option<Artifact> {
+    let file: ArtifactFile = read_streamed(path).ok()?;
+    Some(Artifact {
+        contract_name: file.contract_name,
+        source_name: file.source_name,
+        abi: file.abi,
+        bytecode_size: file.bytecode.map_or(0, |size| size.0),
+        deployed_size: file.deployed_bytecode.map_or(0, |size| size.0),
+        path: path.to_path_buf(),
+    })
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).ok()?.modified().ok()
+}
+
+// Every contract artifact Hardhat produced (skipping the .dbg.json companions). Files
+// whose mtime didn't change since `previous` was built are taken from it unparsed
+fn read_artifacts(project_path: &Path, previous: Option<&ArtifactIndex>) -> Vec<(Artifact, Option<SystemTime>)> {
+    let artifacts_dir = project_path.join("artifacts");
+    let paths: Vec<PathBuf> = project::collect_files(&artifacts_dir, "json")
+        .into_iter()
+        .filter(|path| {
+            !path.to_string_lossy().ends_with(".dbg.json")
+                && !path.starts_with(artifacts_dir.join("build-info"))
+        })
+        .collect();
+
+    let mut artifacts: Vec<(Artifact, Option<SystemTime>)> = paths
+        .into_iter()
+        .filter_map(|path| {
+            let modified = modified(&path);
+            let unchanged = previous.and_then(|previous| {
+                let (was_modified, i) = previous.by_path.get(&path)?;
+                (modified.is_some() && *was_modified == modified).then(|| previous.artifacts[*i].clone())
+            });
+            let artifact = unchanged.or_else(|| read_artifact(&path))?;
+            Some((artifact, modified))
+        })
+        .collect();
+
+    artifacts.sort_by(|(a, _), (b, _)| a.contract_name.cmp(&b.contract_name));
+    artifacts
+}
+
+// Every compile adds or replaces a build-info file, so the directory's mtime and entry
+// count change even when the compile ran outside the GUI
+fn build_stamp(project_path: &Path) -> Option<(SystemTime, usize)> {
+    let dir = project_path.join("artifacts").join("build-info");
+    let modified = fs::metadata(&dir).ok()?.modified().ok()?;
+    Some((modified, fs::read_dir(&dir).ok()?.count()))
+}
+
+// A project's artifacts parsed once and looked up by contract name or fully-qualified
+// name (`contracts/Token.sol:Token`)
+pub struct ArtifactIndex {
+    artifacts: Vec<Artifact>,
+    by_name: HashMap<String, Vec<usize>>,
+    by_fqn: HashMap<String, usize>,
+    by_path: HashMap<PathBuf, (Option<SystemTime>, usize)>,
+}
+
+impl ArtifactIndex {
+    fn build(project_path: &Path, previous: Option<&ArtifactIndex>) -> Self {
+        let mut artifacts = Vec::new();
+        let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut by_fqn = HashMap::new();
+        let mut by_path = HashMap::new();
+        for (i, (artifact, modified)) in read_artifacts(project_path, previous).into_iter().enumerate() {
+            by_name.entry(artifact.contract_name.clone()).or_default().push(i);
+            by_fqn.insert(artifact.fully_qualified_name(), i);
+            by_path.insert(artifact.path.clone(), (modified, i));
+            artifacts.push(artifact);
+        }
+        ArtifactIndex { artifacts, by_name, by_fqn, by_path }
+    }
+
+    pub fn all(&self) -> &[Artifact] {
+        &self.artifacts
+    }
+
+    // Accepts either form; a bare name shared by several sources resolves to the
+    // project's own contract over a dependency's
+    pub fn get(&self, contract: &str) -> Option<&Artifact> {
+        if let Some(i) = self.by_fqn.get(contract) {
+            return Some(&self.artifacts[*i]);
+        }
+        let candidates = self.by_name.get(contract)?;
+        candidates
+            .iter()
+            .map(|i| &self.artifacts[*i])
+            .find(|artifact| !is_dependency_source(&artifact.source_name))
+            .or_else(|| candidates.first().map(|i| &self.artifacts[*i]))
+    }
+}
+
+struct CachedIndex {
+    stamp: Option<(SystemTime, usize)>,
+    // Set after a compile so the next lookup refreshes even if the stamp looks the same
+    stale: bool,
+    index: Arc<ArtifactIndex>,
+}
+
+static INDEXES: OnceLock<Mutex<HashMap<PathBuf, CachedIndex>>> = OnceLock::new();
+
+fn indexes() -> &'static Mutex<HashMap<PathBuf, CachedIndex>> {
+    INDEXES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// Built on first use and refreshed once a compile has changed artifacts/; a refresh
+// only parses the artifact files that changed
+pub fn index(project_path: &Path) -> Arc<ArtifactIndex> {
+    let stamp = build_stamp(project_path);
+    let previous = match indexes().lock().unwrap().get(project_path) {
+        Some(cached) if !cached.stale && cached.stamp == stamp => return cached.index.clone(),
+        Some(cached) => Some(cached.index.clone()),
+        None => None,
+    };
+    let index = Arc::new(ArtifactIndex::build(project_path, previous.as_deref()));
+    indexes()
+        .lock()
+        .unwrap()
+        .insert(project_path.to_path_buf(), CachedIndex { stamp, stale: false, index: index.clone() });
+    index
+}
+
+// Called after anything that may have rewritten artifacts/ (compile, test, deploy)
+pub fn invalidate(project_path: &Path) {
+    if let Some(cached) = indexes().lock().unwrap().get_mut(project_path) {
+        cached.stale = true;
+    }
+}
+
+// Drops the index entirely, e.g. when the project is closed
+pub fn forget(project_path: &Path) {
+    indexes().lock().unwrap().remove(project_path);
+}
+
+// Every artifact in the project, from the shared index
+pub fn load_artifacts(project_path: &Path) -> Vec<Artifact> {
+    index(project_path).all().to_vec()
+}
+
+pub fn read_json(path: &Path) -> Option<Value> {
+    read_streamed(path).ok()
+}
+
+// Newest first, so the first hit for a contract or source is from the latest compile
+pub fn build_info_files(project_path: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<(SystemTime, PathBuf)> =
+        project::collect_files(&project_path.join("artifacts").join("build-info"), "json")
+            .into_iter()
+            .filter_map(|path| Some((fs::metadata(&path).ok()?.modified().ok()?, path)))
+            .collect();
+    files.sort_by_key(|(modified, _)| Reverse(*modified));
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+// The part of a build-info file a command reads. Bytecode, source maps, gas estimates
+// and metadata, most of the file, are skipped while streaming
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BuildInfoView {
+    // input.sources[*].content and output.sources[*] (ids and ASTs)
+    Sources,
+    // output.contracts[*][*] with only storageLayout and evm.methodIdentifiers
+    Contracts,
+}
+
+#[derive(Deserialize, Default)]
+struct SourceInput {
+    #[serde(default)]
+    content: String,
+}
+
+#[derive(Deserialize, Default)]
+struct InputSources {
+    #[serde(default)]
+    sources: BTreeMap<String, SourceInput>,
+}
+
+#[derive(Deserialize, Default)]
+struct OutputSources {
+    #[serde(default)]
+    sources: Map<String, Value>,
+}
+
+#[derive(Deserialize, Default)]
+struct SourcesFile {
+    #[serde(default)]
+    input: InputSources,
+    #[serde(default)]
+    output: OutputSources,
+}
+
+#[derive(Deserialize, Default)]
+struct ContractEvm {
+    #[serde(rename = "methodIdentifiers")]
+    method_identifiers: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct ContractOutput {
+    #[serde(rename = "storageLayout")]
+    storage_layout: Option<Value>,
+    #[serde(default)]
+    evm: ContractEvm,
+}
+
+#[derive(Deserialize, Default)]
+struct OutputContracts {
+    #[serde(default)]
+    contracts: BTreeMap<String, BTreeMap<String, ContractOutput>>,
+}
+
+#[derive(Deserialize, Default)]
+struct ContractsFile {
+    #[serde(default)]
+    output: OutputContracts,
+}
+
+fn object<I: IntoIterator<Item = (String, Value)>>(entries: I) -> Value {
+    Value::Object(entries.into_iter().collect())
+}
+
+// Rebuilt as JSON with the same paths as the full file, so lookups like
+// `/output/sources/<name>/ast` work unchanged; values are moved, not copied
+impl SourcesFile {
+    fn into_value(self) -> Value {
+        let input = self
+            .input
+            .sources
+            .into_iter()
+            .map(|(name, source)| (name, object([("content".to_string(), Value::String(source.content))])));
+        object([
+            ("input".to_string(), object([("sources".to_string(), object(input))])),
+            ("output".to_string(), object([("sources".to_string(), object(self.output.sources))])),
+        ])
+    }
+}
+
+impl ContractsFile {
+    fn into_value(self) -> Value {
+        let contracts = self.output.contracts.into_iter().map(|(source, contracts)| {
+            let contracts = contracts.into_iter().map(|(name, contract)| {
+                let mut fields = Map::new();
+                if let Some(layout) = contract.storage_layout {
+                    fields.insert("storageLayout".to_string(), layout);
+                }
+                if let Some(ids) = contract.evm.method_identifiers {
+                    fields.insert("evm".to_string(), object([("methodIdentifiers".to_string(), ids)]));
+                }
+                (name, Value::Object(fields))
+            });
+            (source, object(contracts))
+        });
+        object([("output".to_string(), object([("contracts".to_string(), object(contracts))]))])
+    }
+}
+
+// Parsed views of recently read build-info files, least recently used first. Bounded by
+// the size of the files they came from; a project rarely has more than a few
+const BUILD_INFO_CACHE_BYTES: u64 = 256 * 1024 * 1024;
+
+struct CachedBuildInfo {
+    path: PathBuf,
+    view: BuildInfoView,
+    modified: SystemTime,
+    size: u64,
+    value: Arc<Value>,
+}
+
+static BUILD_INFOS: OnceLock<Mutex<Vec<CachedBuildInfo>>> = OnceLock::new();
+
+fn build_infos() -> &'static Mutex<Vec<CachedBuildInfo>> {
+    BUILD_INFOS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+// One view of a build-info file, parsed once per mtime
+pub fn read_build_info(path: &Path, view: BuildInfoView) -> Option<Arc<Value>> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?;
+    {
+        let mut cache = build_infos().lock().unwrap();
+        if let Some(i) = cache.iter().position(|cached| cached.path == path && cached.view == view) {
+            let cached = cache.remove(i);
+            if cached.modified == modified {
+                let value = cached.value.clone();
+                cache.push(cached);
+                return Some(value);
+            }
+        }
+    }
+
+    let value = Arc::new(match view {
+        BuildInfoView::Sources => read_streamed::<SourcesFile>(path).ok()?.into_value(),
+        BuildInfoView::Contracts => read_streamed::<ContractsFile>(path).ok()?.into_value(),
+    });
+    let mut cache = build_infos().lock().unwrap();
+    cache.push(CachedBuildInfo { path: path.to_path_buf(), view, modified, size: metadata.len(), value: value.clone() });
+    let mut total: u64 = cache.iter().map(|cached| cached.size).sum();
+    while total > BUILD_INFO_CACHE_BYTES && cache.len() > 1 {
+        total -= cache.remove(0).size;
+    }
+    Some(value)
+}
+
+// solc's output for one contract (`output.contracts[source][name]`) from a build-info
+pub fn contract_output(build_info: &Value, contract_name: &str) -> Option<(String, Value)> {
+    build_info
+        .pointer("/output/contracts")?
+        .as_object()?
+        .iter()
+        .find_map(|(source, contracts)| Some((source.clone(), contracts.get(contract_name)?.clone())))
+}
+
+// Sources pulled in from packages rather than written in the project itself
+pub fn is_dependency_source(source_name: &str) -> bool {
+    source_name.starts_with('@') || source_name.contains("node_modules")
+}
+
+// A compiled source file as recorded in build-info: the exact source text that was
+// compiled plus solc's AST for it
+pub struct SourceUnit {
+    pub source_name: String,
+    pub content: String,
+    pub ast: Value,
+}
+
+// Each compile writes a new build-info file; walking them newest first and keeping the
+// first occurrence of every source yields the most recent AST per file
+pub fn load_source_units(project_path: &Path) -> Vec<SourceUnit> {
+    let mut units: Vec<SourceUnit> = Vec::new();
+    for path in build_info_files(project_path) {
+        let Some(build_info) = read_build_info(&path, BuildInfoView::Sources) else {
+            continue;
+        };
+        let Some(sources) = build_info.pointer("/output/sources").and_then(Value::as_object) else {
+            continue;
+        };
+
+        for (source_name, output) in sources {
+            if units.iter().any(|unit| &unit.source_name == source_name) {
+                continue;
+            }
+            let content = build_info
+                .pointer("/input/sources")
+                .and_then(|input| input.get(source_name))
+                .and_then(|input| input.get("content"))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            units.push(SourceUnit {
+                source_name: source_name.clone(),
+                content,
+                ast: output.get("ast").cloned().unwrap_or(Value::Null),
+            });
+        }
+    }
+
+    units.sort_by(|a, b| a.source_name.cmp(&b.source_name));
+    units
+}
+
+// One build-info's sources indexed by AST id. Ids are only unique within a single
+// compilation, so cross-references must be resolved against the same build-info
+pub struct Compilation<'a> {
+    pub build_info: &'a Value,
+    nodes: HashMap<i64, &'a Value>,
+    files: HashMap<String, (&'a str, &'a str)>,
+}
+
+impl<'a> Compilation<'a> {
+    pub fn new(build_info: &'a Value) -> Self {
+        let mut nodes = HashMap::new();
+        let mut files = HashMap::new();
+        let sources = build_info.pointer("/output/sources").and_then(Value::as_object).into_iter().flatten();
+        for (name, output) in sources {
+            let content = build_info
+                .pointer("/input/sources")
+                .and_then(|input| input.get(name))
+                .and_then(|input| input.get("content"))
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if let Some(id) = output.get("id").and_then(Value::as_i64) {
+                files.insert(id.to_string(), (name.as_str(), content));
+            }
+            ast::walk(&output["ast"], &mut |node| {
+                if matches!(ast::node_type(node), "ContractDefinition" | "FunctionDefinition" | "ModifierDefinition") {
+                    if let Some(id) = ast::id(node) {
+                        nodes.insert(id, node);
+                    }
+                }
+            });
+        }
+        Compilation { build_info, nodes, files }
+    }
+
+    // Contract, function and modifier definitions by id
+    pub fn node(&self, id: i64) -> Option<&'a Value> {
+        self.nodes.get(&id).copied()
+    }
+
+    pub fn source_names(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.files.values().map(|(name, _)| *name)
+    }
+
+    pub fn source_ast(&self, source_name: &str) -> Option<&'a Value> {
+        self.build_info.pointer("/output/sources")?.get(source_name)?.get("ast")
+    }
+
+    pub fn location(&self, node: &Value) -> Option<SourceLocation> {
+        let src = ast::str_field(node, "src");
+        let (file, content) = self.files.get(src.rsplit(':').next()?)?;
+        Some(SourceLocation { file: file.to_string(), line: src_line(src, content) })
+    }
+}
+
+// Converts a solc `src` attribute ("start:length:fileIndex") into a 1-based line
+pub fn src_line(src: &str, content: &str) -> Option<u32> {
+    let start: usize = src.split(':').next()?.parse().ok()?;
+    let prefix = content.as_bytes().get(..start)?;
+    Some(prefix.iter().filter(|b| **b == b'\n').count() as u32 + 1)
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ArtifactDetails {
+    #[serde(flatten)]
+    pub artifact: Artifact,
+    pub code: Option<ArtifactCode>,
+}
+
+// The bytecode is only read when `include_code` is set
+#[tauri::command]
+pub async fn get_artifact(
+    project_path: String,
+    contract: String,
+    include_code: Option<bool>,
+) -> Result<ArtifactDetails, AppError> {
+    let details = tauri::async_runtime::spawn_blocking(move || {
+        let index = index(Path::new(&project_path));
+        let artifact = index
+            .get(&contract)
+            .ok_or_else(|| format!("No compiled artifact found for {}", contract))?;
+        let code = match include_code {
+            Some(true) => Some(artifact.code()?),
+            _ => None,
+        };
+        Ok::<_, String>(ArtifactDetails { artifact: artifact.clone(), code })
+    })
+    .await
+    .map_err(|e| format!("Failed to load artifacts: {}", e))?;
+    Ok(details?)
+}
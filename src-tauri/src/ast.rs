@@ -0,0 +1,61 @@
+use serde_json::Value;
+
+// Helpers for walking solc's compact JSON AST
+
+pub fn node_type(node: &Value) -> &str {
+    node.get("nodeType").and_then(Value::as_str).unwrap_or_default()
+}
+
+pub fn str_field<'a>(node: &'a Value, field: &str) -> &'a str {
+    node.get(field).and_then(Value::as_str).unwrap_or_default()
+}
+
+pub fn id(node: &Value) -> Option<i64> {
+    node.get("id").and_then(Value::as_i64)
+}
+
+// Visits every AST node below (and including) `node`. Order follows JSON keys, not
+// the source; use `src_start` when statement order matters
+pub fn walk<'a>(node: &'a Value, visit: &mut impl FnMut(&'a Value)) {
+    match node {
+        Value::Object(map) => {
+            if map.contains_key("nodeType") {
+                visit(node);
+            }
+            for child in map.values() {
+                walk(child, visit);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                walk(item, visit);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn find_all<'a>(node: &'a Value, kind: &str) -> Vec<&'a Value> {
+    let mut found = Vec::new();
+    walk(node, &mut |n| {
+        if node_type(n) == kind {
+            found.push(n);
+        }
+    });
+    found
+}
+
+// Offset of a node in its source file, for ordering statements within a function
+pub fn src_start(node: &Value) -> usize {
+    str_field(node, "src")
+        .split(':')
+        .next()
+        .and_then(|start| start.parse().ok())
+        .unwrap_or(0)
+}
+
+pub fn type_string(node: &Value) -> &str {
+    node.pointer("/typeDescriptions/typeString")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+}
@@ -0,0 +1,224 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::ipc::{Invoke, InvokeBody};
+use tauri::{AppHandle, Manager};
+
+use crate::db;
+use crate::error::AppError;
+use crate::metrics::Outcome;
+use crate::store;
+
+const AUDIT_FILE: &str = "audit.db";
+const DEFAULT_AUDIT_LIMIT: usize = 500;
+// Argument names whose values are never written; matched case-insensitively as substrings
+const SECRET_ARGS: [&str; 9] =
+    ["private", "secret", "password", "mnemonic", "apikey", "api_key", "access_token", "accesskey", "access_key"];
+
+// Shared by every project, so it lives in the app data directory rather than a project store
+static AUDIT_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditStatus {
+    // The command was dispatched; commands that spawn a process get a second entry
+    // with the outcome once it exits
+    Invoked,
+    UnknownCommand,
+    Succeeded,
+    Failed,
+    Cancelled,
+    TimedOut,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    pub user: String,
+    pub command: String,
+    #[serde(default)]
+    pub project_path: Option<String>,
+    #[serde(default)]
+    pub args: Value,
+    pub status: AuditStatus,
+    #[serde(default)]
+    pub execution_id: Option<String>,
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct AuditQuery {
+    pub command: Option<String>,
+    pub project_path: Option<String>,
+    pub user: Option<String>,
+    pub status: Option<AuditStatus>,
+    pub since: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    let _ = AUDIT_PATH.set(dir.join(AUDIT_FILE));
+    Ok(())
+}
+
+fn current_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn is_secret(name: &str) -> bool {
+    let name = name.to_lowercase();
+    // A bare `key` is always a credential (set_provider_key)
+    name == "key" || SECRET_ARGS.iter().any(|secret| name.contains(secret))
+}
+
+fn redact(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| {
+                    let v = if is_secret(k) { Value::String("[REDACTED]".to_string()) } else { redact(v) };
+                    (k.clone(), v)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact).collect()),
+        other => other.clone(),
+    }
+}
+
+pub fn insert(conn: &Connection, entry: &AuditEntry) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO audit (timestamp, user, command, project_path, args, status, execution_id, exit_code)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            entry.timestamp,
+            entry.user,
+            entry.command,
+            entry.project_path,
+            entry.args.to_string(),
+            db::enum_to_sql(&entry.status),
+            entry.execution_id,
+            entry.exit_code,
+        ],
+    )?;
+    Ok(())
+}
+
+// Audit failures must never block the command itself, so errors are only logged
+fn append(entry: &AuditEntry) {
+    let Some(path) = AUDIT_PATH.get() else {
+        return;
+    };
+    if let Err(e) = db::with_at(path, |conn| insert(conn, entry)) {
+        tracing::warn!(error = %e, "failed to write audit entry");
+    }
+}
+
+fn invoked(invoke: &Invoke) -> AuditEntry {
+    let args = match invoke.message.payload() {
+        InvokeBody::Json(args) => redact(args),
+        InvokeBody::Raw(_) => Value::Null,
+    };
+    AuditEntry {
+        timestamp: store::timestamp_millis(),
+        user: current_user(),
+        command: invoke.message.command().to_string(),
+        project_path: args.get("projectPath").and_then(Value::as_str).map(str::to_string),
+        execution_id: args.get("executionId").and_then(Value::as_str).map(str::to_string),
+        args,
+        status: AuditStatus::Invoked,
+        exit_code: None,
+    }
+}
+
+// Wraps the generated invoke handler so every command is recorded as it's dispatched
+pub fn audited<F>(handler: F) -> impl Fn(Invoke) -> bool + Send + Sync + 'static
+where
+    F: Fn(Invoke) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        let mut entry = invoked(&invoke);
+        let known = handler(invoke);
+        if !known {
+            entry.status = AuditStatus::UnknownCommand;
+        }
+        append(&entry);
+        known
+    }
+}
+
+// Called by run_tracked so deploys, upgrades and tasks have a recorded result
+pub fn record_outcome(
+    kind: &str,
+    execution_id: &str,
+    project_path: Option<String>,
+    outcome: Outcome,
+    exit_code: Option<i32>,
+) {
+    let status = match outcome {
+        Outcome::Success => AuditStatus::Succeeded,
+        Outcome::Failure => AuditStatus::Failed,
+        Outcome::Cancelled => AuditStatus::Cancelled,
+        Outcome::TimedOut => AuditStatus::TimedOut,
+    };
+    append(&AuditEntry {
+        timestamp: store::timestamp_millis(),
+        user: current_user(),
+        command: kind.to_string(),
+        project_path,
+        args: Value::Null,
+        status,
+        execution_id: Some(execution_id.to_string()),
+        exit_code,
+    });
+}
+
+// Newest entries last
+#[tauri::command]
+pub async fn get_audit_log(query: Option<AuditQuery>) -> Result<Vec<AuditEntry>, AppError> {
+    let query = query.unwrap_or_default();
+    let Some(path) = AUDIT_PATH.get() else {
+        return Err("Audit log is not initialized".to_string().into());
+    };
+    let status = query.status.as_ref().map(db::enum_to_sql);
+    let limit = query.limit.unwrap_or(DEFAULT_AUDIT_LIMIT) as i64;
+    let mut entries = db::with_at(path, |conn| {
+        conn.prepare_cached(
+            "SELECT timestamp, user, command, project_path, args, status, execution_id, exit_code FROM audit
+             WHERE (?1 IS NULL OR command = ?1) AND (?2 IS NULL OR project_path = ?2) AND (?3 IS NULL OR user = ?3)
+               AND (?4 IS NULL OR status = ?4) AND timestamp >= ?5
+             ORDER BY id DESC LIMIT ?6",
+        )?
+        .query_map(
+            params![query.command, query.project_path, query.user, status, query.since.unwrap_or(0), limit],
+            |row| {
+                let args: String = row.get(4)?;
+                Ok(AuditEntry {
+                    timestamp: row.get(0)?,
+                    user: row.get(1)?,
+                    command: row.get(2)?,
+                    project_path: row.get(3)?,
+                    args: serde_json::from_str(&args).unwrap_or(Value::Null),
+                    status: db::enum_from_sql(row.get(5)?)?,
+                    execution_id: row.get(6)?,
+                    exit_code: row.get(7)?,
+                })
+            },
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })?;
+    entries.reverse();
+    Ok(entries)
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::State;
+
+use crate::artifacts;
+use crate::deployments;
+use crate::error::AppError;
+use crate::project;
+use crate::rpc;
+
+// Names the console runner injects into every evaluation context
+const CONSOLE_GLOBALS: [&str; 5] = ["hre", "ethers", "network", "artifacts", "config"];
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ContractCompletion {
+    pub name: String,
+    pub source_name: String,
+    pub addresses: Vec<String>,
+    pub functions: Vec<String>,
+    pub events: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AutocompleteData {
+    pub globals: Vec<String>,
+    pub signers: Vec<String>,
+    pub contracts: Vec<ContractCompletion>,
+}
+
+struct CachedContracts {
+    fingerprint: Option<SystemTime>,
+    contracts: Vec<ContractCompletion>,
+}
+
+// Contract data only changes when a compile or deploy writes new files, so it is
+// cached per project and rebuilt when the newest artifact/deployment file changes
+#[derive(Default)]
+pub struct AutocompleteCache(Mutex<HashMap<String, CachedContracts>>);
+
+fn fingerprint(root: &Path) -> Option<SystemTime> {
+    let mut files = project::collect_files(&root.join("artifacts").join("build-info"), "json");
+    files.extend(project::collect_files(&root.join("ignition").join("deployments"), "json"));
+    files
+        .iter()
+        .filter_map(|f| fs::metadata(f).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+fn build_contracts(root: &Path) -> Vec<ContractCompletion> {
+    let deployments = deployments::load_deployments(root);
+
+    artifacts::load_artifacts(root)
+        .into_iter()
+        .map(|artifact| {
+            let addresses = deployments
+                .iter()
+                .filter(|d| d.contract_name == artifact.contract_name)
+                .map(|d| d.address.clone())
+                .collect();
+            let functions = artifact
+                .abi
+                .iter()
+                .filter(|item| item.is_function())
+                .map(|item| item.signature())
+                .collect();
+            let events = artifact
+                .abi
+                .iter()
+                .filter(|item| item.is_event())
+                .map(|item| item.signature())
+                .collect();
+
+            ContractCompletion {
+                name: artifact.contract_name,
+                source_name: artifact.source_name,
+                addresses,
+                functions,
+                events,
+            }
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_console_autocomplete(
+    cache: State<'_, AutocompleteCache>,
+    project_path: String,
+) -> Result<AutocompleteData, AppError> {
+    let root = Path::new(&project_path);
+    let current = fingerprint(root);
+
+    let contracts = {
+        let mut cache = cache.0.lock().unwrap();
+        match cache.get(&project_path) {
+            Some(cached) if cached.fingerprint == current => cached.contracts.clone(),
+            _ => {
+                let contracts = build_contracts(root);
+                cache.insert(
+                    project_path.clone(),
+                    CachedContracts { fingerprint: current, contracts: contracts.clone() },
+                );
+                contracts
+            }
+        }
+    };
+
+    // Signers come from the live node; an offline node just means no signer hints
+    let signers = rpc::call(&rpc::default_url(), "eth_accounts", json!([]))
+        .await
+        .ok()
+        .and_then(|accounts| serde_json::from_value(accounts).ok())
+        .unwrap_or_default();
+
+    Ok(AutocompleteData {
+        globals: CONSOLE_GLOBALS.iter().map(|g| g.to_string()).collect(),
+        signers,
+        contracts,
+    })
+}
@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::address_book;
+use crate::decode;
+use crate::error::AppError;
+use crate::networks::{self, NetworkProfile};
+use crate::provider_keys;
+use crate::rpc;
+use crate::store;
+use crate::utils::format_units;
+
+const BALANCE_OF_SELECTOR: &str = "0x70a08231";
+const DECIMALS_SELECTOR: &str = "0x313ce567";
+const SYMBOL_SELECTOR: &str = "0x95d89b41";
+
+// Amounts are decimal strings: raw in the smallest unit, formatted with the token's decimals
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TokenBalance {
+    pub token: String,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+    pub balance: Option<String>,
+    pub formatted: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NetworkBalance {
+    pub network: String,
+    pub chain_id: u64,
+    pub currency_symbol: String,
+    pub native_wei: Option<String>,
+    pub native_formatted: Option<String>,
+    pub tokens: Vec<TokenBalance>,
+    // An unreachable network fills this instead of failing the whole overview
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BalanceOverview {
+    pub address: String,
+    pub networks: Vec<NetworkBalance>,
+    pub checked_at: u64,
+}
+
+// Old tokens (MKR, SAI) return symbol as bytes32 rather than a string
+fn decode_symbol(result: &str) -> Option<String> {
+    let data = decode::from_hex(result)?;
+    let (_, params) = decode::parse_signature("symbol(string)")?;
+    let decoded = decode::decode_params(&params, &data).and_then(|p| p.into_iter().next());
+    if let Some(Value::String(symbol)) = decoded.map(|p| p.value) {
+        return Some(symbol);
+    }
+    let word = data.get(..32)?;
+    let symbol = String::from_utf8_lossy(word).trim_end_matches('\0').to_string();
+    (!symbol.is_empty()).then_some(symbol)
+}
+
+async fn token_balance(url: &str, address: &str, token: &str) -> TokenBalance {
+    let mut balance = TokenBalance {
+        token: token.to_string(),
+        symbol: None,
+        decimals: None,
+        balance: None,
+        formatted: None,
+        error: None,
+    };
+    if !address_book::is_address(token) {
+        balance.error = Some(format!("Invalid token address: {}", token));
+        return balance;
+    }
+
+    let data = format!("{}{:0>64}", BALANCE_OF_SELECTOR, address.trim_start_matches("0x").to_lowercase());
+    match rpc::eth_call(url, token, &data).await {
+        Ok(result) if result.len() > 2 => balance.balance = Some(decode::quantity_to_decimal(&result)),
+        Ok(_) => balance.error = Some("Not an ERC20 contract on this network".to_string()),
+        Err(e) => balance.error = Some(e.to_string()),
+    }
+    balance.decimals = rpc::eth_call(url, token, DECIMALS_SELECTOR)
+        .await
+        .ok()
+        .and_then(|result| decode::quantity_to_decimal(&result).parse().ok());
+    balance.symbol = rpc::eth_call(url, token, SYMBOL_SELECTOR).await.ok().and_then(|r| decode_symbol(&r));
+    if let (Some(raw), Some(decimals)) = (&balance.balance, balance.decimals) {
+        balance.formatted = Some(format_units(raw, decimals));
+    }
+    balance
+}
+
+async fn network_balance(profile: NetworkProfile, address: String, tokens: Vec<String>) -> NetworkBalance {
+    let url = provider_keys::expand(&profile.rpc_url);
+    let mut row = NetworkBalance {
+        network: profile.name,
+        chain_id: profile.chain_id,
+        currency_symbol: profile.currency_symbol,
+        native_wei: None,
+        native_formatted: None,
+        tokens: Vec::new(),
+        error: None,
+    };
+    match rpc::call(&url, "eth_getBalance", json!([address, "latest"])).await {
+        Ok(result) => {
+            let wei = decode::quantity_to_decimal(result.as_str().unwrap_or("0x0"));
+            row.native_formatted = Some(format_units(&wei, 18));
+            row.native_wei = Some(wei);
+        }
+        // Skip the token calls; they'd fail the same way
+        Err(e) => {
+            row.error = Some(e.to_string());
+            return row;
+        }
+    }
+    for token in tokens {
+        row.tokens.push(token_balance(&url, &address, &token).await);
+    }
+    row
+}
+
+// `tokens` maps a profile name to the ERC20 addresses to check there; `networks`
+// limits the overview to some profiles. Every network is queried at once
+#[tauri::command]
+pub async fn get_cross_network_balances(
+    address: String,
+    tokens: Option<BTreeMap<String, Vec<String>>>,
+    networks: Option<Vec<String>>,
+) -> Result<BalanceOverview, AppError> {
+    if !address_book::is_address(&address) {
+        return Err(format!("Invalid address: {}", address).into());
+    }
+    let mut tokens = tokens.unwrap_or_default();
+    let profiles: Vec<NetworkProfile> = networks::load_profiles()?
+        .into_iter()
+        .filter(|p| networks.as_ref().is_none_or(|names| names.contains(&p.name)))
+        .collect();
+
+    let handles: Vec<_> = profiles
+        .into_iter()
+        .map(|profile| {
+            let network_tokens = tokens.remove(&profile.name).unwrap_or_default();
+            tauri::async_runtime::spawn(network_balance(profile, address.clone(), network_tokens))
+        })
+        .collect();
+    let mut rows = Vec::new();
+    for handle in handles {
+        rows.push(handle.await.map_err(|e| format!("Failed to check balances: {}", e))?);
+    }
+
+    Ok(BalanceOverview { address, networks: rows, checked_at: store::timestamp_millis() })
+}
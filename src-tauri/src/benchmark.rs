@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppError;
+use crate::metrics;
+use crate::processes;
+use crate::queue::JobSpec;
+
+const MAX_ITERATIONS: u32 = 50;
+// Hardhat's default build output locations
+const BUILD_DIRS: [&str; 2] = ["cache", "artifacts"];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheMode {
+    // One untimed run first, so every timed run reuses the compile cache
+    #[default]
+    Warm,
+    // cache/ and artifacts/ are removed before every run
+    Cold,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BenchmarkRun {
+    pub iteration: u32,
+    pub duration_ms: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub operation: String,
+    pub cache: CacheMode,
+    pub runs: Vec<BenchmarkRun>,
+    pub mean_ms: u64,
+    pub median_ms: u64,
+    pub min_ms: u64,
+    pub max_ms: u64,
+    pub p95_ms: u64,
+    pub stddev_ms: f64,
+}
+
+fn clear_build_cache(project_path: &str) -> Result<(), String> {
+    for dir in BUILD_DIRS {
+        let path = Path::new(project_path).join(dir);
+        if path.exists() {
+            fs::remove_dir_all(&path).map_err(|e| format!("Failed to clear {}: {}", dir, e))?;
+        }
+    }
+    Ok(())
+}
+
+async fn run_once(app: &AppHandle, project_path: &str, operation: &JobSpec) -> Result<u64, AppError> {
+    let cmd = operation.command(project_path)?;
+    let started = Instant::now();
+    let output = processes::run_tracked(app, None, operation.kind(), cmd).await?;
+    let duration_ms = started.elapsed().as_millis() as u64;
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed during benchmark: {}{}",
+            operation.kind(),
+            String::from_utf8_lossy(&output.stderr),
+            String::from_utf8_lossy(&output.stdout)
+        ).into());
+    }
+    Ok(duration_ms)
+}
+
+fn summarize(operation: &JobSpec, cache: CacheMode, runs: Vec<BenchmarkRun>) -> BenchmarkResult {
+    let mut durations: Vec<u64> = runs.iter().map(|r| r.duration_ms).collect();
+    durations.sort_unstable();
+    let count = durations.len().max(1) as f64;
+    let mean = durations.iter().sum::<u64>() as f64 / count;
+    let variance = durations.iter().map(|d| (*d as f64 - mean).powi(2)).sum::<f64>() / count;
+    BenchmarkResult {
+        operation: operation.kind().to_string(),
+        cache,
+        mean_ms: mean.round() as u64,
+        median_ms: metrics::percentile(&durations, 50),
+        min_ms: durations.first().copied().unwrap_or(0),
+        max_ms: durations.last().copied().unwrap_or(0),
+        p95_ms: metrics::percentile(&durations, 95),
+        stddev_ms: variance.sqrt(),
+        runs,
+    }
+}
+
+// Runs the operation `iterations` times back to back. A failing run aborts the benchmark,
+// since timings of a broken build say nothing about the settings being compared
+#[tauri::command]
+pub async fn run_benchmark(
+    app: AppHandle,
+    project_path: String,
+    operation: JobSpec,
+    iterations: u32,
+    cache: Option<CacheMode>,
+) -> Result<BenchmarkResult, AppError> {
+    if !(1..=MAX_ITERATIONS).contains(&iterations) {
+        return Err(format!("Iterations must be between 1 and {}", MAX_ITERATIONS).into());
+    }
+    let cache = cache.unwrap_or_default();
+
+    if cache == CacheMode::Warm {
+        run_once(&app, &project_path, &operation).await?;
+    }
+
+    let mut runs = Vec::new();
+    for iteration in 1..=iterations {
+        if cache == CacheMode::Cold {
+            clear_build_cache(&project_path)?;
+        }
+        let duration_ms = run_once(&app, &project_path, &operation).await?;
+        let run = BenchmarkRun { iteration, duration_ms };
+        let _ = app.emit("benchmark-progress", run.clone());
+        runs.push(run);
+    }
+
+    Ok(summarize(&operation, cache, runs))
+}
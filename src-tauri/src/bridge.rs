@@ -0,0 +1,551 @@
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::decode;
+use crate::error::AppError;
+use crate::rpc;
+
+// Predeploys on every OP Stack L2
+const OP_L2_MESSENGER: &str = "0x4200000000000000000000000000000000000007";
+const OP_L2_STANDARD_BRIDGE: &str = "0x4200000000000000000000000000000000000010";
+// The portal's l2Sender outside of a withdrawal
+const OP_DEFAULT_L2_SENDER: &str = "0x000000000000000000000000000000000000dead";
+// Stand-in token address the L2 bridge uses for ETH
+const OP_LEGACY_ETH: &str = "0xdeaddeaddeaddeaddeaddeaddeaddeaddead0000";
+// ArbSys precompile on every Arbitrum chain
+const ARB_SYS: &str = "0x0000000000000000000000000000000000000064";
+// The outbox's l2ToL1 context sender (and packed l1Block) outside of an execution
+const ARB_DEFAULT_CONTEXT_SENDER: &str = "0xffffffffffffffffffffffffffffffffffffffff";
+// Inbox message kinds
+const ARB_RETRYABLE_KIND: u64 = 9;
+const ARB_ETH_DEPOSIT_KIND: u64 = 12;
+// Added to L1 contract addresses when they act on L2
+const L1_TO_L2_ALIAS_OFFSET: &str = "0x1111000000000000000000000000000000001111";
+
+// Challenge periods the L1 clock is moved past before a withdrawal is relayed
+const OP_FINALIZATION_SECS: u64 = 7 * 24 * 3600;
+// 45818 L1 blocks of 12 seconds
+const ARB_FINALIZATION_SECS: u64 = 45_818 * 12;
+// Storage slots searched for the portal's and outbox's sender variables
+const SLOT_SCAN: u64 = 256;
+// Gas for the relayed call on top of the message's own limit
+const RELAY_GAS: u64 = 1_000_000;
+// Given to impersonated system addresses so they can pay for the relay transaction
+const GAS_ALLOWANCE_WEI: u128 = 1_000_000_000_000_000_000;
+
+// L2 chain id, L1CrossDomainMessenger, OptimismPortal, L1StandardBridge
+const OP_STACK_CHAINS: [(u64, &str, &str, &str); 4] = [
+    (10, "0x25ace71c97B33Cc4729CF772ae268934F7ab5fA1", "0xbEb5Fc579115071764c7423A4f12eDde41f106Ed", "0x99C9fc46f92E8a1c0deC1b1747d010903E884bE1"),
+    (8453, "0x866E82a600A1414e583f7F13623F1aC5d58b0Afa", "0x49048044D57e1C92A77f79988d21Fa8fAF74E97e", "0x3154Cf16ccdb4C6d922629664174b904d80F2C35"),
+    (11155420, "0x58Cc85b8D04EA49cC6DBd3CbFFd00B4B8D6cb3ef", "0x16Fc5058F25648194471939df75CF27A2fdC48BC", "0xFBb0621E0B23b5478B630BD55a5f21f67730B0F1"),
+    (84532, "0xC34855F4De64F1840e5686e64278da901e261f20", "0x49f53e41452C74589E85cA1677426Ba426459e85", "0xfd0Bf71F60660E2f608ed56e1659C450eB113120"),
+];
+
+// L2 chain id, Inbox, Bridge, Outbox
+const ARBITRUM_CHAINS: [(u64, &str, &str, &str); 2] = [
+    (42161, "0x4Dbd4fc535Ac27206064B68FfCf827b0A60BAB3f", "0x8315177aB297bA92A06054cE80a67Ed4DBd7ed3a", "0x0B9857ae2D4A3DBe74ffE1d7DF045bb7F96E4840"),
+    (421614, "0xaAe29B0366299461418F5324a79Afc425BE5ae21", "0x38f918D0E9F1b721EDaA41302E399fa1B79333a9", "0x65f07C7D521164a4d5DaC6eB8Fac8DA067A3B78F"),
+];
+
+// The rollup's canonical L1 contracts; known chains are looked up by the L2 chain id
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "rollup", rename_all = "snake_case")]
+pub enum BridgeContracts {
+    OpStack {
+        l1_messenger: String,
+        portal: String,
+        l1_standard_bridge: String,
+    },
+    Arbitrum {
+        inbox: String,
+        bridge: String,
+        outbox: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeDirection {
+    // L1 -> L2
+    Deposit,
+    // L2 -> L1
+    Withdrawal,
+}
+
+// A forked L1 and a fork of one of its rollups, each on its own local node
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BridgeForks {
+    pub l1_rpc_url: String,
+    pub l2_rpc_url: String,
+    pub contracts: Option<BridgeContracts>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RelayedMessage {
+    pub sender: String,
+    pub target: String,
+    // wei
+    pub value: String,
+    pub data: String,
+    // None when the destination only needed a balance credit
+    pub relay_tx: Option<String>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BridgeResult {
+    pub direction: BridgeDirection,
+    pub source_tx: String,
+    pub messages: Vec<RelayedMessage>,
+    // Seconds the L1 clock was moved forward to get past the challenge period
+    pub fast_forwarded_secs: Option<u64>,
+}
+
+// What a message needs on the destination chain
+enum Relay {
+    // relayMessage on the destination messenger, called as the source messenger
+    OpMessage { nonce: String, sender: String, target: String, value: String, gas_limit: u64, message: String },
+    ArbEthDeposit { to: String, value: u128 },
+    // Retryable ticket redeemed as a plain call from the (aliased) L1 sender
+    ArbRetryable { from: String, to: String, value: u128, gas_limit: u64, data: String },
+    // Bridge.executeCall from the outbox, with the outbox reporting `caller` as L2 sender
+    ArbOutbox { caller: String, to: String, value: u128, data: String },
+}
+
+fn known_contracts(chain_id: u64) -> Option<BridgeContracts> {
+    let op = OP_STACK_CHAINS.iter().find(|(id, ..)| *id == chain_id).map(|(_, messenger, portal, bridge)| BridgeContracts::OpStack {
+        l1_messenger: messenger.to_string(),
+        portal: portal.to_string(),
+        l1_standard_bridge: bridge.to_string(),
+    });
+    op.or_else(|| {
+        ARBITRUM_CHAINS.iter().find(|(id, ..)| *id == chain_id).map(|(_, inbox, bridge, outbox)| BridgeContracts::Arbitrum {
+            inbox: inbox.to_string(),
+            bridge: bridge.to_string(),
+            outbox: outbox.to_string(),
+        })
+    })
+}
+
+// Hardhat forks run as chain 31337, so the forked chain's id comes from its metadata
+async fn forked_chain_id(url: &str) -> Result<u64, AppError> {
+    let forked = rpc::call(url, "hardhat_metadata", json!([]))
+        .await
+        .ok()
+        .and_then(|metadata| metadata.pointer("/forkedNetwork/chainId").and_then(Value::as_u64));
+    match forked {
+        Some(chain_id) => Ok(chain_id),
+        None => rpc::chain_id(url).await,
+    }
+}
+
+async fn contracts(forks: &BridgeForks) -> Result<BridgeContracts, AppError> {
+    if let Some(contracts) = &forks.contracts {
+        return Ok(contracts.clone());
+    }
+    let chain_id = forked_chain_id(&forks.l2_rpc_url).await?;
+    known_contracts(chain_id)
+        .ok_or_else(|| format!("No canonical bridge contracts are known for chain {}; pass them in `contracts`", chain_id).into())
+}
+
+fn calldata(signature: &str, args: &[Value]) -> Result<String, String> {
+    let (_, params) = decode::parse_signature(signature).ok_or_else(|| format!("Invalid signature: {}", signature))?;
+    let encoded = decode::encode_params(&params, args)?;
+    Ok(format!("{}{}", decode::selector(signature), &decode::to_hex(&encoded)[2..]))
+}
+
+fn decode_data(types: &str, data: &str) -> Option<Vec<Value>> {
+    let (_, params) = decode::parse_signature(&format!("f({})", types))?;
+    let values = decode::decode_params(&params, &decode::from_hex(data)?)?;
+    Some(values.into_iter().map(|param| param.value).collect())
+}
+
+fn lower(address: &str) -> String {
+    address.to_lowercase()
+}
+
+fn word_u128(word: &[u8]) -> u128 {
+    word.iter().skip(word.len().saturating_sub(16)).fold(0, |n, b| (n << 8) | *b as u128)
+}
+
+// (address + 0x1111000000000000000000000000000000001111) mod 2^160
+fn apply_alias(address: &str) -> String {
+    let mut bytes = decode::from_hex(address).unwrap_or_default();
+    let offset = decode::from_hex(L1_TO_L2_ALIAS_OFFSET).unwrap_or_default();
+    let mut carry = 0u16;
+    for (byte, add) in bytes.iter_mut().rev().zip(offset.iter().rev()) {
+        let sum = *byte as u16 + *add as u16 + carry;
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+    decode::to_hex(&bytes)
+}
+
+fn log_address(log: &Value) -> String {
+    log.get("address").and_then(Value::as_str).map(lower).unwrap_or_default()
+}
+
+fn topics(log: &Value) -> Vec<String> {
+    log.get("topics")
+        .and_then(Value::as_array)
+        .map(|topics| topics.iter().filter_map(Value::as_str).map(lower).collect())
+        .unwrap_or_default()
+}
+
+fn log_data(log: &Value) -> &str {
+    log.get("data").and_then(Value::as_str).unwrap_or("0x")
+}
+
+fn topic_address(topic: &str) -> String {
+    format!("0x{}", &topic[topic.len().saturating_sub(40)..])
+}
+
+fn as_string(value: &Value) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}
+
+// SentMessage + SentMessageExtension1 pairs from either messenger
+fn op_messages(logs: &[Value], messenger: &str) -> Vec<Relay> {
+    let sent = decode::event_topic("SentMessage(address,address,bytes,uint256,uint256)");
+    let extension = decode::event_topic("SentMessageExtension1(address,uint256)");
+    let mut relays = Vec::new();
+    for (i, log) in logs.iter().enumerate() {
+        let topics = topics(log);
+        if log_address(log) != lower(messenger) || topics.first() != Some(&sent) || topics.len() < 2 {
+            continue;
+        }
+        let Some(fields) = decode_data("address,bytes,uint256,uint256", log_data(log)) else {
+            continue;
+        };
+        let value = logs
+            .get(i + 1)
+            .filter(|next| topics_match(next, &extension))
+            .and_then(|next| decode_data("uint256", log_data(next)))
+            .map(|values| as_string(&values[0]))
+            .unwrap_or_else(|| "0".to_string());
+        relays.push(Relay::OpMessage {
+            target: topic_address(&topics[1]),
+            sender: as_string(&fields[0]),
+            message: as_string(&fields[1]),
+            nonce: as_string(&fields[2]),
+            gas_limit: as_string(&fields[3]).parse().unwrap_or(0),
+            value,
+        });
+    }
+    relays
+}
+
+fn topics_match(log: &Value, topic: &str) -> bool {
+    topics(log).first().map(String::as_str) == Some(topic)
+}
+
+// Bridge.MessageDelivered carries the kind and sender, the inbox's InboxMessageDelivered
+// with the same message number carries the payload
+fn arb_deposits(logs: &[Value], inbox: &str, bridge: &str) -> Vec<Relay> {
+    let delivered = decode::event_topic("MessageDelivered(uint256,bytes32,address,uint8,address,bytes32,uint256,uint64)");
+    let inbox_delivered = decode::event_topic("InboxMessageDelivered(uint256,bytes)");
+    let mut relays = Vec::new();
+    for log in logs.iter().filter(|log| log_address(log) == lower(bridge) && topics_match(log, &delivered)) {
+        let message_topics = topics(log);
+        let Some(fields) = decode_data("address,uint8,address,bytes32,uint256,uint64", log_data(log)) else {
+            continue;
+        };
+        let kind: u64 = as_string(&fields[1]).parse().unwrap_or(0);
+        let payload = logs
+            .iter()
+            .find(|l| log_address(l) == lower(inbox) && topics_match(l, &inbox_delivered) && topics(l).get(1) == message_topics.get(1))
+            .and_then(|l| decode_data("bytes", log_data(l)))
+            .and_then(|values| decode::from_hex(values[0].as_str()?));
+        let Some(payload) = payload else {
+            continue;
+        };
+        match kind {
+            ARB_ETH_DEPOSIT_KIND if payload.len() >= 52 => relays.push(Relay::ArbEthDeposit {
+                to: decode::to_hex(&payload[..20]),
+                value: word_u128(&payload[20..52]),
+            }),
+            ARB_RETRYABLE_KIND if payload.len() >= 9 * 32 => {
+                let word = |i: usize| &payload[i * 32..(i + 1) * 32];
+                let length = word_u128(word(8)) as usize;
+                let data = payload.get(9 * 32..9 * 32 + length).unwrap_or_default();
+                relays.push(Relay::ArbRetryable {
+                    from: as_string(&fields[2]),
+                    to: decode::to_hex(&word(0)[12..]),
+                    value: word_u128(word(1)),
+                    gas_limit: word_u128(word(6)) as u64,
+                    data: decode::to_hex(data),
+                });
+            }
+            _ => {}
+        }
+    }
+    relays
+}
+
+fn arb_withdrawals(logs: &[Value]) -> Vec<Relay> {
+    let l2_to_l1 = decode::event_topic("L2ToL1Tx(address,address,uint256,uint256,uint256,uint256,uint256,uint256,bytes)");
+    logs.iter()
+        .filter(|log| log_address(log) == ARB_SYS && topics_match(log, &l2_to_l1) && topics(log).len() >= 2)
+        .filter_map(|log| {
+            let fields = decode_data("address,uint256,uint256,uint256,uint256,bytes", log_data(log))?;
+            Some(Relay::ArbOutbox {
+                caller: as_string(&fields[0]),
+                to: topic_address(&topics(log)[1]),
+                value: as_string(&fields[4]).parse().unwrap_or(0),
+                data: as_string(&fields[5]),
+            })
+        })
+        .collect()
+}
+
+async fn add_balance(url: &str, address: &str, wei: u128) -> Result<(), AppError> {
+    let current = rpc::call(url, "eth_getBalance", json!([address, "latest"])).await?;
+    let current = current
+        .as_str()
+        .and_then(|hex| u128::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+        .unwrap_or(0);
+    rpc::call(url, "hardhat_setBalance", json!([address, format!("0x{:x}", current.saturating_add(wei))])).await?;
+    Ok(())
+}
+
+// Sends as any address; returns the hash and the mined receipt
+async fn send_as(url: &str, from: &str, to: &str, data: &str, value: u128, gas: Option<u64>) -> Result<(String, Value), AppError> {
+    let mut tx = json!({ "from": from, "to": to, "data": data, "value": format!("0x{:x}", value) });
+    if let Some(gas) = gas {
+        tx["gas"] = json!(format!("0x{:x}", gas));
+    }
+    let _ = rpc::call(url, "hardhat_impersonateAccount", json!([from])).await;
+    let sent = rpc::call(url, "eth_sendTransaction", json!([tx])).await;
+    let _ = rpc::call(url, "hardhat_stopImpersonatingAccount", json!([from])).await;
+    let hash = sent?.as_str().map(str::to_string).ok_or("The node returned no transaction hash")?;
+    let receipt = rpc::call(url, "eth_getTransactionReceipt", json!([hash])).await?;
+    if receipt.get("status").and_then(rpc::parse_quantity) != Some(1) {
+        return Err(format!("Transaction {} reverted", hash).into());
+    }
+    Ok((hash, receipt))
+}
+
+// The slot among the first SLOT_SCAN whose low 20 bytes hold `current`, with its full word
+async fn find_address_slot(url: &str, contract: &str, current: &str) -> Result<(String, String), AppError> {
+    let current = current.trim_start_matches("0x").to_lowercase();
+    for slot in 0..SLOT_SCAN {
+        let position = format!("0x{:x}", slot);
+        let word = rpc::storage_at(url, contract, &position).await?.to_lowercase();
+        if word.ends_with(&current) {
+            return Ok((position, word));
+        }
+    }
+    Err(format!("Couldn't find the cross-domain sender slot of {}", contract).into())
+}
+
+// Runs a relay with `contract`'s sender variable temporarily reading `sender`, the way it
+// does while the contract itself executes a proven withdrawal
+async fn with_sender_slot<F>(url: &str, contract: &str, default: &str, sender: &str, relay: F) -> Result<(String, Value), AppError>
+where
+    F: Future<Output = Result<(String, Value), AppError>>,
+{
+    let (slot, original) = find_address_slot(url, contract, default).await?;
+    let patched = format!("0x{}{}", &original[..24], sender.trim_start_matches("0x").to_lowercase());
+    rpc::call(url, "hardhat_setStorageAt", json!([contract, slot, patched])).await?;
+    let result = relay.await;
+    rpc::call(url, "hardhat_setStorageAt", json!([contract, slot, format!("0x{}", original)])).await?;
+    result
+}
+
+async fn relay_op(url: &str, direction: BridgeDirection, l1_messenger: &str, portal: &str, relay: &Relay) -> Result<String, AppError> {
+    let Relay::OpMessage { nonce, sender, target, value, gas_limit, message } = relay else {
+        return Err("Not an OP Stack message".to_string().into());
+    };
+    let wei: u128 = value.parse().unwrap_or(0);
+    let gas = Some(gas_limit * 64 / 63 + RELAY_GAS);
+    let calldata = calldata(
+        "relayMessage(uint256,address,address,uint256,uint256,bytes)",
+        &[json!(nonce), json!(sender), json!(target), json!(value), json!(gas_limit.to_string()), json!(message)],
+    )?;
+    let (messenger, (hash, receipt)) = match direction {
+        // Deposits arrive from the aliased L1 messenger, which mints the value
+        BridgeDirection::Deposit => {
+            let from = apply_alias(l1_messenger);
+            add_balance(url, &from, wei.saturating_add(GAS_ALLOWANCE_WEI)).await?;
+            (OP_L2_MESSENGER, send_as(url, &from, OP_L2_MESSENGER, &calldata, wei, gas).await?)
+        }
+        // Withdrawals are paid out of the portal's locked ETH
+        BridgeDirection::Withdrawal => {
+            add_balance(url, portal, GAS_ALLOWANCE_WEI).await?;
+            let sent = with_sender_slot(url, portal, OP_DEFAULT_L2_SENDER, OP_L2_MESSENGER, send_as(url, portal, l1_messenger, &calldata, wei, gas)).await?;
+            (l1_messenger, sent)
+        }
+    };
+    // The messenger catches reverts of the target and stores the message for replay instead
+    let failed = decode::event_topic("FailedRelayedMessage(bytes32)");
+    let logs = receipt.get("logs").and_then(Value::as_array).cloned().unwrap_or_default();
+    if logs.iter().any(|log| log_address(log) == lower(messenger) && topics_match(log, &failed)) {
+        return Err(format!("The target reverted in {}; the messenger stored the message for replay", hash).into());
+    }
+    Ok(hash)
+}
+
+async fn relay_arb_outbox(url: &str, bridge: &str, outbox: &str, caller: &str, to: &str, value: u128, data: &str) -> Result<String, AppError> {
+    let calldata = calldata("executeCall(address,uint256,bytes)", &[json!(to), json!(value.to_string()), json!(data)])?;
+    add_balance(url, outbox, GAS_ALLOWANCE_WEI).await?;
+    let execute = async {
+        // executeCall reports the target's revert in its return value instead of reverting
+        let tx = json!({ "from": outbox, "to": bridge, "data": calldata });
+        let output = rpc::call(url, "eth_call", json!([tx, "latest"])).await?;
+        let succeeded = decode_data("bool,bytes", output.as_str().unwrap_or("0x"))
+            .and_then(|values| values[0].as_bool())
+            .unwrap_or(false);
+        if !succeeded {
+            return Err(format!("The call to {} reverted", to).into());
+        }
+        send_as(url, outbox, bridge, &calldata, 0, Some(RELAY_GAS * 4)).await
+    };
+    let (hash, _) = with_sender_slot(url, outbox, ARB_DEFAULT_CONTEXT_SENDER, caller, execute).await?;
+    Ok(hash)
+}
+
+async fn relay(forks: &BridgeForks, contracts: &BridgeContracts, direction: BridgeDirection, relay: Relay) -> RelayedMessage {
+    let url = match direction {
+        BridgeDirection::Deposit => &forks.l2_rpc_url,
+        BridgeDirection::Withdrawal => &forks.l1_rpc_url,
+    };
+    let (sender, target, value, data) = match &relay {
+        Relay::OpMessage { sender, target, value, message, .. } => (sender.clone(), target.clone(), value.clone(), message.clone()),
+        Relay::ArbEthDeposit { to, value } => (to.clone(), to.clone(), value.to_string(), "0x".to_string()),
+        Relay::ArbRetryable { from, to, value, data, .. } => (from.clone(), to.clone(), value.to_string(), data.clone()),
+        Relay::ArbOutbox { caller, to, value, data } => (caller.clone(), to.clone(), value.to_string(), data.clone()),
+    };
+
+    let outcome = match (&relay, contracts) {
+        (Relay::OpMessage { .. }, BridgeContracts::OpStack { l1_messenger, portal, .. }) => {
+            relay_op(url, direction, l1_messenger, portal, &relay).await.map(Some)
+        }
+        (Relay::ArbEthDeposit { to, value }, _) => add_balance(url, to, *value).await.map(|()| None),
+        (Relay::ArbRetryable { from, to, value, gas_limit, data }, _) => {
+            match add_balance(url, from, value.saturating_add(GAS_ALLOWANCE_WEI)).await {
+                Ok(()) => send_as(url, from, to, data, *value, Some((*gas_limit).max(21_000) + RELAY_GAS)).await.map(|(hash, _)| Some(hash)),
+                Err(e) => Err(e),
+            }
+        }
+        (Relay::ArbOutbox { caller, to, value, data }, BridgeContracts::Arbitrum { bridge, outbox, .. }) => {
+            relay_arb_outbox(url, bridge, outbox, caller, to, *value, data).await.map(Some)
+        }
+        _ => Err("The message doesn't belong to this rollup's bridge".to_string().into()),
+    };
+    let (relay_tx, error) = match outcome {
+        Ok(hash) => (hash, None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    RelayedMessage { sender, target, value, data, relay_tx, success: error.is_none(), error }
+}
+
+async fn receipt_logs(url: &str, tx_hash: &str) -> Result<Vec<Value>, AppError> {
+    let receipt = rpc::call(url, "eth_getTransactionReceipt", json!([tx_hash])).await?;
+    if receipt.is_null() {
+        return Err(format!("No receipt for {}", tx_hash).into());
+    }
+    Ok(receipt.get("logs").and_then(Value::as_array).cloned().unwrap_or_default())
+}
+
+async fn relay_transaction(forks: &BridgeForks, contracts: &BridgeContracts, direction: BridgeDirection, tx_hash: String) -> Result<BridgeResult, AppError> {
+    let (source, relays) = match direction {
+        BridgeDirection::Deposit => {
+            let logs = receipt_logs(&forks.l1_rpc_url, &tx_hash).await?;
+            let relays = match contracts {
+                BridgeContracts::OpStack { l1_messenger, .. } => op_messages(&logs, l1_messenger),
+                BridgeContracts::Arbitrum { inbox, bridge, .. } => arb_deposits(&logs, inbox, bridge),
+            };
+            ("L1", relays)
+        }
+        BridgeDirection::Withdrawal => {
+            let logs = receipt_logs(&forks.l2_rpc_url, &tx_hash).await?;
+            let relays = match contracts {
+                BridgeContracts::OpStack { .. } => op_messages(&logs, OP_L2_MESSENGER),
+                BridgeContracts::Arbitrum { .. } => arb_withdrawals(&logs),
+            };
+            ("L2", relays)
+        }
+    };
+    if relays.is_empty() {
+        return Err(format!("{} transaction {} sent no cross-domain messages", source, tx_hash).into());
+    }
+
+    // Withdrawals become executable once the challenge period is over
+    let mut fast_forwarded_secs = None;
+    if direction == BridgeDirection::Withdrawal {
+        let secs = match contracts {
+            BridgeContracts::OpStack { .. } => OP_FINALIZATION_SECS,
+            BridgeContracts::Arbitrum { .. } => ARB_FINALIZATION_SECS,
+        };
+        rpc::call(&forks.l1_rpc_url, "evm_increaseTime", json!([secs])).await?;
+        rpc::call(&forks.l1_rpc_url, "evm_mine", json!([])).await?;
+        fast_forwarded_secs = Some(secs);
+    }
+
+    let mut messages = Vec::new();
+    for message in relays {
+        messages.push(relay(forks, contracts, direction, message).await);
+    }
+    Ok(BridgeResult { direction, source_tx: tx_hash, messages, fast_forwarded_secs })
+}
+
+async fn sender(url: &str, from: Option<String>) -> Result<String, AppError> {
+    match from {
+        Some(from) => Ok(from),
+        None => rpc::call(url, "eth_accounts", json!([]))
+            .await?
+            .get(0)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| "The node has no unlocked accounts; pass `from`".into()),
+    }
+}
+
+// Delivers the cross-domain messages a transaction sent (deposits from its L1 receipt,
+// withdrawals from its L2 receipt) on the other fork. Nothing is proven: the destination
+// contracts are called the way the rollup's system contracts would call them
+#[tauri::command]
+pub async fn relay_bridge_messages(forks: BridgeForks, direction: BridgeDirection, tx_hash: String) -> Result<BridgeResult, AppError> {
+    let contracts = contracts(&forks).await?;
+    relay_transaction(&forks, &contracts, direction, tx_hash).await
+}
+
+// Deposits ETH through the canonical L1 bridge and delivers it on the L2 fork
+#[tauri::command]
+pub async fn bridge_deposit_eth(forks: BridgeForks, amount_wei: String, from: Option<String>) -> Result<BridgeResult, AppError> {
+    let contracts = contracts(&forks).await?;
+    let from = sender(&forks.l1_rpc_url, from).await?;
+    let amount: u128 = amount_wei.trim().parse().map_err(|_| format!("Invalid amount: {}", amount_wei))?;
+    let (to, data) = match &contracts {
+        BridgeContracts::OpStack { l1_standard_bridge, .. } => {
+            (l1_standard_bridge.clone(), calldata("depositETHTo(address,uint32,bytes)", &[json!(from), json!("200000"), json!("0x")])?)
+        }
+        BridgeContracts::Arbitrum { inbox, .. } => (inbox.clone(), calldata("depositEth()", &[])?),
+    };
+    let (hash, _) = send_as(&forks.l1_rpc_url, &from, &to, &data, amount, None).await?;
+    relay_transaction(&forks, &contracts, BridgeDirection::Deposit, hash).await
+}
+
+// Withdraws ETH through the L2 bridge, moves the L1 fork past the challenge period and
+// pays it out on L1
+#[tauri::command]
+pub async fn bridge_withdraw_eth(forks: BridgeForks, amount_wei: String, from: Option<String>) -> Result<BridgeResult, AppError> {
+    let contracts = contracts(&forks).await?;
+    let from = sender(&forks.l2_rpc_url, from).await?;
+    let amount: u128 = amount_wei.trim().parse().map_err(|_| format!("Invalid amount: {}", amount_wei))?;
+    let (to, data) = match &contracts {
+        BridgeContracts::OpStack { .. } => (
+            OP_L2_STANDARD_BRIDGE.to_string(),
+            calldata(
+                "withdrawTo(address,address,uint256,uint32,bytes)",
+                &[json!(OP_LEGACY_ETH), json!(from), json!(amount.to_string()), json!("200000"), json!("0x")],
+            )?,
+        ),
+        BridgeContracts::Arbitrum { .. } => (ARB_SYS.to_string(), calldata("withdrawEth(address)", &[json!(from)])?),
+    };
+    let (hash, _) = send_as(&forks.l2_rpc_url, &from, &to, &data, amount, None).await?;
+    relay_transaction(&forks, &contracts, BridgeDirection::Withdrawal, hash).await
+}
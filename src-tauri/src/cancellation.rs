@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use tokio_util::sync::CancellationToken;
+
+use crate::artifacts;
+use crate::contracts;
+use crate::db;
+use crate::error::AppError;
+
+// Background work hangs off a token tree: one token per open project with a child per
+// execution id. Closing a project cancels every process, chain sync, schedule and proxy
+// started for it; each of them checks its token and stops at the next step
+#[derive(Default)]
+struct Tokens {
+    projects: HashMap<PathBuf, CancellationToken>,
+    executions: HashMap<String, CancellationToken>,
+}
+
+static TOKENS: OnceLock<Mutex<Tokens>> = OnceLock::new();
+
+fn tokens() -> &'static Mutex<Tokens> {
+    TOKENS.get_or_init(|| Mutex::new(Tokens::default()))
+}
+
+// A closed project gets a fresh token when it is used again
+pub fn project(project_path: &Path) -> CancellationToken {
+    let mut tokens = tokens().lock().unwrap();
+    tokens.projects.entry(project_path.to_path_buf()).or_default().clone()
+}
+
+// Registered under its execution id until dropped
+pub struct ExecutionToken {
+    execution_id: String,
+    pub token: CancellationToken,
+}
+
+impl Drop for ExecutionToken {
+    fn drop(&mut self) {
+        tokens().lock().unwrap().executions.remove(&self.execution_id);
+    }
+}
+
+// Work outside a project (local nodes, app-wide streams) only stops when cancelled by id
+pub fn execution(project_path: Option<&Path>, execution_id: &str) -> ExecutionToken {
+    let token = match project_path {
+        Some(project_path) => project(project_path).child_token(),
+        None => CancellationToken::new(),
+    };
+    tokens().lock().unwrap().executions.insert(execution_id.to_string(), token.clone());
+    ExecutionToken { execution_id: execution_id.to_string(), token }
+}
+
+pub fn cancel(execution_id: &str) -> bool {
+    match tokens().lock().unwrap().executions.get(execution_id) {
+        Some(token) => {
+            token.cancel();
+            true
+        }
+        None => false,
+    }
+}
+
+// Stops all background work for the project and drops what is cached for it (file
+// watchers, artifact index, database connection). Returns false if nothing was running
+#[tauri::command]
+pub async fn close_project(project_path: String) -> Result<bool, AppError> {
+    let root = Path::new(&project_path);
+    let token = tokens().lock().unwrap().projects.remove(root);
+    if let Some(token) = &token {
+        token.cancel();
+    }
+    contracts::forget(root);
+    artifacts::forget(root);
+    db::close(root);
+    tracing::info!(project = %project_path, "project closed");
+    Ok(token.is_some())
+}
@@ -0,0 +1,247 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::address_book::AddressBook;
+use crate::chain_index::{self, ChainIndex};
+use crate::decode::AbiIndex;
+use crate::error::AppError;
+use crate::events;
+use crate::rpc;
+use crate::store;
+
+const EXPORTS_DIR: &str = "exports";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportKind {
+    Blocks,
+    Transactions,
+    Events,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ExportFilter {
+    pub from_block: Option<u64>,
+    pub to_block: Option<u64>,
+    // Transactions sent from or to these addresses, or events emitted by them
+    #[serde(default)]
+    pub addresses: Vec<String>,
+    // Events only: signatures, bare names or topic0 hashes, as in search_logs
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportResult {
+    pub path: String,
+    pub kind: ExportKind,
+    pub format: ExportFormat,
+    pub rows: usize,
+}
+
+impl ExportKind {
+    fn name(self) -> &'static str {
+        match self {
+            ExportKind::Blocks => "blocks",
+            ExportKind::Transactions => "transactions",
+            ExportKind::Events => "events",
+        }
+    }
+
+    // CSV column order; JSON rows carry the same fields
+    fn columns(self) -> &'static [&'static str] {
+        match self {
+            ExportKind::Blocks => &[
+                "number",
+                "hash",
+                "timestamp",
+                "gas_used",
+                "gas_limit",
+                "base_fee_per_gas",
+                "transaction_count",
+            ],
+            ExportKind::Transactions => &["block_number", "timestamp", "hash", "from", "from_label", "to", "to_label"],
+            ExportKind::Events => &[
+                "block_number",
+                "transaction_hash",
+                "log_index",
+                "address",
+                "address_label",
+                "contract",
+                "event",
+                "args",
+                "topics",
+                "data",
+            ],
+        }
+    }
+}
+
+fn row(columns: &[&str], values: Vec<Value>) -> Map<String, Value> {
+    columns.iter().map(|c| c.to_string()).zip(values).collect()
+}
+
+fn label(book: &AddressBook, address: Option<&str>) -> Value {
+    json!(address.and_then(|a| book.label(a)))
+}
+
+fn rows(
+    kind: ExportKind,
+    chain: &ChainIndex,
+    filters: &ExportFilter,
+    index: &AbiIndex,
+    book: &AddressBook,
+) -> Result<Vec<Map<String, Value>>, String> {
+    let from = filters.from_block.unwrap_or(chain.first_block);
+    let to = filters.to_block.or(chain.tip()).unwrap_or(from);
+    let addresses: Vec<String> = filters.addresses.iter().map(|a| a.trim().to_lowercase()).collect();
+    let wanted = |address: Option<&str>| address.is_some_and(|a| addresses.iter().any(|w| w == a));
+    let blocks = chain.blocks.iter().filter(|b| (from..=to).contains(&b.number));
+    let columns = kind.columns();
+
+    let rows = match kind {
+        ExportKind::Blocks => blocks
+            .map(|b| {
+                row(
+                    columns,
+                    vec![
+                        json!(b.number),
+                        json!(b.hash),
+                        json!(b.timestamp),
+                        json!(b.gas_used),
+                        json!(b.gas_limit),
+                        json!(b.base_fee_per_gas),
+                        json!(b.transactions.len()),
+                    ],
+                )
+            })
+            .collect(),
+        ExportKind::Transactions => blocks
+            .flat_map(|b| b.transactions.iter().map(move |tx| (b, tx)))
+            .filter(|(_, tx)| addresses.is_empty() || wanted(Some(&tx.from)) || wanted(tx.to.as_deref()))
+            .map(|(b, tx)| {
+                row(
+                    columns,
+                    vec![
+                        json!(b.number),
+                        json!(b.timestamp),
+                        json!(tx.hash),
+                        json!(tx.from),
+                        label(book, Some(&tx.from)),
+                        json!(tx.to),
+                        label(book, tx.to.as_deref()),
+                    ],
+                )
+            })
+            .collect(),
+        ExportKind::Events => {
+            let topics = events::filter_topics(index, &filters.events)?;
+            chain
+                .logs
+                .iter()
+                .filter(|log| (from..=to).contains(&log.block_number))
+                .filter(|log| addresses.is_empty() || wanted(Some(&log.address)))
+                .filter(|log| topics.is_empty() || log.topics.first().is_some_and(|t| topics.contains(t)))
+                .map(|log| {
+                    let decoded = events::decode_log(index, &log.to_rpc());
+                    let args: Map<String, Value> = decoded
+                        .args
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, arg)| (if arg.name.is_empty() { i.to_string() } else { arg.name }, arg.value))
+                        .collect();
+                    row(
+                        columns,
+                        vec![
+                            json!(log.block_number),
+                            json!(log.transaction_hash),
+                            json!(log.log_index),
+                            json!(log.address),
+                            label(book, Some(&log.address)),
+                            json!(decoded.contract),
+                            json!(decoded.event),
+                            Value::Object(args),
+                            json!(log.topics),
+                            json!(log.data),
+                        ],
+                    )
+                })
+                .collect()
+        }
+    };
+    Ok(rows)
+}
+
+// Nested values (event args, topics) are written as JSON inside the cell
+fn csv_cell(value: Option<&Value>) -> String {
+    let text = match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+fn to_csv(columns: &[&str], rows: &[Map<String, Value>]) -> String {
+    let mut out = columns.join(",");
+    out.push('\n');
+    for row in rows {
+        let cells: Vec<String> = columns.iter().map(|c| csv_cell(row.get(*c))).collect();
+        out.push_str(&cells.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+#[tauri::command]
+pub async fn export_chain_data(
+    project_path: String,
+    kind: ExportKind,
+    format: Option<ExportFormat>,
+    filters: Option<ExportFilter>,
+    output_path: Option<String>,
+    rpc_url: Option<String>,
+) -> Result<ExportResult, AppError> {
+    let root = Path::new(&project_path);
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let format = format.unwrap_or(ExportFormat::Csv);
+    let filters = filters.unwrap_or_default();
+
+    let chain = chain_index::sync(root, url).await?;
+    let book = AddressBook::load(root, Some(url)).await;
+    let rows = rows(kind, &chain, &filters, &AbiIndex::load(root), &book)?;
+
+    let extension = if format == ExportFormat::Csv { "csv" } else { "json" };
+    let path = match output_path {
+        Some(path) => PathBuf::from(path),
+        None => root
+            .join(EXPORTS_DIR)
+            .join(format!("{}-{}.{}", kind.name(), store::timestamp_millis(), extension)),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create export directory: {}", e))?;
+    }
+    let content = match format {
+        ExportFormat::Csv => to_csv(kind.columns(), &rows),
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(&rows).map_err(|e| format!("Failed to serialize export: {}", e))?
+        }
+    };
+    fs::write(&path, content).map_err(|e| format!("Failed to write export: {}", e))?;
+
+    Ok(ExportResult { path: path.to_string_lossy().to_string(), kind, format, rows: rows.len() })
+}
@@ -0,0 +1,311 @@
+use std::path::Path;
+use std::time::Instant;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::cancellation;
+use crate::db;
+use crate::error::AppError;
+use crate::metrics::{self, Outcome};
+use crate::rpc;
+use crate::store;
+
+// Without a known fork point, a fresh index starts this far back from the head
+const MAX_INITIAL_BLOCKS: u64 = 5_000;
+const LOGS_BATCH: u64 = 1_000;
+// Deeper divergence than this means the node was restarted; rebuild instead
+const MAX_REWIND: usize = 128;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct IndexedTransaction {
+    pub hash: String,
+    pub from: String,
+    // None for contract creations
+    pub to: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct IndexedBlock {
+    pub number: u64,
+    pub hash: String,
+    pub timestamp: u64,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    // Wei; absent before London
+    #[serde(default)]
+    pub base_fee_per_gas: Option<u64>,
+    pub transactions: Vec<IndexedTransaction>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct IndexedLog {
+    pub block_number: u64,
+    pub transaction_hash: String,
+    pub log_index: u64,
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String,
+}
+
+impl IndexedLog {
+    // Back into the RPC shape so the shared decoder can read it
+    pub fn to_rpc(&self) -> Value {
+        json!({
+            "address": self.address,
+            "topics": self.topics,
+            "data": self.data,
+            "blockNumber": format!("0x{:x}", self.block_number),
+            "transactionHash": self.transaction_hash,
+            "logIndex": format!("0x{:x}", self.log_index),
+        })
+    }
+}
+
+// Blocks and logs of the local chain as seen through one RPC endpoint, so searches
+// don't have to re-scan the node
+#[derive(Serialize, Deserialize, Default)]
+pub struct ChainIndex {
+    pub rpc_url: String,
+    pub chain_id: u64,
+    pub first_block: u64,
+    pub blocks: Vec<IndexedBlock>,
+    pub logs: Vec<IndexedLog>,
+}
+
+impl ChainIndex {
+    pub fn tip(&self) -> Option<u64> {
+        self.blocks.last().map(|b| b.number)
+    }
+}
+
+fn quantity(value: &Value, key: &str) -> u64 {
+    value.get(key).and_then(rpc::parse_quantity).unwrap_or(0)
+}
+
+fn string(value: &Value, key: &str) -> String {
+    value.get(key).and_then(Value::as_str).unwrap_or_default().to_string()
+}
+
+pub async fn block_number(url: &str) -> Result<u64, AppError> {
+    let result = rpc::call(url, "eth_blockNumber", json!([])).await?;
+    rpc::parse_quantity(&result).ok_or_else(|| format!("Invalid block number: {}", result).into())
+}
+
+async fn block_hash(url: &str, number: u64) -> Result<Option<String>, AppError> {
+    let block = rpc::call(url, "eth_getBlockByNumber", json!([format!("0x{:x}", number), false])).await?;
+    Ok(block.get("hash").and_then(Value::as_str).map(str::to_string))
+}
+
+// Blocks before a fork point belong to the upstream chain and aren't worth indexing
+async fn first_block(url: &str, latest: u64) -> u64 {
+    let fork_block = match rpc::call(url, "hardhat_metadata", json!([])).await {
+        Ok(metadata) => metadata.pointer("/forkedNetwork/forkBlockNumber").and_then(Value::as_u64),
+        Err(_) => None,
+    };
+    match fork_block {
+        Some(fork) => (fork + 1).min(latest),
+        None => latest.saturating_sub(MAX_INITIAL_BLOCKS - 1),
+    }
+}
+
+fn parse_block(block: &Value) -> IndexedBlock {
+    let transactions = block
+        .get("transactions")
+        .and_then(Value::as_array)
+        .map(|txs| {
+            txs.iter()
+                .map(|tx| IndexedTransaction {
+                    hash: string(tx, "hash"),
+                    from: string(tx, "from").to_lowercase(),
+                    to: tx.get("to").and_then(Value::as_str).map(str::to_lowercase),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    IndexedBlock {
+        number: quantity(block, "number"),
+        hash: string(block, "hash"),
+        timestamp: quantity(block, "timestamp"),
+        gas_used: quantity(block, "gasUsed"),
+        gas_limit: quantity(block, "gasLimit"),
+        base_fee_per_gas: block.get("baseFeePerGas").and_then(rpc::parse_quantity),
+        transactions,
+    }
+}
+
+pub fn parse_log(log: &Value) -> IndexedLog {
+    IndexedLog {
+        block_number: quantity(log, "blockNumber"),
+        transaction_hash: string(log, "transactionHash"),
+        log_index: quantity(log, "logIndex"),
+        address: string(log, "address").to_lowercase(),
+        topics: log
+            .get("topics")
+            .and_then(Value::as_array)
+            .map(|topics| topics.iter().filter_map(Value::as_str).map(str::to_lowercase).collect())
+            .unwrap_or_default(),
+        data: string(log, "data"),
+    }
+}
+
+fn load(conn: &mut Connection) -> rusqlite::Result<ChainIndex> {
+    let Some(mut index) = conn
+        .query_row("SELECT rpc_url, chain_id, first_block FROM chain_index WHERE id = 1", [], |row| {
+            Ok(ChainIndex { rpc_url: row.get(0)?, chain_id: row.get(1)?, first_block: row.get(2)?, ..Default::default() })
+        })
+        .optional()?
+    else {
+        return Ok(ChainIndex::default());
+    };
+
+    index.blocks = conn
+        .prepare("SELECT number, hash, timestamp, gas_used, gas_limit, base_fee_per_gas FROM blocks ORDER BY number")?
+        .query_map([], |row| {
+            Ok(IndexedBlock {
+                number: row.get(0)?,
+                hash: row.get(1)?,
+                timestamp: row.get(2)?,
+                gas_used: row.get(3)?,
+                gas_limit: row.get(4)?,
+                base_fee_per_gas: row.get(5)?,
+                transactions: Vec::new(),
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    let first = index.blocks.first().map_or(0, |b| b.number);
+    let mut transactions = conn.prepare("SELECT block_number, hash, from_address, to_address FROM transactions ORDER BY block_number, position")?;
+    let mut rows = transactions.query([])?;
+    while let Some(row) = rows.next()? {
+        let number: u64 = row.get(0)?;
+        // Blocks are contiguous, so the block's position follows from its number
+        if let Some(block) = index.blocks.get_mut(number.saturating_sub(first) as usize).filter(|b| b.number == number) {
+            block.transactions.push(IndexedTransaction { hash: row.get(1)?, from: row.get(2)?, to: row.get(3)? });
+        }
+    }
+
+    index.logs = conn
+        .prepare("SELECT block_number, transaction_hash, log_index, address, topics, data FROM logs ORDER BY block_number, log_index")?
+        .query_map([], |row| {
+            let topics: String = row.get(4)?;
+            Ok(IndexedLog {
+                block_number: row.get(0)?,
+                transaction_hash: row.get(1)?,
+                log_index: row.get(2)?,
+                address: row.get(3)?,
+                topics: serde_json::from_str(&topics).unwrap_or_default(),
+                data: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(index)
+}
+
+// Rows up to `kept` are unchanged since the last sync; everything after it is replaced
+fn persist(conn: &mut Connection, index: &ChainIndex, kept: Option<u64>) -> rusqlite::Result<()> {
+    let kept = kept.map_or(-1, |kept| kept as i64);
+    let tx = conn.transaction()?;
+    tx.execute(
+        "INSERT OR REPLACE INTO chain_index (id, rpc_url, chain_id, first_block) VALUES (1, ?1, ?2, ?3)",
+        params![index.rpc_url, index.chain_id, index.first_block],
+    )?;
+    for table in ["blocks", "transactions", "logs"] {
+        let column = if table == "blocks" { "number" } else { "block_number" };
+        tx.execute(&format!("DELETE FROM {} WHERE {} > ?1", table, column), [kept])?;
+    }
+    {
+        let mut insert_block = tx.prepare(
+            "INSERT INTO blocks (number, hash, timestamp, gas_used, gas_limit, base_fee_per_gas) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        let mut insert_tx = tx.prepare(
+            "INSERT INTO transactions (block_number, position, hash, from_address, to_address) VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        for block in index.blocks.iter().filter(|b| b.number as i64 > kept) {
+            insert_block.execute(params![block.number, block.hash, block.timestamp, block.gas_used, block.gas_limit, block.base_fee_per_gas])?;
+            for (position, t) in block.transactions.iter().enumerate() {
+                insert_tx.execute(params![block.number, position, t.hash, t.from, t.to])?;
+            }
+        }
+        let mut insert_log = tx.prepare(
+            "INSERT OR REPLACE INTO logs (block_number, log_index, transaction_hash, address, topics, data) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for log in index.logs.iter().filter(|l| l.block_number as i64 > kept) {
+            let topics = serde_json::to_string(&log.topics).unwrap_or_default();
+            insert_log.execute(params![log.block_number, log.log_index, log.transaction_hash, log.address, topics, log.data])?;
+        }
+    }
+    tx.commit()
+}
+
+// Brings the stored index up to the node's head, dropping blocks that were replaced
+// by evm_revert or a node restart
+pub async fn sync(project_path: &Path, url: &str) -> Result<ChainIndex, AppError> {
+    // Closing the project abandons the sync; nothing is persisted from a partial run
+    let cancel = cancellation::project(project_path);
+    let cancelled = || format!("Sync of {} was cancelled", project_path.display());
+    let mut index = db::with(project_path, load)?;
+    let chain_id = rpc::chain_id(url).await?;
+    if index.rpc_url != url || index.chain_id != chain_id {
+        index = ChainIndex { rpc_url: url.to_string(), chain_id, ..Default::default() };
+    }
+
+    let mut dropped = 0;
+    while let Some(tip) = index.blocks.last() {
+        if block_hash(url, tip.number).await?.as_deref() == Some(tip.hash.as_str()) {
+            break;
+        }
+        index.blocks.pop();
+        dropped += 1;
+        if dropped > MAX_REWIND {
+            index.blocks.clear();
+        }
+    }
+    let tip = index.tip();
+    index.logs.retain(|log| tip.is_some_and(|tip| log.block_number <= tip));
+
+    let latest = block_number(url).await?;
+    let start = match tip {
+        Some(tip) => tip + 1,
+        None => {
+            index.first_block = first_block(url, latest).await;
+            index.first_block
+        }
+    };
+    if start > latest && dropped == 0 {
+        return Ok(index);
+    }
+
+    let started_at = store::timestamp_millis();
+    let started = Instant::now();
+    for number in start..=latest {
+        if cancel.is_cancelled() {
+            return Err(cancelled().into());
+        }
+        let block = rpc::call(url, "eth_getBlockByNumber", json!([format!("0x{:x}", number), true])).await?;
+        if block.is_null() {
+            break;
+        }
+        index.blocks.push(parse_block(&block));
+    }
+
+    let mut from = start;
+    while from <= latest {
+        if cancel.is_cancelled() {
+            return Err(cancelled().into());
+        }
+        let to = (from + LOGS_BATCH - 1).min(latest);
+        let logs = rpc::call(
+            url,
+            "eth_getLogs",
+            json!([{ "fromBlock": format!("0x{:x}", from), "toBlock": format!("0x{:x}", to) }]),
+        )
+        .await?;
+        index.logs.extend(logs.as_array().into_iter().flatten().map(parse_log));
+        from = to + 1;
+    }
+
+    metrics::record(project_path, "rpc_sync", started_at, started.elapsed(), Outcome::Success, None);
+    db::with(project_path, |conn| persist(conn, &index, tip))?;
+    Ok(index)
+}
@@ -0,0 +1,161 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, State};
+use tokio_util::sync::CancellationToken;
+
+use crate::chain_index;
+use crate::error::AppError;
+use crate::rpc;
+use crate::store;
+
+const DEFAULT_INTERVAL_MS: u64 = 2_000;
+// Catching up after a long pause shouldn't turn one tick into thousands of requests
+const MAX_BLOCKS_PER_TICK: u64 = 100;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChainMetricsSample {
+    pub timestamp: u64,
+    pub interval_ms: u64,
+    // False when the node didn't answer this tick; the counters are then zero
+    pub connected: bool,
+    pub block_number: Option<u64>,
+    // Blocks and transactions mined since the previous sample
+    pub blocks: u64,
+    pub transactions: u64,
+    pub transactions_per_second: f64,
+    pub avg_gas_per_block: Option<u64>,
+    pub pending_transactions: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChainMetricsInfo {
+    pub rpc_url: String,
+    pub interval_ms: u64,
+}
+
+struct MetricsStream {
+    info: ChainMetricsInfo,
+    cancel: CancellationToken,
+}
+
+#[derive(Default)]
+pub struct ChainMetrics {
+    stream: Mutex<Option<MetricsStream>>,
+}
+
+async fn pending_transactions(url: &str) -> Option<u64> {
+    let block = rpc::call(url, "eth_getBlockByNumber", json!(["pending", false])).await.ok()?;
+    let latest = rpc::call(url, "eth_getBlockByNumber", json!(["latest", false])).await.ok()?;
+    let count = |block: &Value| block.get("transactions").and_then(Value::as_array).map_or(0, |txs| txs.len() as u64);
+    // Hardhat's pending block is the latest one while nothing is queued
+    if block.get("hash") == latest.get("hash") {
+        return Some(0);
+    }
+    Some(count(&block))
+}
+
+// One tick: everything mined after `last_block`, which is advanced to the new head
+async fn sample(url: &str, interval_ms: u64, elapsed: Duration, last_block: &mut Option<u64>) -> ChainMetricsSample {
+    let mut sample = ChainMetricsSample {
+        timestamp: store::timestamp_millis(),
+        interval_ms,
+        connected: false,
+        block_number: None,
+        blocks: 0,
+        transactions: 0,
+        transactions_per_second: 0.0,
+        avg_gas_per_block: None,
+        pending_transactions: None,
+    };
+    let Ok(head) = chain_index::block_number(url).await else {
+        return sample;
+    };
+    sample.connected = true;
+    sample.block_number = Some(head);
+
+    // A lower head means the node was reset or reverted; start counting from there
+    let from = match *last_block {
+        Some(last) if last <= head => last + 1,
+        _ => head + 1,
+    };
+    let from = from.max(head.saturating_sub(MAX_BLOCKS_PER_TICK - 1));
+    let mut gas_used = 0;
+    for number in from..=head {
+        let Ok(block) = rpc::call(url, "eth_getBlockByNumber", json!([format!("0x{:x}", number), false])).await else {
+            break;
+        };
+        sample.blocks += 1;
+        sample.transactions += block.get("transactions").and_then(Value::as_array).map_or(0, |txs| txs.len() as u64);
+        gas_used += block.get("gasUsed").and_then(rpc::parse_quantity).unwrap_or(0);
+    }
+    *last_block = Some(head);
+
+    sample.avg_gas_per_block = gas_used.checked_div(sample.blocks);
+    if !elapsed.is_zero() {
+        sample.transactions_per_second = sample.transactions as f64 / elapsed.as_secs_f64();
+    }
+    sample.pending_transactions = pending_transactions(url).await;
+    sample
+}
+
+async fn run_stream(app: AppHandle, info: ChainMetricsInfo, cancel: CancellationToken) {
+    let mut last_block = None;
+    let mut last_tick = Instant::now();
+    while !cancel.is_cancelled() {
+        let elapsed = last_tick.elapsed();
+        last_tick = Instant::now();
+        let Some(metrics) = cancel
+            .run_until_cancelled(sample(&info.rpc_url, info.interval_ms, elapsed, &mut last_block))
+            .await
+        else {
+            break;
+        };
+        let _ = app.emit("chain-metrics", metrics);
+        cancel.run_until_cancelled(tokio::time::sleep(Duration::from_millis(info.interval_ms))).await;
+    }
+}
+
+// Emits a `chain-metrics` event every interval until stopped. Starting again replaces
+// the running stream, e.g. when the dashboard switches networks
+#[tauri::command]
+pub async fn start_chain_metrics(
+    app: AppHandle,
+    metrics: State<'_, ChainMetrics>,
+    rpc_url: Option<String>,
+    interval_ms: Option<u64>,
+) -> Result<ChainMetricsInfo, AppError> {
+    let interval_ms = interval_ms.unwrap_or(DEFAULT_INTERVAL_MS);
+    if interval_ms < 500 {
+        return Err("Metrics interval must be at least 500ms".to_string().into());
+    }
+    let info = ChainMetricsInfo {
+        rpc_url: rpc_url.unwrap_or_else(rpc::default_url),
+        interval_ms,
+    };
+
+    let mut stream = metrics.stream.lock().unwrap();
+    if let Some(previous) = stream.take() {
+        previous.cancel.cancel();
+    }
+    let cancel = CancellationToken::new();
+    tauri::async_runtime::spawn(run_stream(app, info.clone(), cancel.clone()));
+    *stream = Some(MetricsStream { info: info.clone(), cancel });
+    Ok(info)
+}
+
+#[tauri::command]
+pub async fn stop_chain_metrics(metrics: State<'_, ChainMetrics>) -> Result<bool, AppError> {
+    let stopped = metrics.stream.lock().unwrap().take();
+    if let Some(stream) = &stopped {
+        stream.cancel.cancel();
+    }
+    Ok(stopped.is_some())
+}
+
+#[tauri::command]
+pub async fn get_chain_metrics_stream(metrics: State<'_, ChainMetrics>) -> Result<Option<ChainMetricsInfo>, AppError> {
+    Ok(metrics.stream.lock().unwrap().as_ref().map(|s| s.info.clone()))
+}
@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::chain_index::{self, IndexedBlock};
+use crate::error::AppError;
+use crate::rpc;
+
+// Charts don't need more points than this; larger ranges are bucketed
+const MAX_POINTS: u64 = 200;
+
+#[derive(Serialize, Deserialize)]
+pub struct GasPoint {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub timestamp: u64,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    // Share of the gas limit used, 0-100
+    pub utilization: f64,
+    pub base_fee_per_gas: Option<u64>,
+    pub min_base_fee_per_gas: Option<u64>,
+    pub max_base_fee_per_gas: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GasHistory {
+    pub from_block: u64,
+    pub to_block: u64,
+    // Blocks per point
+    pub resolution: u64,
+    pub points: Vec<GasPoint>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ChainSummary {
+    pub chain_id: u64,
+    pub block_height: u64,
+    // The session starts at the first indexed block (the fork point on forks)
+    pub first_block: u64,
+    pub total_transactions: usize,
+    pub active_addresses: usize,
+    pub contracts_deployed: usize,
+    pub total_gas_used: u64,
+    // Sum of base fee x gas used, in wei
+    pub total_fees_burned: String,
+}
+
+fn bucket(blocks: &[&IndexedBlock]) -> Option<GasPoint> {
+    let first = blocks.first()?;
+    let count = blocks.len() as u64;
+    let gas_used = blocks.iter().map(|b| b.gas_used).sum::<u64>() / count;
+    let gas_limit = blocks.iter().map(|b| b.gas_limit).sum::<u64>() / count;
+    let base_fees: Vec<u64> = blocks.iter().filter_map(|b| b.base_fee_per_gas).collect();
+    Some(GasPoint {
+        from_block: first.number,
+        to_block: blocks.last()?.number,
+        timestamp: first.timestamp,
+        gas_used,
+        gas_limit,
+        utilization: if gas_limit == 0 { 0.0 } else { gas_used as f64 * 100.0 / gas_limit as f64 },
+        base_fee_per_gas: (!base_fees.is_empty())
+            .then(|| (base_fees.iter().map(|f| *f as u128).sum::<u128>() / base_fees.len() as u128) as u64),
+        min_base_fee_per_gas: base_fees.iter().min().copied(),
+        max_base_fee_per_gas: base_fees.iter().max().copied(),
+    })
+}
+
+// Averages per bucket of `resolution` blocks; by default sized to fit MAX_POINTS
+#[tauri::command]
+pub async fn get_gas_history(
+    project_path: String,
+    from_block: Option<u64>,
+    to_block: Option<u64>,
+    resolution: Option<u64>,
+    rpc_url: Option<String>,
+) -> Result<GasHistory, AppError> {
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let index = chain_index::sync(Path::new(&project_path), url).await?;
+
+    let from = from_block.unwrap_or(index.first_block).max(index.first_block);
+    let to = to_block.or(index.tip()).unwrap_or(from);
+    let blocks: Vec<&IndexedBlock> = index.blocks.iter().filter(|b| (from..=to).contains(&b.number)).collect();
+    let resolution = resolution
+        .filter(|r| *r > 0)
+        .unwrap_or_else(|| (blocks.len() as u64).div_ceil(MAX_POINTS).max(1));
+
+    Ok(GasHistory {
+        from_block: from,
+        to_block: to,
+        resolution,
+        points: blocks.chunks(resolution as usize).filter_map(bucket).collect(),
+    })
+}
+
+#[tauri::command]
+pub async fn get_chain_summary(project_path: String, rpc_url: Option<String>) -> Result<ChainSummary, AppError> {
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let index = chain_index::sync(Path::new(&project_path), url).await?;
+
+    let transactions = index.blocks.iter().flat_map(|b| &b.transactions);
+    let mut addresses: HashSet<&str> = HashSet::new();
+    for tx in transactions.clone() {
+        addresses.insert(&tx.from);
+        if let Some(to) = &tx.to {
+            addresses.insert(to);
+        }
+    }
+
+    Ok(ChainSummary {
+        chain_id: index.chain_id,
+        block_height: index.tip().unwrap_or(index.first_block),
+        first_block: index.first_block,
+        total_transactions: transactions.clone().count(),
+        active_addresses: addresses.len(),
+        contracts_deployed: transactions.filter(|tx| tx.to.is_none()).count(),
+        total_gas_used: index.blocks.iter().map(|b| b.gas_used).sum(),
+        total_fees_burned: index
+            .blocks
+            .iter()
+            .map(|b| b.gas_used as u128 * b.base_fee_per_gas.unwrap_or(0) as u128)
+            .sum::<u128>()
+            .to_string(),
+    })
+}
@@ -0,0 +1,243 @@
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::AppError;
+use crate::networks::{self, NetworkProfile, SignerSource};
+use crate::project;
+use crate::rpc::{self, RpcPolicy};
+
+const CHAINLIST_URL: &str = "https://chainid.network/chains.json";
+
+// Used when chainid.network can't be reached; public endpoints that need no API key
+const BUNDLED_CHAINS: [(&str, u64, &str, &str, &str, bool); 11] = [
+    ("Ethereum Mainnet", 1, "ETH", "https://ethereum-rpc.publicnode.com", "https://etherscan.io", false),
+    ("Sepolia", 11155111, "ETH", "https://ethereum-sepolia-rpc.publicnode.com", "https://sepolia.etherscan.io", true),
+    ("Holesky", 17000, "ETH", "https://ethereum-holesky-rpc.publicnode.com", "https://holesky.etherscan.io", true),
+    ("Base", 8453, "ETH", "https://mainnet.base.org", "https://basescan.org", false),
+    ("Base Sepolia", 84532, "ETH", "https://sepolia.base.org", "https://sepolia.basescan.org", true),
+    ("Arbitrum One", 42161, "ETH", "https://arb1.arbitrum.io/rpc", "https://arbiscan.io", false),
+    ("Arbitrum Sepolia", 421614, "ETH", "https://sepolia-rollup.arbitrum.io/rpc", "https://sepolia.arbiscan.io", true),
+    ("OP Mainnet", 10, "ETH", "https://mainnet.optimism.io", "https://optimistic.etherscan.io", false),
+    ("OP Sepolia", 11155420, "ETH", "https://sepolia.optimism.io", "https://sepolia-optimism.etherscan.io", true),
+    ("Polygon Mainnet", 137, "POL", "https://polygon-rpc.com", "https://polygonscan.com", false),
+    ("Polygon Amoy", 80002, "POL", "https://rpc-amoy.polygon.technology", "https://amoy.polygonscan.com", true),
+];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainSource {
+    Chainlist,
+    Bundled,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PublicChain {
+    pub name: String,
+    pub chain_id: u64,
+    pub currency_symbol: String,
+    // Only endpoints usable as-is; templated ones like .../${INFURA_API_KEY} are dropped
+    pub rpc_urls: Vec<String>,
+    pub explorer_url: Option<String>,
+    pub testnet: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ChainCatalog {
+    pub source: ChainSource,
+    pub chains: Vec<PublicChain>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainTarget {
+    NetworkProfile,
+    HardhatConfig,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AddedChain {
+    pub network_name: String,
+    pub chain_id: u64,
+    pub rpc_url: String,
+    pub target: ChainTarget,
+    // Set when the chain went into hardhat.config
+    pub config_path: Option<String>,
+}
+
+fn bundled_chains() -> Vec<PublicChain> {
+    BUNDLED_CHAINS
+        .iter()
+        .map(|(name, chain_id, symbol, rpc_url, explorer, testnet)| PublicChain {
+            name: name.to_string(),
+            chain_id: *chain_id,
+            currency_symbol: symbol.to_string(),
+            rpc_urls: vec![rpc_url.to_string()],
+            explorer_url: Some(explorer.to_string()),
+            testnet: *testnet,
+        })
+        .collect()
+}
+
+fn parse_chain(chain: &Value) -> Option<PublicChain> {
+    let name = chain.get("name")?.as_str()?.to_string();
+    let rpc_urls: Vec<String> = chain
+        .get("rpc")?
+        .as_array()?
+        .iter()
+        .filter_map(|rpc| rpc.as_str().or_else(|| rpc.get("url").and_then(Value::as_str)))
+        .filter(|url| url.starts_with("https://") && !url.contains("${"))
+        .map(str::to_string)
+        .collect();
+    let lower = name.to_lowercase();
+    Some(PublicChain {
+        chain_id: chain.get("chainId")?.as_u64()?,
+        currency_symbol: chain
+            .pointer("/nativeCurrency/symbol")
+            .and_then(Value::as_str)
+            .unwrap_or("ETH")
+            .to_string(),
+        rpc_urls,
+        explorer_url: chain.pointer("/explorers/0/url").and_then(Value::as_str).map(str::to_string),
+        testnet: ["testnet", "sepolia", "holesky", "goerli", "amoy", "devnet"].iter().any(|t| lower.contains(t)),
+        name,
+    })
+}
+
+async fn fetch_chains() -> Result<Vec<PublicChain>, String> {
+    let response = rpc::client()
+        .get(CHAINLIST_URL)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch chain list: {}", e))?;
+    let chains: Vec<Value> = response.json().await.map_err(|e| format!("Failed to parse chain list: {}", e))?;
+    Ok(chains.iter().filter_map(parse_chain).filter(|c| !c.rpc_urls.is_empty()).collect())
+}
+
+async fn catalog(offline: bool) -> ChainCatalog {
+    if !offline {
+        match fetch_chains().await {
+            Ok(chains) => return ChainCatalog { source: ChainSource::Chainlist, chains },
+            Err(e) => tracing::warn!(error = %e, "falling back to bundled chain list"),
+        }
+    }
+    ChainCatalog { source: ChainSource::Bundled, chains: bundled_chains() }
+}
+
+// "Arbitrum One" -> "arbitrumOne", the usual shape of hardhat network names
+fn network_name(chain_name: &str) -> String {
+    let mut name = String::new();
+    for word in chain_name.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()) {
+        if name.is_empty() {
+            name.push_str(&word.to_lowercase());
+        } else {
+            let mut chars = word.chars();
+            name.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+            name.push_str(&chars.as_str().to_lowercase());
+        }
+    }
+    name
+}
+
+fn add_to_config(project_path: &str, name: &str, chain: &PublicChain, rpc_url: &str) -> Result<String, String> {
+    let config_path =
+        project::find_hardhat_config(Path::new(project_path)).ok_or("No hardhat.config file found in project")?;
+    let config = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read {}: {}", config_path.display(), e))?;
+
+    let defined = Regex::new(&format!(r#"["'`]?\b{}\b["'`]?\s*:\s*\{{"#, regex::escape(name))).unwrap();
+    if defined.is_match(&config) {
+        return Err(format!("Network {} is already defined in {}", name, config_path.display()));
+    }
+
+    // Deployer keys stay in .env; the entry only names the variable
+    let entry = format!(
+        concat!(
+            "    {}: {{\n",
+            "      url: \"{}\",\n",
+            "      chainId: {},\n",
+            "      accounts: process.env.PRIVATE_KEY ? [process.env.PRIVATE_KEY] : [],\n",
+            "    }},\n"
+        ),
+        name, rpc_url, chain.chain_id
+    );
+    let networks = Regex::new(r"networks\s*:\s*\{[ \t]*\n?").unwrap();
+    let config_object = Regex::new(r"(module\.exports\s*=|export\s+default|HardhatUserConfig\s*=)\s*\{[ \t]*\n?").unwrap();
+    let updated = if let Some(m) = networks.find(&config) {
+        format!("{}{}{}", &config[..m.end()], entry, &config[m.end()..])
+    } else if let Some(m) = config_object.find(&config) {
+        format!("{}  networks: {{\n{}  }},\n{}", &config[..m.end()], entry, &config[m.end()..])
+    } else {
+        return Err(format!("Could not find the config object in {}", config_path.display()));
+    };
+
+    fs::write(&config_path, updated).map_err(|e| format!("Failed to update {}: {}", config_path.display(), e))?;
+    Ok(config_path.to_string_lossy().to_string())
+}
+
+// `search` matches the chain name or id
+#[tauri::command]
+pub async fn list_public_chains(
+    search: Option<String>,
+    testnets_only: Option<bool>,
+    offline: Option<bool>,
+) -> Result<ChainCatalog, AppError> {
+    let mut catalog = catalog(offline.unwrap_or(false)).await;
+    let search = search.map(|s| s.to_lowercase()).filter(|s| !s.is_empty());
+    catalog.chains.retain(|chain| {
+        search
+            .as_ref()
+            .is_none_or(|s| chain.name.to_lowercase().contains(s) || chain.chain_id.to_string() == *s)
+            && (!testnets_only.unwrap_or(false) || chain.testnet)
+    });
+    catalog.chains.sort_by_key(|c| c.chain_id);
+    Ok(catalog)
+}
+
+#[tauri::command]
+pub async fn add_public_chain(
+    chain_id: u64,
+    target: ChainTarget,
+    project_path: Option<String>,
+    name: Option<String>,
+    rpc_url: Option<String>,
+) -> Result<AddedChain, AppError> {
+    let catalog = catalog(false).await;
+    let chain = catalog
+        .chains
+        .into_iter()
+        .find(|c| c.chain_id == chain_id)
+        .or_else(|| bundled_chains().into_iter().find(|c| c.chain_id == chain_id))
+        .ok_or_else(|| format!("Unknown chain id {}", chain_id))?;
+    let network_name = name.unwrap_or_else(|| network_name(&chain.name));
+    let rpc_url = rpc_url
+        .or_else(|| chain.rpc_urls.first().cloned())
+        .ok_or_else(|| format!("{} has no public RPC endpoint", chain.name))?;
+
+    let config_path = match target {
+        ChainTarget::NetworkProfile => {
+            networks::save_network_profile(NetworkProfile {
+                name: network_name.clone(),
+                rpc_url: rpc_url.clone(),
+                chain_id,
+                currency_symbol: chain.currency_symbol.clone(),
+                explorer_url: chain.explorer_url.clone(),
+                signer: SignerSource::EnvPrivateKey { variable: "PRIVATE_KEY".to_string() },
+                rpc_policy: RpcPolicy::default(),
+                created_at: 0,
+                updated_at: 0,
+            })
+            .await?;
+            None
+        }
+        ChainTarget::HardhatConfig => {
+            let project_path = project_path.ok_or("A project is required to add a network to hardhat.config")?;
+            Some(add_to_config(&project_path, &network_name, &chain, &rpc_url)?)
+        }
+    };
+
+    Ok(AddedChain { network_name, chain_id, rpc_url, target, config_path })
+}
@@ -0,0 +1,855 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::State;
+
+use crate::error::AppError;
+use crate::extensions::{self, ExtensionKind, ExtensionStatus};
+use crate::presets::{self, PresetTarget};
+use crate::project;
+use crate::tasks::{self, TaskRegistryCache};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandSource {
+    Builtin,
+    Task,
+    Preset,
+    Script,
+    Extension,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CommandParam {
+    pub name: String,
+    // Where the value goes in the invoke args, dot-separated: "contract",
+    // "invocation.args.account". Top-level keys are camelCase, as Tauri expects command
+    // arguments; fields of nested structs keep their own names
+    pub path: String,
+    pub param_type: String,
+    pub required: bool,
+    pub description: Option<String>,
+    pub default_value: Option<Value>,
+}
+
+// One palette entry. It runs as `invoke(command, args)` with the open project as
+// `projectPath` when `needs_project`, a fresh `executionId` when `streams`, and each
+// param's value set at its path
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CommandEntry {
+    // "builtin:compile_contracts", "task:verify", "preset:seed", "script:scripts/deploy.ts"
+    pub id: String,
+    pub source: CommandSource,
+    pub title: String,
+    pub description: Option<String>,
+    pub category: String,
+    pub keywords: Vec<String>,
+    pub command: String,
+    pub args: Value,
+    pub needs_project: bool,
+    pub streams: bool,
+    pub params: Vec<CommandParam>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CommandIndex {
+    pub entries: Vec<CommandEntry>,
+    // Sources that couldn't be read, e.g. a project whose config fails to load has no
+    // task list; everything else is still indexed
+    pub warnings: Vec<String>,
+}
+
+// `name` is the command argument in snake_case, or "argument.field" for a field of a
+// struct argument
+struct Param {
+    name: &'static str,
+    param_type: &'static str,
+    required: bool,
+}
+
+const fn req(name: &'static str, param_type: &'static str) -> Param {
+    Param { name, param_type, required: true }
+}
+
+const fn opt(name: &'static str, param_type: &'static str) -> Param {
+    Param { name, param_type, required: false }
+}
+
+struct Builtin {
+    command: &'static str,
+    title: &'static str,
+    description: &'static str,
+    category: &'static str,
+    keywords: &'static [&'static str],
+    project: bool,
+    streams: bool,
+    params: &'static [Param],
+}
+
+// Actions worth reaching from the keyboard. Getters that only feed a panel and
+// commands the UI calls as part of a larger flow are left out
+const BUILTINS: &[Builtin] = &[
+    Builtin {
+        command: "create_hardhat_project",
+        title: "Create project",
+        description: "Scaffold a new Hardhat project and install its dependencies",
+        category: "Project",
+        keywords: &["new", "init", "scaffold", "template"],
+        project: false,
+        streams: false,
+        params: &[req("project_path", "path"), opt("template", "string")],
+    },
+    Builtin {
+        command: "discover_hardhat_projects",
+        title: "Find Hardhat projects",
+        description: "List Hardhat projects in, above and next to a folder",
+        category: "Project",
+        keywords: &["open", "monorepo", "workspace"],
+        project: false,
+        streams: false,
+        params: &[req("path", "path")],
+    },
+    Builtin {
+        command: "install_hardhat",
+        title: "Install Hardhat",
+        description: "Install Hardhat into the project, or globally",
+        category: "Project",
+        keywords: &["npm", "dependencies", "setup"],
+        project: true,
+        streams: true,
+        params: &[opt("global", "boolean")],
+    },
+    Builtin {
+        command: "check_project_health",
+        title: "Check project health",
+        description: "Look for missing files and configuration problems",
+        category: "Project",
+        keywords: &["doctor", "issues", "config"],
+        project: true,
+        streams: false,
+        params: &[],
+    },
+    Builtin {
+        command: "export_project_archive",
+        title: "Export project archive",
+        description: "Zip the project sources, optionally with the app's project data",
+        category: "Project",
+        keywords: &["zip", "backup", "share"],
+        project: true,
+        streams: false,
+        params: &[req("destination", "path"), req("include_gui_data", "boolean")],
+    },
+    Builtin {
+        command: "close_project",
+        title: "Close project",
+        description: "Cancel the project's running jobs and release its caches",
+        category: "Project",
+        keywords: &["cancel", "stop"],
+        project: true,
+        streams: false,
+        params: &[],
+    },
+    Builtin {
+        command: "compile_contracts",
+        title: "Compile contracts",
+        description: "Run hardhat compile",
+        category: "Build",
+        keywords: &["build", "solc"],
+        project: true,
+        streams: true,
+        params: &[],
+    },
+    Builtin {
+        command: "run_tests",
+        title: "Run tests",
+        description: "Run hardhat test",
+        category: "Build",
+        keywords: &["test", "mocha", "spec"],
+        project: true,
+        streams: true,
+        params: &[],
+    },
+    Builtin {
+        command: "format_contracts",
+        title: "Format contracts",
+        description: "Format Solidity sources with prettier",
+        category: "Build",
+        keywords: &["prettier", "fmt", "style"],
+        project: true,
+        streams: false,
+        params: &[opt("paths", "json"), opt("check", "boolean")],
+    },
+    Builtin {
+        command: "evaluate_gates",
+        title: "Evaluate quality gates",
+        description: "Check contract sizes and function gas against the project's thresholds",
+        category: "Build",
+        keywords: &["ci", "thresholds", "bytecode size", "gas"],
+        project: true,
+        streams: false,
+        params: &[],
+    },
+    Builtin {
+        command: "run_solhint",
+        title: "Lint contracts",
+        description: "Run solhint over the project's contracts",
+        category: "Analysis",
+        keywords: &["solhint", "lint", "style"],
+        project: true,
+        streams: false,
+        params: &[],
+    },
+    Builtin {
+        command: "run_ast_scanner",
+        title: "Scan contracts",
+        description: "Run the built-in AST checks over the last build",
+        category: "Analysis",
+        keywords: &["security", "static analysis", "findings"],
+        project: true,
+        streams: false,
+        params: &[],
+    },
+    Builtin {
+        command: "run_semgrep",
+        title: "Run Semgrep",
+        description: "Scan the contracts with Semgrep's Solidity rules",
+        category: "Analysis",
+        keywords: &["security", "static analysis", "findings"],
+        project: true,
+        streams: true,
+        params: &[opt("rulesets", "json")],
+    },
+    Builtin {
+        command: "run_mythril",
+        title: "Run Mythril",
+        description: "Symbolically execute a contract with Mythril",
+        category: "Analysis",
+        keywords: &["security", "symbolic", "findings"],
+        project: true,
+        streams: true,
+        params: &[req("contract", "contract"), opt("timeout_secs", "number"), opt("install", "boolean")],
+    },
+    Builtin {
+        command: "scan_licenses",
+        title: "Scan licenses",
+        description: "List dependency and source licenses and flag incompatible ones",
+        category: "Analysis",
+        keywords: &["spdx", "legal", "dependencies"],
+        project: true,
+        streams: false,
+        params: &[],
+    },
+    Builtin {
+        command: "check_vulnerable_dependencies",
+        title: "Check vulnerable dependencies",
+        description: "Match imported Solidity libraries against known advisories",
+        category: "Analysis",
+        keywords: &["security", "advisories", "openzeppelin"],
+        project: true,
+        streams: false,
+        params: &[],
+    },
+    Builtin {
+        command: "audit_proxies",
+        title: "Audit proxies",
+        description: "Check deployed proxies' implementations and admins",
+        category: "Analysis",
+        keywords: &["upgradeable", "implementation", "admin"],
+        project: true,
+        streams: false,
+        params: &[opt("rpc_url", "url")],
+    },
+    Builtin {
+        command: "get_access_control_matrix",
+        title: "Show access control matrix",
+        description: "Which roles and modifiers guard each function",
+        category: "Analysis",
+        keywords: &["roles", "owner", "permissions"],
+        project: true,
+        streams: false,
+        params: &[],
+    },
+    Builtin {
+        command: "snapshot_abi",
+        title: "Snapshot ABI",
+        description: "Save a contract's current ABI to diff against later",
+        category: "Analysis",
+        keywords: &["abi", "breaking changes"],
+        project: true,
+        streams: false,
+        params: &[req("contract", "contract"), opt("label", "string")],
+    },
+    Builtin {
+        command: "snapshot_storage_layout",
+        title: "Snapshot storage layout",
+        description: "Save a contract's storage layout to check upgrades against",
+        category: "Analysis",
+        keywords: &["storage", "upgrade", "layout"],
+        project: true,
+        streams: false,
+        params: &[req("contract", "contract"), opt("label", "string")],
+    },
+    Builtin {
+        command: "generate_report",
+        title: "Generate report",
+        description: "Bundle the project's test, coverage, gas and analysis results into one report",
+        category: "Analysis",
+        keywords: &["html", "markdown", "export"],
+        project: true,
+        streams: false,
+        params: &[opt("format", "string"), opt("output_path", "path")],
+    },
+    Builtin {
+        command: "start_hardhat_network",
+        title: "Start local node",
+        description: "Start a local node, optionally restoring a saved state",
+        category: "Node",
+        keywords: &["hardhat node", "anvil", "chain", "localhost"],
+        project: true,
+        streams: false,
+        params: &[
+            opt("state", "string"),
+            opt("backend", "string"),
+            opt("options.port", "number"),
+            opt("options.fork_url", "url"),
+            opt("options.fork_block_number", "number"),
+            opt("options.mnemonic", "string"),
+            opt("options.chain_id", "number"),
+        ],
+    },
+    Builtin {
+        command: "save_node_state",
+        title: "Save node state",
+        description: "Capture what local transactions changed on the node",
+        category: "Node",
+        keywords: &["snapshot", "dump", "state"],
+        project: true,
+        streams: false,
+        params: &[req("name", "string"), opt("rpc_url", "url")],
+    },
+    Builtin {
+        command: "load_node_state",
+        title: "Load node state",
+        description: "Restore a saved state into the running node",
+        category: "Node",
+        keywords: &["snapshot", "restore", "state"],
+        project: true,
+        streams: false,
+        params: &[req("name", "string"), opt("rpc_url", "url")],
+    },
+    Builtin {
+        command: "load_fixture",
+        title: "Load fixture",
+        description: "Restore a built fixture into the running node",
+        category: "Node",
+        keywords: &["setup", "seed", "state"],
+        project: true,
+        streams: false,
+        params: &[req("name", "string"), opt("rpc_url", "url")],
+    },
+    Builtin {
+        command: "time_travel",
+        title: "Time travel",
+        description: "Move the node to a timestamp, forward by a duration, or up to a block",
+        category: "Node",
+        keywords: &["mine", "increase time", "timestamp", "block", "warp"],
+        project: true,
+        streams: false,
+        params: &[req("target", "json"), opt("rpc_url", "url")],
+    },
+    Builtin {
+        command: "deploy_mock",
+        title: "Deploy mock contract",
+        description: "Deploy a mock ERC20, WETH or price feed to the local node",
+        category: "Node",
+        keywords: &["token", "erc20", "weth", "oracle", "chainlink"],
+        project: true,
+        streams: true,
+        params: &[req("spec", "json")],
+    },
+    Builtin {
+        command: "connect_external_node",
+        title: "Connect external node",
+        description: "Use an already running dev node instead of starting one",
+        category: "Node",
+        keywords: &["anvil", "ganache", "rpc"],
+        project: false,
+        streams: false,
+        params: &[req("rpc_url", "url")],
+    },
+    Builtin {
+        command: "start_l2_devnet",
+        title: "Start L2 devnet",
+        description: "Start a local OP Stack or Arbitrum Nitro devnet",
+        category: "Node",
+        keywords: &["optimism", "arbitrum", "rollup"],
+        project: false,
+        streams: false,
+        params: &[req("kind", "string"), opt("port", "number")],
+    },
+    Builtin {
+        command: "get_fork_diff",
+        title: "Show fork diff",
+        description: "What local transactions changed on top of the forked chain",
+        category: "Node",
+        keywords: &["fork", "changes", "state"],
+        project: true,
+        streams: false,
+        params: &[opt("rpc_url", "url")],
+    },
+    Builtin {
+        command: "export_genesis",
+        title: "Export genesis",
+        description: "Write the local chain's state as a genesis file",
+        category: "Node",
+        keywords: &["genesis", "state", "export"],
+        project: true,
+        streams: false,
+        params: &[opt("output_path", "path"), opt("include_state", "boolean"), opt("rpc_url", "url")],
+    },
+    Builtin {
+        command: "deploy_contracts",
+        title: "Deploy contracts",
+        description: "Deploy the project's Ignition module",
+        category: "Deploy",
+        keywords: &["ignition", "deploy", "module"],
+        project: true,
+        streams: true,
+        params: &[opt("network", "network"), opt("env", "json")],
+    },
+    Builtin {
+        command: "fetch_verified_contract",
+        title: "Fetch verified contract",
+        description: "Download a verified contract's sources and ABI from the explorer",
+        category: "Deploy",
+        keywords: &["etherscan", "source", "abi"],
+        project: true,
+        streams: false,
+        params: &[req("address", "address"), opt("chain_id", "number")],
+    },
+    Builtin {
+        command: "request_testnet_funds",
+        title: "Request testnet funds",
+        description: "Fund an address from a testnet faucet",
+        category: "Deploy",
+        keywords: &["faucet", "eth", "sepolia"],
+        project: false,
+        streams: false,
+        params: &[req("network", "network"), req("address", "address")],
+    },
+    Builtin {
+        command: "get_transaction_details",
+        title: "Look up transaction",
+        description: "Decoded call, events and trace for a transaction hash",
+        category: "Explorer",
+        keywords: &["tx", "hash", "receipt"],
+        project: true,
+        streams: false,
+        params: &[req("hash", "hash"), opt("rpc_url", "url")],
+    },
+    Builtin {
+        command: "decode_calldata",
+        title: "Decode calldata",
+        description: "Decode transaction input against known ABIs",
+        category: "Explorer",
+        keywords: &["input", "selector", "abi"],
+        project: true,
+        streams: false,
+        params: &[req("data", "hex"), opt("to", "address"), opt("lookup", "boolean")],
+    },
+    Builtin {
+        command: "search_logs",
+        title: "Search logs",
+        description: "Find decoded events by address, topic and block range",
+        category: "Explorer",
+        keywords: &["events", "topics", "filter"],
+        project: true,
+        streams: false,
+        params: &[req("filters", "json"), opt("rpc_url", "url")],
+    },
+    Builtin {
+        command: "get_accounts",
+        title: "Show accounts",
+        description: "The node's accounts with balances and nonces",
+        category: "Explorer",
+        keywords: &["signers", "balances", "wallets"],
+        project: true,
+        streams: false,
+        params: &[opt("rpc_url", "url")],
+    },
+    Builtin {
+        command: "simulate_transaction",
+        title: "Simulate transaction",
+        description: "Dry-run a call with optional state overrides",
+        category: "Explorer",
+        keywords: &["eth_call", "dry run", "override"],
+        project: true,
+        streams: false,
+        params: &[req("base", "json"), opt("overrides", "json"), opt("state", "json"), opt("rpc_url", "url")],
+    },
+    Builtin {
+        command: "convert_units",
+        title: "Convert units",
+        description: "Convert an amount between wei, gwei and ether",
+        category: "Utilities",
+        keywords: &["wei", "gwei", "ether", "eth"],
+        project: false,
+        streams: false,
+        params: &[req("amount", "string"), req("unit", "string")],
+    },
+    Builtin {
+        command: "keccak256_hash",
+        title: "Keccak-256 hash",
+        description: "Hash text or hex bytes",
+        category: "Utilities",
+        keywords: &["hash", "sha3", "keccak"],
+        project: false,
+        streams: false,
+        params: &[req("input", "string"), opt("encoding", "string")],
+    },
+    Builtin {
+        command: "compute_signature_hashes",
+        title: "Selector and topic",
+        description: "Function selector and event topic for a signature",
+        category: "Utilities",
+        keywords: &["selector", "topic", "signature", "4byte"],
+        project: false,
+        streams: false,
+        params: &[req("signature", "string")],
+    },
+    Builtin {
+        command: "abi_encode",
+        title: "ABI encode",
+        description: "Encode values, optionally as a function call",
+        category: "Utilities",
+        keywords: &["encode", "calldata"],
+        project: false,
+        streams: false,
+        params: &[req("types", "json"), req("values", "json"), opt("signature", "string")],
+    },
+    Builtin {
+        command: "abi_decode",
+        title: "ABI decode",
+        description: "Decode hex data as the given types",
+        category: "Utilities",
+        keywords: &["decode", "return data"],
+        project: false,
+        streams: false,
+        params: &[req("types", "json"), req("data", "hex")],
+    },
+    Builtin {
+        command: "check_address",
+        title: "Check address",
+        description: "Validate an address and its checksum",
+        category: "Utilities",
+        keywords: &["checksum", "eip-55"],
+        project: false,
+        streams: false,
+        params: &[req("address", "address")],
+    },
+    Builtin {
+        command: "check_environment",
+        title: "Check environment",
+        description: "Check that Node, npm and git are installed",
+        category: "App",
+        keywords: &["node", "npm", "tools", "setup"],
+        project: false,
+        streams: false,
+        params: &[],
+    },
+    Builtin {
+        command: "export_diagnostics",
+        title: "Export diagnostics",
+        description: "Bundle logs and environment details for a bug report",
+        category: "App",
+        keywords: &["logs", "bug", "support"],
+        project: false,
+        streams: false,
+        params: &[req("destination", "path"), opt("project_path", "path")],
+    },
+];
+
+fn to_camel_case(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            camel.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            camel.push(c);
+        }
+    }
+    camel
+}
+
+fn argument_path(name: &str) -> String {
+    match name.split_once('.') {
+        Some((argument, field)) => format!("{}.{}", to_camel_case(argument), field),
+        None => to_camel_case(name),
+    }
+}
+
+fn builtin_entries() -> Vec<CommandEntry> {
+    BUILTINS
+        .iter()
+        .map(|builtin| CommandEntry {
+            id: format!("builtin:{}", builtin.command),
+            source: CommandSource::Builtin,
+            title: builtin.title.to_string(),
+            description: Some(builtin.description.to_string()),
+            category: builtin.category.to_string(),
+            keywords: builtin.keywords.iter().map(|k| k.to_string()).collect(),
+            command: builtin.command.to_string(),
+            args: json!({}),
+            needs_project: builtin.project,
+            streams: builtin.streams,
+            params: builtin
+                .params
+                .iter()
+                .map(|param| CommandParam {
+                    name: param.name.rsplit('.').next().unwrap_or(param.name).to_string(),
+                    path: argument_path(param.name),
+                    param_type: param.param_type.to_string(),
+                    required: param.required,
+                    description: None,
+                    default_value: None,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn task_entries(tasks: Vec<tasks::HardhatTask>) -> Vec<CommandEntry> {
+    tasks
+        .into_iter()
+        .filter(|task| !task.subtask)
+        .map(|task| {
+            let title = match &task.scope {
+                Some(scope) => format!("{} {}", scope, task.name),
+                None => task.name.clone(),
+            };
+            let mut params: Vec<CommandParam> = task
+                .params
+                .iter()
+                .map(|param| CommandParam {
+                    name: param.name.clone(),
+                    path: format!("invocation.args.{}", param.name),
+                    param_type: if param.flag { "boolean".to_string() } else { param.param_type.clone() },
+                    required: !param.optional && !param.flag,
+                    description: param.description.clone(),
+                    default_value: param.default_value.clone(),
+                })
+                .collect();
+            params.push(CommandParam {
+                name: "network".to_string(),
+                path: "invocation.network".to_string(),
+                param_type: "network".to_string(),
+                required: false,
+                description: None,
+                default_value: None,
+            });
+            CommandEntry {
+                id: format!("task:{}", title.replace(' ', ":")),
+                source: CommandSource::Task,
+                title,
+                description: task.description.clone(),
+                category: "Hardhat tasks".to_string(),
+                keywords: task.scope.iter().cloned().collect(),
+                command: "run_hardhat_task_with_args".to_string(),
+                args: json!({ "invocation": { "task": task.name, "scope": task.scope, "args": {} } }),
+                needs_project: true,
+                streams: true,
+                params,
+            }
+        })
+        .collect()
+}
+
+fn preset_entries(presets: Vec<presets::TaskPreset>) -> Vec<CommandEntry> {
+    presets
+        .into_iter()
+        .map(|preset| {
+            let runs = match &preset.target {
+                PresetTarget::Task { task, args } if args.is_empty() => task.clone(),
+                PresetTarget::Task { task, args } => format!("{} {}", task, args.join(" ")),
+                PresetTarget::Script { script } => format!("run {}", script),
+            };
+            let mut keywords = vec![runs];
+            keywords.extend(preset.network.clone());
+            CommandEntry {
+                id: format!("preset:{}", preset.name),
+                source: CommandSource::Preset,
+                title: preset.name.clone(),
+                description: preset.description.clone(),
+                category: "Presets".to_string(),
+                keywords,
+                command: "run_task_preset".to_string(),
+                args: json!({ "name": preset.name }),
+                needs_project: true,
+                streams: true,
+                params: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+fn script_entries(root: &Path) -> Vec<CommandEntry> {
+    let dir = root.join("scripts");
+    let mut scripts: Vec<String> = ["js", "ts", "cjs", "mjs"]
+        .iter()
+        .flat_map(|extension| project::collect_files(&dir, extension))
+        .filter_map(|path| path.strip_prefix(root).ok().map(|p| p.to_string_lossy().replace('\\', "/")))
+        .collect();
+    scripts.sort();
+    scripts
+        .into_iter()
+        .map(|script| CommandEntry {
+            id: format!("script:{}", script),
+            source: CommandSource::Script,
+            title: format!("Run {}", script),
+            description: None,
+            category: "Scripts".to_string(),
+            keywords: vec!["hardhat run".to_string()],
+            command: "run_hardhat_task".to_string(),
+            args: json!({ "task": "run", "args": [script] }),
+            needs_project: true,
+            streams: true,
+            params: Vec::new(),
+        })
+        .collect()
+}
+
+// Built-in extensions own Tauri commands; sidecar methods go through call_extension
+async fn extension_entries() -> Result<Vec<CommandEntry>, String> {
+    let mut entries = Vec::new();
+    for extension in extensions::list_extensions().await.map_err(|e| e.to_string())? {
+        if extension.status != ExtensionStatus::Running {
+            continue;
+        }
+        let info = extension.info;
+        for command in &info.commands {
+            let (invoke, args, params) = match extension.kind {
+                ExtensionKind::Builtin => (command.clone(), json!({}), Vec::new()),
+                ExtensionKind::Sidecar => {
+                    let params = vec![CommandParam {
+                        name: "params".to_string(),
+                        path: "params".to_string(),
+                        param_type: "json".to_string(),
+                        required: false,
+                        description: None,
+                        default_value: None,
+                    }];
+                    ("call_extension".to_string(), json!({ "name": info.name, "method": command }), params)
+                }
+            };
+            entries.push(CommandEntry {
+                id: format!("extension:{}:{}", info.name, command),
+                source: CommandSource::Extension,
+                title: format!("{}: {}", info.name, command),
+                description: Some(info.description.clone()).filter(|d| !d.is_empty()),
+                category: info.name.clone(),
+                keywords: vec!["extension".to_string()],
+                command: invoke,
+                args,
+                needs_project: false,
+                streams: false,
+                params,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+// Every term has to appear somewhere in the entry; hits in the title rank highest
+fn score(entry: &CommandEntry, terms: &[String]) -> Option<u32> {
+    let title = entry.title.to_lowercase();
+    let rest = format!(
+        "{} {} {} {} {}",
+        entry.id,
+        entry.command,
+        entry.category,
+        entry.description.as_deref().unwrap_or_default(),
+        entry.keywords.join(" ")
+    )
+    .to_lowercase();
+    terms.iter().try_fold(0, |total, term| {
+        let points = if title.starts_with(term.as_str()) {
+            4
+        } else if title.split_whitespace().any(|word| word.starts_with(term.as_str())) {
+            3
+        } else if title.contains(term.as_str()) {
+            2
+        } else if rest.contains(term.as_str()) {
+            1
+        } else {
+            return None;
+        };
+        Some(total + points)
+    })
+}
+
+// Everything the palette can run: built-in actions, then the project's Hardhat tasks,
+// presets and scripts, then extension commands. With `query`, only matching entries
+// are returned, best match first
+#[tauri::command]
+pub async fn get_command_index(
+    cache: State<'_, TaskRegistryCache>,
+    project_path: Option<String>,
+    query: Option<String>,
+) -> Result<CommandIndex, AppError> {
+    let mut entries = builtin_entries();
+    let mut warnings = Vec::new();
+
+    if let Some(project_path) = &project_path {
+        let root = Path::new(project_path);
+        match tasks::load_tasks(&cache, project_path).await {
+            Ok(tasks) => entries.extend(task_entries(tasks)),
+            Err(e) => warnings.push(e.text),
+        }
+        match presets::load_presets(project_path) {
+            Ok(presets) => entries.extend(preset_entries(presets)),
+            Err(e) => warnings.push(e),
+        }
+        entries.extend(script_entries(root));
+    }
+    match extension_entries().await {
+        Ok(extension_entries) => entries.extend(extension_entries),
+        Err(e) => warnings.push(e),
+    }
+
+    let terms: Vec<String> = query.iter().flat_map(|q| q.split_whitespace()).map(str::to_lowercase).collect();
+    if !terms.is_empty() {
+        let mut scored: Vec<(u32, CommandEntry)> = entries
+            .into_iter()
+            .filter_map(|entry| score(&entry, &terms).map(|score| (score, entry)))
+            .collect();
+        // Stable, so equal scores keep the index order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        entries = scored.into_iter().map(|(_, entry)| entry).collect();
+    }
+
+    Ok(CommandIndex { entries, warnings })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_params_use_camel_case_argument_names() {
+        let start = builtin_entries().into_iter().find(|e| e.command == "start_hardhat_network").unwrap();
+        let paths: Vec<&str> = start.params.iter().map(|p| p.path.as_str()).collect();
+        assert!(paths.contains(&"options.fork_url"));
+        let mythril = builtin_entries().into_iter().find(|e| e.command == "run_mythril").unwrap();
+        assert_eq!(mythril.params[1].path, "timeoutSecs");
+        assert_eq!(mythril.params[1].name, "timeout_secs");
+        assert!(builtin_entries()
+            .iter()
+            .flat_map(|entry| &entry.params)
+            .all(|param| !param.path.split('.').next().unwrap().contains('_')));
+    }
+}
@@ -0,0 +1,603 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Stdio};
+use std::sync::{Arc, Mutex};
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+
+use crate::cancellation;
+use crate::db;
+use crate::error::AppError;
+use crate::messages::Message;
+use crate::processes::{self, ProcessManager};
+use crate::resolver;
+use crate::store;
+
+const OUTPUT_MARKER: &str = "__HARDHAT_GUI_CONSOLE__";
+
+// Keep only the tail of stderr so a chatty session can't grow without bound
+const STDERR_TAIL_BYTES: usize = 16 * 1024;
+
+// Hosts a REPL server with hre preloaded. Statements arrive as JSON lines on stdin and
+// are evaluated with the REPL's own evaluator, so variables survive between calls and
+// top-level `await` works exactly like in `npx hardhat console`.
+const SESSION_RUNNER: &str = r#"
+const hre = require("hardhat");
+const readline = require("node:readline");
+const repl = require("node:repl");
+const util = require("node:util");
+const { PassThrough } = require("node:stream");
+
+const MARKER = "__HARDHAT_GUI_CONSOLE__";
+const send = (message) => process.stdout.write(MARKER + JSON.stringify(message) + "\n");
+
+let captured = [];
+const capture = (...args) => captured.push(util.format(...args));
+const sessionConsole = { log: capture, info: capture, warn: capture, error: capture, debug: capture };
+
+// The REPL reports errors thrown by awaited code through its own handler instead of the
+// eval callback. That handler records the error as `lastError` before printing it, so a
+// new error object while an evaluation is pending is what fails it; the printed text
+// itself is never inspected
+let pending = null;
+let seenError = undefined;
+const finish = (message) => {
+  if (!pending) return;
+  const resolve = pending;
+  pending = null;
+  resolve(message);
+};
+const errorMessage = (error) => ({
+  ok: false,
+  error: error && error.message ? error.message : String(error),
+  stack: (error && error.stack) || null,
+});
+const output = new PassThrough();
+output.on("data", () => {
+  if (pending && server.lastError !== seenError) {
+    seenError = server.lastError;
+    finish(errorMessage(seenError));
+  }
+});
+
+const server = repl.start({
+  input: new PassThrough(),
+  output,
+  prompt: "",
+  terminal: false,
+  useGlobal: false,
+});
+Object.assign(server.context, {
+  hre,
+  ethers: hre.ethers,
+  network: hre.network,
+  artifacts: hre.artifacts,
+  config: hre.config,
+  console: sessionConsole,
+});
+
+const format = (value) => util.inspect(value, { depth: 4, colors: false });
+
+function evaluate(code) {
+  return new Promise((resolve) => {
+    pending = resolve;
+    seenError = server.lastError;
+    server.eval(code + "\n", server.context, "console", (error, result) => {
+      if (error instanceof repl.Recoverable) {
+        finish({ ok: false, error: "Incomplete input", stack: null });
+      } else if (error) {
+        finish(errorMessage(error));
+      } else {
+        Promise.resolve(result).then(
+          (value) => finish({ ok: true, result: value === undefined ? null : format(value) }),
+          (err) => finish(errorMessage(err))
+        );
+      }
+    });
+  });
+}
+
+// Statements run strictly one after another, like typing into a REPL
+let queue = Promise.resolve();
+readline.createInterface({ input: process.stdin }).on("line", (line) => {
+  const { id, code } = JSON.parse(line);
+  queue = queue.then(async () => {
+    captured = [];
+    const message = await evaluate(code);
+    send({ id, ...message, output: captured });
+  });
+});
+
+send({ ready: true, network: hre.network.name });
+"#;
+
+#[derive(Deserialize)]
+struct RunnerMessage {
+    id: Option<u64>,
+    ready: Option<bool>,
+    network: Option<String>,
+    ok: Option<bool>,
+    result: Option<String>,
+    error: Option<String>,
+    stack: Option<String>,
+    #[serde(default)]
+    output: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ConsoleSessionInfo {
+    pub project_path: String,
+    pub network: String,
+    pub pid: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ConsoleEvalResult {
+    pub success: bool,
+    pub result: Option<String>,
+    pub error: Option<String>,
+    pub stack: Option<String>,
+    pub output: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ConsoleHistoryEntry {
+    pub timestamp: u64,
+    pub command: String,
+    pub network: Option<String>,
+    pub success: bool,
+    pub result: Option<String>,
+    pub error: Option<String>,
+}
+
+pub struct ConsoleSession {
+    info: ConsoleSessionInfo,
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    stderr_tail: Arc<Mutex<String>>,
+    next_id: u64,
+}
+
+impl ConsoleSession {
+    fn spawn(project_path: &str, network: &str) -> Result<Self, String> {
+        let store_dir = store::project_store_dir(Path::new(project_path));
+        fs::create_dir_all(&store_dir)
+            .map_err(|e| format!("Failed to create {} directory: {}", store::STORE_DIR, e))?;
+
+        // .cjs keeps the runner working in projects with "type": "module"
+        let runner_path = store_dir.join("console-session.cjs");
+        fs::write(&runner_path, SESSION_RUNNER)
+            .map_err(|e| format!("Failed to create console runner: {}", e))?;
+
+        // Its own process group, so a runaway evaluation can be killed with everything
+        // npx/hardhat started under it
+        let mut cmd = resolver::hardhat(Path::new(project_path));
+        processes::isolate(&mut cmd);
+        let mut child = cmd
+            .args(["run", "--no-compile", &runner_path.to_string_lossy(), "--network", network])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+
+        let stdin = child.stdin.take().ok_or("Failed to open console stdin")?;
+        let stdout = BufReader::new(child.stdout.take().ok_or("Failed to open console stdout")?);
+        let stderr = child.stderr.take().ok_or("Failed to open console stderr")?;
+
+        // Drain stderr continuously so the child never blocks on a full pipe
+        let stderr_tail = Arc::new(Mutex::new(String::new()));
+        let tail = stderr_tail.clone();
+        std::thread::spawn(move || {
+            let mut reader = BufReader::new(stderr);
+            let mut buf = [0u8; 4096];
+            while let Ok(n) = reader.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                let mut tail = tail.lock().unwrap();
+                tail.push_str(&String::from_utf8_lossy(&buf[..n]));
+                if tail.len() > STDERR_TAIL_BYTES {
+                    let mut cut = tail.len() - STDERR_TAIL_BYTES;
+                    while !tail.is_char_boundary(cut) {
+                        cut += 1;
+                    }
+                    tail.drain(..cut);
+                }
+            }
+        });
+
+        let mut session = ConsoleSession {
+            info: ConsoleSessionInfo {
+                project_path: project_path.to_string(),
+                network: network.to_string(),
+                pid: child.id(),
+            },
+            child,
+            stdin,
+            stdout,
+            stderr_tail,
+            next_id: 1,
+        };
+
+        // Hardhat startup (config loading, plugins) happens once, here
+        let ready = session.read_message()?;
+        if ready.ready != Some(true) {
+            return Err("Console session did not start correctly".to_string());
+        }
+        if let Some(network) = ready.network {
+            session.info.network = network;
+        }
+
+        Ok(session)
+    }
+
+    fn read_message(&mut self) -> Result<RunnerMessage, String> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = self
+                .stdout
+                .read_line(&mut line)
+                .map_err(|e| format!("Failed to read console output: {}", e))?;
+
+            if read == 0 {
+                let stderr = self.stderr_tail.lock().unwrap().clone();
+                return Err(format!("Console session exited unexpectedly\n{}", stderr));
+            }
+
+            // Anything without the marker is hardhat/plugin noise, not a response
+            if let Some(json) = line.trim_end().strip_prefix(OUTPUT_MARKER) {
+                return serde_json::from_str(json)
+                    .map_err(|e| format!("Failed to parse console response: {}", e));
+            }
+        }
+    }
+
+    fn eval(&mut self, code: &str) -> Result<ConsoleEvalResult, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let request = json!({ "id": id, "code": code });
+        writeln!(self.stdin, "{}", request)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| format!("Failed to send command to console: {}", e))?;
+
+        loop {
+            let message = self.read_message()?;
+            if message.id != Some(id) {
+                continue;
+            }
+
+            return Ok(ConsoleEvalResult {
+                success: message.ok.unwrap_or(false),
+                result: message.result,
+                error: message.error,
+                stack: message.stack,
+                output: message.output,
+            });
+        }
+    }
+}
+
+impl Drop for ConsoleSession {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+// The session is locked for as long as an evaluation runs, so its info is kept
+// alongside it for callers that mustn't wait on that
+#[derive(Clone)]
+struct OpenSession {
+    info: ConsoleSessionInfo,
+    session: Arc<Mutex<ConsoleSession>>,
+}
+
+// One long-lived session per project path
+#[derive(Default)]
+pub struct ConsoleSessions(Mutex<HashMap<String, OpenSession>>);
+
+impl ConsoleSessions {
+    fn get(&self, project_path: &str) -> Option<OpenSession> {
+        self.0.lock().unwrap().get(project_path).cloned()
+    }
+}
+
+#[tauri::command]
+pub async fn open_console_session(
+    sessions: State<'_, ConsoleSessions>,
+    project_path: String,
+    network: Option<String>,
+) -> Result<ConsoleSessionInfo, AppError> {
+    let network = network.unwrap_or_else(|| "localhost".to_string());
+
+    if let Some(existing) = sessions.get(&project_path) {
+        if existing.info.network == network {
+            return Ok(existing.info);
+        }
+        // Switching networks means a fresh runtime environment
+        sessions.0.lock().unwrap().remove(&project_path);
+    }
+
+    let path = project_path.clone();
+    let session = tauri::async_runtime::spawn_blocking(move || ConsoleSession::spawn(&path, &network))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|session| session)
+        .map_err(|error| Message::new("console.start_failed", json!({ "error": error })))?;
+
+    let info = session.info.clone();
+    let open = OpenSession { info: info.clone(), session: Arc::new(Mutex::new(session)) };
+    sessions.0.lock().unwrap().insert(project_path, open);
+
+    Ok(info)
+}
+
+// Runs under an execution id like other long-running commands: cancel_execution, closing
+// the project or the "console" timeout stop it. A statement that never yields
+// (`while (true) {}`) can't be interrupted inside the runner, so stopping it kills the
+// session; the next open starts a fresh one
+#[tauri::command]
+pub async fn eval_console_session(
+    app: AppHandle,
+    sessions: State<'_, ConsoleSessions>,
+    project_path: String,
+    code: String,
+    execution_id: Option<String>,
+) -> Result<ConsoleEvalResult, AppError> {
+    let open = sessions
+        .get(&project_path)
+        .ok_or_else(|| Message::new("console.no_session", json!({})))?;
+
+    let manager = app.state::<ProcessManager>();
+    let execution_id = execution_id.unwrap_or_else(|| manager.new_execution_id());
+    let execution = cancellation::execution(Some(Path::new(&project_path)), &execution_id);
+    let timeout = manager.timeout_for("console");
+    let expired = async {
+        match timeout {
+            Some(timeout) => tokio::time::sleep(timeout).await,
+            None => std::future::pending().await,
+        }
+    };
+
+    let command = code.clone();
+    let session = open.session.clone();
+    let evaluation = tauri::async_runtime::spawn_blocking(move || session.lock().unwrap().eval(&code));
+    let result: Result<ConsoleEvalResult, AppError> = tokio::select! {
+        joined = evaluation => joined
+            .map_err(|e| e.to_string())
+            .and_then(|result| result)
+            .map_err(|error| Message::new("console.eval_failed", json!({ "error": error })).into()),
+        _ = expired => Err(AppError::ProcessTimeout {
+            execution_id: execution_id.clone(),
+            kind: "console".to_string(),
+            seconds: timeout.map_or(0, |timeout| timeout.as_secs()),
+            output: String::new(),
+        }),
+        _ = execution.token.cancelled() => Err(AppError::Cancelled { execution_id: execution_id.clone() }),
+    };
+    if matches!(result, Err(AppError::ProcessTimeout { .. } | AppError::Cancelled { .. })) {
+        // The blocked evaluation sees the runner exit and returns on its own
+        processes::kill_process_tree(open.info.pid).await;
+    }
+
+    let network = open.info.network;
+    match &result {
+        Ok(eval) => record_history(
+            &project_path,
+            &command,
+            Some(&network),
+            eval.success,
+            eval.result.clone().or_else(|| (!eval.output.is_empty()).then(|| eval.output.join("\n"))),
+            eval.error.clone(),
+        ),
+        Err(error) => {
+            record_history(&project_path, &command, Some(&network), false, None, Some(error.to_string()));
+            // A dead runner can't be reused; drop it so the next open starts clean
+            sessions.0.lock().unwrap().remove(&project_path);
+        }
+    }
+
+    result
+}
+
+#[tauri::command]
+pub async fn close_console_session(
+    sessions: State<'_, ConsoleSessions>,
+    project_path: String,
+) -> Result<bool, AppError> {
+    let removed = sessions.0.lock().unwrap().remove(&project_path);
+    Ok(removed.is_some())
+}
+
+// One-shot execution for run_hardhat_console_command. The user's code never becomes part
+// of a generated script: it arrives as JSON on stdin and runs inside a node:vm context
+// with hre injected, so quotes/braces in the input can't break or escape the runner.
+const EXEC_RUNNER: &str = r#"
+const hre = require("hardhat");
+const util = require("node:util");
+const vm = require("node:vm");
+
+const MARKER = "__HARDHAT_GUI_CONSOLE__";
+const send = (message) => process.stdout.write(MARKER + JSON.stringify(message) + "\n");
+
+const captured = [];
+const capture = (...args) => captured.push(util.format(...args));
+const sandboxConsole = { log: capture, info: capture, warn: capture, error: capture, debug: capture };
+
+const context = vm.createContext({
+  hre,
+  ethers: hre.ethers,
+  network: hre.network,
+  artifacts: hre.artifacts,
+  config: hre.config,
+  provider: hre.ethers ? hre.ethers.provider : undefined,
+  console: sandboxConsole,
+  setTimeout,
+  clearTimeout,
+  setInterval,
+  clearInterval,
+  TextEncoder,
+  TextDecoder,
+  URL,
+  Buffer,
+});
+
+// Expressions ("await token.name()") return their value; anything else runs as a
+// function body where `return` picks the result
+function compile(code) {
+  try {
+    const expression = code.trim().replace(/;+$/, "");
+    return new vm.Script(`(async () => (${expression}\n))()`, { filename: "console" });
+  } catch (_) {
+    return new vm.Script(`(async () => {${code}\n})()`, { filename: "console" });
+  }
+}
+
+let input = "";
+process.stdin.setEncoding("utf8");
+process.stdin.on("data", (chunk) => (input += chunk));
+process.stdin.on("end", async () => {
+  try {
+    const { code } = JSON.parse(input);
+    const value = await compile(code).runInContext(context);
+    send({
+      ok: true,
+      result: value === undefined ? null : util.inspect(value, { depth: 4, colors: false }),
+      output: captured,
+    });
+  } catch (error) {
+    send({
+      ok: false,
+      error: error && error.message ? error.message : String(error),
+      stack: (error && error.stack) || null,
+      output: captured,
+    });
+  }
+});
+"#;
+
+// Tracked like any other command, so it can be cancelled and the "console" timeout applies
+#[tauri::command]
+pub async fn run_hardhat_console_command(
+    app: AppHandle,
+    project_path: String,
+    command: String,
+    network: Option<String>,
+    execution_id: Option<String>,
+) -> Result<ConsoleEvalResult, AppError> {
+    let network = network.unwrap_or_else(|| "localhost".to_string());
+
+    let start_failed = |error: String| Message::new("console.start_failed", json!({ "error": error }));
+    let store_dir = store::project_store_dir(Path::new(&project_path));
+    fs::create_dir_all(&store_dir)
+        .map_err(|e| start_failed(format!("Failed to create {} directory: {}", store::STORE_DIR, e)))?;
+    let runner_path = store_dir.join("console-exec.cjs");
+    fs::write(&runner_path, EXEC_RUNNER)
+        .map_err(|e| start_failed(format!("Failed to create console runner: {}", e)))?;
+
+    let mut cmd = resolver::hardhat(Path::new(&project_path));
+    cmd.args(["run", "--no-compile", &runner_path.to_string_lossy(), "--network", &network]);
+    let request = format!("{}\n", json!({ "code": command }));
+    let output = processes::run_tracked_with_input(&app, execution_id, "console", cmd, Some(request.into_bytes())).await?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let message = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix(OUTPUT_MARKER))
+        .and_then(|json| serde_json::from_str::<RunnerMessage>(json).ok());
+
+    let result = match message {
+        Some(message) => ConsoleEvalResult {
+            success: message.ok.unwrap_or(false),
+            result: message.result,
+            error: message.error,
+            stack: message.stack,
+            output: message.output,
+        },
+        // No structured response means hardhat itself failed (bad config, node down...)
+        None => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(Message::new("console.command_failed", json!({ "stderr": stderr, "stdout": stdout })).into());
+        }
+    };
+
+    record_history(
+        &project_path,
+        &command,
+        Some(&network),
+        result.success,
+        result.result.clone(),
+        result.error.clone(),
+    );
+
+    Ok(result)
+}
+
+// History is best-effort: failing to record it must never fail the command itself
+pub fn record_history(
+    project_path: &str,
+    command: &str,
+    network: Option<&str>,
+    success: bool,
+    result: Option<String>,
+    error: Option<String>,
+) {
+    let entry = ConsoleHistoryEntry {
+        timestamp: store::timestamp_millis(),
+        command: command.to_string(),
+        network: network.map(|n| n.to_string()),
+        success,
+        result,
+        error,
+    };
+    if let Err(e) = db::with(Path::new(project_path), |conn| insert_history(conn, &entry)) {
+        tracing::debug!(error = %e, "failed to record console history");
+    }
+}
+
+pub fn insert_history(conn: &Connection, entry: &ConsoleHistoryEntry) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO console_history (timestamp, command, network, success, result, error) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![entry.timestamp, entry.command, entry.network, entry.success, entry.result, entry.error],
+    )?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_console_history(
+    project_path: String,
+    search: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<ConsoleHistoryEntry>, AppError> {
+    let search = search.map(|s| s.to_lowercase()).filter(|s| !s.is_empty());
+    let limit = limit.map_or(-1, |limit| limit as i64);
+    // Newest first, which is what both recall lists and up-arrow navigation want
+    let entries = db::with(Path::new(&project_path), |conn| {
+        conn.prepare_cached(
+            "SELECT timestamp, command, network, success, result, error FROM console_history
+             WHERE ?1 IS NULL OR instr(lower(command), ?1) > 0 ORDER BY id DESC LIMIT ?2",
+        )?
+        .query_map(params![search, limit], |row| {
+            Ok(ConsoleHistoryEntry {
+                timestamp: row.get(0)?,
+                command: row.get(1)?,
+                network: row.get(2)?,
+                success: row.get(3)?,
+                result: row.get(4)?,
+                error: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })?;
+
+    Ok(entries)
+}
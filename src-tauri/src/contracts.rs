@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::store;
+
+const IGNORE_FILE: &str = "contracts-ignore.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ContractInfo {
+    pub name: String,
+    pub path: String,
+    pub compiled: bool,
+    pub size: Option<u64>,
+}
+
+struct ProjectIndex {
+    contracts: Vec<ContractInfo>,
+    // Set by the watcher whenever sources or artifacts change
+    stale: Arc<AtomicBool>,
+    // Kept alive for as long as the index; dropping it stops the watch
+    _watcher: Option<RecommendedWatcher>,
+}
+
+static INDEXES: OnceLock<Mutex<HashMap<PathBuf, ProjectIndex>>> = OnceLock::new();
+
+fn indexes() -> &'static Mutex<HashMap<PathBuf, ProjectIndex>> {
+    INDEXES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// Watches contracts/ and artifacts/, plus the project root itself so either directory
+// appearing (first compile) or disappearing (clean) also invalidates the index
+fn watch(root: &Path, stale: Arc<AtomicBool>) -> Option<RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+        Ok(event) if matches!(event.kind, EventKind::Access(_)) => {}
+        _ => stale.store(true, Ordering::SeqCst),
+    })
+    .map_err(|e| tracing::warn!(error = %e, "contract watcher unavailable"))
+    .ok()?;
+    watcher.watch(root, RecursiveMode::NonRecursive).ok()?;
+    for dir in ["contracts", "artifacts"] {
+        let dir = root.join(dir);
+        if dir.is_dir() {
+            if let Err(e) = watcher.watch(&dir, RecursiveMode::Recursive) {
+                tracing::warn!(dir = ?dir, error = %e, "failed to watch directory");
+            }
+        }
+    }
+    Some(watcher)
+}
+
+// Patterns are matched against paths under contracts/: a bare name matches any directory
+// or file with that name, `*.suffix` / `prefix*` match file names, and anything with a
+// `/` is a path prefix such as `vendor/old`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ContractIgnore {
+    pub patterns: Vec<String>,
+}
+
+impl Default for ContractIgnore {
+    fn default() -> Self {
+        ContractIgnore {
+            patterns: ["node_modules", "mocks", "test", "*.t.sol"].map(String::from).to_vec(),
+        }
+    }
+}
+
+impl ContractIgnore {
+    fn matches(&self, relative: &Path) -> bool {
+        let relative_str = relative.to_string_lossy().replace('\\', "/");
+        let names: Vec<&str> = relative.iter().filter_map(|part| part.to_str()).collect();
+        let file_name = names.last().copied().unwrap_or_default();
+        self.patterns.iter().map(|pattern| pattern.trim().trim_matches('/')).any(|pattern| {
+            if pattern.is_empty() {
+                false
+            } else if pattern.contains('/') {
+                relative_str == pattern || relative_str.starts_with(&format!("{}/", pattern))
+            } else if let Some(suffix) = pattern.strip_prefix('*') {
+                file_name.ends_with(suffix)
+            } else if let Some(prefix) = pattern.strip_suffix('*') {
+                file_name.starts_with(prefix)
+            } else {
+                names.contains(&pattern)
+            }
+        })
+    }
+}
+
+// Hardhat mirrors the source tree: artifacts/contracts/interfaces/IFoo.sol/IFoo.json
+fn contract_info(root: &Path, path: PathBuf) -> ContractInfo {
+    let name = path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Unknown")
+        .to_string();
+    let size = fs::metadata(&path).ok().map(|m| m.len());
+    let source_name = path.strip_prefix(root).unwrap_or(&path);
+    let artifact_dir = root.join("artifacts").join(source_name);
+    // Also check for dbg.json which is sometimes created
+    let compiled = artifact_dir.join(format!("{}.json", name)).exists()
+        || artifact_dir.join(format!("{}.dbg.json", name)).exists();
+
+    tracing::debug!(contract = %name, artifacts = ?artifact_dir, compiled, "listed contract");
+
+    ContractInfo {
+        name,
+        path: path.to_string_lossy().to_string(),
+        compiled,
+        size,
+    }
+}
+
+fn collect_sources(dir: &Path, contracts_dir: &Path, ignore: &ContractIgnore, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read contracts directory: {}", e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let path = entry.path();
+        if ignore.matches(path.strip_prefix(contracts_dir).unwrap_or(&path)) {
+            continue;
+        }
+        // file_type doesn't follow symlinks, so a link loop can't recurse forever
+        let file_type = entry.file_type().map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        if file_type.is_dir() {
+            collect_sources(&path, contracts_dir, ignore, files)?;
+        } else if path.extension().and_then(|s| s.to_str()) == Some("sol") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn source_files(root: &Path) -> Result<Vec<PathBuf>, String> {
+    let contracts_dir = root.join("contracts");
+    if !contracts_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let ignore: ContractIgnore = store::load_json(root, IGNORE_FILE)?;
+    let mut files = Vec::new();
+    collect_sources(&contracts_dir, &contracts_dir, &ignore, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+// Stats sources and artifacts across a few threads; large projects have hundreds of files
+fn scan(root: &Path) -> Result<Vec<ContractInfo>, String> {
+    let files = source_files(root)?;
+    let workers = thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+    let chunk = files.len().div_ceil(workers).max(1);
+    Ok(thread::scope(|scope| {
+        let handles: Vec<_> = files
+            .chunks(chunk)
+            .map(|paths| scope.spawn(move || paths.iter().map(|path| contract_info(root, path.clone())).collect::<Vec<_>>()))
+            .collect();
+        handles.into_iter().flat_map(|handle| handle.join().unwrap_or_default()).collect()
+    }))
+}
+
+fn cached(root: &Path) -> Option<Vec<ContractInfo>> {
+    let indexes = indexes().lock().ok()?;
+    let index = indexes.get(root)?;
+    (!index.stale.load(Ordering::SeqCst)).then(|| index.contracts.clone())
+}
+
+// Served from memory until the watcher reports a change under contracts/ or artifacts/
+pub async fn contracts(project_path: &str) -> Result<Vec<ContractInfo>, String> {
+    let root = PathBuf::from(project_path);
+    if let Some(contracts) = cached(&root) {
+        return Ok(contracts);
+    }
+
+    // Watch before scanning so edits made while the scan runs still mark it stale
+    let stale = Arc::new(AtomicBool::new(false));
+    let watcher = watch(&root, stale.clone());
+    let scan_root = root.clone();
+    let contracts = tauri::async_runtime::spawn_blocking(move || scan(&scan_root))
+        .await
+        .map_err(|e| format!("Contract scan failed: {}", e))??;
+    if watcher.is_none() {
+        // Nothing would ever invalidate it, so don't cache
+        return Ok(contracts);
+    }
+
+    let mut indexes = indexes().lock().map_err(|_| "Contract index lock poisoned".to_string())?;
+    indexes.insert(root, ProjectIndex {
+        contracts: contracts.clone(),
+        stale,
+        _watcher: watcher,
+    });
+    Ok(contracts)
+}
+
+// Drops the cached listing along with its file watcher
+pub fn forget(project_path: &Path) {
+    if let Ok(mut indexes) = indexes().lock() {
+        indexes.remove(project_path);
+    }
+}
+
+#[tauri::command]
+pub async fn list_contracts(project_path: String) -> Result<Vec<ContractInfo>, AppError> {
+    Ok(contracts(&project_path).await?)
+}
+
+#[tauri::command]
+pub async fn get_contract_ignore(project_path: String) -> Result<ContractIgnore, AppError> {
+    Ok(store::load_json(Path::new(&project_path), IGNORE_FILE)?)
+}
+
+#[tauri::command]
+pub async fn set_contract_ignore(project_path: String, patterns: Vec<String>) -> Result<ContractIgnore, AppError> {
+    let ignore = ContractIgnore { patterns };
+    store::save_json(Path::new(&project_path), IGNORE_FILE, &ignore)?;
+    // The store directory isn't watched, so drop the cached listing here
+    forget(Path::new(&project_path));
+    Ok(ignore)
+}
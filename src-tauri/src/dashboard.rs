@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::chain_index;
+use crate::chain_metrics::{self, ChainMetrics, ChainMetricsInfo};
+use crate::deployments;
+use crate::error::AppError;
+use crate::metrics::{self, OperationRecord, Outcome};
+use crate::processes::{ProcessManager, RecentOutput};
+use crate::rpc;
+use crate::scheduler::{self, Scheduler};
+use crate::subgraph::{self, SubgraphHealth};
+
+#[derive(Serialize, Deserialize)]
+pub struct NodeStatus {
+    pub rpc_url: String,
+    pub running: bool,
+    pub chain_id: Option<u64>,
+    pub block_number: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CompileSummary {
+    pub finished_at: u64,
+    pub duration_ms: u64,
+    pub outcome: Outcome,
+    // Counted from the captured output, so only known while this session ran the compile
+    pub warnings: Option<usize>,
+    pub errors: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TestSummary {
+    pub finished_at: u64,
+    pub duration_ms: u64,
+    pub outcome: Outcome,
+    pub passing: Option<u64>,
+    pub failing: Option<u64>,
+    pub pending: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeploymentCounts {
+    pub total: usize,
+    // Deployment id -> contracts deployed under it
+    pub by_deployment: BTreeMap<String, usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WatcherStates {
+    pub chain_metrics: Option<ChainMetricsInfo>,
+    pub active_schedules: usize,
+    pub total_schedules: usize,
+    pub running_executions: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DashboardState {
+    pub node: NodeStatus,
+    pub last_compile: Option<CompileSummary>,
+    pub last_test: Option<TestSummary>,
+    pub deployments: DeploymentCounts,
+    pub watchers: WatcherStates,
+    // Only for projects with a subgraph/ directory
+    pub subgraph: Option<SubgraphHealth>,
+}
+
+async fn node_status(url: &str) -> NodeStatus {
+    let block_number = chain_index::block_number(url).await.ok();
+    let chain_id = match block_number {
+        Some(_) => rpc::chain_id(url).await.ok(),
+        None => None,
+    };
+    NodeStatus { rpc_url: url.to_string(), running: block_number.is_some(), chain_id, block_number }
+}
+
+// Captured output only belongs to the recorded run if it finished after that run started
+fn output_for<'a>(
+    outputs: &'a [RecentOutput],
+    project_path: &str,
+    run: &OperationRecord,
+) -> Option<&'a RecentOutput> {
+    outputs.iter().rev().find(|o| {
+        o.kind == run.kind && o.project_path.as_deref() == Some(project_path) && o.finished_at >= run.started_at
+    })
+}
+
+// solc prefixes diagnostics with their type: "Warning:", "TypeError:", "ParserError:"
+fn count_diagnostics(text: &str, pattern: &str) -> usize {
+    let pattern = Regex::new(pattern).unwrap();
+    text.lines().filter(|line| pattern.is_match(line)).count()
+}
+
+// Mocha's closing summary: "12 passing (3s)", "1 failing", "2 pending"
+fn mocha_count(text: &str, label: &str) -> Option<u64> {
+    let pattern = Regex::new(&format!(r"(?m)^\s*(\d+) {}", label)).unwrap();
+    pattern.captures_iter(text).last().and_then(|c| c[1].parse().ok())
+}
+
+fn compile_summary(run: OperationRecord, output: Option<&RecentOutput>) -> CompileSummary {
+    let text = output.map(|o| format!("{}\n{}", o.stdout, o.stderr));
+    CompileSummary {
+        finished_at: run.started_at + run.duration_ms,
+        duration_ms: run.duration_ms,
+        outcome: run.outcome,
+        warnings: text.as_deref().map(|t| count_diagnostics(t, r"^\s*Warning:")),
+        errors: text.as_deref().map(|t| count_diagnostics(t, r"^\s*\w*Error:")),
+    }
+}
+
+fn test_summary(run: OperationRecord, output: Option<&RecentOutput>) -> TestSummary {
+    let stdout = output.map(|o| o.stdout.as_str()).unwrap_or_default();
+    TestSummary {
+        finished_at: run.started_at + run.duration_ms,
+        duration_ms: run.duration_ms,
+        outcome: run.outcome,
+        passing: mocha_count(stdout, "passing"),
+        failing: mocha_count(stdout, "failing"),
+        pending: mocha_count(stdout, "pending"),
+    }
+}
+
+// Everything the dashboard shows in one call, instead of one poll per panel
+#[tauri::command]
+pub async fn get_dashboard_state(
+    processes: State<'_, ProcessManager>,
+    scheduler: State<'_, Scheduler>,
+    chain_metrics: State<'_, ChainMetrics>,
+    project_path: String,
+    rpc_url: Option<String>,
+) -> Result<DashboardState, AppError> {
+    let root = Path::new(&project_path);
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let outputs = processes.recent_outputs();
+
+    let last_compile = metrics::last_run(root, "compile").map(|run| {
+        let output = output_for(&outputs, &project_path, &run);
+        compile_summary(run, output)
+    });
+    let last_test = metrics::last_run(root, "test").map(|run| {
+        let output = output_for(&outputs, &project_path, &run);
+        test_summary(run, output)
+    });
+
+    let mut by_deployment = BTreeMap::new();
+    let all_deployments = deployments::load_deployments(root);
+    for deployment in &all_deployments {
+        *by_deployment.entry(deployment.deployment_id.clone()).or_insert(0) += 1;
+    }
+
+    let schedules = scheduler::list_schedules(scheduler, Some(project_path.clone())).await?;
+    let watchers = WatcherStates {
+        chain_metrics: chain_metrics::get_chain_metrics_stream(chain_metrics).await?,
+        active_schedules: schedules.iter().filter(|s| s.active).count(),
+        total_schedules: schedules.len(),
+        running_executions: processes.running_count(),
+    };
+
+    Ok(DashboardState {
+        node: node_status(url).await,
+        last_compile,
+        last_test,
+        deployments: DeploymentCounts { total: all_deployments.len(), by_deployment },
+        watchers,
+        subgraph: if subgraph::has_subgraph(root) { Some(subgraph::health(root).await) } else { None },
+    })
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::audit;
+use crate::console;
+use crate::metrics;
+use crate::store;
+
+// Per-project history (operations, console, chain index, deployments, gas) lives in
+// one SQLite file in the store directory; the audit trail uses the same schema in an
+// app-wide file since it spans projects
+pub const DB_FILE: &str = "project.db";
+
+// Applied in order and recorded in `PRAGMA user_version`. Released migrations are
+// never edited; schema changes get a new entry
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE operations (
+        id INTEGER PRIMARY KEY,
+        kind TEXT NOT NULL,
+        started_at INTEGER NOT NULL,
+        duration_ms INTEGER NOT NULL,
+        outcome TEXT NOT NULL,
+        exit_code INTEGER
+    );
+    CREATE INDEX operations_kind ON operations (kind, id);
+
+    CREATE TABLE console_history (
+        id INTEGER PRIMARY KEY,
+        timestamp INTEGER NOT NULL,
+        command TEXT NOT NULL,
+        network TEXT,
+        success INTEGER NOT NULL,
+        result TEXT,
+        error TEXT
+    );
+
+    CREATE TABLE chain_index (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        rpc_url TEXT NOT NULL,
+        chain_id INTEGER NOT NULL,
+        first_block INTEGER NOT NULL
+    );
+    CREATE TABLE blocks (
+        number INTEGER PRIMARY KEY,
+        hash TEXT NOT NULL,
+        timestamp INTEGER NOT NULL,
+        gas_used INTEGER NOT NULL,
+        gas_limit INTEGER NOT NULL,
+        base_fee_per_gas INTEGER
+    );
+    CREATE TABLE transactions (
+        block_number INTEGER NOT NULL,
+        position INTEGER NOT NULL,
+        hash TEXT NOT NULL,
+        from_address TEXT NOT NULL,
+        to_address TEXT,
+        PRIMARY KEY (block_number, position)
+    );
+    CREATE INDEX transactions_hash ON transactions (hash);
+    CREATE TABLE logs (
+        block_number INTEGER NOT NULL,
+        log_index INTEGER NOT NULL,
+        transaction_hash TEXT NOT NULL,
+        address TEXT NOT NULL,
+        topics TEXT NOT NULL,
+        data TEXT NOT NULL,
+        PRIMARY KEY (block_number, log_index)
+    );
+    CREATE INDEX logs_address ON logs (address);
+
+    CREATE TABLE deployments (
+        deployment_id TEXT NOT NULL,
+        future_id TEXT NOT NULL,
+        address TEXT NOT NULL,
+        contract_name TEXT NOT NULL,
+        chain_id INTEGER,
+        recorded_at INTEGER NOT NULL,
+        PRIMARY KEY (deployment_id, future_id, address)
+    );
+
+    CREATE TABLE gas_snapshots (
+        taken_at INTEGER NOT NULL,
+        method TEXT NOT NULL,
+        gas INTEGER NOT NULL,
+        PRIMARY KEY (taken_at, method)
+    );
+
+    CREATE TABLE audit (
+        id INTEGER PRIMARY KEY,
+        timestamp INTEGER NOT NULL,
+        user TEXT NOT NULL,
+        command TEXT NOT NULL,
+        project_path TEXT,
+        args TEXT NOT NULL,
+        status TEXT NOT NULL,
+        execution_id TEXT,
+        exit_code INTEGER
+    );
+    CREATE INDEX audit_command ON audit (command, timestamp);",
+];
+
+// Files the tables above replace. They are imported once, when the database is
+// created next to them, and renamed so nothing reads them again
+const LEGACY_FILES: [&str; 4] = ["operations.jsonl", "console-history.jsonl", "audit.jsonl", "chain-index.json"];
+
+static CONNECTIONS: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<Connection>>>>> = OnceLock::new();
+
+fn migrate(conn: &mut Connection) -> rusqlite::Result<usize> {
+    let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", i + 1)?;
+        tx.commit()?;
+    }
+    Ok(version)
+}
+
+fn read_lines<T: DeserializeOwned>(path: &Path) -> Vec<T> {
+    let Ok(file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+fn import_legacy(conn: &mut Connection, dir: &Path) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    for record in read_lines(&dir.join(LEGACY_FILES[0])) {
+        metrics::insert(&tx, &record)?;
+    }
+    for entry in read_lines(&dir.join(LEGACY_FILES[1])) {
+        console::insert_history(&tx, &entry)?;
+    }
+    for entry in read_lines(&dir.join(LEGACY_FILES[2])) {
+        audit::insert(&tx, &entry)?;
+    }
+    tx.commit()?;
+    // The chain index is a cache and is simply rebuilt from the node
+    for name in LEGACY_FILES {
+        let path = dir.join(name);
+        if path.exists() {
+            let _ = fs::rename(&path, dir.join(format!("{}.imported", name)));
+        }
+    }
+    Ok(())
+}
+
+fn open(path: &Path) -> Result<Connection, String> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create database directory: {}", e))?;
+    let mut conn = Connection::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    // WAL lets readers continue while a long chain sync is writing
+    conn.pragma_update(None, "journal_mode", "WAL")
+        .and_then(|_| conn.busy_timeout(std::time::Duration::from_secs(5)))
+        .map_err(|e| format!("Failed to configure database: {}", e))?;
+    let previous = migrate(&mut conn).map_err(|e| format!("Failed to migrate database: {}", e))?;
+    if previous == 0 {
+        if let Err(e) = import_legacy(&mut conn, dir) {
+            tracing::warn!(error = %e, dir = ?dir, "failed to import legacy history files");
+        }
+    }
+    Ok(conn)
+}
+
+// Runs `f` on the database at `path`, opening and migrating it on first use. Calls are
+// serialized per file; they're short and already off the UI thread
+pub fn with_at<T, F>(path: &Path, f: F) -> Result<T, String>
+where
+    F: FnOnce(&mut Connection) -> rusqlite::Result<T>,
+{
+    let connection = {
+        let mut connections = CONNECTIONS.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap();
+        match connections.get(path) {
+            Some(connection) => connection.clone(),
+            None => {
+                let connection = Arc::new(Mutex::new(open(path)?));
+                connections.insert(path.to_path_buf(), connection.clone());
+                connection
+            }
+        }
+    };
+    let mut conn = connection.lock().unwrap();
+    f(&mut conn).map_err(|e| format!("Database error: {}", e))
+}
+
+pub fn with<T, F>(project_path: &Path, f: F) -> Result<T, String>
+where
+    F: FnOnce(&mut Connection) -> rusqlite::Result<T>,
+{
+    with_at(&store::project_store_dir(project_path).join(DB_FILE), f)
+}
+
+// snake_case enums are stored as their serde name
+pub fn enum_to_sql<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+pub fn enum_from_sql<T: DeserializeOwned>(value: String) -> rusqlite::Result<T> {
+    serde_json::from_value(serde_json::Value::String(value))
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+// Closes the project's cached connection; the next call reopens it
+pub fn close(project_path: &Path) {
+    let path = store::project_store_dir(project_path).join(DB_FILE);
+    if let Some(connections) = CONNECTIONS.get() {
+        connections.lock().unwrap().remove(&path);
+    }
+}
@@ -0,0 +1,659 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha3::{Digest, Keccak256};
+
+use crate::abi::{AbiItem, AbiParam};
+use crate::artifacts;
+use crate::deployments;
+use crate::external_abis;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DecodedParam {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    // Integers are decimal strings, bytes and addresses 0x-prefixed hex
+    pub value: Value,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AbiSource {
+    Project,
+    External,
+    SelectorDatabase,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DecodedCall {
+    pub selector: String,
+    // None when no known ABI has the selector
+    pub function: Option<String>,
+    pub contract: Option<String>,
+    pub source: Option<AbiSource>,
+    pub args: Vec<DecodedParam>,
+}
+
+pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 + bytes.len() * 2);
+    hex.push_str("0x");
+    for byte in bytes {
+        hex.push_str(&format!("{:02x}", byte));
+    }
+    hex
+}
+
+pub fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.trim_start_matches("0x");
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// First 4 bytes of keccak256("transfer(address,uint256)")
+pub fn selector(signature: &str) -> String {
+    to_hex(&keccak256(signature.as_bytes())[..4])
+}
+
+pub fn event_topic(signature: &str) -> String {
+    to_hex(&keccak256(signature.as_bytes()))
+}
+
+// Big-endian unsigned integer of any width as a decimal string
+fn to_decimal(bytes: &[u8]) -> String {
+    let mut number = bytes.to_vec();
+    let mut digits = Vec::new();
+    while number.iter().any(|b| *b != 0) {
+        let mut remainder = 0u32;
+        for byte in number.iter_mut() {
+            let current = (remainder << 8) | *byte as u32;
+            *byte = (current / 10) as u8;
+            remainder = current % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+    }
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap_or_default()
+}
+
+// Hex RPC quantities ("0x1bc16d674ec80000") can exceed u64 for wei amounts
+pub fn quantity_to_decimal(hex: &str) -> String {
+    let digits = hex.trim_start_matches("0x");
+    let padded = if digits.len() % 2 == 1 { format!("0{}", digits) } else { digits.to_string() };
+    from_hex(&padded).map(|bytes| to_decimal(&bytes)).unwrap_or_else(|| "0".to_string())
+}
+
+// Decimal wei amounts back into RPC quantities
+pub fn decimal_to_quantity(decimal: &str) -> Result<String, String> {
+    let word = encode_integer(&Value::String(decimal.to_string()), false)?;
+    let digits = to_hex(&word)[2..].trim_start_matches('0').to_string();
+    Ok(if digits.is_empty() { "0x0".to_string() } else { format!("0x{}", digits) })
+}
+
+fn signed_decimal(word: &[u8]) -> String {
+    if word[0] & 0x80 == 0 {
+        return to_decimal(word);
+    }
+    // Two's complement: invert and add one to get the magnitude
+    let mut magnitude: Vec<u8> = word.iter().map(|b| !b).collect();
+    for byte in magnitude.iter_mut().rev() {
+        let (sum, overflow) = byte.overflowing_add(1);
+        *byte = sum;
+        if !overflow {
+            break;
+        }
+    }
+    format!("-{}", to_decimal(&magnitude))
+}
+
+fn word(data: &[u8], at: usize) -> Option<&[u8]> {
+    data.get(at..at.checked_add(32)?)
+}
+
+fn read_usize(data: &[u8], at: usize) -> Option<usize> {
+    let word = word(data, at)?;
+    if word[..24].iter().any(|b| *b != 0) {
+        return None;
+    }
+    usize::try_from(u64::from_be_bytes(word[24..].try_into().ok()?)).ok()
+}
+
+// "uint256[3]" -> ("uint256", Some(3)), "bytes[]" -> ("bytes", None)
+pub fn array_parts(kind: &str) -> Option<(&str, Option<usize>)> {
+    let inner = kind.strip_suffix(']')?;
+    let open = inner.rfind('[')?;
+    let length = &inner[open + 1..];
+    Some((&inner[..open], if length.is_empty() { None } else { length.parse().ok() }))
+}
+
+pub fn element(param: &AbiParam, kind: &str) -> AbiParam {
+    AbiParam {
+        name: String::new(),
+        kind: kind.to_string(),
+        components: param.components.clone(),
+        indexed: false,
+        internal_type: None,
+    }
+}
+
+fn is_dynamic(param: &AbiParam) -> bool {
+    match array_parts(&param.kind) {
+        Some((_, None)) => true,
+        Some((inner, Some(_))) => is_dynamic(&element(param, inner)),
+        None => {
+            param.kind == "bytes"
+                || param.kind == "string"
+                || (param.kind == "tuple" && param.components.iter().any(is_dynamic))
+        }
+    }
+}
+
+// Bytes a parameter occupies in the head of its enclosing tuple
+fn head_size(param: &AbiParam) -> usize {
+    if is_dynamic(param) {
+        return 32;
+    }
+    match array_parts(&param.kind) {
+        Some((inner, Some(length))) => length * head_size(&element(param, inner)),
+        _ if param.kind == "tuple" => param.components.iter().map(head_size).sum(),
+        _ => 32,
+    }
+}
+
+fn decode_tuple(params: &[AbiParam], data: &[u8], base: usize) -> Option<Vec<Value>> {
+    let mut head = base;
+    let mut values = Vec::with_capacity(params.len());
+    for param in params {
+        let value = if is_dynamic(param) {
+            decode_value(param, data, base.checked_add(read_usize(data, head)?)?)?
+        } else {
+            decode_value(param, data, head)?
+        };
+        head += head_size(param);
+        values.push(value);
+    }
+    Some(values)
+}
+
+fn decode_value(param: &AbiParam, data: &[u8], at: usize) -> Option<Value> {
+    if let Some((inner, length)) = array_parts(&param.kind) {
+        let (count, start) = match length {
+            Some(length) => (length, at),
+            None => (read_usize(data, at)?, at + 32),
+        };
+        // A corrupt length must not turn into a huge allocation
+        if count > data.len() / 32 + 1 {
+            return None;
+        }
+        let elements = vec![element(param, inner); count];
+        return decode_tuple(&elements, data, start).map(Value::Array);
+    }
+
+    let kind = param.kind.as_str();
+    let value = match kind {
+        "tuple" => {
+            let values = decode_tuple(&param.components, data, at)?;
+            if param.components.iter().all(|c| !c.name.is_empty()) {
+                let fields: Map<String, Value> = param.components.iter().map(|c| c.name.clone()).zip(values).collect();
+                Value::Object(fields)
+            } else {
+                Value::Array(values)
+            }
+        }
+        "string" | "bytes" => {
+            let length = read_usize(data, at)?;
+            let bytes = data.get(at + 32..(at + 32).checked_add(length)?)?;
+            if kind == "string" {
+                Value::String(String::from_utf8_lossy(bytes).to_string())
+            } else {
+                Value::String(to_hex(bytes))
+            }
+        }
+        "address" => Value::String(to_hex(&word(data, at)?[12..])),
+        "bool" => Value::Bool(word(data, at)?[31] != 0),
+        "function" => Value::String(to_hex(&word(data, at)?[..24])),
+        _ if kind.starts_with("uint") => Value::String(to_decimal(word(data, at)?)),
+        _ if kind.starts_with("int") => Value::String(signed_decimal(word(data, at)?)),
+        _ if kind.starts_with("bytes") => {
+            let size: usize = kind["bytes".len()..].parse().ok().filter(|n| (1..=32).contains(n))?;
+            Value::String(to_hex(&word(data, at)?[..size]))
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn named(params: &[AbiParam], values: Vec<Value>) -> Vec<DecodedParam> {
+    params
+        .iter()
+        .zip(values)
+        .map(|(param, value)| DecodedParam { name: param.name.clone(), kind: param.canonical_type(), value })
+        .collect()
+}
+
+// ABI-encoded parameter list, e.g. calldata after the selector or a return value
+pub fn decode_params(params: &[AbiParam], data: &[u8]) -> Option<Vec<DecodedParam>> {
+    decode_tuple(params, data, 0).map(|values| named(params, values))
+}
+
+fn usize_word(value: usize) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+fn pad_right(bytes: &[u8]) -> Vec<u8> {
+    let mut padded = bytes.to_vec();
+    padded.resize(bytes.len().div_ceil(32) * 32, 0);
+    padded
+}
+
+// Decimal or 0x-hex integer, as a string or JSON number, into a two's complement word
+fn encode_integer(value: &Value, signed: bool) -> Result<[u8; 32], String> {
+    let text = match value {
+        Value::String(text) => text.trim().to_string(),
+        Value::Number(number) => number.to_string(),
+        other => return Err(format!("Expected an integer, got {}", other)),
+    };
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) if signed => (true, rest),
+        _ => (false, text.as_str()),
+    };
+
+    let mut word = [0u8; 32];
+    if let Some(hex) = digits.strip_prefix("0x") {
+        let bytes = from_hex(&format!("{:0>64}", hex))
+            .filter(|b| b.len() == 32)
+            .ok_or_else(|| format!("Invalid integer: {}", text))?;
+        word.copy_from_slice(&bytes);
+    } else {
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("Invalid integer: {}", text));
+        }
+        for digit in digits.bytes() {
+            let mut carry = (digit - b'0') as u32;
+            for byte in word.iter_mut().rev() {
+                let current = *byte as u32 * 10 + carry;
+                *byte = current as u8;
+                carry = current >> 8;
+            }
+            if carry != 0 {
+                return Err(format!("Integer out of range: {}", text));
+            }
+        }
+    }
+    if negative {
+        word.iter_mut().for_each(|byte| *byte = !*byte);
+        for byte in word.iter_mut().rev() {
+            let (sum, overflow) = byte.overflowing_add(1);
+            *byte = sum;
+            if !overflow {
+                break;
+            }
+        }
+    }
+    Ok(word)
+}
+
+fn encode_tuple(params: &[AbiParam], values: &[Value]) -> Result<Vec<u8>, String> {
+    if params.len() != values.len() {
+        return Err(format!("Expected {} values, got {}", params.len(), values.len()));
+    }
+    let head_length: usize = params.iter().map(head_size).sum();
+    let mut head = Vec::with_capacity(head_length);
+    let mut tail = Vec::new();
+    for (param, value) in params.iter().zip(values) {
+        let encoded = encode_value(param, value)?;
+        if is_dynamic(param) {
+            head.extend(usize_word(head_length + tail.len()));
+            tail.extend(encoded);
+        } else {
+            head.extend(encoded);
+        }
+    }
+    head.extend(tail);
+    Ok(head)
+}
+
+// Accepts the same shapes decode_value produces, so decoded arguments can be edited and re-encoded
+fn encode_value(param: &AbiParam, value: &Value) -> Result<Vec<u8>, String> {
+    if let Some((inner, length)) = array_parts(&param.kind) {
+        let items = value.as_array().ok_or_else(|| format!("Expected an array for {}, got {}", param.kind, value))?;
+        if length.is_some_and(|length| length != items.len()) {
+            return Err(format!("Expected {} elements for {}", length.unwrap_or_default(), param.kind));
+        }
+        let elements = vec![element(param, inner); items.len()];
+        let encoded = encode_tuple(&elements, items)?;
+        return Ok(match length {
+            Some(_) => encoded,
+            None => [usize_word(items.len()).to_vec(), encoded].concat(),
+        });
+    }
+
+    let kind = param.kind.as_str();
+    let text = value.as_str();
+    let encoded = match kind {
+        "tuple" => {
+            let values = match value {
+                Value::Object(fields) => param
+                    .components
+                    .iter()
+                    .map(|c| fields.get(&c.name).cloned().ok_or_else(|| format!("Missing tuple field {}", c.name)))
+                    .collect::<Result<Vec<_>, _>>()?,
+                Value::Array(items) => items.clone(),
+                other => return Err(format!("Expected a tuple, got {}", other)),
+            };
+            encode_tuple(&param.components, &values)?
+        }
+        "string" => {
+            let text = text.ok_or_else(|| format!("Expected a string, got {}", value))?;
+            [usize_word(text.len()).to_vec(), pad_right(text.as_bytes())].concat()
+        }
+        "bytes" => {
+            let bytes = text.and_then(from_hex).ok_or_else(|| format!("Expected hex bytes, got {}", value))?;
+            [usize_word(bytes.len()).to_vec(), pad_right(&bytes)].concat()
+        }
+        "address" => {
+            let bytes = text
+                .and_then(from_hex)
+                .filter(|b| b.len() == 20)
+                .ok_or_else(|| format!("Invalid address: {}", value))?;
+            [vec![0u8; 12], bytes].concat()
+        }
+        "bool" => {
+            let flag = value
+                .as_bool()
+                .or_else(|| text.and_then(|t| t.parse().ok()))
+                .ok_or_else(|| format!("Expected a bool, got {}", value))?;
+            usize_word(flag as usize).to_vec()
+        }
+        _ if kind.starts_with("uint") => encode_integer(value, false)?.to_vec(),
+        _ if kind.starts_with("int") => encode_integer(value, true)?.to_vec(),
+        _ if kind.starts_with("bytes") => {
+            let size: Option<usize> = kind["bytes".len()..].parse().ok().filter(|n| (1..=32).contains(n));
+            let bytes = text
+                .and_then(from_hex)
+                .filter(|b| Some(b.len()) == size)
+                .ok_or_else(|| format!("Invalid {}: {}", kind, value))?;
+            pad_right(&bytes)
+        }
+        _ => return Err(format!("Unsupported ABI type {}", kind)),
+    };
+    Ok(encoded)
+}
+
+// Inverse of decode_params: one JSON value per parameter
+pub fn encode_params(params: &[AbiParam], values: &[Value]) -> Result<Vec<u8>, String> {
+    encode_tuple(params, values)
+}
+
+// Splits at commas outside parentheses: "address,(uint256,bytes)[]" -> 2 parts
+fn split_types(list: &str) -> Option<Vec<&str>> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0i32, 0);
+    for (i, c) in list.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&list[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        if depth < 0 {
+            return None;
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+    if !list.is_empty() {
+        parts.push(&list[start..]);
+    }
+    Some(parts)
+}
+
+fn parse_type(text: &str) -> Option<AbiParam> {
+    let text = text.trim();
+    let (kind, components) = match text.strip_prefix('(') {
+        Some(rest) => {
+            let close = rest.rfind(')')?;
+            let components = split_types(&rest[..close])?.into_iter().map(parse_type).collect::<Option<Vec<_>>>()?;
+            (format!("tuple{}", &rest[close + 1..]), components)
+        }
+        None if !text.is_empty() && !text.contains(['(', ')', ' ']) => (text.to_string(), Vec::new()),
+        None => return None,
+    };
+    Some(AbiParam { name: String::new(), kind, components, indexed: false, internal_type: None })
+}
+
+// Text signature from a selector database into a name and parameter list
+pub fn parse_signature(signature: &str) -> Option<(String, Vec<AbiParam>)> {
+    let open = signature.find('(')?;
+    let params = signature.get(open + 1..signature.len().checked_sub(1)?)?;
+    if !signature.ends_with(')') {
+        return None;
+    }
+    let params = split_types(params)?.into_iter().map(parse_type).collect::<Option<Vec<_>>>()?;
+    Some((signature[..open].to_string(), params))
+}
+
+// Indexed parameters come from topics[1..]; dynamic ones are only stored as their hash
+pub fn decode_event(event: &AbiItem, topics: &[String], data: &[u8]) -> Option<Vec<DecodedParam>> {
+    let indexed: Vec<&AbiParam> = event.inputs.iter().filter(|p| p.indexed).collect();
+    let skip = if event.anonymous { 0 } else { 1 };
+    if topics.len() != indexed.len() + skip {
+        return None;
+    }
+
+    let unindexed: Vec<AbiParam> = event.inputs.iter().filter(|p| !p.indexed).cloned().collect();
+    let mut data_values = decode_tuple(&unindexed, data, 0)?.into_iter();
+    let mut topic_values = topics[skip..].iter();
+
+    let mut values = Vec::with_capacity(event.inputs.len());
+    for param in &event.inputs {
+        let value = if param.indexed {
+            let topic = topic_values.next()?;
+            if is_dynamic(param) || param.kind == "tuple" {
+                Value::String(topic.clone())
+            } else {
+                decode_value(param, &from_hex(topic)?, 0)?
+            }
+        } else {
+            data_values.next()?
+        };
+        values.push(value);
+    }
+    Some(named(&event.inputs, values))
+}
+
+// Every ABI the project knows about, for turning raw chain data back into names
+pub struct AbiIndex {
+    contracts: Vec<(String, AbiSource, Vec<AbiItem>)>,
+    deployed: HashMap<String, String>,
+    functions: HashMap<String, Vec<(usize, usize)>>,
+    events: HashMap<String, Vec<(usize, usize)>>,
+}
+
+impl AbiIndex {
+    pub fn load(project_path: &Path) -> Self {
+        let mut contracts: Vec<(String, AbiSource, Vec<AbiItem>)> = artifacts::load_artifacts(project_path)
+            .into_iter()
+            .map(|a| (a.contract_name, AbiSource::Project, a.abi))
+            .collect();
+        let mut deployed: HashMap<String, String> = deployments::load_deployments(project_path)
+            .into_iter()
+            .map(|d| (d.address.to_lowercase(), d.contract_name))
+            .collect();
+        for external in external_abis::load(project_path).unwrap_or_default() {
+            if let Some(address) = external.address {
+                deployed.entry(address).or_insert_with(|| external.name.clone());
+            }
+            contracts.push((external.name, AbiSource::External, external.abi));
+        }
+
+        let mut functions: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        let mut events: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        for (contract, (_, _, abi)) in contracts.iter().enumerate() {
+            for (item, entry) in abi.iter().enumerate() {
+                if entry.is_function() {
+                    functions.entry(selector(&entry.signature())).or_default().push((contract, item));
+                } else if entry.is_event() && !entry.anonymous {
+                    events.entry(event_topic(&entry.signature())).or_default().push((contract, item));
+                }
+            }
+        }
+        AbiIndex { contracts, deployed, functions, events }
+    }
+
+    // Name of the contract deployed at `address`, from the registry or an imported ABI
+    pub fn contract_at(&self, address: &str) -> Option<&str> {
+        self.deployed.get(&address.to_lowercase()).map(String::as_str)
+    }
+
+    // topic0 of every known event with this name, across overloads and contracts
+    pub fn event_topics(&self, name: &str) -> Vec<String> {
+        let mut topics: Vec<String> = self
+            .contracts
+            .iter()
+            .flat_map(|(_, _, abi)| abi.iter())
+            .filter(|item| item.is_event() && item.name == name)
+            .map(|event| event_topic(&event.signature()))
+            .collect();
+        topics.sort();
+        topics.dedup();
+        topics
+    }
+
+    // Candidates for a selector or topic, the contract deployed at `address` first
+    fn candidates<'a>(&'a self, matches: Option<&'a Vec<(usize, usize)>>, address: Option<&str>) -> Vec<&'a (usize, usize)> {
+        let deployed = address.and_then(|a| self.contract_at(a));
+        let mut candidates: Vec<&(usize, usize)> = matches.map(|m| m.iter().collect()).unwrap_or_default();
+        candidates.sort_by_key(|(contract, _)| deployed != Some(self.contracts[*contract].0.as_str()));
+        candidates
+    }
+
+    // None for plain value transfers; an undecoded call (function None) for unknown selectors
+    pub fn decode_call(&self, to: Option<&str>, input: &str) -> Option<DecodedCall> {
+        let bytes = from_hex(input).filter(|b| b.len() >= 4)?;
+        let selector = to_hex(&bytes[..4]);
+        let mut call = DecodedCall {
+            contract: to.and_then(|to| self.contract_at(to)).map(str::to_string),
+            selector,
+            function: None,
+            source: None,
+            args: Vec::new(),
+        };
+
+        for (contract, item) in self.candidates(self.functions.get(&call.selector), to) {
+            let (name, source, abi) = &self.contracts[*contract];
+            if let Some(args) = decode_params(&abi[*item].inputs, &bytes[4..]) {
+                call.contract.get_or_insert_with(|| name.clone());
+                call.function = Some(abi[*item].signature());
+                call.source = Some(*source);
+                call.args = args;
+                break;
+            }
+        }
+        Some(call)
+    }
+
+    // Events matching a topic0, the contract deployed at `address` first. Several can
+    // match: ERC-20 and ERC-721 Transfer share a signature but differ in indexing
+    pub fn event_candidates(&self, topic: &str, address: Option<&str>) -> Vec<(&str, &AbiItem)> {
+        self.candidates(self.events.get(&topic.to_lowercase()), address)
+            .into_iter()
+            .map(|(contract, item)| {
+                let (name, _, abi) = &self.contracts[*contract];
+                (name.as_str(), &abi[*item])
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn params(value: Value) -> Vec<AbiParam> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn words(words: &[&str]) -> Vec<u8> {
+        words.iter().flat_map(|w| from_hex(&format!("{:0>64}", w)).unwrap()).collect()
+    }
+
+    #[test]
+    fn hashes_selectors_and_topics() {
+        assert_eq!(selector("transfer(address,uint256)"), "0xa9059cbb");
+        assert_eq!(event_topic("Transfer(address,address,uint256)"), "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+    }
+
+    #[test]
+    fn converts_integers_to_decimal() {
+        assert_eq!(quantity_to_decimal("0x1bc16d674ec80000"), "2000000000000000000");
+        assert_eq!(quantity_to_decimal("0x0"), "0");
+        assert_eq!(signed_decimal(&[0xff; 32]), "-1");
+        assert_eq!(to_decimal(&[0xff; 32]), "115792089237316195423570985008687907853269984665640564039457584007913129639935");
+    }
+
+    #[test]
+    fn decodes_static_and_dynamic_values() {
+        let params = params(json!([
+            { "name": "to", "type": "address" },
+            { "name": "note", "type": "string" },
+            { "name": "ids", "type": "uint256[]" },
+            { "name": "ok", "type": "bool" },
+        ]));
+        let data = words(&["00000000000000000000000000000000000000aa", "80", "c0", "1", "5", "68656c6c6f000000000000000000000000000000000000000000000000000000", "2", "7", "8"]);
+        assert_eq!(
+            decode_tuple(&params, &data, 0).unwrap(),
+            vec![json!("0x00000000000000000000000000000000000000aa"), json!("hello"), json!(["7", "8"]), json!(true)]
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_and_oversized_data() {
+        let params = params(json!([{ "name": "ids", "type": "uint256[]" }]));
+        assert!(decode_tuple(&params, &words(&["20", "2", "7"]), 0).is_none());
+        assert!(decode_tuple(&params, &words(&["20", "ffffffffffff"]), 0).is_none());
+    }
+
+    #[test]
+    fn decodes_events_from_topics_and_data() {
+        let event: AbiItem = serde_json::from_value(json!({
+            "type": "event",
+            "name": "Transfer",
+            "anonymous": false,
+            "inputs": [
+                { "name": "from", "type": "address", "indexed": true },
+                { "name": "to", "type": "address", "indexed": true },
+                { "name": "value", "type": "uint256", "indexed": false },
+            ],
+        }))
+        .unwrap();
+        let topic = |address: &str| format!("0x{:0>64}", address);
+        let topics = vec![event_topic("Transfer(address,address,uint256)"), topic("aa"), topic("bb")];
+        let decoded = decode_event(&event, &topics, &words(&["3e8"])).unwrap();
+        let values: Vec<&Value> = decoded.iter().map(|p| &p.value).collect();
+        assert_eq!(values, [&json!(format!("0x{:0>40}", "aa")), &json!(format!("0x{:0>40}", "bb")), &json!("1000")]);
+        assert!(decode_event(&event, &topics[..2], &words(&["3e8"])).is_none());
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+use crate::db;
+use crate::error::AppError;
+use crate::store;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Deployment {
+    pub deployment_id: String,
+    pub chain_id: Option<u64>,
+    pub future_id: String,
+    pub contract_name: String,
+    pub address: String,
+}
+
+// Hardhat Ignition records every deployment in
+// ignition/deployments/<deployment-id>/deployed_addresses.json, keyed by future id
+// ("LockModule#Lock"). That file is the project's source of truth for addresses.
+pub fn load_deployments(project_path: &Path) -> Vec<Deployment> {
+    let deployments_dir = project_path.join("ignition").join("deployments");
+    let Ok(entries) = fs::read_dir(&deployments_dir) else {
+        return Vec::new();
+    };
+
+    let mut deployments = Vec::new();
+    for entry in entries.flatten() {
+        let deployment_id = entry.file_name().to_string_lossy().to_string();
+        let Some(addresses) = fs::read_to_string(entry.path().join("deployed_addresses.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<BTreeMap<String, String>>(&content).ok())
+        else {
+            continue;
+        };
+
+        // Default deployment ids are "chain-<chainId>"; the GUI's mocks use "chain-<chainId>-mocks"
+        let chain_id = deployment_id
+            .strip_prefix("chain-")
+            .and_then(|id| id.split('-').next()?.parse().ok());
+
+        for (future_id, address) in addresses {
+            let contract_name = future_id
+                .rsplit('#')
+                .next()
+                .unwrap_or(&future_id)
+                .to_string();
+            deployments.push(Deployment {
+                deployment_id: deployment_id.clone(),
+                chain_id,
+                future_id,
+                contract_name,
+                address,
+            });
+        }
+    }
+
+    deployments
+}
+
+// A deployment as first seen by the GUI. Ignition's files only hold the latest address
+// per future, so redeploys (e.g. after a node restart) are only kept here
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DeploymentHistoryEntry {
+    #[serde(flatten)]
+    pub deployment: Deployment,
+    pub recorded_at: u64,
+}
+
+// Called after successful deploys; addresses already recorded keep their first timestamp
+pub fn record_history(project_path: &Path) {
+    let deployments = load_deployments(project_path);
+    let recorded_at = store::timestamp_millis();
+    let written = db::with(project_path, |conn| {
+        let tx = conn.transaction()?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR IGNORE INTO deployments (deployment_id, future_id, address, contract_name, chain_id, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for d in &deployments {
+                insert.execute(params![d.deployment_id, d.future_id, d.address, d.contract_name, d.chain_id, recorded_at])?;
+            }
+        }
+        tx.commit()
+    });
+    if let Err(e) = written {
+        tracing::warn!(error = %e, "failed to record deployment history");
+    }
+}
+
+// For contracts deployed outside Ignition (e.g. signed with WalletConnect): written to
+// their own deployment id so Ignition never resumes it, then recorded like any deploy
+pub fn record_external(project_path: &Path, deployment_id: &str, future_id: &str, address: &str) -> Result<(), String> {
+    let dir = project_path.join("ignition").join("deployments").join(deployment_id);
+    let path = dir.join("deployed_addresses.json");
+    let mut addresses: BTreeMap<String, String> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    addresses.insert(future_id.to_string(), address.to_string());
+
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let content = serde_json::to_string_pretty(&addresses).map_err(|e| format!("Failed to serialize deployed addresses: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    record_history(project_path);
+    Ok(())
+}
+
+// Newest first
+#[tauri::command]
+pub async fn get_deployment_history(
+    project_path: String,
+    chain_id: Option<u64>,
+    contract: Option<String>,
+) -> Result<Vec<DeploymentHistoryEntry>, AppError> {
+    Ok(db::with(Path::new(&project_path), |conn| {
+        conn.prepare_cached(
+            "SELECT deployment_id, chain_id, future_id, contract_name, address, recorded_at FROM deployments
+             WHERE (?1 IS NULL OR chain_id = ?1) AND (?2 IS NULL OR contract_name = ?2)
+             ORDER BY recorded_at DESC, future_id",
+        )?
+        .query_map(params![chain_id, contract], |row| {
+            Ok(DeploymentHistoryEntry {
+                deployment: Deployment {
+                    deployment_id: row.get(0)?,
+                    chain_id: row.get(1)?,
+                    future_id: row.get(2)?,
+                    contract_name: row.get(3)?,
+                    address: row.get(4)?,
+                },
+                recorded_at: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn external_deployments_join_the_registry() {
+        let dir = std::env::temp_dir().join(format!("hardhat-gui-deployments-{}", std::process::id()));
+        record_external(&dir, "chain-11155111-walletconnect", "WalletConnect#Token", "0xaa").unwrap();
+        record_external(&dir, "chain-11155111-walletconnect", "WalletConnect#Vault", "0xbb").unwrap();
+
+        let deployments = load_deployments(&dir);
+        assert_eq!(deployments.len(), 2);
+        assert_eq!(deployments[0].chain_id, Some(11155111));
+        assert_eq!(deployments[0].contract_name, "Token");
+        assert_eq!(deployments[1].address, "0xbb");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,385 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::error::AppError;
+use crate::networks::{self, NetworkProfile, SignerSource};
+use crate::notifications::{self, NotificationEvent};
+use crate::processes::{self, ProcessManager};
+use crate::provider_keys;
+use crate::resolver;
+use crate::rpc::{self, RpcPolicy};
+use crate::store;
+
+const DEFAULT_PORT: u16 = 8545;
+const DEFAULT_CHAIN_ID: u64 = 31337;
+const FOUNDRY_INSTALLER: &str = "https://foundry.paradigm.xyz";
+const NITRO_IMAGE: &str = "offchainlabs/nitro-node:v3.2.1-d81324d";
+const NITRO_CHAIN_ID: u64 = 412346;
+// Prefunded in nitro's --dev mode; its private key is published in the Arbitrum docs
+const NITRO_DEV_ACCOUNT: &str = "0x3f1Eae7D46d88F08fc2F8ed27FCb2AB183EB2d0E";
+const NITRO_DEFAULT_PORT: u16 = 8547;
+// supersim brings its own L1 with the OP contracts predeployed; it can't share 8545
+// with the Hardhat node
+const SUPERSIM_L1_PORT: u16 = 8546;
+const SUPERSIM_DEFAULT_PORT: u16 = 9545;
+// network name, chain id, port offset from the first L2 port (None for the L1)
+const SUPERSIM_CHAINS: [(&str, u64, Option<u16>); 3] =
+    [("opDevnetL1", 900, None), ("opDevnetA", 901, Some(0)), ("opDevnetB", 902, Some(1))];
+// A first nitro run pulls the image, which can take a while
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(180);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    Hardhat,
+    Anvil,
+    OpStack,
+    ArbitrumNitro,
+}
+
+// Implementation behind the local L1 node
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeBackend {
+    #[default]
+    Hardhat,
+    Anvil,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct NodeOptions {
+    pub port: Option<u16>,
+    // May hold a ${INFURA_API_KEY} style placeholder
+    pub fork_url: Option<String>,
+    pub fork_block_number: Option<u64>,
+    pub mnemonic: Option<String>,
+    pub chain_id: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AnvilStatus {
+    pub installed: bool,
+    pub path: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DevnetChain {
+    // Network profile the chain is registered under
+    pub network: String,
+    pub chain_id: u64,
+    pub rpc_url: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LocalNode {
+    pub execution_id: String,
+    pub kind: NodeKind,
+    // Docker nodes keep running when the docker CLI is killed, so they're removed by name
+    pub container: Option<String>,
+    pub chains: Vec<DevnetChain>,
+    pub funded_account: Option<String>,
+    pub started_at: u64,
+}
+
+static NODES: OnceLock<Mutex<Vec<LocalNode>>> = OnceLock::new();
+
+fn nodes() -> &'static Mutex<Vec<LocalNode>> {
+    NODES.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+pub fn register(node: LocalNode) {
+    nodes().lock().unwrap().push(node);
+}
+
+// The most recently started node that is still running, preferring the L1 node since
+// that's the one commands default to
+pub fn active_url(processes: &ProcessManager) -> Option<String> {
+    let nodes = nodes().lock().unwrap();
+    let running: Vec<&LocalNode> = nodes.iter().rev().filter(|node| processes.is_running(&node.execution_id)).collect();
+    running
+        .iter()
+        .find(|node| matches!(node.kind, NodeKind::Hardhat | NodeKind::Anvil))
+        .or(running.first())
+        .and_then(|node| node.chains.first())
+        .map(|chain| chain.rpc_url.clone())
+}
+
+// Exit notification for a node registered with processes::spawn_service
+pub fn on_node_exit(label: &'static str) -> impl FnOnce(&AppHandle, Option<i32>) + Send + 'static {
+    move |app, exit_code| {
+        let body = match exit_code {
+            Some(code) => format!("The {} exited with code {}", label, code),
+            None => format!("The {} was terminated", label),
+        };
+        notifications::notify(app, NotificationEvent::NodeStopped, "Node stopped", &body);
+    }
+}
+
+// foundryup installs outside the search path resolved at startup, so a fresh install
+// is looked up there directly
+fn anvil_path() -> Option<PathBuf> {
+    resolver::locate("anvil").or_else(|| {
+        let binary = if cfg!(windows) { "anvil.exe" } else { "anvil" };
+        resolver::foundry_bin().map(|bin| bin.join(binary)).filter(|path| path.is_file())
+    })
+}
+
+// `hardhat node` takes the mnemonic and chain id from hardhat.config only; they're
+// exported for configs that read them:
+// `hardhat: { chainId: Number(process.env.HARDHAT_GUI_NODE_CHAIN_ID ?? 31337) }`
+fn node_command(project_path: &str, backend: NodeBackend, options: &NodeOptions) -> Result<Command, String> {
+    let port = options.port.unwrap_or(DEFAULT_PORT).to_string();
+    let fork_url = options.fork_url.as_deref().map(provider_keys::expand);
+    let fork_block = options.fork_block_number.map(|block| block.to_string());
+    if options.fork_block_number.is_some() && fork_url.is_none() {
+        return Err("A fork block number needs a fork URL".to_string());
+    }
+    let mut cmd = match backend {
+        NodeBackend::Hardhat => {
+            let mut cmd = resolver::hardhat(Path::new(project_path));
+            cmd.args(["node", "--port", &port]);
+            if let Some(url) = &fork_url {
+                cmd.args(["--fork", url]);
+            }
+            if let Some(block) = &fork_block {
+                cmd.args(["--fork-block-number", block]);
+            }
+            if let Some(mnemonic) = &options.mnemonic {
+                cmd.env("HARDHAT_GUI_NODE_MNEMONIC", mnemonic);
+            }
+            if let Some(chain_id) = options.chain_id {
+                cmd.env("HARDHAT_GUI_NODE_CHAIN_ID", chain_id.to_string());
+            }
+            cmd
+        }
+        NodeBackend::Anvil => {
+            let path = anvil_path().ok_or("Anvil is not installed; install Foundry first")?;
+            let mut cmd = Command::new(path);
+            cmd.args(["--port", &port]);
+            if let Some(url) = &fork_url {
+                cmd.args(["--fork-url", url]);
+            }
+            if let Some(block) = &fork_block {
+                cmd.args(["--fork-block-number", block]);
+            }
+            if let Some(mnemonic) = &options.mnemonic {
+                cmd.args(["--mnemonic", mnemonic]);
+            }
+            if let Some(chain_id) = options.chain_id {
+                cmd.args(["--chain-id", &chain_id.to_string()]);
+            }
+            cmd
+        }
+    };
+    cmd.current_dir(project_path);
+    Ok(cmd)
+}
+
+// Starts the local L1 node, registered with the process manager so it can be listed
+// and stopped like the L2 devnets
+pub fn start_l1_node(
+    app: &AppHandle,
+    project_path: &str,
+    backend: NodeBackend,
+    options: &NodeOptions,
+) -> Result<LocalNode, String> {
+    let command = node_command(project_path, backend, options)?;
+    let (kind, label) = match backend {
+        NodeBackend::Hardhat => (NodeKind::Hardhat, "Hardhat node"),
+        NodeBackend::Anvil => (NodeKind::Anvil, "Anvil node"),
+    };
+    let execution_id = processes::spawn_service(app, "node", command, on_node_exit(label))?;
+    let node = LocalNode {
+        execution_id,
+        kind,
+        container: None,
+        chains: vec![chain(
+            "localhost",
+            options.chain_id.unwrap_or(DEFAULT_CHAIN_ID),
+            options.port.unwrap_or(DEFAULT_PORT),
+        )],
+        funded_account: None,
+        started_at: store::timestamp_millis(),
+    };
+    register(node.clone());
+    Ok(node)
+}
+
+fn local_url(port: u16) -> String {
+    format!("http://127.0.0.1:{}", port)
+}
+
+fn chain(network: &str, chain_id: u64, port: u16) -> DevnetChain {
+    DevnetChain { network: network.to_string(), chain_id, rpc_url: local_url(port) }
+}
+
+async fn wait_until_ready(processes: &ProcessManager, execution_id: &str, url: &str) -> Result<(), String> {
+    let started = Instant::now();
+    while rpc::chain_id(url).await.is_err() {
+        if !processes.is_running(execution_id) {
+            return Err(format!("The node exited before {} answered", url));
+        }
+        if started.elapsed() > STARTUP_TIMEOUT {
+            return Err(format!("Node did not come up within {}s", STARTUP_TIMEOUT.as_secs()));
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    Ok(())
+}
+
+async fn stop(app: AppHandle, processes: State<'_, ProcessManager>, node: &LocalNode) -> Result<bool, AppError> {
+    if let Some(container) = &node.container {
+        let mut rm = resolver::command("docker");
+        rm.args(["rm", "-f", container]);
+        processes::output(rm)
+            .await
+            .map_err(|e| format!("Failed to stop container {}: {}", container, e))?;
+    }
+    processes::cancel_execution(app, processes, node.execution_id.clone()).await
+}
+
+// Launches an OP Stack (supersim) or Arbitrum (nitro --dev) devnet next to the Hardhat
+// node and adds a network profile for each of its chains
+#[tauri::command]
+pub async fn start_l2_devnet(
+    app: AppHandle,
+    processes: State<'_, ProcessManager>,
+    kind: NodeKind,
+    port: Option<u16>,
+) -> Result<LocalNode, AppError> {
+    let (command, container, chains, funded_account, label) = match kind {
+        NodeKind::Hardhat | NodeKind::Anvil => {
+            return Err("Start the local L1 node with start_hardhat_network".to_string().into())
+        }
+        NodeKind::OpStack => {
+            if resolver::locate("supersim").is_none() {
+                return Err("supersim is not installed. Install it with `brew install ethereum-optimism/tap/supersim` or from https://github.com/ethereum-optimism/supersim/releases".to_string().into());
+            }
+            let port = port.unwrap_or(SUPERSIM_DEFAULT_PORT);
+            let mut cmd = resolver::command("supersim");
+            cmd.args(["--l1.port", &SUPERSIM_L1_PORT.to_string(), "--l2.starting.port", &port.to_string()]);
+            let chains = SUPERSIM_CHAINS
+                .iter()
+                .map(|(name, chain_id, offset)| {
+                    chain(name, *chain_id, offset.map_or(SUPERSIM_L1_PORT, |offset| port + offset))
+                })
+                .collect();
+            (cmd, None, chains, None, "OP Stack devnet")
+        }
+        NodeKind::ArbitrumNitro => {
+            if resolver::locate("docker").is_none() {
+                return Err("Docker is required to run the Arbitrum nitro dev node".to_string().into());
+            }
+            let port = port.unwrap_or(NITRO_DEFAULT_PORT);
+            let container = format!("hardhat-gui-nitro-{}", port);
+            let mut cmd = resolver::command("docker");
+            cmd.args(["run", "--rm", "--name", &container, "-p", &format!("{}:8547", port), NITRO_IMAGE]);
+            cmd.args(["--dev", "--http.addr", "0.0.0.0", "--http.api", "net,web3,eth,arb,debug"]);
+            let chains = vec![chain("nitroDevnet", NITRO_CHAIN_ID, port)];
+            (cmd, Some(container), chains, Some(NITRO_DEV_ACCOUNT.to_string()), "Arbitrum nitro devnet")
+        }
+    };
+
+    let execution_id = processes::spawn_service(&app, "l2-node", command, on_node_exit(label))?;
+    let node = LocalNode {
+        execution_id: execution_id.clone(),
+        kind,
+        container,
+        chains,
+        funded_account,
+        started_at: store::timestamp_millis(),
+    };
+    for chain in &node.chains {
+        if let Err(e) = wait_until_ready(&processes, &execution_id, &chain.rpc_url).await {
+            let _ = stop(app.clone(), processes, &node).await;
+            return Err(e.into());
+        }
+    }
+
+    // supersim's chains run on anvil and sign with its unlocked accounts; nitro has none
+    let signer = match kind {
+        NodeKind::ArbitrumNitro => SignerSource::EnvPrivateKey { variable: "PRIVATE_KEY".to_string() },
+        _ => SignerSource::NodeAccounts,
+    };
+    for chain in &node.chains {
+        networks::save_network_profile(NetworkProfile {
+            name: chain.network.clone(),
+            rpc_url: chain.rpc_url.clone(),
+            chain_id: chain.chain_id,
+            currency_symbol: "ETH".to_string(),
+            explorer_url: None,
+            signer: signer.clone(),
+            rpc_policy: RpcPolicy::default(),
+            created_at: 0,
+            updated_at: 0,
+        })
+        .await?;
+    }
+
+    register(node.clone());
+    Ok(node)
+}
+
+// Nodes started by the GUI that are still running, the Hardhat L1 node included
+#[tauri::command]
+pub async fn list_local_nodes(processes: State<'_, ProcessManager>) -> Result<Vec<LocalNode>, AppError> {
+    let mut nodes = nodes().lock().unwrap();
+    nodes.retain(|node| processes.is_running(&node.execution_id));
+    Ok(nodes.clone())
+}
+
+#[tauri::command]
+pub async fn stop_local_node(
+    app: AppHandle,
+    processes: State<'_, ProcessManager>,
+    execution_id: String,
+) -> Result<bool, AppError> {
+    let node = {
+        let mut nodes = nodes().lock().unwrap();
+        let index = nodes.iter().position(|node| node.execution_id == execution_id);
+        index.map(|index| nodes.remove(index))
+    };
+    match node {
+        Some(node) => stop(app, processes, &node).await,
+        None => Ok(false),
+    }
+}
+
+#[tauri::command]
+pub async fn get_anvil_status() -> Result<AnvilStatus, AppError> {
+    let Some(path) = anvil_path() else {
+        return Ok(AnvilStatus { installed: false, path: None, version: None });
+    };
+    let mut command = Command::new(&path);
+    command.arg("--version");
+    let version = processes::output(command)
+        .await
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    Ok(AnvilStatus { installed: true, path: Some(path.to_string_lossy().to_string()), version })
+}
+
+// foundryup is a shell script; on Windows Foundry has to be installed by hand or in WSL
+#[tauri::command]
+pub async fn install_anvil(app: AppHandle, execution_id: Option<String>) -> Result<AnvilStatus, AppError> {
+    if cfg!(windows) {
+        return Err("Automatic install isn't available on Windows; see https://book.getfoundry.sh/getting-started/installation".to_string().into());
+    }
+    let mut cmd = resolver::command("sh");
+    cmd.args([
+        "-c",
+        &format!("curl -sSfL {} | bash && \"${{FOUNDRY_DIR:-$HOME/.foundry}}/bin/foundryup\"", FOUNDRY_INSTALLER),
+    ]);
+    let output = processes::run_tracked(&app, execution_id, "install", cmd).await?;
+    if !output.status.success() {
+        return Err(format!("Failed to install Foundry: {}", String::from_utf8_lossy(&output.stderr)).into());
+    }
+    get_anvil_status().await
+}
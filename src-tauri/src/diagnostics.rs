@@ -0,0 +1,170 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::State;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::error::AppError;
+use crate::health;
+use crate::logging;
+use crate::processes::ProcessManager;
+use crate::project;
+use crate::provider_keys;
+use crate::resolver;
+
+const REDACTED: &str = "[REDACTED]";
+// Shorter .env values (flags, chain ids, ports) aren't secrets and would redact half the logs
+const MIN_SECRET_LEN: usize = 8;
+
+#[derive(Serialize, Deserialize)]
+pub struct DiagnosticsSummary {
+    pub archive_path: String,
+    pub files: Vec<String>,
+    pub redactions: usize,
+}
+
+// Hides private keys, API keys and anything the project's .env or the keychain holds
+struct Redactor {
+    literals: Vec<String>,
+    patterns: Vec<Regex>,
+    count: usize,
+}
+
+impl Redactor {
+    fn new(project_path: Option<&Path>) -> Self {
+        let env_file = project_path
+            .and_then(|root| fs::read_to_string(root.join(".env")).ok())
+            .unwrap_or_default();
+        let mut literals: Vec<String> = env_file
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(_, value)| value.trim().trim_matches(['"', '\'']).to_string())
+            .filter(|value| value.len() >= MIN_SECRET_LEN)
+            .chain(provider_keys::env().into_values())
+            .collect();
+        // Longest first, so a value containing another is replaced whole
+        literals.sort_by_key(|value| std::cmp::Reverse(value.len()));
+
+        let patterns = [
+            // KEY=value, "apiKey": "value", PRIVATE_KEY: value
+            r#"(?i)((?:private_?key|api_?key|secret|token|password|mnemonic)["']?\s*[:=]\s*["']?)[^\s"',}]+"#,
+            // Provider URLs carry the key as the last path segment
+            r"(?i)(https?://[^\s/]*(?:infura\.io|alchemy\.com|alchemyapi\.io|quiknode\.pro)/(?:v\d/)?)[A-Za-z0-9_-]+",
+        ];
+        Self {
+            literals,
+            patterns: patterns.iter().map(|p| Regex::new(p).unwrap()).collect(),
+            count: 0,
+        }
+    }
+
+    fn redact(&mut self, text: &str) -> String {
+        let mut text = text.to_string();
+        for literal in &self.literals {
+            self.count += text.matches(literal.as_str()).count();
+            text = text.replace(literal.as_str(), REDACTED);
+        }
+        for pattern in &self.patterns {
+            self.count += pattern.find_iter(&text).filter(|m| !m.as_str().ends_with(REDACTED)).count();
+            text = pattern.replace_all(&text, format!("${{1}}{}", REDACTED)).to_string();
+        }
+        text
+    }
+
+    // Config files have no transaction hashes, so any 32-byte hex string there is a key
+    fn redact_config(&mut self, text: &str) -> String {
+        let key = Regex::new(r"\b(0x)?[0-9a-fA-F]{64}\b").unwrap();
+        self.count += key.find_iter(text).count();
+        let text = key.replace_all(text, REDACTED).to_string();
+        self.redact(&text)
+    }
+}
+
+struct Bundle {
+    zip: ZipWriter<File>,
+    options: SimpleFileOptions,
+    files: Vec<String>,
+}
+
+impl Bundle {
+    fn add(&mut self, name: &str, content: &str) -> Result<(), String> {
+        self.zip
+            .start_file(name, self.options)
+            .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))?;
+        self.zip
+            .write_all(content.as_bytes())
+            .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))?;
+        self.files.push(name.to_string());
+        Ok(())
+    }
+
+    fn add_json<T: Serialize>(&mut self, name: &str, value: &T, redactor: &mut Redactor) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(value)
+            .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+        self.add(name, &redactor.redact(&content))
+    }
+}
+
+// Everything needed to reproduce a problem in one zip for a bug report. The .env file
+// itself is never included; its values are redacted wherever they show up
+#[tauri::command]
+pub async fn export_diagnostics(
+    processes: State<'_, ProcessManager>,
+    project_path: Option<String>,
+    destination: String,
+) -> Result<DiagnosticsSummary, AppError> {
+    let root = project_path.as_deref().map(Path::new).filter(|root| root.is_dir());
+    let mut redactor = Redactor::new(root);
+
+    let destination = PathBuf::from(destination);
+    let archive = File::create(&destination).map_err(|e| format!("Failed to create bundle: {}", e))?;
+    let mut bundle = Bundle {
+        zip: ZipWriter::new(archive),
+        options: SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated),
+        files: Vec::new(),
+    };
+
+    let environment = json!({
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "toolchain": resolver::get_node_toolchain().await?,
+        "log_settings": logging::get_log_settings().await.ok(),
+    });
+    bundle.add_json("environment.json", &environment, &mut redactor)?;
+
+    if let (Some(root), Some(project_path)) = (root, project_path.clone()) {
+        let health = health::check_project_health(project_path).await?;
+        bundle.add_json("project/health.json", &health, &mut redactor)?;
+        if let Some(config) = project::find_hardhat_config(root) {
+            let name = config.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let content = fs::read_to_string(&config).map_err(|e| format!("Failed to read {}: {}", name, e))?;
+            bundle.add(&format!("project/{}", name), &redactor.redact_config(&content))?;
+        }
+        if let Ok(content) = fs::read_to_string(root.join("package.json")) {
+            bundle.add("project/package.json", &redactor.redact_config(&content))?;
+        }
+    }
+
+    bundle.add_json("recent-outputs.json", &processes.recent_outputs(), &mut redactor)?;
+
+    for path in logging::log_files() {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        bundle.add(&format!("logs/{}", name), &redactor.redact(&content))?;
+    }
+
+    bundle.zip.finish().map_err(|e| format!("Failed to finalize bundle: {}", e))?;
+    Ok(DiagnosticsSummary {
+        archive_path: destination.to_string_lossy().to_string(),
+        files: bundle.files,
+        redactions: redactor.count,
+    })
+}
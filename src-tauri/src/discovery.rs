@@ -0,0 +1,201 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::project;
+
+// Deep enough for packages/*/ and apps/contracts/packages/*/ layouts
+const MAX_DEPTH: usize = 4;
+const MAX_CANDIDATES: usize = 50;
+const SKIPPED_DIRS: [&str; 6] = ["node_modules", "artifacts", "cache", "typechain-types", "coverage", "lib"];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateRelation {
+    // The selected folder itself
+    Selected,
+    // The nearest folder above it with a config; Hardhat itself resolves configs this way
+    Ancestor,
+    Nested,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProjectCandidate {
+    pub path: String,
+    pub config_file: String,
+    pub relation: CandidateRelation,
+    // From the candidate's package.json
+    pub package_name: Option<String>,
+    // Matched by the monorepo's workspace globs
+    pub workspace_member: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProjectDiscovery {
+    pub selected: String,
+    // The nearest folder declaring workspaces (package.json or pnpm-workspace.yaml)
+    pub workspace_root: Option<String>,
+    pub candidates: Vec<ProjectCandidate>,
+}
+
+// "workspaces": [...] or "workspaces": { "packages": [...] } (yarn's form)
+fn package_json_workspaces(dir: &Path) -> Option<Vec<String>> {
+    let json = project::read_package_json(dir).ok().flatten()?;
+    let workspaces = json.get("workspaces")?;
+    let globs = workspaces.as_array().or_else(|| workspaces.get("packages")?.as_array())?;
+    Some(globs.iter().filter_map(|glob| glob.as_str().map(String::from)).collect())
+}
+
+// Only the `packages:` list is needed, so this reads the YAML line by line
+fn pnpm_workspaces(dir: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(dir.join("pnpm-workspace.yaml")).ok()?;
+    let mut globs = Vec::new();
+    let mut in_packages = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if !line.starts_with([' ', '\t', '-']) && !trimmed.is_empty() {
+            in_packages = trimmed.starts_with("packages:");
+            continue;
+        }
+        if let Some(glob) = trimmed.strip_prefix('-').filter(|_| in_packages) {
+            globs.push(glob.trim().trim_matches(['"', '\'']).to_string());
+        }
+    }
+    Some(globs)
+}
+
+fn workspace_globs(dir: &Path) -> Option<Vec<String>> {
+    package_json_workspaces(dir).or_else(|| pnpm_workspaces(dir))
+}
+
+// `*` matches one path segment and `**` any number; negated globs are ignored
+fn glob_matches(glob: &[&str], path: &[&str]) -> bool {
+    match (glob.first(), path.first()) {
+        (None, None) => true,
+        (Some(&"**"), _) => glob_matches(&glob[1..], path) || (!path.is_empty() && glob_matches(glob, &path[1..])),
+        (Some(segment), Some(name)) => segment_matches(segment, name) && glob_matches(&glob[1..], &path[1..]),
+        _ => false,
+    }
+}
+
+fn segment_matches(segment: &str, name: &str) -> bool {
+    match segment.split_once('*') {
+        Some((prefix, suffix)) => {
+            name.len() >= prefix.len() + suffix.len() && name.starts_with(prefix) && name.ends_with(suffix)
+        }
+        None => segment == name,
+    }
+}
+
+fn is_workspace_member(root: &Path, globs: &[String], dir: &Path) -> bool {
+    let Ok(relative) = dir.strip_prefix(root) else {
+        return false;
+    };
+    let path: Vec<String> = relative.components().map(|c| c.as_os_str().to_string_lossy().to_string()).collect();
+    let path: Vec<&str> = path.iter().map(String::as_str).collect();
+    globs.iter().filter(|glob| !glob.starts_with('!')).any(|glob| {
+        let glob: Vec<&str> = glob.trim_start_matches("./").trim_end_matches('/').split('/').collect();
+        glob_matches(&glob, &path)
+    })
+}
+
+fn collect_configs(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) {
+    if found.len() >= MAX_CANDIDATES {
+        return;
+    }
+    if project::find_hardhat_config(dir).is_some() {
+        found.push(dir.to_path_buf());
+    }
+    if depth == MAX_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str())
+        })
+        .map(|entry| entry.path())
+        .collect();
+    dirs.sort();
+    for child in dirs {
+        collect_configs(&child, depth + 1, found);
+    }
+}
+
+fn candidate(dir: &Path, relation: CandidateRelation, workspace: Option<(&Path, &[String])>) -> Option<ProjectCandidate> {
+    let config = project::find_hardhat_config(dir)?;
+    let package_name = project::read_package_json(dir)
+        .ok()
+        .flatten()
+        .and_then(|json| json.get("name")?.as_str().map(String::from));
+    Some(ProjectCandidate {
+        path: dir.to_string_lossy().to_string(),
+        config_file: config.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+        relation,
+        package_name,
+        workspace_member: workspace.is_some_and(|(root, globs)| is_workspace_member(root, globs, dir)),
+    })
+}
+
+// Everything under the selected folder that has a config, the nearest config above it,
+// and, inside a monorepo, every workspace package with one
+pub fn discover(selected: &Path) -> ProjectDiscovery {
+    let workspace = selected
+        .ancestors()
+        .find_map(|dir| workspace_globs(dir).map(|globs| (dir.to_path_buf(), globs)));
+    let workspace_ref = workspace.as_ref().map(|(root, globs)| (root.as_path(), globs.as_slice()));
+
+    let mut candidates = Vec::new();
+    if let Some(found) = candidate(selected, CandidateRelation::Selected, workspace_ref) {
+        candidates.push(found);
+    }
+    if let Some(found) = selected
+        .ancestors()
+        .skip(1)
+        .find_map(|dir| candidate(dir, CandidateRelation::Ancestor, workspace_ref))
+    {
+        candidates.push(found);
+    }
+
+    let mut nested = Vec::new();
+    collect_configs(selected, 0, &mut nested);
+    // Picking a subfolder of a monorepo still lists its sibling packages
+    if let Some((root, _)) = &workspace {
+        if root != selected {
+            collect_configs(root, 0, &mut nested);
+        }
+    }
+    for dir in nested {
+        let path = dir.to_string_lossy().to_string();
+        if candidates.iter().any(|existing| existing.path == path) {
+            continue;
+        }
+        candidates.extend(candidate(&dir, CandidateRelation::Nested, workspace_ref));
+    }
+    candidates.truncate(MAX_CANDIDATES);
+    candidates.sort_by(|a, b| {
+        (a.relation, !a.workspace_member, &a.path).cmp(&(b.relation, !b.workspace_member, &b.path))
+    });
+
+    ProjectDiscovery {
+        selected: selected.to_string_lossy().to_string(),
+        workspace_root: workspace.map(|(root, _)| root.to_string_lossy().to_string()),
+        candidates,
+    }
+}
+
+#[tauri::command]
+pub async fn discover_hardhat_projects(path: String) -> Result<ProjectDiscovery, AppError> {
+    let selected = Path::new(&path);
+    if !selected.is_dir() {
+        return Err(format!("{} is not a folder", path).into());
+    }
+    Ok(discover(selected))
+}
@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use serde::ser::{SerializeMap, Serializer};
+use serde::Serialize;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::messages::Message;
+use crate::project;
+use crate::solidity::CompilerDiagnostic;
+
+// What commands return to the frontend. Serialized as `{ kind, message, key, params,
+// ...details }`, so the UI can branch on `kind`, localize by `key` and `params`, and still
+// show `message` for anything it doesn't handle
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error("{tool} is not installed or not on PATH")]
+    NotInstalled { tool: String },
+    #[error("No Hardhat project found at {path}")]
+    ProjectNotFound { path: String },
+    #[error("Compilation failed: {output}")]
+    CompileFailed { diagnostics: Vec<CompilerDiagnostic>, output: String },
+    // `code` is the JSON-RPC error code
+    #[error("{message}")]
+    RpcError { method: String, code: Option<i64>, message: String },
+    // The node couldn't be reached or didn't answer in time, after every retry
+    #[error("{message}")]
+    RpcUnavailable { method: String, attempts: u32, timed_out: bool, elapsed_ms: u64, message: String },
+    #[error("{kind} timed out after {seconds}s (execution {execution_id})\n{output}")]
+    ProcessTimeout { execution_id: String, kind: String, seconds: u64, output: String },
+    #[error("Execution {execution_id} was cancelled")]
+    Cancelled { execution_id: String },
+    // A catalog message; the UI treats it like `Other`
+    #[error("{}", .0.text)]
+    Message(Message),
+    // Everything that still reports a plain message
+    #[error("{0}")]
+    Other(String),
+}
+
+impl AppError {
+    fn kind(&self) -> &'static str {
+        match self {
+            AppError::NotInstalled { .. } => "not_installed",
+            AppError::ProjectNotFound { .. } => "project_not_found",
+            AppError::CompileFailed { .. } => "compile_failed",
+            AppError::RpcError { .. } => "rpc_error",
+            AppError::RpcUnavailable { .. } => "rpc_unavailable",
+            AppError::ProcessTimeout { .. } => "process_timeout",
+            AppError::Cancelled { .. } => "cancelled",
+            AppError::Message(_) | AppError::Other(_) => "other",
+        }
+    }
+
+    fn key(&self) -> String {
+        match self {
+            AppError::Message(message) => message.key.clone(),
+            _ => format!("error.{}", self.kind()),
+        }
+    }
+
+    // Named after the placeholders in the key's catalog text
+    fn params(&self) -> Value {
+        match self {
+            AppError::NotInstalled { tool } => json!({ "tool": tool }),
+            AppError::ProjectNotFound { path } => json!({ "path": path }),
+            AppError::CompileFailed { output, .. } => json!({ "output": output }),
+            AppError::RpcError { method, code, message } => json!({ "method": method, "code": code, "message": message }),
+            AppError::RpcUnavailable { method, attempts, timed_out, elapsed_ms, message } => json!({
+                "method": method,
+                "attempts": attempts,
+                "timed_out": timed_out,
+                "elapsed_ms": elapsed_ms,
+                "message": message,
+            }),
+            AppError::ProcessTimeout { execution_id, kind, seconds, output } => json!({
+                "execution_id": execution_id,
+                "process_kind": kind,
+                "seconds": seconds,
+                "output": output,
+            }),
+            AppError::Cancelled { execution_id } => json!({ "execution_id": execution_id }),
+            AppError::Message(message) => json!(message.params),
+            AppError::Other(message) => json!({ "message": message }),
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("kind", self.kind())?;
+        map.serialize_entry("message", &self.to_string())?;
+        map.serialize_entry("key", &self.key())?;
+        map.serialize_entry("params", &self.params())?;
+        match self {
+            AppError::NotInstalled { tool } => map.serialize_entry("tool", tool)?,
+            AppError::ProjectNotFound { path } => map.serialize_entry("path", path)?,
+            AppError::CompileFailed { diagnostics, .. } => map.serialize_entry("diagnostics", diagnostics)?,
+            AppError::RpcError { method, code, .. } => {
+                map.serialize_entry("method", method)?;
+                map.serialize_entry("code", code)?;
+            }
+            AppError::RpcUnavailable { method, attempts, timed_out, elapsed_ms, .. } => {
+                map.serialize_entry("method", method)?;
+                map.serialize_entry("attempts", attempts)?;
+                map.serialize_entry("timed_out", timed_out)?;
+                map.serialize_entry("elapsed_ms", elapsed_ms)?;
+            }
+            AppError::ProcessTimeout { execution_id, kind, seconds, .. } => {
+                map.serialize_entry("execution_id", execution_id)?;
+                map.serialize_entry("process_kind", kind)?;
+                map.serialize_entry("seconds", seconds)?;
+            }
+            AppError::Cancelled { execution_id } => map.serialize_entry("execution_id", execution_id)?,
+            AppError::Message(_) | AppError::Other(_) => {}
+        }
+        map.end()
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Other(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::Other(message.to_string())
+    }
+}
+
+pub fn require_project(project_path: &str) -> Result<(), AppError> {
+    if project::find_hardhat_config(Path::new(project_path)).is_none() {
+        return Err(AppError::ProjectNotFound { path: project_path.to_string() });
+    }
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::address_book::is_address;
+use crate::error::AppError;
+use crate::external_abis::{self, ExternalAbi};
+use crate::provider_keys::Provider;
+use crate::rpc;
+use crate::store;
+
+const API_URL: &str = "https://api.etherscan.io/v2/api";
+// Verified sources land here, one directory per contract
+const EXTERNAL_SOURCES_DIR: &str = "contracts/external";
+
+#[derive(Serialize, Deserialize)]
+pub struct FetchedContract {
+    pub name: String,
+    pub address: String,
+    pub chain_id: u64,
+    pub compiler_version: String,
+    pub optimization_runs: Option<u64>,
+    pub evm_version: Option<String>,
+    // Relative to the project root
+    pub source_dir: String,
+    pub source_files: Vec<String>,
+    pub abi_name: String,
+    // Set for proxies; fetch the implementation too to call through the proxy
+    pub implementation: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SourceResponse {
+    status: String,
+    message: String,
+    result: Value,
+}
+
+// Paths come from the explorer; only plain relative ones are written
+fn safe_relative(path: &str) -> Option<PathBuf> {
+    let path = Path::new(path.trim_start_matches('/'));
+    path.components()
+        .all(|c| matches!(c, Component::Normal(_)))
+        .then(|| path.to_path_buf())
+}
+
+// SourceCode is a single file, a JSON map of files, or standard JSON input wrapped in
+// an extra pair of braces
+fn source_files(name: &str, source: &str) -> Result<BTreeMap<String, String>, String> {
+    let trimmed = source.trim();
+    let json = if trimmed.starts_with("{{") && trimmed.ends_with("}}") {
+        &trimmed[1..trimmed.len() - 1]
+    } else {
+        trimmed
+    };
+    let Ok(parsed) = serde_json::from_str::<Value>(json) else {
+        return Ok(BTreeMap::from([(format!("{}.sol", name), source.to_string())]));
+    };
+    let sources = parsed.get("sources").unwrap_or(&parsed);
+    let files: BTreeMap<String, String> = sources
+        .as_object()
+        .ok_or("Unexpected verified source format")?
+        .iter()
+        .filter_map(|(path, file)| Some((path.clone(), file.get("content")?.as_str()?.to_string())))
+        .collect();
+    if files.is_empty() {
+        return Err("The verified source has no files".to_string());
+    }
+    Ok(files)
+}
+
+// Etherscan's getsourcecode entry for a contract: SourceCode, ABI, ContractName,
+// CompilerVersion, Proxy, Implementation, ...
+pub async fn source_code(address: &str, chain_id: u64) -> Result<Value, String> {
+    let key = Provider::Etherscan.key()?.ok_or("No ETHERSCAN_API_KEY is configured")?;
+    let response: SourceResponse = rpc::client()
+        .get(API_URL)
+        .query(&[
+            ("chainid", chain_id.to_string().as_str()),
+            ("module", "contract"),
+            ("action", "getsourcecode"),
+            ("address", address),
+            ("apikey", key.as_str()),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Etherscan: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Etherscan response: {}", e))?;
+    if response.status != "1" {
+        let detail = response.result.as_str().unwrap_or(&response.message);
+        return Err(format!("Etherscan lookup failed: {}", detail));
+    }
+    response.result.get(0).cloned().ok_or_else(|| "Etherscan returned no contract".to_string())
+}
+
+// Pulls a verified contract's sources into contracts/external/<Name>/ and its ABI into
+// the imported-ABI store, so it can be compiled against on a fork and called by address
+#[tauri::command]
+pub async fn fetch_verified_contract(
+    project_path: String,
+    address: String,
+    chain_id: Option<u64>,
+) -> Result<FetchedContract, AppError> {
+    let root = Path::new(&project_path);
+    let address = address.trim().to_lowercase();
+    if !is_address(&address) {
+        return Err(format!("Invalid address: {}", address).into());
+    }
+    let chain_id = chain_id.unwrap_or(1);
+    let contract = source_code(&address, chain_id).await?;
+    let field = |key: &str| contract.get(key).and_then(Value::as_str).unwrap_or_default().to_string();
+
+    let name = field("ContractName");
+    let source = field("SourceCode");
+    if name.is_empty() || source.is_empty() {
+        return Err(format!("{} is not verified on chain {}", address, chain_id).into());
+    }
+    let abi = external_abis::parse_abi(Value::String(field("ABI")))?;
+
+    // Suffixed with the address so two deployments of the same contract don't collide
+    let dir_name = format!("{}_{}", name, &address[2..10]);
+    let source_dir = root.join(EXTERNAL_SOURCES_DIR).join(&dir_name);
+    let mut written = Vec::new();
+    for (path, content) in source_files(&name, &source)? {
+        let relative = safe_relative(&path).ok_or_else(|| format!("Refusing to write source path {}", path))?;
+        let target = source_dir.join(&relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        fs::write(&target, content).map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        written.push(relative.to_string_lossy().replace('\\', "/"));
+    }
+
+    external_abis::save(
+        root,
+        ExternalAbi {
+            name: dir_name.clone(),
+            address: Some(address.clone()),
+            chain_id: Some(chain_id),
+            imported_at: store::timestamp_millis(),
+            abi,
+        },
+    )?;
+
+    let implementation = Some(field("Implementation")).filter(|i| field("Proxy") == "1" && is_address(i));
+    Ok(FetchedContract {
+        name,
+        address,
+        chain_id,
+        compiler_version: field("CompilerVersion"),
+        optimization_runs: (field("OptimizationUsed") == "1").then(|| field("Runs").parse().ok()).flatten(),
+        evm_version: Some(field("EVMVersion")).filter(|v| !v.is_empty() && v != "Default"),
+        source_dir: format!("{}/{}", EXTERNAL_SOURCES_DIR, dir_name),
+        source_files: written,
+        abi_name: dir_name,
+        implementation: implementation.map(|i| i.to_lowercase()),
+    })
+}
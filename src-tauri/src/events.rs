@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::address_book::AddressBook;
+use crate::decode::{self, AbiIndex, DecodedParam};
+use crate::error::AppError;
+use crate::rpc;
+
+// Log decoding shared by every command that returns logs, so an event gets the same
+// name and arguments in the explorer, log search and decoded receipts
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DecodedLog {
+    pub address: String,
+    pub block_number: Option<u64>,
+    pub transaction_hash: Option<String>,
+    pub log_index: Option<u64>,
+    pub topics: Vec<String>,
+    pub data: String,
+    // Contract whose ABI decoded the log, preferring the one deployed at `address`
+    pub contract: Option<String>,
+    pub event: Option<String>,
+    pub args: Vec<DecodedParam>,
+}
+
+// Hex quantities from RPC responses, plain numbers from ethers/viem objects
+fn number(log: &Value, keys: &[&str]) -> Option<u64> {
+    keys.iter()
+        .filter_map(|key| log.get(*key))
+        .find_map(|value| value.as_u64().or_else(|| rpc::parse_quantity(value)))
+}
+
+fn text(log: &Value, key: &str) -> Option<String> {
+    log.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+pub fn decode_log(index: &AbiIndex, log: &Value) -> DecodedLog {
+    let address = text(log, "address").unwrap_or_default();
+    let topics: Vec<String> = log
+        .get("topics")
+        .and_then(Value::as_array)
+        .map(|topics| topics.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default();
+    let mut decoded = DecodedLog {
+        block_number: number(log, &["blockNumber"]),
+        transaction_hash: text(log, "transactionHash"),
+        log_index: number(log, &["logIndex", "index"]),
+        contract: index.contract_at(&address).map(str::to_string),
+        data: text(log, "data").unwrap_or_else(|| "0x".to_string()),
+        address,
+        topics,
+        event: None,
+        args: Vec::new(),
+    };
+
+    let (Some(topic), Some(bytes)) = (decoded.topics.first(), decode::from_hex(&decoded.data)) else {
+        return decoded;
+    };
+    for (contract, event) in index.event_candidates(topic, Some(&decoded.address)) {
+        if let Some(args) = decode::decode_event(event, &decoded.topics, &bytes) {
+            decoded.contract.get_or_insert_with(|| contract.to_string());
+            decoded.event = Some(event.signature());
+            decoded.args = args;
+            break;
+        }
+    }
+    decoded
+}
+
+pub fn decode_logs(index: &AbiIndex, logs: &[Value]) -> Vec<DecodedLog> {
+    logs.iter().map(|log| decode_log(index, log)).collect()
+}
+
+// Event filters to topic0 values: signatures ("Transfer(address,address,uint256)"),
+// bare names looked up in every known ABI, or topic hashes as-is
+pub fn filter_topics(index: &AbiIndex, events: &[String]) -> Result<Vec<String>, String> {
+    let mut topics = Vec::new();
+    for event in events.iter().map(|e| e.trim()).filter(|e| !e.is_empty()) {
+        if event.len() == 66 && decode::from_hex(event).is_some() {
+            topics.push(event.to_lowercase());
+        } else if event.contains('(') {
+            let (name, params) =
+                decode::parse_signature(event).ok_or_else(|| format!("Invalid event signature: {}", event))?;
+            let types: Vec<String> = params.iter().map(|p| p.canonical_type()).collect();
+            topics.push(decode::event_topic(&format!("{}({})", name, types.join(","))));
+        } else {
+            let named = index.event_topics(event);
+            if named.is_empty() {
+                return Err(format!("No known ABI has an event named {}", event));
+            }
+            topics.extend(named);
+        }
+    }
+    Ok(topics)
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DecodedLogs {
+    pub logs: Vec<DecodedLog>,
+    pub labels: BTreeMap<String, String>,
+}
+
+// For logs the frontend already has, e.g. receipts from console sessions or test output
+#[tauri::command]
+pub async fn decode_event_logs(
+    project_path: String,
+    logs: Vec<Value>,
+    rpc_url: Option<String>,
+) -> Result<DecodedLogs, AppError> {
+    let root = Path::new(&project_path);
+    let logs = decode_logs(&AbiIndex::load(root), &logs);
+    let book = AddressBook::load(root, Some(rpc_url.unwrap_or_else(rpc::default_url).as_str())).await;
+    Ok(DecodedLogs { labels: book.labels_for(&logs), logs })
+}
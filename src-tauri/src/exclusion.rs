@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::watch;
+
+use crate::error::AppError;
+
+// Operations in the same group and project never run at the same time: `build` shares
+// cache/ and artifacts/, `rpc` sends transactions from the same accounts (nonces), and
+// `node` starts, stops or rewrites the local node
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum JobGroup {
+    Build,
+    Node,
+    Rpc,
+}
+
+// Groups a tracked process kind holds while it runs. Kinds without groups (lint,
+// report, semgrep, ...) don't touch shared state and run immediately
+pub fn groups_for(kind: &str) -> &'static [JobGroup] {
+    match kind {
+        "compile" | "test" | "install" | "verify" | "coverage" => &[JobGroup::Build],
+        "deploy" | "task" | "script" | "preset" | "schedule" => &[JobGroup::Build, JobGroup::Rpc],
+        _ => &[],
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JobSlot {
+    pub ticket: u64,
+    pub execution_id: String,
+    pub kind: String,
+    pub groups: Vec<JobGroup>,
+    pub running: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JobWaiting {
+    pub execution_id: String,
+    pub kind: String,
+    pub project_path: String,
+    // 1 when next in line
+    pub position: usize,
+    pub blocked_by: String,
+    pub blocked_by_kind: String,
+}
+
+#[derive(Default)]
+struct Slots {
+    next_ticket: u64,
+    // Per project, in arrival order
+    projects: HashMap<PathBuf, Vec<JobSlot>>,
+}
+
+struct Scheduler {
+    slots: Mutex<Slots>,
+    // Bumped on every release so waiters re-check their place
+    released: watch::Sender<u64>,
+}
+
+static SCHEDULER: OnceLock<Scheduler> = OnceLock::new();
+
+fn scheduler() -> &'static Scheduler {
+    SCHEDULER.get_or_init(|| Scheduler {
+        slots: Mutex::new(Slots::default()),
+        released: watch::channel(0).0,
+    })
+}
+
+// Holding it keeps the groups; dropping it (finished, failed or the waiting future
+// dropped on cancel) lets the next operation in
+pub struct SlotGuard {
+    project: PathBuf,
+    ticket: u64,
+}
+
+impl Drop for SlotGuard {
+    fn drop(&mut self) {
+        let scheduler = scheduler();
+        let mut slots = scheduler.slots.lock().unwrap();
+        if let Some(queue) = slots.projects.get_mut(&self.project) {
+            queue.retain(|slot| slot.ticket != self.ticket);
+            if queue.is_empty() {
+                slots.projects.remove(&self.project);
+            }
+        }
+        scheduler.released.send_modify(|count| *count += 1);
+    }
+}
+
+fn conflicts(a: &[JobGroup], b: &[JobGroup]) -> bool {
+    a.iter().any(|group| b.contains(group))
+}
+
+// Everything that arrived earlier and shares a group is ahead, running or not, so
+// operations start in order and a steady stream of builds can't starve a deploy
+fn ahead(queue: &[JobSlot], ticket: u64) -> Vec<&JobSlot> {
+    let Some(mine) = queue.iter().find(|slot| slot.ticket == ticket) else {
+        return Vec::new();
+    };
+    queue
+        .iter()
+        .take_while(|slot| slot.ticket != ticket)
+        .filter(|slot| conflicts(&slot.groups, &mine.groups))
+        .collect()
+}
+
+// Waits until no earlier operation in the project holds any of `groups`. Emits
+// `job-waiting` whenever the queue position changes
+pub async fn acquire(
+    app: &AppHandle,
+    project_path: &Path,
+    execution_id: &str,
+    kind: &str,
+    groups: &[JobGroup],
+) -> Option<SlotGuard> {
+    if groups.is_empty() {
+        return None;
+    }
+    let scheduler = scheduler();
+    let mut released = scheduler.released.subscribe();
+    let guard = {
+        let mut slots = scheduler.slots.lock().unwrap();
+        slots.next_ticket += 1;
+        let ticket = slots.next_ticket;
+        slots.projects.entry(project_path.to_path_buf()).or_default().push(JobSlot {
+            ticket,
+            execution_id: execution_id.to_string(),
+            kind: kind.to_string(),
+            groups: groups.to_vec(),
+            running: false,
+        });
+        SlotGuard { project: project_path.to_path_buf(), ticket }
+    };
+
+    let mut last_position = 0;
+    loop {
+        let waiting = {
+            let mut slots = scheduler.slots.lock().unwrap();
+            let queue = slots.projects.get_mut(project_path).map(Vec::as_mut_slice).unwrap_or_default();
+            let ahead = ahead(queue, guard.ticket);
+            match ahead.first() {
+                Some(blocker) => Some(JobWaiting {
+                    execution_id: execution_id.to_string(),
+                    kind: kind.to_string(),
+                    project_path: project_path.to_string_lossy().to_string(),
+                    position: ahead.len(),
+                    blocked_by: blocker.execution_id.clone(),
+                    blocked_by_kind: blocker.kind.clone(),
+                }),
+                None => {
+                    if let Some(slot) = queue.iter_mut().find(|slot| slot.ticket == guard.ticket) {
+                        slot.running = true;
+                    }
+                    None
+                }
+            }
+        };
+        let Some(waiting) = waiting else {
+            return Some(guard);
+        };
+        if waiting.position != last_position {
+            last_position = waiting.position;
+            tracing::info!(execution_id, kind, position = waiting.position, blocked_by = %waiting.blocked_by, "waiting for conflicting operation");
+            let _ = app.emit("job-waiting", waiting);
+        }
+        if released.changed().await.is_err() {
+            return Some(guard);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn list_job_slots(project_path: String) -> Result<Vec<JobSlot>, AppError> {
+    let slots = scheduler().slots.lock().unwrap();
+    Ok(slots.projects.get(Path::new(&project_path)).cloned().unwrap_or_default())
+}
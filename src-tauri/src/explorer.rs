@@ -0,0 +1,486 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::address_book::AddressBook;
+use crate::chain_index::{self, IndexedLog};
+use crate::decode::{self, AbiIndex, DecodedCall};
+use crate::error::AppError;
+use crate::events::{self, DecodedLog};
+use crate::rpc;
+use crate::selectors;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CallFrame {
+    // CALL, DELEGATECALL, STATICCALL, CREATE, CREATE2, SELFDESTRUCT
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub from: String,
+    #[serde(default)]
+    pub to: Option<String>,
+    // Wei as a decimal string
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(rename = "gasUsed", default)]
+    pub gas_used: Option<String>,
+    #[serde(default)]
+    pub input: Option<String>,
+    #[serde(default)]
+    pub output: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+    // Contract at `to`, from the deployment registry or an imported ABI
+    #[serde(default)]
+    pub contract: Option<String>,
+    // Signature of the called function when a known ABI matches the input
+    #[serde(default)]
+    pub function: Option<String>,
+    #[serde(default)]
+    pub calls: Vec<CallFrame>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TransactionDetails {
+    pub hash: String,
+    pub block_number: Option<u64>,
+    pub from: String,
+    pub to: Option<String>,
+    pub contract: Option<String>,
+    pub value: String,
+    pub nonce: Option<u64>,
+    pub input: String,
+    pub decoded_input: Option<DecodedCall>,
+    pub gas_limit: Option<u64>,
+    // Receipt fields are absent while the transaction is pending
+    pub status: Option<bool>,
+    pub gas_used: Option<u64>,
+    pub effective_gas_price: Option<String>,
+    pub contract_address: Option<String>,
+    pub logs: Vec<DecodedLog>,
+    pub call_tree: Option<CallFrame>,
+    // Set when the node has no callTracer (Hardhat's built-in network only has the struct logger)
+    pub trace_error: Option<String>,
+    // Address book names for the addresses above, keyed by lowercase address
+    pub labels: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TraceTarget {
+    Transaction { hash: String },
+    // Latest block when no number is given
+    Block { number: Option<u64> },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct InternalTransaction {
+    pub tx_hash: String,
+    // Child indices from the top-level call, e.g. [0, 2]
+    pub trace_address: Vec<usize>,
+    pub kind: String,
+    pub from: String,
+    pub to: Option<String>,
+    pub contract: Option<String>,
+    pub function: Option<String>,
+    pub value: String,
+    // The frame or one of its parents reverted, so nothing actually moved
+    pub reverted: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AddressFlow {
+    pub address: String,
+    pub contract: Option<String>,
+    pub received: String,
+    pub sent: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ValueFlow {
+    pub transactions: Vec<String>,
+    pub transfers: Vec<InternalTransaction>,
+    pub creations: Vec<InternalTransaction>,
+    // Net effect of the successful internal transfers per address, in wei
+    pub flows: Vec<AddressFlow>,
+    pub labels: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct LogFilter {
+    #[serde(default)]
+    pub addresses: Vec<String>,
+    // Signatures ("Transfer(address,address,uint256)"), bare event names or topic0 hashes
+    #[serde(default)]
+    pub events: Vec<String>,
+    // Accepted values for indexed topics 1-3 in order; an empty list matches anything
+    #[serde(default)]
+    pub topics: Vec<Vec<String>>,
+    pub from_block: Option<u64>,
+    pub to_block: Option<u64>,
+    pub page: Option<usize>,
+    pub page_size: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LogPage {
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+    pub logs: Vec<DecodedLog>,
+    pub labels: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AccountInfo {
+    pub address: String,
+    pub label: Option<String>,
+    // Wei as a decimal string
+    pub balance: String,
+    pub nonce: u64,
+}
+
+fn str_value(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+// callTracer reports value and gas as hex quantities
+fn normalize_frame(frame: &mut CallFrame, index: &AbiIndex) {
+    frame.value = frame.value.as_deref().map(decode::quantity_to_decimal);
+    frame.gas_used = frame.gas_used.as_deref().map(decode::quantity_to_decimal);
+    frame.contract = frame.to.as_deref().and_then(|to| index.contract_at(to)).map(str::to_string);
+    // CREATE input is init code, not calldata
+    if !frame.kind.starts_with("CREATE") {
+        frame.function = frame
+            .input
+            .as_deref()
+            .and_then(|input| index.decode_call(frame.to.as_deref(), input))
+            .and_then(|call| call.function);
+    }
+    for call in &mut frame.calls {
+        normalize_frame(call, index);
+    }
+}
+
+pub fn parse_trace(trace: Value, index: &AbiIndex) -> Result<CallFrame, String> {
+    let mut frame: CallFrame =
+        serde_json::from_value(trace).map_err(|e| format!("Failed to parse call trace: {}", e))?;
+    normalize_frame(&mut frame, index);
+    Ok(frame)
+}
+
+pub async fn call_tree(url: &str, hash: &str, index: &AbiIndex) -> Result<CallFrame, AppError> {
+    let trace = rpc::call(url, "debug_traceTransaction", json!([hash, { "tracer": "callTracer" }])).await?;
+    Ok(parse_trace(trace, index)?)
+}
+
+#[tauri::command]
+pub async fn get_transaction_details(
+    project_path: String,
+    hash: String,
+    rpc_url: Option<String>,
+) -> Result<TransactionDetails, AppError> {
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let tx = rpc::call(url, "eth_getTransactionByHash", json!([hash])).await?;
+    if tx.is_null() {
+        return Err(format!("Transaction {} not found", hash).into());
+    }
+    let receipt = rpc::call(url, "eth_getTransactionReceipt", json!([hash])).await?;
+    let index = AbiIndex::load(Path::new(&project_path));
+
+    let to = str_value(&tx, "to");
+    let input = str_value(&tx, "input").unwrap_or_else(|| "0x".to_string());
+    let mut decoded_input = to.as_deref().and_then(|to| index.decode_call(Some(to), &input));
+    if let Some(call) = decoded_input.as_mut() {
+        selectors::resolve(Path::new(&project_path), call, &input).await;
+    }
+    let logs = receipt
+        .get("logs")
+        .and_then(Value::as_array)
+        .map(|logs| events::decode_logs(&index, logs))
+        .unwrap_or_default();
+
+    // Pending transactions have nothing to trace yet
+    let (call_tree, trace_error) = if receipt.is_null() {
+        (None, None)
+    } else {
+        match call_tree(url, &hash, &index).await {
+            Ok(frame) => (Some(frame), None),
+            Err(e) => (None, Some(e.to_string())),
+        }
+    };
+
+    let mut details = TransactionDetails {
+        hash,
+        block_number: tx.get("blockNumber").and_then(rpc::parse_quantity),
+        from: str_value(&tx, "from").unwrap_or_default(),
+        contract: to.as_deref().and_then(|to| index.contract_at(to)).map(str::to_string),
+        to,
+        value: decode::quantity_to_decimal(tx.get("value").and_then(Value::as_str).unwrap_or("0x0")),
+        nonce: tx.get("nonce").and_then(rpc::parse_quantity),
+        input,
+        decoded_input,
+        gas_limit: tx.get("gas").and_then(rpc::parse_quantity),
+        status: receipt.get("status").and_then(rpc::parse_quantity).map(|s| s == 1),
+        gas_used: receipt.get("gasUsed").and_then(rpc::parse_quantity),
+        effective_gas_price: str_value(&receipt, "effectiveGasPrice").map(|p| decode::quantity_to_decimal(&p)),
+        contract_address: str_value(&receipt, "contractAddress"),
+        logs,
+        call_tree,
+        trace_error,
+        labels: BTreeMap::new(),
+    };
+    details.labels = AddressBook::load(Path::new(&project_path), Some(url)).await.labels_for(&details);
+    Ok(details)
+}
+
+// Raw calldata, e.g. pasted from a wallet prompt; `to` picks the right ABI when selectors collide
+#[tauri::command]
+pub async fn decode_calldata(
+    project_path: String,
+    data: String,
+    to: Option<String>,
+    lookup: Option<bool>,
+) -> Result<Option<DecodedCall>, AppError> {
+    let root = Path::new(&project_path);
+    let data = data.trim();
+    if decode::from_hex(data).is_none() {
+        return Err("Calldata must be 0x-prefixed hex".to_string().into());
+    }
+
+    let mut call = AbiIndex::load(root).decode_call(to.as_deref(), data);
+    if let Some(call) = call.as_mut().filter(|_| lookup.unwrap_or(true)) {
+        selectors::resolve(root, call, data).await;
+    }
+    Ok(call)
+}
+
+fn collect_internal(
+    frame: &CallFrame,
+    tx_hash: &str,
+    path: &mut Vec<usize>,
+    parent_reverted: bool,
+    flow: &mut ValueFlow,
+) {
+    let reverted = parent_reverted || frame.error.is_some();
+    // The top-level frame is the transaction itself, not an internal one
+    if !path.is_empty() {
+        let value = frame.value.clone().unwrap_or_else(|| "0".to_string());
+        let internal = InternalTransaction {
+            tx_hash: tx_hash.to_string(),
+            trace_address: path.clone(),
+            kind: frame.kind.clone(),
+            from: frame.from.clone(),
+            to: frame.to.clone(),
+            contract: frame.contract.clone(),
+            function: frame.function.clone(),
+            value: value.clone(),
+            reverted,
+        };
+        if frame.kind.starts_with("CREATE") {
+            flow.creations.push(internal.clone());
+        }
+        if value != "0" {
+            flow.transfers.push(internal);
+        }
+    }
+
+    for (i, call) in frame.calls.iter().enumerate() {
+        path.push(i);
+        collect_internal(call, tx_hash, path, reverted, flow);
+        path.pop();
+    }
+}
+
+async fn block_traces(url: &str, number: Option<u64>, index: &AbiIndex) -> Result<Vec<(String, CallFrame)>, AppError> {
+    let tag = number.map(|n| format!("0x{:x}", n)).unwrap_or_else(|| "latest".to_string());
+    let block = rpc::call(url, "eth_getBlockByNumber", json!([tag, false])).await?;
+    if block.is_null() {
+        return Err(format!("Block {} not found", tag).into());
+    }
+    let hashes: Vec<String> = block
+        .get("transactions")
+        .and_then(Value::as_array)
+        .map(|txs| txs.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default();
+
+    // One request for the whole block where the node supports it, else one per transaction
+    if let Ok(Value::Array(results)) =
+        rpc::call(url, "debug_traceBlockByNumber", json!([tag, { "tracer": "callTracer" }])).await
+    {
+        let mut traces = Vec::new();
+        for (i, result) in results.into_iter().enumerate() {
+            let hash = str_value(&result, "txHash").or_else(|| hashes.get(i).cloned()).unwrap_or_default();
+            let Some(trace) = result.get("result").cloned() else {
+                continue;
+            };
+            traces.push((hash, parse_trace(trace, index)?));
+        }
+        return Ok(traces);
+    }
+
+    let mut traces = Vec::new();
+    for hash in hashes {
+        let frame = call_tree(url, &hash, index).await?;
+        traces.push((hash, frame));
+    }
+    Ok(traces)
+}
+
+fn address_flows(transfers: &[InternalTransaction], index: &AbiIndex) -> Vec<AddressFlow> {
+    let mut totals: BTreeMap<String, (u128, u128)> = BTreeMap::new();
+    for transfer in transfers.iter().filter(|t| !t.reverted) {
+        let value: u128 = transfer.value.parse().unwrap_or(0);
+        let sender = totals.entry(transfer.from.to_lowercase()).or_default();
+        sender.1 = sender.1.saturating_add(value);
+        if let Some(to) = &transfer.to {
+            let receiver = totals.entry(to.to_lowercase()).or_default();
+            receiver.0 = receiver.0.saturating_add(value);
+        }
+    }
+    totals
+        .into_iter()
+        .map(|(address, (received, sent))| AddressFlow {
+            contract: index.contract_at(&address).map(str::to_string),
+            address,
+            received: received.to_string(),
+            sent: sent.to_string(),
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_internal_transactions(
+    project_path: String,
+    target: TraceTarget,
+    rpc_url: Option<String>,
+) -> Result<ValueFlow, AppError> {
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let index = AbiIndex::load(Path::new(&project_path));
+
+    let traces = match &target {
+        TraceTarget::Transaction { hash } => vec![(hash.clone(), call_tree(url, hash, &index).await?)],
+        TraceTarget::Block { number } => block_traces(url, *number, &index).await?,
+    };
+
+    let mut flow = ValueFlow {
+        transactions: traces.iter().map(|(hash, _)| hash.clone()).collect(),
+        transfers: Vec::new(),
+        creations: Vec::new(),
+        flows: Vec::new(),
+        labels: BTreeMap::new(),
+    };
+    for (hash, frame) in &traces {
+        collect_internal(frame, hash, &mut Vec::new(), false, &mut flow);
+    }
+    flow.flows = address_flows(&flow.transfers, &index);
+    flow.labels = AddressBook::load(Path::new(&project_path), Some(url)).await.labels_for(&flow);
+    Ok(flow)
+}
+
+fn is_hex(value: &str) -> bool {
+    value.starts_with("0x") && value[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// Addresses and short values are left-padded to a full 32-byte topic
+fn normalize_topic(value: &str) -> String {
+    let value = value.trim().to_lowercase();
+    match value.parse::<u128>() {
+        Ok(number) if !value.starts_with("0x") => format!("0x{:064x}", number),
+        _ => format!("0x{:0>64}", value.trim_start_matches("0x")),
+    }
+}
+
+// Blocks before the index starts (e.g. pre-fork history) are asked from the node directly
+async fn query_logs(url: &str, from: u64, to: u64, addresses: &[String], topics: &[Vec<String>]) -> Result<Vec<IndexedLog>, AppError> {
+    let topics: Vec<Value> = topics
+        .iter()
+        .map(|values| if values.is_empty() { Value::Null } else { json!(values) })
+        .collect();
+    let mut filter = json!({
+        "fromBlock": format!("0x{:x}", from),
+        "toBlock": format!("0x{:x}", to),
+        "topics": topics,
+    });
+    if !addresses.is_empty() {
+        filter["address"] = json!(addresses);
+    }
+    let logs = rpc::call(url, "eth_getLogs", json!([filter])).await?;
+    Ok(logs.as_array().into_iter().flatten().map(chain_index::parse_log).collect())
+}
+
+#[tauri::command]
+pub async fn search_logs(project_path: String, filters: LogFilter, rpc_url: Option<String>) -> Result<LogPage, AppError> {
+    let root = Path::new(&project_path);
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let index = AbiIndex::load(root);
+    let chain = chain_index::sync(root, url).await?;
+
+    let addresses: Vec<String> = filters.addresses.iter().map(|a| a.trim().to_lowercase()).collect();
+    if let Some(address) = addresses.iter().find(|a| !is_hex(a) || a.len() != 42) {
+        return Err(format!("Invalid address: {}", address).into());
+    }
+    // Position 0 is the event, 1-3 the indexed arguments
+    let mut topics = vec![events::filter_topics(&index, &filters.events)?];
+    for values in filters.topics.iter().take(3) {
+        topics.push(values.iter().map(|v| normalize_topic(v)).collect());
+    }
+
+    let from = filters.from_block.unwrap_or(chain.first_block);
+    let to = filters.to_block.or(chain.tip()).unwrap_or(from);
+    if from > to {
+        return Err(format!("Invalid block range {}..{}", from, to).into());
+    }
+
+    let matches = |log: &&IndexedLog| {
+        (from..=to).contains(&log.block_number)
+            && (addresses.is_empty() || addresses.contains(&log.address))
+            && topics.iter().enumerate().all(|(i, values)| {
+                values.is_empty() || log.topics.get(i).is_some_and(|topic| values.contains(topic))
+            })
+    };
+    let mut logs: Vec<IndexedLog> = chain.logs.iter().filter(matches).cloned().collect();
+    if from < chain.first_block {
+        logs.extend(query_logs(url, from, to.min(chain.first_block.saturating_sub(1)), &addresses, &topics).await?);
+    }
+    logs.sort_by_key(|log| std::cmp::Reverse((log.block_number, log.log_index)));
+
+    let page_size = filters.page_size.unwrap_or(50).clamp(1, 1000);
+    let page = filters.page.unwrap_or(0);
+    let mut result = LogPage {
+        total: logs.len(),
+        page,
+        page_size,
+        logs: logs
+            .iter()
+            .skip(page * page_size)
+            .take(page_size)
+            .map(|log| events::decode_log(&index, &log.to_rpc()))
+            .collect(),
+        labels: BTreeMap::new(),
+    };
+    result.labels = AddressBook::load(root, Some(url)).await.labels_for(&result.logs);
+    Ok(result)
+}
+
+// The node's dev accounts with their labels and current balances
+#[tauri::command]
+pub async fn get_accounts(project_path: String, rpc_url: Option<String>) -> Result<Vec<AccountInfo>, AppError> {
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let book = AddressBook::load(Path::new(&project_path), Some(url)).await;
+    let addresses = rpc::call(url, "eth_accounts", json!([])).await?;
+
+    let mut accounts = Vec::new();
+    for address in addresses.as_array().into_iter().flatten().filter_map(Value::as_str) {
+        let balance = rpc::call(url, "eth_getBalance", json!([address, "latest"])).await?;
+        let nonce = rpc::call(url, "eth_getTransactionCount", json!([address, "latest"])).await?;
+        accounts.push(AccountInfo {
+            address: address.to_string(),
+            label: book.label(address).map(str::to_string),
+            balance: decode::quantity_to_decimal(balance.as_str().unwrap_or("0x0")),
+            nonce: rpc::parse_quantity(&nonce).unwrap_or(0),
+        });
+    }
+    Ok(accounts)
+}
@@ -0,0 +1,513 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::ipc::Invoke;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin};
+use tokio::sync::oneshot;
+
+use crate::error::AppError;
+
+const SIDECARS_FILE: &str = "extensions.json";
+const DEFAULT_CALL_TIMEOUT_MS: u64 = 30_000;
+
+// Integrations come in two shapes:
+// - built-in extensions are Rust modules compiled in behind a cargo feature. They
+//   implement `Extension`, own their `#[tauri::command]`s and are listed in `builtin()`
+// - sidecars are executables configured in extensions.json that speak JSON lines over
+//   stdio, so they can ship without rebuilding the app
+pub trait Extension: Send + Sync {
+    fn info(&self) -> ExtensionInfo;
+
+    fn setup(&self, _app: &AppHandle) -> Result<(), String> {
+        Ok(())
+    }
+
+    // Usually `tauri::generate_handler![...]` over the commands listed in `info`
+    fn handle(&self, invoke: Invoke) -> bool;
+}
+
+// Optional modules add themselves here behind their feature, e.g.
+// `#[cfg(feature = "slither")] Box::new(slither::Slither),`
+fn builtin() -> Vec<Box<dyn Extension>> {
+    Vec::new()
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtensionKind {
+    Builtin,
+    Sidecar,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtensionStatus {
+    Running,
+    Stopped,
+    Disabled,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtensionInfo {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    // Tauri commands for built-ins, protocol methods for sidecars
+    #[serde(default)]
+    pub commands: Vec<String>,
+    // Emitted as `extension:<name>:<event>`
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtensionEntry {
+    pub kind: ExtensionKind,
+    pub status: ExtensionStatus,
+    pub info: ExtensionInfo,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SidecarConfig {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    pub timeout_ms: Option<u64>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+// Event and command names end up in Tauri event names, which only allow these
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+pub fn event_name(extension: &str, event: &str) -> String {
+    format!("extension:{}:{}", extension, event)
+}
+
+struct Builtin {
+    extension: Box<dyn Extension>,
+    info: ExtensionInfo,
+    error: Option<String>,
+}
+
+static BUILTINS: OnceLock<Vec<Builtin>> = OnceLock::new();
+// Command name to index in BUILTINS
+static ROUTES: OnceLock<HashMap<String, usize>> = OnceLock::new();
+
+// Checked once at startup: every command has to be prefixed with its extension's name,
+// so extensions can't shadow the app's commands or each other's
+fn builtins() -> &'static Vec<Builtin> {
+    BUILTINS.get_or_init(|| {
+        builtin()
+            .into_iter()
+            .map(|extension| {
+                let info = extension.info();
+                let prefix = format!("{}_", info.name);
+                let error = if !valid_name(&info.name) {
+                    Some(format!("Invalid extension name \"{}\"", info.name))
+                } else {
+                    info.commands
+                        .iter()
+                        .find(|command| !command.starts_with(&prefix))
+                        .map(|command| format!("Command {} must start with {}", command, prefix))
+                };
+                Builtin { extension, info, error }
+            })
+            .collect()
+    })
+}
+
+fn routes() -> &'static HashMap<String, usize> {
+    ROUTES.get_or_init(|| {
+        let mut routes = HashMap::new();
+        for (index, builtin) in builtins().iter().enumerate() {
+            if builtin.error.is_some() {
+                continue;
+            }
+            for command in &builtin.info.commands {
+                routes.entry(command.clone()).or_insert(index);
+            }
+        }
+        routes
+    })
+}
+
+// Wraps the app's invoke handler so built-in extensions' commands reach their own
+// handler; the app's commands never need to know about them
+pub fn dispatch<F>(handler: F) -> impl Fn(Invoke) -> bool + Send + Sync + 'static
+where
+    F: Fn(Invoke) -> bool + Send + Sync + 'static,
+{
+    move |invoke| match routes().get(invoke.message.command()) {
+        Some(index) => builtins()[*index].extension.handle(invoke),
+        None => handler(invoke),
+    }
+}
+
+// A running sidecar. Requests are `{"id", "method", "params"}` lines on stdin; the
+// sidecar answers `{"id", "result"}` or `{"id", "error"}` and may send
+// `{"event", "payload"}` lines at any time. Anything else on stdout is logged
+struct Sidecar {
+    config: SidecarConfig,
+    info: Mutex<Option<ExtensionInfo>>,
+    stdin: tokio::sync::Mutex<ChildStdin>,
+    pending: Mutex<HashMap<u64, oneshot::Sender<Result<Value, String>>>>,
+    next_id: AtomicU64,
+    child: Mutex<Option<Child>>,
+}
+
+#[derive(Deserialize)]
+struct SidecarMessage {
+    id: Option<u64>,
+    result: Option<Value>,
+    error: Option<Value>,
+    event: Option<String>,
+    #[serde(default)]
+    payload: Value,
+}
+
+// Errors may be a plain string or a JSON-RPC style `{ "message": ... }` object
+fn error_message(error: Value) -> String {
+    match error {
+        Value::String(message) => message,
+        Value::Object(ref object) => {
+            object.get("message").and_then(Value::as_str).map(String::from).unwrap_or_else(|| error.to_string())
+        }
+        other => other.to_string(),
+    }
+}
+
+impl Sidecar {
+    fn running(&self) -> bool {
+        self.child.lock().unwrap().is_some()
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id, sender);
+
+        let request = serde_json::json!({ "id": id, "method": method, "params": params });
+        let written = {
+            let mut stdin = self.stdin.lock().await;
+            match stdin.write_all(format!("{}\n", request).as_bytes()).await {
+                Ok(()) => stdin.flush().await,
+                Err(e) => Err(e),
+            }
+        };
+        if let Err(e) = written {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(format!("Failed to send {} to extension {}: {}", method, self.config.name, e));
+        }
+
+        let timeout = Duration::from_millis(self.config.timeout_ms.unwrap_or(DEFAULT_CALL_TIMEOUT_MS));
+        match tokio::time::timeout(timeout, receiver).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(_)) => Err(format!("Extension {} exited before answering {}", self.config.name, method)),
+            Err(_) => {
+                self.pending.lock().unwrap().remove(&id);
+                Err(format!("Extension {} did not answer {} within {}ms", self.config.name, method, timeout.as_millis()))
+            }
+        }
+    }
+
+    fn receive(&self, app: &AppHandle, line: &str) {
+        let Ok(message) = serde_json::from_str::<SidecarMessage>(line) else {
+            tracing::debug!(extension = %self.config.name, line, "extension output");
+            return;
+        };
+        if let Some(event) = message.event {
+            if valid_name(&event) {
+                let _ = app.emit(&event_name(&self.config.name, &event), message.payload);
+            }
+            return;
+        }
+        let Some(sender) = message.id.and_then(|id| self.pending.lock().unwrap().remove(&id)) else {
+            return;
+        };
+        let response = match message.error {
+            Some(error) => Err(error_message(error)),
+            None => Ok(message.result.unwrap_or(Value::Null)),
+        };
+        let _ = sender.send(response);
+    }
+
+    fn stop(&self) {
+        // Dropping the child kills it; pending calls fail as their senders are dropped
+        self.child.lock().unwrap().take();
+        self.pending.lock().unwrap().clear();
+    }
+}
+
+#[derive(Default)]
+struct Sidecars {
+    configs: Vec<SidecarConfig>,
+    running: HashMap<String, Arc<Sidecar>>,
+    errors: HashMap<String, String>,
+}
+
+static SIDECARS_PATH: OnceLock<PathBuf> = OnceLock::new();
+static SIDECARS: OnceLock<Mutex<Sidecars>> = OnceLock::new();
+
+fn sidecars() -> &'static Mutex<Sidecars> {
+    SIDECARS.get_or_init(|| Mutex::new(Sidecars::default()))
+}
+
+fn sidecars_path() -> Result<&'static PathBuf, String> {
+    SIDECARS_PATH.get().ok_or_else(|| "Extensions are not initialized".to_string())
+}
+
+fn load_configs() -> Result<Vec<SidecarConfig>, String> {
+    let path = sidecars_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", SIDECARS_FILE, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", SIDECARS_FILE, e))
+}
+
+fn validate(configs: &[SidecarConfig]) -> Result<(), String> {
+    let builtin_names: Vec<&str> = builtins().iter().map(|b| b.info.name.as_str()).collect();
+    for (i, config) in configs.iter().enumerate() {
+        if !valid_name(&config.name) {
+            return Err(format!("Extension names may only contain letters, digits, - and _, got \"{}\"", config.name));
+        }
+        if builtin_names.contains(&config.name.as_str()) || configs[..i].iter().any(|c| c.name == config.name) {
+            return Err(format!("Extension {} is defined more than once", config.name));
+        }
+        if config.command.trim().is_empty() {
+            return Err(format!("Extension {} has no command", config.name));
+        }
+    }
+    Ok(())
+}
+
+async fn start(app: &AppHandle, config: SidecarConfig) -> Result<Arc<Sidecar>, String> {
+    let mut cmd = tokio::process::Command::new(&config.command);
+    cmd.args(&config.args)
+        .envs(&config.env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to start extension {}: {}", config.name, e))?;
+    let stdin = child.stdin.take().ok_or("Failed to open extension stdin")?;
+    let stdout = child.stdout.take().ok_or("Failed to open extension stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to open extension stderr")?;
+
+    let sidecar = Arc::new(Sidecar {
+        config,
+        info: Mutex::new(None),
+        stdin: tokio::sync::Mutex::new(stdin),
+        pending: Mutex::new(HashMap::new()),
+        next_id: AtomicU64::new(1),
+        child: Mutex::new(Some(child)),
+    });
+
+    let reader = sidecar.clone();
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            reader.receive(&handle, &line);
+        }
+        reader.stop();
+        let _ = handle.emit("extension-stopped", &reader.config.name);
+    });
+    let name = sidecar.config.name.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            tracing::info!(extension = %name, "{}", line);
+        }
+    });
+
+    // The handshake tells the sidecar who is calling and returns what it offers
+    let params = serde_json::json!({ "app_version": app.package_info().version.to_string() });
+    let described = sidecar.call("describe", params).await.and_then(|value| {
+        serde_json::from_value::<ExtensionInfo>(value)
+            .map_err(|e| format!("Failed to parse the description of extension {}: {}", sidecar.config.name, e))
+    });
+    match described {
+        Ok(mut info) => {
+            info.name = sidecar.config.name.clone();
+            *sidecar.info.lock().unwrap() = Some(info);
+            Ok(sidecar)
+        }
+        Err(e) => {
+            sidecar.stop();
+            Err(e)
+        }
+    }
+}
+
+async fn start_all(app: &AppHandle) {
+    let configs: Vec<SidecarConfig> = {
+        let state = sidecars().lock().unwrap();
+        state.configs.iter().filter(|c| c.enabled && !state.running.contains_key(&c.name)).cloned().collect()
+    };
+    for config in configs {
+        let name = config.name.clone();
+        let started = start(app, config).await;
+        let mut state = sidecars().lock().unwrap();
+        match started {
+            Ok(sidecar) => {
+                state.errors.remove(&name);
+                state.running.insert(name, sidecar);
+            }
+            Err(e) => {
+                tracing::warn!(extension = %name, error = %e, "extension failed to start");
+                state.errors.insert(name, e);
+            }
+        }
+    }
+}
+
+fn stop_all() {
+    let running = std::mem::take(&mut sidecars().lock().unwrap().running);
+    for sidecar in running.values() {
+        sidecar.stop();
+    }
+}
+
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    for builtin in builtins() {
+        let setup = match &builtin.error {
+            Some(e) => Err(e.clone()),
+            None => builtin.extension.setup(app),
+        };
+        if let Err(e) = setup {
+            tracing::warn!(extension = %builtin.info.name, error = %e, "extension disabled");
+        }
+    }
+
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?;
+    let _ = SIDECARS_PATH.set(dir.join(SIDECARS_FILE));
+    // A broken extensions file is reported when extensions are listed, not at startup
+    match load_configs().and_then(|configs| validate(&configs).map(|_| configs)) {
+        Ok(configs) => sidecars().lock().unwrap().configs = configs,
+        Err(e) => tracing::warn!(error = %e, "extensions not loaded"),
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move { start_all(&app).await });
+    Ok(())
+}
+
+fn sidecar(name: &str) -> Result<Arc<Sidecar>, String> {
+    let state = sidecars().lock().unwrap();
+    if let Some(sidecar) = state.running.get(name).filter(|s| s.running()) {
+        return Ok(sidecar.clone());
+    }
+    match state.configs.iter().find(|c| c.name == name) {
+        Some(config) if !config.enabled => Err(format!("Extension {} is disabled", name)),
+        Some(_) => Err(format!("Extension {} is not running", name)),
+        None => Err(format!("No extension named {}", name)),
+    }
+}
+
+#[tauri::command]
+pub async fn list_extensions() -> Result<Vec<ExtensionEntry>, AppError> {
+    let mut entries: Vec<ExtensionEntry> = builtins()
+        .iter()
+        .map(|builtin| ExtensionEntry {
+            kind: ExtensionKind::Builtin,
+            status: if builtin.error.is_some() { ExtensionStatus::Failed } else { ExtensionStatus::Running },
+            info: builtin.info.clone(),
+            error: builtin.error.clone(),
+        })
+        .collect();
+
+    let configs = load_configs()?;
+    let state = sidecars().lock().unwrap();
+    for config in configs {
+        let running = state.running.get(&config.name).filter(|s| s.running());
+        let error = state.errors.get(&config.name).cloned();
+        let status = match (config.enabled, running, &error) {
+            (false, _, _) => ExtensionStatus::Disabled,
+            (true, Some(_), _) => ExtensionStatus::Running,
+            (true, None, Some(_)) => ExtensionStatus::Failed,
+            (true, None, None) => ExtensionStatus::Stopped,
+        };
+        let info = running.and_then(|s| s.info.lock().unwrap().clone()).unwrap_or_else(|| ExtensionInfo {
+            name: config.name.clone(),
+            description: String::new(),
+            commands: Vec::new(),
+            events: Vec::new(),
+        });
+        entries.push(ExtensionEntry { kind: ExtensionKind::Sidecar, status, info, error });
+    }
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn call_extension(name: String, method: String, params: Option<Value>) -> Result<Value, AppError> {
+    let sidecar = sidecar(&name)?;
+    Ok(sidecar.call(&method, params.unwrap_or(Value::Null)).await?)
+}
+
+#[tauri::command]
+pub async fn get_sidecar_configs() -> Result<Vec<SidecarConfig>, AppError> {
+    Ok(load_configs()?)
+}
+
+// Saving restarts every sidecar so changed commands and environments take effect
+#[tauri::command]
+pub async fn save_sidecar_configs(app: AppHandle, configs: Vec<SidecarConfig>) -> Result<Vec<ExtensionEntry>, AppError> {
+    validate(&configs)?;
+    let path = sidecars_path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(&configs).map_err(|e| format!("Failed to serialize extensions: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", SIDECARS_FILE, e))?;
+
+    stop_all();
+    {
+        let mut state = sidecars().lock().unwrap();
+        state.configs = configs;
+        state.errors.clear();
+    }
+    start_all(&app).await;
+    list_extensions().await
+}
+
+#[tauri::command]
+pub async fn restart_extension(app: AppHandle, name: String) -> Result<Vec<ExtensionEntry>, AppError> {
+    let removed = {
+        let mut state = sidecars().lock().unwrap();
+        if !state.configs.iter().any(|c| c.name == name) {
+            return Err(format!("No extension named {}", name).into());
+        }
+        state.errors.remove(&name);
+        state.running.remove(&name)
+    };
+    if let Some(sidecar) = removed {
+        sidecar.stop();
+    }
+    start_all(&app).await;
+    list_extensions().await
+}
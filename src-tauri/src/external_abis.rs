@@ -0,0 +1,257 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::abi::AbiItem;
+use crate::address_book::is_address;
+use crate::decode::{self, AbiIndex, DecodedParam};
+use crate::error::AppError;
+use crate::etherscan;
+use crate::events::{self, DecodedLog};
+use crate::rpc;
+use crate::store;
+use crate::transactions::{self, TxType};
+
+const EXTERNAL_ABIS_FILE: &str = "external-abis.json";
+
+// ABIs for contracts the project talks to but doesn't compile (routers, tokens on a fork)
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExternalAbi {
+    pub name: String,
+    pub address: Option<String>,
+    // Chain the ABI was fetched from; a fork of it serves the same address
+    #[serde(default)]
+    pub chain_id: Option<u64>,
+    pub imported_at: u64,
+    pub abi: Vec<AbiItem>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ContractCall {
+    // A name, or a full signature when the name is overloaded
+    pub function: String,
+    #[serde(default)]
+    pub args: Vec<Value>,
+    // Defaults to the address the ABI was imported with
+    pub address: Option<String>,
+    pub from: Option<String>,
+    // Wei as a decimal string
+    pub value: Option<String>,
+    // Runs a state-changing function with eth_call instead of sending it
+    #[serde(default)]
+    pub dry_run: bool,
+    pub tx_type: Option<TxType>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ContractCallResult {
+    pub function: String,
+    // Decoded return values of a read; empty for transactions
+    pub outputs: Vec<DecodedParam>,
+    pub transaction_hash: Option<String>,
+    pub success: bool,
+    pub gas_used: Option<u64>,
+    pub logs: Vec<DecodedLog>,
+}
+
+pub fn load(project_path: &Path) -> Result<Vec<ExternalAbi>, String> {
+    store::load_json(project_path, EXTERNAL_ABIS_FILE)
+}
+
+// Replaces any ABI with the same name
+pub fn save(project_path: &Path, imported: ExternalAbi) -> Result<(), String> {
+    let mut abis = load(project_path)?;
+    abis.retain(|a| a.name != imported.name);
+    abis.push(imported);
+    store::save_json(project_path, EXTERNAL_ABIS_FILE, &abis)
+}
+
+// Accepts a bare ABI array or anything with an `abi` field (Hardhat/Foundry artifacts, Etherscan exports)
+pub fn parse_abi(abi: Value) -> Result<Vec<AbiItem>, String> {
+    let abi = match abi {
+        Value::String(text) => serde_json::from_str(&text).map_err(|e| format!("Failed to parse ABI JSON: {}", e))?,
+        other => other,
+    };
+    let items = match abi {
+        Value::Object(mut artifact) => artifact.remove("abi").ok_or("No `abi` field found in the imported JSON")?,
+        other => other,
+    };
+    serde_json::from_value(items).map_err(|e| format!("Invalid ABI: {}", e))
+}
+
+#[tauri::command]
+pub async fn import_external_abi(
+    project_path: String,
+    name: String,
+    address: Option<String>,
+    chain_id: Option<u64>,
+    abi: Value,
+) -> Result<ExternalAbi, AppError> {
+    let root = Path::new(&project_path);
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("ABI name cannot be empty".to_string().into());
+    }
+    let address = address.map(|a| a.trim().to_lowercase()).filter(|a| !a.is_empty());
+    if let Some(address) = address.as_deref().filter(|a| !is_address(a)) {
+        return Err(format!("Invalid address: {}", address).into());
+    }
+
+    let imported = ExternalAbi { name, address, chain_id, imported_at: store::timestamp_millis(), abi: parse_abi(abi)? };
+    save(root, imported.clone())?;
+    Ok(imported)
+}
+
+#[tauri::command]
+pub async fn list_external_abis(project_path: String) -> Result<Vec<ExternalAbi>, AppError> {
+    Ok(load(Path::new(&project_path))?)
+}
+
+#[tauri::command]
+pub async fn remove_external_abi(project_path: String, name: String) -> Result<(), AppError> {
+    let root = Path::new(&project_path);
+    let mut abis = load(root)?;
+    abis.retain(|a| a.name != name);
+    Ok(store::save_json(root, EXTERNAL_ABIS_FILE, &abis)?)
+}
+
+// Imports the verified ABI of a deployed contract. For proxies the implementation's
+// functions are merged in, so the contract can be called through the proxy address
+#[tauri::command]
+pub async fn fetch_external_abi(
+    project_path: String,
+    address: String,
+    chain_id: Option<u64>,
+    name: Option<String>,
+) -> Result<ExternalAbi, AppError> {
+    let address = address.trim().to_lowercase();
+    if !is_address(&address) {
+        return Err(format!("Invalid address: {}", address).into());
+    }
+    let chain_id = chain_id.unwrap_or(1);
+    let contract = etherscan::source_code(&address, chain_id).await?;
+    let field = |contract: &Value, key: &str| contract.get(key).and_then(Value::as_str).unwrap_or_default().to_string();
+    if field(&contract, "SourceCode").is_empty() {
+        return Err(format!("{} is not verified on chain {}", address, chain_id).into());
+    }
+    let mut abi = parse_abi(Value::String(field(&contract, "ABI")))?;
+    let mut contract_name = field(&contract, "ContractName");
+
+    let implementation = field(&contract, "Implementation");
+    if field(&contract, "Proxy") == "1" && is_address(&implementation) {
+        let target = etherscan::source_code(&implementation, chain_id).await?;
+        let target_abi = parse_abi(Value::String(field(&target, "ABI")))?;
+        let known: Vec<(String, String)> = abi.iter().map(|item| (item.kind.clone(), item.signature())).collect();
+        abi.extend(target_abi.into_iter().filter(|item| {
+            item.kind != "constructor" && !known.contains(&(item.kind.clone(), item.signature()))
+        }));
+        contract_name = field(&target, "ContractName");
+    }
+
+    let imported = ExternalAbi {
+        name: name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()).unwrap_or(contract_name),
+        address: Some(address),
+        chain_id: Some(chain_id),
+        imported_at: store::timestamp_millis(),
+        abi,
+    };
+    save(Path::new(&project_path), imported.clone())?;
+    Ok(imported)
+}
+
+fn find_function<'a>(external: &'a ExternalAbi, function: &str) -> Result<&'a AbiItem, String> {
+    let matches: Vec<&AbiItem> = external
+        .abi
+        .iter()
+        .filter(|item| item.is_function() && (item.name == function || item.signature() == function))
+        .collect();
+    match matches.as_slice() {
+        [item] => Ok(item),
+        [] => Err(format!("{} has no function {}", external.name, function)),
+        _ => Err(format!(
+            "{} is overloaded in {}; use one of: {}",
+            function,
+            external.name,
+            matches.iter().map(|item| item.signature()).collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+// Reads with eth_call; state-changing functions are sent as a transaction unless it's a
+// dry run. On a fork, `from` can be any address: it's impersonated for the send
+#[tauri::command]
+pub async fn call_external_contract(
+    project_path: String,
+    name: String,
+    call: ContractCall,
+    rpc_url: Option<String>,
+) -> Result<ContractCallResult, AppError> {
+    let root = Path::new(&project_path);
+    let external = load(root)?
+        .into_iter()
+        .find(|a| a.name == name)
+        .ok_or_else(|| format!("No imported ABI named {}", name))?;
+    let to = call
+        .address
+        .or_else(|| external.address.clone())
+        .ok_or_else(|| format!("{} has no address; pass one to call it", name))?;
+    let item = find_function(&external, &call.function)?;
+    let signature = item.signature();
+    let data = format!(
+        "{}{}",
+        decode::selector(&signature),
+        decode::to_hex(&decode::encode_params(&item.inputs, &call.args)?).trim_start_matches("0x")
+    );
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let value = decode::decimal_to_quantity(call.value.as_deref().unwrap_or("0"))?;
+    let read_only = matches!(item.state_mutability.as_deref(), Some("view") | Some("pure"));
+
+    if read_only || call.dry_run {
+        let mut tx = json!({ "to": to, "data": data, "value": value });
+        if let Some(from) = &call.from {
+            tx["from"] = json!(from);
+        }
+        let result = rpc::call(url, "eth_call", json!([tx, "latest"])).await?;
+        let bytes = decode::from_hex(result.as_str().unwrap_or("0x")).unwrap_or_default();
+        return Ok(ContractCallResult {
+            function: signature,
+            outputs: decode::decode_params(&item.outputs, &bytes).unwrap_or_default(),
+            transaction_hash: None,
+            success: true,
+            gas_used: None,
+            logs: Vec::new(),
+        });
+    }
+
+    let from = match call.from {
+        Some(from) => from,
+        None => rpc::call(url, "eth_accounts", json!([]))
+            .await?
+            .get(0)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or("The node has no unlocked accounts; pass `from`")?,
+    };
+    let mut tx = json!({ "from": from, "to": to, "data": data, "value": value });
+    transactions::apply_tx_type(url, &mut tx, call.tx_type).await?;
+    let _ = rpc::call(url, "hardhat_impersonateAccount", json!([from])).await;
+    let sent = rpc::call(url, "eth_sendTransaction", json!([tx])).await;
+    let _ = rpc::call(url, "hardhat_stopImpersonatingAccount", json!([from])).await;
+    let hash = sent?.as_str().map(str::to_string).ok_or("The node returned no transaction hash")?;
+
+    let receipt = rpc::call(url, "eth_getTransactionReceipt", json!([hash])).await.unwrap_or(Value::Null);
+    let logs = receipt
+        .get("logs")
+        .and_then(Value::as_array)
+        .map(|logs| events::decode_logs(&AbiIndex::load(root), logs))
+        .unwrap_or_default();
+    Ok(ContractCallResult {
+        function: signature,
+        outputs: Vec::new(),
+        transaction_hash: Some(hash),
+        success: receipt.get("status").and_then(rpc::parse_quantity) == Some(1),
+        gas_used: receipt.get("gasUsed").and_then(rpc::parse_quantity),
+        logs,
+    })
+}
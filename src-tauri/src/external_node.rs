@@ -0,0 +1,156 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::chain_index;
+use crate::error::AppError;
+use crate::rpc;
+use crate::store;
+
+// Dev-node methods the GUI relies on; hardhat_* ones are also tried as anvil_*
+const PROBED_METHODS: [&str; 13] = [
+    "hardhat_metadata",
+    "hardhat_setBalance",
+    "hardhat_setCode",
+    "hardhat_setNonce",
+    "hardhat_setStorageAt",
+    "hardhat_mine",
+    "hardhat_impersonateAccount",
+    "hardhat_stopImpersonatingAccount",
+    "evm_snapshot",
+    "evm_revert",
+    "debug_traceTransaction",
+    "debug_traceCall",
+    "debug_traceBlockByNumber",
+];
+
+// Feature, and the methods it can't work without
+const FEATURES: [(&str, &[&str]); 6] = [
+    ("Save and restore node state", &[
+        "hardhat_setBalance",
+        "hardhat_setCode",
+        "hardhat_setNonce",
+        "hardhat_setStorageAt",
+        "hardhat_mine",
+    ]),
+    ("Local faucet top-ups", &["hardhat_setBalance"]),
+    ("Transaction simulation", &[
+        "evm_snapshot",
+        "evm_revert",
+        "hardhat_impersonateAccount",
+        "hardhat_stopImpersonatingAccount",
+    ]),
+    ("Call traces", &["debug_traceTransaction"]),
+    ("Fork diff", &["debug_traceTransaction", "hardhat_metadata"]),
+    ("Block explorer traces", &["debug_traceBlockByNumber"]),
+];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeFlavor {
+    Hardhat,
+    Anvil,
+    Ganache,
+    Geth,
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NodeCapabilities {
+    pub rpc_url: String,
+    pub client_version: Option<String>,
+    pub flavor: NodeFlavor,
+    pub chain_id: u64,
+    pub block_number: Option<u64>,
+    pub methods: BTreeMap<String, bool>,
+    // Features that are turned off against this node
+    pub unavailable_features: Vec<String>,
+    pub checked_at: u64,
+}
+
+static EXTERNAL_NODE: Mutex<Option<NodeCapabilities>> = Mutex::new(None);
+
+fn flavor(client_version: &str) -> NodeFlavor {
+    let client = client_version.to_lowercase();
+    if client.starts_with("hardhatnetwork") {
+        NodeFlavor::Hardhat
+    } else if client.starts_with("anvil") {
+        NodeFlavor::Anvil
+    } else if client.starts_with("ganache") || client.starts_with("ethereumjs testrpc") {
+        NodeFlavor::Ganache
+    } else if client.starts_with("geth") {
+        NodeFlavor::Geth
+    } else {
+        NodeFlavor::Unknown
+    }
+}
+
+pub async fn detect(url: &str) -> Result<NodeCapabilities, AppError> {
+    let chain_id = rpc::chain_id(url).await?;
+    let client_version = rpc::call(url, "web3_clientVersion", json!([]))
+        .await
+        .ok()
+        .and_then(|version| version.as_str().map(str::to_string));
+
+    let mut methods = BTreeMap::new();
+    for method in PROBED_METHODS {
+        methods.insert(method.to_string(), rpc::supports(url, method).await?);
+    }
+    let unavailable_features = FEATURES
+        .iter()
+        .filter(|(_, required)| required.iter().any(|method| !methods[*method]))
+        .map(|(feature, _)| feature.to_string())
+        .collect();
+
+    Ok(NodeCapabilities {
+        rpc_url: url.to_string(),
+        flavor: client_version.as_deref().map_or(NodeFlavor::Unknown, flavor),
+        client_version,
+        chain_id,
+        block_number: chain_index::block_number(url).await.ok(),
+        methods,
+        unavailable_features,
+        checked_at: store::timestamp_millis(),
+    })
+}
+
+// Makes the endpoint the default for every command that takes an optional RPC URL.
+// Methods it lacks fail fast with a clear error instead of an opaque one from the node
+#[tauri::command]
+pub async fn connect_external_node(rpc_url: String) -> Result<NodeCapabilities, AppError> {
+    let url = rpc_url.trim_end_matches('/').to_string();
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(format!("Invalid RPC URL: {}", rpc_url).into());
+    }
+    let capabilities = detect(&url).await?;
+    let unsupported: BTreeSet<String> = capabilities
+        .methods
+        .iter()
+        .filter(|(_, supported)| !**supported)
+        .map(|(method, _)| method.clone())
+        .collect();
+    rpc::set_unsupported(&url, unsupported);
+    rpc::set_external_url(Some(url));
+    *EXTERNAL_NODE.lock().unwrap() = Some(capabilities.clone());
+    Ok(capabilities)
+}
+
+// Back to the managed node on 127.0.0.1:8545
+#[tauri::command]
+pub async fn disconnect_external_node() -> Result<bool, AppError> {
+    rpc::set_external_url(None);
+    Ok(EXTERNAL_NODE.lock().unwrap().take().is_some())
+}
+
+#[tauri::command]
+pub async fn get_external_node() -> Result<Option<NodeCapabilities>, AppError> {
+    Ok(EXTERNAL_NODE.lock().unwrap().clone())
+}
+
+// Probes any endpoint without switching to it
+#[tauri::command]
+pub async fn get_node_capabilities(rpc_url: Option<String>) -> Result<NodeCapabilities, AppError> {
+    detect(&rpc_url.unwrap_or_else(rpc::default_url)).await
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::address_book;
+use crate::error::AppError;
+use crate::networks;
+use crate::provider_keys;
+use crate::rpc;
+
+// Chain ids of local development nodes, which can simply mint balance
+const LOCAL_CHAIN_IDS: [u64; 2] = [31337, 1337];
+// 100 ETH in wei
+const LOCAL_FUNDING_WEI: u128 = 100_000_000_000_000_000_000;
+
+// chain id, faucet, URL, what it asks of the user
+const FAUCETS: [(u64, &str, &str, Option<&str>); 11] = [
+    (11155111, "Google Cloud Web3 Faucet", "https://cloud.google.com/application/web3/faucet/ethereum/sepolia", Some("Google account")),
+    (11155111, "Alchemy Sepolia Faucet", "https://www.alchemy.com/faucets/ethereum-sepolia", Some("Alchemy account and mainnet balance")),
+    (11155111, "Sepolia PoW Faucet", "https://sepolia-faucet.pk910.de/", Some("Browser mining")),
+    (17000, "Holesky PoW Faucet", "https://holesky-faucet.pk910.de/", Some("Browser mining")),
+    (17000, "Google Cloud Web3 Faucet", "https://cloud.google.com/application/web3/faucet/ethereum/holesky", Some("Google account")),
+    (84532, "Coinbase Developer Faucet", "https://portal.cdp.coinbase.com/products/faucet", Some("Coinbase account")),
+    (84532, "Alchemy Base Sepolia Faucet", "https://www.alchemy.com/faucets/base-sepolia", Some("Alchemy account")),
+    (421614, "Alchemy Arbitrum Sepolia Faucet", "https://www.alchemy.com/faucets/arbitrum-sepolia", Some("Alchemy account")),
+    (11155420, "Superchain Faucet", "https://console.optimism.io/faucet", Some("GitHub or Coinbase verification")),
+    (80002, "Polygon Faucet", "https://faucet.polygon.technology/", Some("Discord or GitHub login")),
+    (80002, "Alchemy Amoy Faucet", "https://www.alchemy.com/faucets/polygon-amoy", Some("Alchemy account")),
+];
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FaucetLink {
+    pub name: String,
+    pub url: String,
+    pub requirement: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FaucetResponse {
+    pub network: String,
+    pub chain_id: u64,
+    pub address: String,
+    // Set when the node itself credited the address (local chains only)
+    pub funded_wei: Option<String>,
+    // Current balance as a hex quantity, so the UI can poll until the drip arrives
+    pub balance: Option<String>,
+    // Public faucets have no open API; the user finishes the request in the browser
+    pub faucets: Vec<FaucetLink>,
+}
+
+fn faucets_for(chain_id: u64) -> Vec<FaucetLink> {
+    FAUCETS
+        .iter()
+        .filter(|(id, _, _, _)| *id == chain_id)
+        .map(|(_, name, url, requirement)| FaucetLink {
+            name: name.to_string(),
+            url: url.to_string(),
+            requirement: requirement.map(str::to_string),
+        })
+        .collect()
+}
+
+// Local nodes are topped up directly; public testnets get the faucets that serve them
+#[tauri::command]
+pub async fn request_testnet_funds(network: String, address: String) -> Result<FaucetResponse, AppError> {
+    if !address_book::is_address(&address) {
+        return Err(format!("Invalid address: {}", address).into());
+    }
+    let profile = networks::find_profile(&network).ok_or_else(|| format!("Unknown network profile: {}", network))?;
+    let url = provider_keys::expand(&profile.rpc_url);
+
+    let mut funded_wei = None;
+    if LOCAL_CHAIN_IDS.contains(&profile.chain_id) {
+        let current = rpc::call(&url, "eth_getBalance", json!([address, "latest"])).await?;
+        let current = current
+            .as_str()
+            .and_then(|hex| u128::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+            .unwrap_or(0);
+        let target = current.saturating_add(LOCAL_FUNDING_WEI);
+        rpc::call(&url, "hardhat_setBalance", json!([address, format!("0x{:x}", target)])).await?;
+        funded_wei = Some(LOCAL_FUNDING_WEI.to_string());
+    }
+
+    let faucets = faucets_for(profile.chain_id);
+    if funded_wei.is_none() && faucets.is_empty() {
+        return Err(format!("No faucet is known for chain {} ({})", profile.chain_id, network).into());
+    }
+
+    let balance = rpc::call(&url, "eth_getBalance", json!([address, "latest"]))
+        .await
+        .ok()
+        .and_then(|b| b.as_str().map(str::to_string));
+    Ok(FaucetResponse {
+        network,
+        chain_id: profile.chain_id,
+        address,
+        funded_wei,
+        balance,
+        faucets,
+    })
+}
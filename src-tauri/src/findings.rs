@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::health::Severity;
+use crate::store;
+
+// Latest report per tool, for the report bundle
+const RESULTS_FILE: &str = "analysis-results.json";
+
+// Common shape for everything the analysis tools report, so the frontend and the
+// report bundle don't need a parser per tool
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SourceLocation {
+    pub file: String,
+    pub line: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Finding {
+    pub tool: String,
+    // Tool-specific rule or detector id
+    pub rule: String,
+    pub swc_id: Option<String>,
+    pub title: String,
+    pub severity: Severity,
+    pub description: String,
+    pub contract: Option<String>,
+    pub function: Option<String>,
+    pub locations: Vec<SourceLocation>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AnalysisReport {
+    pub tool: String,
+    #[serde(default)]
+    pub generated_at: u64,
+    pub error_count: usize,
+    pub warning_count: usize,
+    pub info_count: usize,
+    pub findings: Vec<Finding>,
+}
+
+impl AnalysisReport {
+    pub fn new(tool: &str, mut findings: Vec<Finding>) -> Self {
+        findings.sort_by(|a, b| b.severity.cmp(&a.severity).then_with(|| a.title.cmp(&b.title)));
+        let count = |severity: Severity| findings.iter().filter(|f| f.severity == severity).count();
+        AnalysisReport {
+            tool: tool.to_string(),
+            generated_at: store::timestamp_millis(),
+            error_count: count(Severity::Error),
+            warning_count: count(Severity::Warning),
+            info_count: count(Severity::Info),
+            findings,
+        }
+    }
+}
+
+pub fn latest_reports(project_path: &Path) -> Result<BTreeMap<String, AnalysisReport>, String> {
+    store::load_json(project_path, RESULTS_FILE)
+}
+
+// Keeps the report as the tool's latest result and hands it back to the caller
+pub fn record(project_path: &Path, report: AnalysisReport) -> Result<AnalysisReport, String> {
+    let mut reports = latest_reports(project_path)?;
+    reports.insert(report.tool.clone(), report.clone());
+    store::save_json(project_path, RESULTS_FILE, &reports)?;
+    Ok(report)
+}
@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::chain_index;
+use crate::decode;
+use crate::error::AppError;
+use crate::exclusion::{self, JobGroup};
+use crate::networks;
+use crate::node_state::{self, NodeStateInfo};
+use crate::processes;
+use crate::resolver;
+use crate::rpc;
+use crate::store;
+
+const FIXTURES_FILE: &str = "fixtures.json";
+
+// A named starting point for manual testing ("10 users funded, token deployed, pool
+// seeded"): a setup script whose effect on the node is captured as a node state once
+// and restored from then on, into a running node or a fresh one
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Fixture {
+    pub name: String,
+    pub description: Option<String>,
+    // Relative to the project root, run with `hardhat run`
+    pub script: String,
+    // Defaults to localhost
+    pub network: Option<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub created_at: u64,
+    #[serde(default)]
+    pub updated_at: u64,
+    // Set by build_fixture
+    #[serde(default)]
+    pub build: Option<FixtureBuild>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FixtureBuild {
+    // The node state holding the result; start_hardhat_network takes it as `state`
+    pub state: String,
+    pub built_at: u64,
+    pub script_hash: String,
+    // The script ran on a node with no earlier local transactions, so the state holds
+    // nothing but the fixture
+    pub clean: bool,
+    pub block_number: u64,
+    pub accounts: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FixtureStatus {
+    #[serde(flatten)]
+    pub fixture: Fixture,
+    // Built, but the script has changed or the state file is gone since
+    pub stale: bool,
+}
+
+fn load_fixtures(project_path: &Path) -> Result<Vec<Fixture>, String> {
+    store::load_json(project_path, FIXTURES_FILE)
+}
+
+fn find_fixture(project_path: &Path, name: &str) -> Result<Fixture, String> {
+    load_fixtures(project_path)?
+        .into_iter()
+        .find(|fixture| fixture.name == name)
+        .ok_or_else(|| format!("Fixture \"{}\" not found", name))
+}
+
+fn store_fixture(project_path: &Path, fixture: &Fixture) -> Result<(), String> {
+    let mut fixtures = load_fixtures(project_path)?;
+    match fixtures.iter_mut().find(|f| f.name == fixture.name) {
+        Some(existing) => *existing = fixture.clone(),
+        None => fixtures.push(fixture.clone()),
+    }
+    store::save_json(project_path, FIXTURES_FILE, &fixtures)
+}
+
+// Fixture names become part of a node state name, so they follow the same rules
+fn validate_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("Invalid fixture name {:?}: use letters, digits, '-' and '_'", name));
+    }
+    Ok(())
+}
+
+fn state_name(name: &str) -> String {
+    format!("fixture-{}", name)
+}
+
+fn script_hash(project_path: &Path, script: &str) -> Result<String, String> {
+    let content = fs::read(project_path.join(script)).map_err(|e| format!("Failed to read {}: {}", script, e))?;
+    Ok(decode::to_hex(&decode::keccak256(&content)))
+}
+
+fn status(project_path: &Path, fixture: Fixture) -> FixtureStatus {
+    let stale = fixture.build.as_ref().is_some_and(|build| {
+        !node_state::state_exists(project_path, &build.state)
+            || script_hash(project_path, &fixture.script).ok().as_deref() != Some(build.script_hash.as_str())
+    });
+    FixtureStatus { fixture, stale }
+}
+
+#[tauri::command]
+pub async fn list_fixtures(project_path: String) -> Result<Vec<FixtureStatus>, AppError> {
+    let root = Path::new(&project_path);
+    Ok(load_fixtures(root)?.into_iter().map(|fixture| status(root, fixture)).collect())
+}
+
+// Saving under an existing name replaces the definition but keeps its last build
+#[tauri::command]
+pub async fn save_fixture(project_path: String, fixture: Fixture) -> Result<FixtureStatus, AppError> {
+    let root = Path::new(&project_path);
+    validate_name(&fixture.name)?;
+    if !root.join(&fixture.script).is_file() {
+        return Err(format!("Setup script {} does not exist", fixture.script).into());
+    }
+    processes::validate_env(&fixture.env)?;
+
+    let now = store::timestamp_millis();
+    let mut fixture = Fixture { updated_at: now, created_at: now, build: None, ..fixture };
+    if let Ok(existing) = find_fixture(root, &fixture.name) {
+        fixture.created_at = existing.created_at;
+        fixture.build = existing.build;
+    }
+    store_fixture(root, &fixture)?;
+    Ok(status(root, fixture))
+}
+
+#[tauri::command]
+pub async fn delete_fixture(project_path: String, name: String) -> Result<(), AppError> {
+    let root = Path::new(&project_path);
+    let mut fixtures = load_fixtures(root)?;
+    let Some(position) = fixtures.iter().position(|fixture| fixture.name == name) else {
+        return Err(format!("Fixture \"{}\" not found", name).into());
+    };
+    let fixture = fixtures.remove(position);
+    if let Some(build) = fixture.build.filter(|build| node_state::state_exists(root, &build.state)) {
+        node_state::delete_node_state(project_path.clone(), build.state).await?;
+    }
+    Ok(store::save_json(root, FIXTURES_FILE, &fixtures)?)
+}
+
+// Runs the setup script against the node and captures what it left behind. Build on a
+// freshly started node so the fixture holds nothing else
+#[tauri::command]
+pub async fn build_fixture(
+    app: AppHandle,
+    project_path: String,
+    name: String,
+    rpc_url: Option<String>,
+    execution_id: Option<String>,
+) -> Result<FixtureStatus, AppError> {
+    let root = Path::new(&project_path);
+    let mut fixture = find_fixture(root, &name)?;
+    let script_hash = script_hash(root, &fixture.script)?;
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let (base_block, _) = node_state::chain_base(url).await?;
+    let clean = chain_index::block_number(url).await? == base_block;
+
+    let mut cmd = resolver::hardhat(root);
+    cmd.args(["run", &fixture.script]);
+    networks::apply_network(&mut cmd, fixture.network.as_deref().unwrap_or("localhost"));
+    processes::apply_env(&mut cmd, &fixture.env)?;
+    let output = processes::run_tracked(&app, execution_id, "script", cmd).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Setup script for {} failed: {}", name, stderr.trim()).into());
+    }
+
+    let state = state_name(&name);
+    let info = {
+        let _slot = exclusion::acquire(&app, root, "build_fixture", "node-state", &[JobGroup::Node, JobGroup::Rpc]).await;
+        node_state::capture(root, &state, url).await?
+    };
+    fixture.build = Some(FixtureBuild {
+        state,
+        built_at: store::timestamp_millis(),
+        script_hash,
+        clean,
+        block_number: info.block_number,
+        accounts: info.accounts,
+    });
+    store_fixture(root, &fixture)?;
+    Ok(status(root, fixture))
+}
+
+// Restores the captured state into the running node. On a node that already has local
+// transactions the fixture is written on top of them
+#[tauri::command]
+pub async fn load_fixture(app: AppHandle, project_path: String, name: String, rpc_url: Option<String>) -> Result<NodeStateInfo, AppError> {
+    let root = Path::new(&project_path);
+    let build = find_fixture(root, &name)?
+        .build
+        .filter(|build| node_state::state_exists(root, &build.state))
+        .ok_or_else(|| format!("Fixture {} has not been built yet", name))?;
+    let _slot = exclusion::acquire(&app, root, "load_fixture", "node-state", &[JobGroup::Node, JobGroup::Rpc]).await;
+    node_state::restore(root, &build.state, &rpc_url.unwrap_or_else(rpc::default_url)).await
+}
@@ -0,0 +1,259 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::address_book::AddressBook;
+use crate::chain_index;
+use crate::decode::{self, AbiIndex};
+use crate::error::AppError;
+use crate::rpc;
+use crate::store;
+
+const STATE_DIFFS_FILE: &str = "fork-state-diffs.json";
+const ZERO_WORD: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+struct AccountState {
+    #[serde(default)]
+    balance: Option<String>,
+    #[serde(default)]
+    nonce: Option<u64>,
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    storage: BTreeMap<String, String>,
+}
+
+// prestateTracer output in diff mode: `pre` holds the old value of everything the
+// transaction changed, `post` the new one
+#[derive(Serialize, Deserialize, Clone, Default)]
+struct TxStateDiff {
+    #[serde(default)]
+    pre: BTreeMap<String, AccountState>,
+    #[serde(default)]
+    post: BTreeMap<String, AccountState>,
+    // Accounts the transaction touched when the node couldn't produce a state diff
+    #[serde(default)]
+    untraced: Vec<String>,
+}
+
+// Per-transaction diffs keyed by hash. Blocks undone by evm_revert disappear from the
+// chain index, so their cached diffs are simply not visited again
+#[derive(Serialize, Deserialize, Default)]
+struct DiffCache {
+    base_hash: String,
+    transactions: BTreeMap<String, TxStateDiff>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ValueChange {
+    // Value on the upstream chain at the fork point; None for accounts created locally
+    pub upstream: Option<String>,
+    pub local: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SlotChange {
+    pub slot: String,
+    pub upstream: String,
+    pub local: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct AccountDiff {
+    pub address: String,
+    pub label: Option<String>,
+    pub contract: Option<String>,
+    pub created: bool,
+    // Balances are wei as decimal strings
+    pub balance: Option<ValueChange>,
+    pub nonce: Option<ValueChange>,
+    pub code_changed: bool,
+    pub storage: Vec<SlotChange>,
+    // Touched by a transaction the node couldn't trace, so the changes above may be incomplete
+    pub untraced: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ForkDiff {
+    pub upstream_chain_id: Option<u64>,
+    pub fork_block: u64,
+    pub local_blocks: usize,
+    pub transactions_scanned: usize,
+    pub untraced_transactions: usize,
+    pub accounts: Vec<AccountDiff>,
+}
+
+async fn trace_state_diff(url: &str, hash: &str, from: &str, to: Option<&str>) -> TxStateDiff {
+    let tracer = json!({ "tracer": "prestateTracer", "tracerConfig": { "diffMode": true } });
+    match rpc::call(url, "debug_traceTransaction", json!([hash, tracer])).await {
+        Ok(trace) if trace.get("pre").is_some() => serde_json::from_value(trace).unwrap_or_default(),
+        _ => TxStateDiff {
+            untraced: [Some(from), to].into_iter().flatten().map(str::to_lowercase).collect(),
+            ..Default::default()
+        },
+    }
+}
+
+#[derive(Default)]
+struct Tracked {
+    created: bool,
+    balance: Option<(Option<String>, Option<String>)>,
+    nonce: Option<(Option<u64>, Option<u64>)>,
+    code: Option<(Option<String>, Option<String>)>,
+    storage: BTreeMap<String, (String, String)>,
+    untraced: bool,
+}
+
+// The first `pre` seen is the upstream value; the last `post` is the local one
+fn track<T: Clone>(change: &mut Option<(Option<T>, Option<T>)>, pre: Option<&T>, post: Option<&T>) {
+    let entry = change.get_or_insert_with(|| (pre.cloned(), None));
+    entry.1 = post.cloned();
+}
+
+fn apply(accounts: &mut BTreeMap<String, Tracked>, diff: &TxStateDiff) {
+    let empty = AccountState::default();
+    let addresses: BTreeSet<&String> = diff.pre.keys().chain(diff.post.keys()).collect();
+    for address in addresses {
+        let pre = diff.pre.get(address);
+        let post = diff.post.get(address).unwrap_or(&empty);
+        let tracked = accounts.entry(address.to_lowercase()).or_default();
+        if pre.is_none() && tracked.balance.is_none() && tracked.nonce.is_none() && tracked.code.is_none() {
+            tracked.created = true;
+        }
+        let pre = pre.unwrap_or(&empty);
+
+        if post.balance.is_some() {
+            track(&mut tracked.balance, pre.balance.as_ref(), post.balance.as_ref());
+        }
+        if post.nonce.is_some() {
+            track(&mut tracked.nonce, pre.nonce.as_ref(), post.nonce.as_ref());
+        }
+        if post.code.is_some() {
+            track(&mut tracked.code, pre.code.as_ref(), post.code.as_ref());
+        }
+        for slot in pre.storage.keys().chain(post.storage.keys()) {
+            // Zero slots are left out: a new slot has no `pre`, a cleared one no `post`
+            let word = |state: &AccountState| state.storage.get(slot).cloned().unwrap_or_else(|| ZERO_WORD.to_string());
+            let entry = tracked.storage.entry(slot.clone()).or_insert_with(|| (word(pre), String::new()));
+            entry.1 = word(post);
+        }
+    }
+    for address in &diff.untraced {
+        accounts.entry(address.clone()).or_default().untraced = true;
+    }
+}
+
+fn value_change(change: Option<(Option<String>, Option<String>)>) -> Option<ValueChange> {
+    let (upstream, local) = change?;
+    (upstream != local).then_some(ValueChange { upstream, local })
+}
+
+// Accounts and slots written by the local transactions after `after_block`
+pub struct LocalChanges {
+    pub local_blocks: usize,
+    pub transactions_scanned: usize,
+    pub untraced_transactions: usize,
+    // False when the chain index starts later than `after_block`, so older
+    // transactions weren't looked at
+    pub complete: bool,
+    accounts: BTreeMap<String, Tracked>,
+}
+
+impl LocalChanges {
+    // Every written storage slot per account, including accounts with none
+    pub fn touched_slots(&self) -> BTreeMap<String, Vec<String>> {
+        self.accounts
+            .iter()
+            .map(|(address, tracked)| (address.clone(), tracked.storage.keys().cloned().collect()))
+            .collect()
+    }
+}
+
+// Each local transaction is traced once and the result cached. `base_hash` identifies
+// what the local chain builds on (fork block or genesis); a new base clears the cache
+pub async fn local_changes(project_path: &Path, url: &str, after_block: u64, base_hash: &str) -> Result<LocalChanges, AppError> {
+    let chain = chain_index::sync(project_path, url).await?;
+    let mut cache: DiffCache = store::load_json(project_path, STATE_DIFFS_FILE)?;
+    if cache.base_hash != base_hash {
+        cache = DiffCache { base_hash: base_hash.to_string(), transactions: BTreeMap::new() };
+    }
+
+    let local_blocks: Vec<_> = chain.blocks.iter().filter(|b| b.number > after_block).collect();
+    let mut changes = LocalChanges {
+        local_blocks: local_blocks.len(),
+        transactions_scanned: 0,
+        untraced_transactions: 0,
+        complete: chain.first_block <= after_block + 1,
+        accounts: BTreeMap::new(),
+    };
+    for tx in local_blocks.iter().flat_map(|b| &b.transactions) {
+        if !cache.transactions.contains_key(&tx.hash) {
+            let diff = trace_state_diff(url, &tx.hash, &tx.from, tx.to.as_deref()).await;
+            cache.transactions.insert(tx.hash.clone(), diff);
+        }
+        let diff = &cache.transactions[&tx.hash];
+        apply(&mut changes.accounts, diff);
+        changes.transactions_scanned += 1;
+        if !diff.untraced.is_empty() {
+            changes.untraced_transactions += 1;
+        }
+    }
+    store::save_json(project_path, STATE_DIFFS_FILE, &cache)?;
+    Ok(changes)
+}
+
+// On a forked node, which accounts, contracts and storage slots now differ from the
+// upstream chain
+#[tauri::command]
+pub async fn get_fork_diff(project_path: String, rpc_url: Option<String>) -> Result<ForkDiff, AppError> {
+    let root = Path::new(&project_path);
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let metadata = rpc::call(url, "hardhat_metadata", json!([])).await?;
+    let Some(fork) = metadata.get("forkedNetwork").filter(|f| !f.is_null()) else {
+        return Err("The node is not running a fork".to_string().into());
+    };
+    let fork_block = fork.get("forkBlockNumber").and_then(Value::as_u64).unwrap_or(0);
+    let fork_block_hash = fork.get("forkBlockHash").and_then(Value::as_str).unwrap_or_default();
+    let changes = local_changes(root, url, fork_block, fork_block_hash).await?;
+
+    let index = AbiIndex::load(root);
+    let book = AddressBook::load(root, Some(url)).await;
+    let accounts = changes
+        .accounts
+        .into_iter()
+        .map(|(address, tracked)| AccountDiff {
+            label: book.label(&address).map(str::to_string),
+            contract: index.contract_at(&address).map(str::to_string),
+            created: tracked.created,
+            balance: value_change(tracked.balance.map(|(upstream, local)| {
+                let decimal = |v: Option<String>| v.map(|v| decode::quantity_to_decimal(&v));
+                (decimal(upstream), decimal(local))
+            })),
+            nonce: value_change(
+                tracked.nonce.map(|(upstream, local)| (upstream.map(|n| n.to_string()), local.map(|n| n.to_string()))),
+            ),
+            code_changed: tracked.code.is_some_and(|(upstream, local)| upstream != local),
+            storage: tracked
+                .storage
+                .into_iter()
+                .filter(|(_, (upstream, local))| upstream != local)
+                .map(|(slot, (upstream, local))| SlotChange { slot, upstream, local })
+                .collect(),
+            untraced: tracked.untraced,
+            address,
+        })
+        .filter(|a| a.untraced || a.balance.is_some() || a.nonce.is_some() || a.code_changed || !a.storage.is_empty())
+        .collect();
+
+    Ok(ForkDiff {
+        upstream_chain_id: fork.get("chainId").and_then(Value::as_u64),
+        fork_block,
+        local_blocks: changes.local_blocks,
+        transactions_scanned: changes.transactions_scanned,
+        untraced_transactions: changes.untraced_transactions,
+        accounts,
+    })
+}
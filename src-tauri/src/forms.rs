@@ -0,0 +1,225 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::abi::{AbiItem, AbiParam};
+use crate::artifacts;
+use crate::decode;
+use crate::error::AppError;
+
+// What a form control needs to know about one ABI value. Values are entered in the shapes
+// `decode::encode_params` accepts: integers as decimal or 0x strings, bytes and addresses
+// as 0x hex, arrays as arrays and tuples as objects keyed by component name
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FieldKind {
+    Address,
+    Bool,
+    String,
+    Uint {
+        bits: u16,
+        max: String,
+    },
+    Int {
+        bits: u16,
+        min: String,
+        max: String,
+    },
+    // `length` is None for dynamic `bytes`
+    Bytes {
+        length: Option<u8>,
+    },
+    // `length` is None for dynamic arrays
+    Array {
+        length: Option<usize>,
+        item: Box<FormField>,
+    },
+    Tuple {
+        fields: Vec<FormField>,
+    },
+    // External function pointers and anything else encode_params can't take
+    Unsupported,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FormField {
+    pub name: String,
+    // The name, or `arg0` / `output0` when the ABI leaves it blank
+    pub label: String,
+    // Canonical type, e.g. "(address,uint256)[]"
+    #[serde(rename = "type")]
+    pub solidity_type: String,
+    // The source-level type: "contract IERC20", "enum Vault.State", "struct Order"
+    pub internal_type: Option<String>,
+    #[serde(flatten)]
+    pub field: FieldKind,
+    // For text inputs; None where the value isn't typed in (bool, arrays, tuples)
+    pub pattern: Option<String>,
+    pub placeholder: String,
+    // A valid starting value in the accepted shape
+    pub default_value: Value,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FunctionForm {
+    pub name: String,
+    pub signature: String,
+    pub selector: String,
+    pub state_mutability: String,
+    // view and pure run through eth_call
+    pub read_only: bool,
+    // Shows the value (wei) input
+    pub payable: bool,
+    pub inputs: Vec<FormField>,
+    pub outputs: Vec<FormField>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ContractForms {
+    pub contract_name: String,
+    pub source_name: String,
+    pub constructor: Option<FunctionForm>,
+    // Read-only functions first, then by name
+    pub functions: Vec<FunctionForm>,
+    // Plain ether transfers are accepted
+    pub accepts_ether: bool,
+    pub has_fallback: bool,
+}
+
+const HEX: &str = "[0-9a-fA-F]";
+
+fn max_unsigned(bits: u16) -> String {
+    decode::quantity_to_decimal(&"f".repeat(bits as usize / 4))
+}
+
+fn max_signed(bits: u16) -> String {
+    decode::quantity_to_decimal(&format!("7{}", "f".repeat(bits as usize / 4 - 1)))
+}
+
+fn min_signed(bits: u16) -> String {
+    format!("-{}", decode::quantity_to_decimal(&format!("8{}", "0".repeat(bits as usize / 4 - 1))))
+}
+
+// Integer widths are multiples of 8 from 8 to 256; a bare `uint` is uint256
+fn bits(suffix: &str) -> Option<u16> {
+    if suffix.is_empty() {
+        return Some(256);
+    }
+    suffix.parse().ok().filter(|bits| bits % 8 == 0 && (8..=256).contains(bits))
+}
+
+fn field(param: &AbiParam, label: String) -> FormField {
+    let kind = param.kind.as_str();
+    let (field_kind, pattern, placeholder, default_value) = if let Some((inner, length)) = decode::array_parts(kind) {
+        let item = field(&decode::element(param, inner), format!("{} item", label));
+        let default_value = Value::Array(vec![item.default_value.clone(); length.unwrap_or(0)]);
+        (FieldKind::Array { length, item: Box::new(item) }, None, String::new(), default_value)
+    } else if kind == "tuple" {
+        let fields: Vec<FormField> = param
+            .components
+            .iter()
+            .enumerate()
+            .map(|(i, component)| field(component, label_for(component, "field", i)))
+            .collect();
+        // encode_params takes unnamed components positionally
+        let default_value = if fields.iter().all(|field| !field.name.is_empty()) {
+            Value::Object(fields.iter().map(|field| (field.name.clone(), field.default_value.clone())).collect::<Map<_, _>>())
+        } else {
+            Value::Array(fields.iter().map(|field| field.default_value.clone()).collect())
+        };
+        (FieldKind::Tuple { fields }, None, String::new(), default_value)
+    } else if kind == "address" {
+        let pattern = format!("^0x{}{{40}}$", HEX);
+        (FieldKind::Address, Some(pattern), "0x0000…".to_string(), json!("0x0000000000000000000000000000000000000000"))
+    } else if kind == "bool" {
+        (FieldKind::Bool, None, String::new(), json!(false))
+    } else if kind == "string" {
+        (FieldKind::String, None, "text".to_string(), json!(""))
+    } else if kind == "bytes" {
+        let pattern = format!("^0x({}{{2}})*$", HEX);
+        (FieldKind::Bytes { length: None }, Some(pattern), "0x…".to_string(), json!("0x"))
+    } else if let Some(length) = kind.strip_prefix("bytes").and_then(|n| n.parse::<u8>().ok()).filter(|n| (1..=32).contains(n)) {
+        let pattern = format!("^0x{}{{{}}}$", HEX, length as usize * 2);
+        let default_value = json!(format!("0x{}", "00".repeat(length as usize)));
+        (FieldKind::Bytes { length: Some(length) }, Some(pattern), format!("0x… ({} bytes)", length), default_value)
+    } else if let Some(bits) = kind.strip_prefix("uint").and_then(bits) {
+        let uint = FieldKind::Uint { bits, max: max_unsigned(bits) };
+        (uint, Some(format!("^(0x{}+|[0-9]+)$", HEX)), format!("0 to 2^{} - 1", bits), json!("0"))
+    } else if let Some(bits) = kind.strip_prefix("int").and_then(bits) {
+        let int = FieldKind::Int { bits, min: min_signed(bits), max: max_signed(bits) };
+        (int, Some(format!("^-?(0x{}+|[0-9]+)$", HEX)), format!("-2^{} to 2^{} - 1", bits - 1, bits - 1), json!("0"))
+    } else {
+        (FieldKind::Unsupported, None, String::new(), Value::Null)
+    };
+    FormField {
+        name: param.name.clone(),
+        label,
+        solidity_type: param.canonical_type(),
+        internal_type: param.internal_type.clone(),
+        field: field_kind,
+        pattern,
+        placeholder,
+        default_value,
+    }
+}
+
+fn label_for(param: &AbiParam, prefix: &str, i: usize) -> String {
+    if param.name.is_empty() {
+        format!("{}{}", prefix, i)
+    } else {
+        param.name.clone()
+    }
+}
+
+fn fields(params: &[AbiParam], prefix: &str) -> Vec<FormField> {
+    params.iter().enumerate().map(|(i, param)| field(param, label_for(param, prefix, i))).collect()
+}
+
+fn function_form(item: &AbiItem) -> FunctionForm {
+    let state_mutability = item.state_mutability.clone().unwrap_or_else(|| "nonpayable".to_string());
+    let signature = item.signature();
+    FunctionForm {
+        name: item.name.clone(),
+        selector: decode::selector(&signature),
+        signature,
+        read_only: matches!(state_mutability.as_str(), "view" | "pure"),
+        payable: state_mutability == "payable",
+        state_mutability,
+        inputs: fields(&item.inputs, "arg"),
+        outputs: fields(&item.outputs, "output"),
+    }
+}
+
+pub fn contract_forms(contract_name: &str, source_name: &str, abi: &[AbiItem]) -> ContractForms {
+    let mut functions: Vec<FunctionForm> = abi.iter().filter(|item| item.is_function()).map(function_form).collect();
+    functions.sort_by(|a, b| (!a.read_only, &a.name, &a.signature).cmp(&(!b.read_only, &b.name, &b.signature)));
+    let fallback_payable = abi
+        .iter()
+        .any(|item| item.kind == "fallback" && item.state_mutability.as_deref() == Some("payable"));
+    ContractForms {
+        contract_name: contract_name.to_string(),
+        source_name: source_name.to_string(),
+        constructor: abi.iter().find(|item| item.kind == "constructor").map(|item| FunctionForm {
+            name: "constructor".to_string(),
+            ..function_form(item)
+        }),
+        functions,
+        accepts_ether: fallback_payable || abi.iter().any(|item| item.kind == "receive"),
+        has_fallback: abi.iter().any(|item| item.kind == "fallback"),
+    }
+}
+
+#[tauri::command]
+pub async fn get_contract_forms(project_path: String, contract: String) -> Result<ContractForms, AppError> {
+    let forms = tauri::async_runtime::spawn_blocking(move || {
+        let index = artifacts::index(Path::new(&project_path));
+        let artifact = index
+            .get(&contract)
+            .ok_or_else(|| format!("No compiled artifact found for {}", contract))?;
+        Ok::<_, String>(contract_forms(&artifact.contract_name, &artifact.source_name, &artifact.abi))
+    })
+    .await
+    .map_err(|e| format!("Failed to load artifacts: {}", e))?;
+    Ok(forms?)
+}
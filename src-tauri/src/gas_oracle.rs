@@ -0,0 +1,194 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::AppError;
+use crate::networks;
+use crate::provider_keys;
+use crate::rpc;
+use crate::store;
+
+const FEE_HISTORY_BLOCKS: u64 = 20;
+const REWARD_PERCENTILES: [u32; 3] = [10, 50, 90];
+// Base fee headroom per tier, in percent: the base fee can rise 12.5% per full block
+const BASE_FEE_MULTIPLIERS: [u128; 3] = [110, 150, 200];
+// How long a suggestion is reused to pre-fill commands before it's fetched again
+const SUGGESTION_TTL: Duration = Duration::from_secs(60);
+// Local dev chains mine everything immediately; fee suggestions are meaningless there
+const LOCAL_CHAIN_IDS: [u64; 2] = [31337, 1337];
+
+// Wei amounts are decimal strings; they overflow JavaScript numbers
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FeeSuggestion {
+    pub max_fee_per_gas: String,
+    pub max_priority_fee_per_gas: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FeeSuggestions {
+    pub network: Option<String>,
+    pub chain_id: u64,
+    pub block_number: u64,
+    // False on chains without EIP-1559; the tiers then all carry the legacy gas price
+    pub eip1559: bool,
+    pub next_base_fee: String,
+    pub slow: FeeSuggestion,
+    pub standard: FeeSuggestion,
+    pub fast: FeeSuggestion,
+    pub fetched_at: u64,
+}
+
+static CACHE: OnceLock<Mutex<HashMap<String, (Instant, FeeSuggestions)>>> = OnceLock::new();
+
+fn cache() -> &'static Mutex<HashMap<String, (Instant, FeeSuggestions)>> {
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn wei(value: &Value) -> Option<u128> {
+    u128::from_str_radix(value.as_str()?.trim_start_matches("0x"), 16).ok()
+}
+
+fn median(mut values: Vec<u128>) -> Option<u128> {
+    values.sort_unstable();
+    values.get(values.len() / 2).copied()
+}
+
+fn suggestion(max_fee: u128, priority: u128) -> FeeSuggestion {
+    FeeSuggestion {
+        max_fee_per_gas: max_fee.to_string(),
+        max_priority_fee_per_gas: priority.to_string(),
+    }
+}
+
+async fn legacy(url: &str, chain_id: u64, block_number: u64) -> Result<FeeSuggestions, AppError> {
+    let gas_price = wei(&rpc::call(url, "eth_gasPrice", json!([])).await?).unwrap_or(0);
+    let tier = |percent: u128| suggestion(gas_price * percent / 100, gas_price * percent / 100);
+    Ok(FeeSuggestions {
+        network: None,
+        chain_id,
+        block_number,
+        eip1559: false,
+        next_base_fee: "0".to_string(),
+        slow: tier(100),
+        standard: tier(110),
+        fast: tier(125),
+        fetched_at: store::timestamp_millis(),
+    })
+}
+
+// Priority fees are the median of each reward percentile over recent blocks, skipping
+// empty blocks; max fees add headroom over the next block's base fee
+pub async fn suggest(url: &str) -> Result<FeeSuggestions, AppError> {
+    let chain_id = rpc::chain_id(url).await?;
+    let history = rpc::call(
+        url,
+        "eth_feeHistory",
+        json!([format!("0x{:x}", FEE_HISTORY_BLOCKS), "latest", REWARD_PERCENTILES]),
+    )
+    .await;
+    let block_number = rpc::parse_quantity(&rpc::call(url, "eth_blockNumber", json!([])).await?).unwrap_or(0);
+
+    let Ok(history) = history else {
+        return legacy(url, chain_id, block_number).await;
+    };
+    let Some(next_base_fee) = history
+        .get("baseFeePerGas")
+        .and_then(Value::as_array)
+        .and_then(|fees| fees.last())
+        .and_then(wei)
+        .filter(|fee| *fee > 0)
+    else {
+        return legacy(url, chain_id, block_number).await;
+    };
+
+    let rewards: Vec<Vec<u128>> = history
+        .get("reward")
+        .and_then(Value::as_array)
+        .map(|blocks| {
+            blocks
+                .iter()
+                .filter_map(|block| block.as_array().map(|r| r.iter().filter_map(wei).collect()))
+                .filter(|r: &Vec<u128>| r.iter().any(|fee| *fee > 0))
+                .collect()
+        })
+        .unwrap_or_default();
+    let fallback_priority = rpc::call(url, "eth_maxPriorityFeePerGas", json!([]))
+        .await
+        .ok()
+        .and_then(|fee| wei(&fee))
+        .unwrap_or(0);
+    let tier = |index: usize| {
+        let priority = median(rewards.iter().filter_map(|r| r.get(index).copied()).collect())
+            .unwrap_or(fallback_priority);
+        suggestion(next_base_fee * BASE_FEE_MULTIPLIERS[index] / 100 + priority, priority)
+    };
+
+    Ok(FeeSuggestions {
+        network: None,
+        chain_id,
+        block_number,
+        eip1559: true,
+        next_base_fee: next_base_fee.to_string(),
+        slow: tier(0),
+        standard: tier(1),
+        fast: tier(2),
+        fetched_at: store::timestamp_millis(),
+    })
+}
+
+// Refreshes the cached suggestion for a public network so the command about to run is
+// pre-filled; failures just mean the run goes without
+pub async fn prefetch(network: &str) {
+    let Some(profile) = networks::find_profile(network) else {
+        return;
+    };
+    if LOCAL_CHAIN_IDS.contains(&profile.chain_id) {
+        return;
+    }
+    let fresh = cache()
+        .lock()
+        .unwrap()
+        .get(network)
+        .is_some_and(|(at, _)| at.elapsed() < SUGGESTION_TTL);
+    if fresh {
+        return;
+    }
+    match suggest(&provider_keys::expand(&profile.rpc_url)).await {
+        Ok(mut suggestions) => {
+            suggestions.network = Some(network.to_string());
+            cache().lock().unwrap().insert(network.to_string(), (Instant::now(), suggestions));
+        }
+        Err(e) => tracing::warn!(network, error = %e, "fee suggestion failed"),
+    }
+}
+
+// The standard tier, for configs that read it:
+// `ignition: { maxPriorityFeePerGas: BigInt(process.env.HARDHAT_GUI_MAX_PRIORITY_FEE_PER_GAS) }`
+pub fn env(network: &str) -> BTreeMap<String, String> {
+    let cache = cache().lock().unwrap();
+    let Some((_, suggestions)) = cache.get(network).filter(|(at, _)| at.elapsed() < SUGGESTION_TTL) else {
+        return BTreeMap::new();
+    };
+    BTreeMap::from([
+        ("HARDHAT_GUI_MAX_FEE_PER_GAS".to_string(), suggestions.standard.max_fee_per_gas.clone()),
+        (
+            "HARDHAT_GUI_MAX_PRIORITY_FEE_PER_GAS".to_string(),
+            suggestions.standard.max_priority_fee_per_gas.clone(),
+        ),
+    ])
+}
+
+#[tauri::command]
+pub async fn get_fee_suggestions(network: Option<String>, rpc_url: Option<String>) -> Result<FeeSuggestions, AppError> {
+    let Some(network) = network else {
+        return suggest(&rpc_url.unwrap_or_else(rpc::default_url)).await;
+    };
+    let profile = networks::find_profile(&network).ok_or_else(|| format!("Unknown network profile: {}", network))?;
+    let mut suggestions = suggest(&provider_keys::expand(&profile.rpc_url)).await?;
+    suggestions.network = Some(network.clone());
+    cache().lock().unwrap().insert(network, (Instant::now(), suggestions.clone()));
+    Ok(suggestions)
+}
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::artifacts;
+use crate::db;
+use crate::error::AppError;
+use crate::store;
+
+const GATES_FILE: &str = "gates.json";
+// hardhat-gas-reporter writes this when `outputJSON: true` is set in its config
+const GAS_REPORT_FILE: &str = "gasReporterOutput.json";
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct GateThresholds {
+    // Applies to every contract without its own entry in `contract_sizes`
+    pub max_bytecode_size: Option<usize>,
+    #[serde(default)]
+    pub contract_sizes: BTreeMap<String, usize>,
+    // Keyed by "Contract.function"
+    #[serde(default)]
+    pub function_gas: BTreeMap<String, u64>,
+}
+
+impl GateThresholds {
+    pub fn is_empty(&self) -> bool {
+        self.max_bytecode_size.is_none() && self.contract_sizes.is_empty() && self.function_gas.is_empty()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GateViolation {
+    BytecodeSize { contract: String, size: usize, limit: usize },
+    FunctionGas { contract: String, function: String, gas: u64, limit: u64 },
+    // A gas threshold exists but the last gas report has no data for it
+    MissingGasData { contract: String, function: String },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GateReport {
+    pub passed: bool,
+    pub checked_contracts: usize,
+    pub gas_report_found: bool,
+    pub violations: Vec<GateViolation>,
+}
+
+pub fn load_thresholds(project_path: &Path) -> Result<GateThresholds, String> {
+    store::load_json(project_path, GATES_FILE)
+}
+
+// Reads both the hardhat-gas-reporter v2 format (`data.methods.*.max`) and the older
+// eth-gas-reporter one (`info.methods.*.gasData`)
+pub fn load_gas_report(project_path: &Path) -> Option<BTreeMap<String, u64>> {
+    let content = fs::read_to_string(project_path.join(GAS_REPORT_FILE)).ok()?;
+    let json: Value = serde_json::from_str(&content).ok()?;
+    let methods = json
+        .pointer("/data/methods")
+        .or_else(|| json.pointer("/info/methods"))?
+        .as_object()?;
+
+    let mut gas = BTreeMap::new();
+    for method in methods.values() {
+        let (Some(contract), Some(name)) = (
+            method.get("contract").and_then(Value::as_str),
+            method.get("method").and_then(Value::as_str),
+        ) else {
+            continue;
+        };
+
+        let max = method.get("max").and_then(Value::as_u64).or_else(|| {
+            method
+                .get("gasData")
+                .and_then(Value::as_array)
+                .and_then(|data| data.iter().filter_map(Value::as_u64).max())
+        });
+        if let Some(max) = max {
+            let entry = gas.entry(format!("{}.{}", contract, name)).or_insert(0);
+            *entry = (*entry).max(max);
+        }
+    }
+    Some(gas)
+}
+
+// One gas report's per-method maximums
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GasSnapshot {
+    pub taken_at: u64,
+    pub methods: BTreeMap<String, u64>,
+}
+
+// Keyed by the report's modification time, so checking an unchanged report again
+// records nothing new
+pub fn record_gas_snapshot(project_path: &Path) {
+    let Some(report) = load_gas_report(project_path) else {
+        return;
+    };
+    let Some(taken_at) = fs::metadata(project_path.join(GAS_REPORT_FILE))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+    else {
+        return;
+    };
+    let written = db::with(project_path, |conn| {
+        let tx = conn.transaction()?;
+        {
+            let mut insert = tx.prepare("INSERT OR IGNORE INTO gas_snapshots (taken_at, method, gas) VALUES (?1, ?2, ?3)")?;
+            for (method, gas) in &report {
+                insert.execute(params![taken_at, method, gas])?;
+            }
+        }
+        tx.commit()
+    });
+    if let Err(e) = written {
+        tracing::warn!(error = %e, "failed to record gas snapshot");
+    }
+}
+
+pub fn evaluate(project_path: &Path, thresholds: &GateThresholds) -> GateReport {
+    let mut violations = Vec::new();
+
+    let artifacts = artifacts::load_artifacts(project_path);
+    for artifact in &artifacts {
+        let limit = thresholds
+            .contract_sizes
+            .get(&artifact.contract_name)
+            .copied()
+            .or(thresholds.max_bytecode_size);
+        let size = artifact.deployed_size;
+        if let Some(limit) = limit.filter(|limit| size > *limit) {
+            violations.push(GateViolation::BytecodeSize {
+                contract: artifact.contract_name.clone(),
+                size,
+                limit,
+            });
+        }
+    }
+
+    let gas_report = load_gas_report(project_path);
+    if let Some(report) = &gas_report {
+        for (key, limit) in &thresholds.function_gas {
+            let (contract, function) = key.split_once('.').unwrap_or((key.as_str(), ""));
+            match report.get(key) {
+                Some(gas) if gas > limit => violations.push(GateViolation::FunctionGas {
+                    contract: contract.to_string(),
+                    function: function.to_string(),
+                    gas: *gas,
+                    limit: *limit,
+                }),
+                Some(_) => {}
+                None => violations.push(GateViolation::MissingGasData {
+                    contract: contract.to_string(),
+                    function: function.to_string(),
+                }),
+            }
+        }
+    }
+
+    GateReport {
+        passed: violations.is_empty(),
+        checked_contracts: artifacts.len(),
+        gas_report_found: gas_report.is_some(),
+        violations,
+    }
+}
+
+// Called after compile and test runs; gates do nothing until thresholds are configured
+pub fn check_after_run(app: &AppHandle, project_path: &str) {
+    let root = Path::new(project_path);
+    record_gas_snapshot(root);
+    let Ok(thresholds) = load_thresholds(root) else {
+        return;
+    };
+    if thresholds.is_empty() {
+        return;
+    }
+    let _ = app.emit("gates-evaluated", evaluate(root, &thresholds));
+}
+
+#[tauri::command]
+pub async fn get_gate_thresholds(project_path: String) -> Result<GateThresholds, AppError> {
+    Ok(load_thresholds(Path::new(&project_path))?)
+}
+
+#[tauri::command]
+pub async fn save_gate_thresholds(project_path: String, thresholds: GateThresholds) -> Result<GateThresholds, AppError> {
+    if let Some(key) = thresholds.function_gas.keys().find(|key| !key.contains('.')) {
+        return Err(format!("Gas thresholds must be keyed as Contract.function, got \"{}\"", key).into());
+    }
+    store::save_json(Path::new(&project_path), GATES_FILE, &thresholds)?;
+    Ok(thresholds)
+}
+
+#[tauri::command]
+pub async fn evaluate_gates(project_path: String) -> Result<GateReport, AppError> {
+    let root = Path::new(&project_path);
+    Ok(evaluate(root, &load_thresholds(root)?))
+}
+
+// Newest first; `method` ("Contract.function") narrows each snapshot to that method
+#[tauri::command]
+pub async fn get_gas_snapshots(
+    project_path: String,
+    method: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<GasSnapshot>, AppError> {
+    let limit = limit.map_or(-1, |limit| limit as i64);
+    let rows = db::with(Path::new(&project_path), |conn| {
+        conn.prepare_cached(
+            "SELECT taken_at, method, gas FROM gas_snapshots
+             WHERE taken_at IN (SELECT DISTINCT taken_at FROM gas_snapshots ORDER BY taken_at DESC LIMIT ?2)
+               AND (?1 IS NULL OR method = ?1)
+             ORDER BY taken_at DESC",
+        )?
+        .query_map(params![method, limit], |row| Ok((row.get::<_, u64>(0)?, row.get::<_, String>(1)?, row.get::<_, u64>(2)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })?;
+
+    let mut snapshots: Vec<GasSnapshot> = Vec::new();
+    for (taken_at, method, gas) in rows {
+        match snapshots.last_mut() {
+            Some(snapshot) if snapshot.taken_at == taken_at => {
+                snapshot.methods.insert(method, gas);
+            }
+            _ => snapshots.push(GasSnapshot { taken_at, methods: BTreeMap::from([(method, gas)]) }),
+        }
+    }
+    Ok(snapshots)
+}
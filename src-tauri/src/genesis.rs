@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::decode;
+use crate::error::AppError;
+use crate::fork_diff;
+use crate::node_state::{self, StoredAccount};
+use crate::rpc;
+use crate::store;
+use crate::transactions;
+
+const EXPORTS_DIR: &str = "exports";
+// How many of the node's accounts are checked against the default mnemonic
+const MAX_DEV_ACCOUNTS: usize = 50;
+
+#[derive(Serialize, Deserialize)]
+pub struct GenesisExport {
+    pub path: String,
+    // hardhat.config snippet reproducing the chain, also written next to the genesis file
+    pub hardhat_config_path: String,
+    pub hardhat_network: Value,
+    pub chain_id: u64,
+    pub hardfork: String,
+    pub accounts: usize,
+    // True when the alloc is the chain's current state rather than its genesis
+    pub includes_state: bool,
+}
+
+// Newest fork the block format shows; nodes don't report their hardfork directly
+fn hardfork(block: &Value) -> &'static str {
+    if block.get("blobGasUsed").is_some() {
+        "cancun"
+    } else if block.get("withdrawalsRoot").is_some() {
+        "shanghai"
+    } else if block.get("baseFeePerGas").is_some() {
+        "london"
+    } else {
+        "berlin"
+    }
+}
+
+// geth's chain config with every fork up to `hardfork` active from genesis
+fn chain_config(chain_id: u64, hardfork: &str) -> Value {
+    let mut config = json!({ "chainId": chain_id });
+    let blocks = [
+        "homesteadBlock",
+        "eip150Block",
+        "eip155Block",
+        "eip158Block",
+        "byzantiumBlock",
+        "constantinopleBlock",
+        "petersburgBlock",
+        "istanbulBlock",
+        "berlinBlock",
+    ];
+    for field in blocks {
+        config[field] = json!(0);
+    }
+    if hardfork != "berlin" {
+        config["londonBlock"] = json!(0);
+        config["terminalTotalDifficulty"] = json!(0);
+        config["terminalTotalDifficultyPassed"] = json!(true);
+    }
+    if hardfork == "shanghai" || hardfork == "cancun" {
+        config["shanghaiTime"] = json!(0);
+    }
+    if hardfork == "cancun" {
+        config["cancunTime"] = json!(0);
+    }
+    config
+}
+
+// Seconds since the epoch as "2024-01-31T12:00:00Z", for Hardhat's initialDate
+fn iso_date(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+    // Civil-from-days (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+fn alloc_entry(account: &StoredAccount) -> Value {
+    let mut entry = json!({ "balance": account.balance });
+    if account.nonce != "0x0" {
+        entry["nonce"] = json!(account.nonce);
+    }
+    if account.code.trim_start_matches("0x").is_empty() {
+        return entry;
+    }
+    entry["code"] = json!(account.code);
+    if !account.storage.is_empty() {
+        entry["storage"] = json!(account.storage);
+    }
+    entry
+}
+
+// The node's accounts as a Hardhat `accounts` setting when they are the default
+// mnemonic's first N addresses; other keys aren't known to the GUI
+fn hardhat_accounts(addresses: &[String], balances: &[String]) -> Option<Value> {
+    let derived: Vec<Option<String>> = (0..addresses.len().min(MAX_DEV_ACCOUNTS))
+        .map(|index| transactions::dev_account_address(index as u32).ok())
+        .collect();
+    let from_mnemonic = !addresses.is_empty()
+        && addresses.len() <= MAX_DEV_ACCOUNTS
+        && addresses.iter().zip(&derived).all(|(address, derived)| derived.as_deref() == Some(address.as_str()));
+    if !from_mnemonic {
+        return None;
+    }
+    let mut accounts = json!({ "mnemonic": transactions::DEV_MNEMONIC, "count": addresses.len() });
+    if balances.windows(2).all(|pair| pair[0] == pair[1]) {
+        if let Some(balance) = balances.first() {
+            accounts["accountsBalance"] = json!(decode::quantity_to_decimal(balance));
+        }
+    }
+    Some(accounts)
+}
+
+// Writes a geth-style genesis (also accepted by `anvil --init`) with the node's chain id,
+// block settings and pre-funded accounts, plus the hardhat.config network entry that
+// recreates the same devnet. With `include_state`, the alloc is the current state of
+// every account the local transactions touched, so deployed contracts come along too
+#[tauri::command]
+pub async fn export_genesis(
+    project_path: String,
+    output_path: Option<String>,
+    include_state: Option<bool>,
+    rpc_url: Option<String>,
+) -> Result<GenesisExport, AppError> {
+    let root = Path::new(&project_path);
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let include_state = include_state.unwrap_or(false);
+
+    let fork = rpc::call(url, "hardhat_metadata", json!([]))
+        .await
+        .ok()
+        .and_then(|metadata| metadata.get("forkedNetwork").cloned())
+        .filter(|fork| !fork.is_null());
+    if fork.is_some() {
+        return Err("A forked chain's state lives on the forked network and can't be exported as genesis; save a node state instead".to_string().into());
+    }
+
+    let chain_id = rpc::chain_id(url).await?;
+    let genesis = rpc::call(url, "eth_getBlockByNumber", json!(["0x0", false])).await?;
+    let latest = rpc::call(url, "eth_getBlockByNumber", json!(["latest", false])).await?;
+    let hardfork = hardfork(&latest);
+    let field = |block: &Value, key: &str| block.get(key).and_then(Value::as_str).unwrap_or("0x0").to_string();
+
+    let addresses: Vec<String> = rpc::call(url, "eth_accounts", json!([]))
+        .await
+        .unwrap_or(Value::Null)
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_lowercase)
+        .collect();
+    let mut genesis_balances = Vec::new();
+    for address in &addresses {
+        let balance = rpc::call(url, "eth_getBalance", json!([address, "0x0"])).await?;
+        genesis_balances.push(balance.as_str().unwrap_or("0x0").to_string());
+    }
+
+    let mut accounts: BTreeMap<String, StoredAccount> = BTreeMap::new();
+    if include_state {
+        let (base_block, base_hash) = node_state::chain_base(url).await?;
+        let changes = fork_diff::local_changes(root, url, base_block, &base_hash).await?;
+        let mut touched = changes.touched_slots();
+        for address in &addresses {
+            touched.entry(address.clone()).or_default();
+        }
+        for (address, slots) in touched {
+            accounts.insert(address.clone(), node_state::read_account(url, &address, &slots).await?);
+        }
+    } else {
+        for (address, balance) in addresses.iter().zip(&genesis_balances) {
+            accounts.insert(address.clone(), StoredAccount {
+                address: address.clone(),
+                balance: balance.clone(),
+                nonce: "0x0".to_string(),
+                code: "0x".to_string(),
+                storage: BTreeMap::new(),
+            });
+        }
+    }
+    let alloc: Map<String, Value> = accounts
+        .iter()
+        .map(|(address, account)| (address.trim_start_matches("0x").to_string(), alloc_entry(account)))
+        .collect();
+
+    let block = if include_state { &latest } else { &genesis };
+    let mut genesis_json = json!({
+        "config": chain_config(chain_id, hardfork),
+        "nonce": "0x0",
+        "timestamp": field(block, "timestamp"),
+        "extraData": "0x",
+        "gasLimit": field(&latest, "gasLimit"),
+        "difficulty": "0x0",
+        "mixHash": format!("0x{}", "0".repeat(64)),
+        "coinbase": format!("0x{}", "0".repeat(40)),
+        "alloc": alloc,
+    });
+    if hardfork != "berlin" {
+        genesis_json["baseFeePerGas"] = json!(field(block, "baseFeePerGas"));
+    }
+
+    let mut hardhat_network = json!({
+        "chainId": chain_id,
+        "hardfork": hardfork,
+        "blockGasLimit": latest.get("gasLimit").and_then(rpc::parse_quantity).unwrap_or_default(),
+        "initialDate": iso_date(block.get("timestamp").and_then(rpc::parse_quantity).unwrap_or_default()),
+    });
+    if hardfork != "berlin" {
+        hardhat_network["initialBaseFeePerGas"] = json!(block.get("baseFeePerGas").and_then(rpc::parse_quantity).unwrap_or_default());
+    }
+    if let Some(accounts) = hardhat_accounts(&addresses, &genesis_balances) {
+        hardhat_network["accounts"] = accounts;
+    }
+
+    let path = match output_path {
+        Some(path) => PathBuf::from(path),
+        None => root.join(EXPORTS_DIR).join(format!("genesis-{}-{}.json", chain_id, store::timestamp_millis())),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create export directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&genesis_json).map_err(|e| format!("Failed to serialize genesis: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write genesis: {}", e))?;
+
+    let network = serde_json::to_string_pretty(&hardhat_network).map_err(|e| format!("Failed to serialize network: {}", e))?;
+    let mut snippet = format!("// networks.hardhat for hardhat.config, matching {}\n", path.file_name().unwrap_or_default().to_string_lossy());
+    if include_state {
+        snippet.push_str("// Deployed contracts and balances are in the genesis alloc; Hardhat can't load it, use `anvil --init` for those\n");
+    }
+    snippet.push_str(&format!("module.exports = {{ networks: {{ hardhat: {} }} }};\n", network));
+    let config_path = path.with_extension("hardhat.js");
+    fs::write(&config_path, snippet).map_err(|e| format!("Failed to write network config: {}", e))?;
+
+    Ok(GenesisExport {
+        path: path.to_string_lossy().to_string(),
+        hardhat_config_path: config_path.to_string_lossy().to_string(),
+        hardhat_network,
+        chain_id,
+        hardfork: hardfork.to_string(),
+        accounts: accounts.len(),
+        includes_state: include_state,
+    })
+}
@@ -0,0 +1,275 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::artifacts::{self, BuildInfoView, Compilation};
+use crate::ast;
+use crate::error::AppError;
+use crate::findings::SourceLocation;
+
+const LOW_LEVEL_CALLS: [&str; 5] = ["call", "delegatecall", "staticcall", "send", "transfer"];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    Contract,
+    AbstractContract,
+    Interface,
+    Library,
+    Function,
+    Modifier,
+    // `address.call` and friends; the target isn't known statically
+    LowLevelCall,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    Inherits,
+    Internal,
+    External,
+    Library,
+    Modifier,
+    LowLevel,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GraphNode {
+    // "Token" for contracts, "Token.transfer" for functions and modifiers
+    pub id: String,
+    pub label: String,
+    pub kind: NodeKind,
+    pub contract: Option<String>,
+    pub visibility: Option<String>,
+    pub dependency: bool,
+    pub location: Option<SourceLocation>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Graph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ContractGraphs {
+    pub inheritance: Graph,
+    pub call_graph: Graph,
+}
+
+#[derive(Default)]
+struct GraphBuilder {
+    nodes: BTreeMap<String, GraphNode>,
+    edges: BTreeSet<(String, String, EdgeKind)>,
+}
+
+impl GraphBuilder {
+    fn add_edge(&mut self, from: &str, to: &str, kind: EdgeKind) {
+        self.edges.insert((from.to_string(), to.to_string(), kind));
+    }
+
+    fn build(self, include_dependencies: bool) -> Graph {
+        let nodes: Vec<GraphNode> = self
+            .nodes
+            .into_values()
+            .filter(|n| include_dependencies || !n.dependency)
+            .collect();
+        let ids: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+        let edges = self
+            .edges
+            .into_iter()
+            .filter(|(from, to, _)| ids.contains(from.as_str()) && ids.contains(to.as_str()))
+            .map(|(from, to, kind)| GraphEdge { from, to, kind })
+            .collect();
+        Graph { nodes, edges }
+    }
+}
+
+fn contract_kind(contract: &Value) -> NodeKind {
+    match ast::str_field(contract, "contractKind") {
+        "interface" => NodeKind::Interface,
+        "library" => NodeKind::Library,
+        _ if contract.get("abstract").and_then(Value::as_bool) == Some(true) => NodeKind::AbstractContract,
+        _ => NodeKind::Contract,
+    }
+}
+
+fn member_name(member: &Value) -> &str {
+    match ast::str_field(member, "kind") {
+        "constructor" | "fallback" | "receive" => ast::str_field(member, "kind"),
+        _ => ast::str_field(member, "name"),
+    }
+}
+
+fn is_dependency(compilation: &Compilation, node: &Value) -> bool {
+    compilation
+        .location(node)
+        .is_some_and(|location| artifacts::is_dependency_source(&location.file))
+}
+
+fn contract_node(graph: &mut GraphBuilder, compilation: &Compilation, contract: &Value) -> String {
+    let name = ast::str_field(contract, "name").to_string();
+    graph.nodes.entry(name.clone()).or_insert_with(|| GraphNode {
+        id: name.clone(),
+        label: name.clone(),
+        kind: contract_kind(contract),
+        contract: None,
+        visibility: None,
+        dependency: is_dependency(compilation, contract),
+        location: compilation.location(contract),
+    });
+    name
+}
+
+// Adds a function or modifier by its definition; the owning contract comes from `scope`
+fn member_node(graph: &mut GraphBuilder, compilation: &Compilation, member: &Value) -> Option<String> {
+    let contract = member.get("scope").and_then(Value::as_i64).and_then(|id| compilation.node(id))?;
+    let contract_name = ast::str_field(contract, "name");
+    let id = format!("{}.{}", contract_name, member_name(member));
+    graph.nodes.entry(id.clone()).or_insert_with(|| GraphNode {
+        id: id.clone(),
+        label: member_name(member).to_string(),
+        kind: if ast::node_type(member) == "ModifierDefinition" { NodeKind::Modifier } else { NodeKind::Function },
+        contract: Some(contract_name.to_string()),
+        visibility: Some(ast::str_field(member, "visibility").to_string()).filter(|v| !v.is_empty()),
+        dependency: is_dependency(compilation, member),
+        location: compilation.location(member),
+    });
+    Some(id)
+}
+
+fn add_calls(graph: &mut GraphBuilder, compilation: &Compilation, from: &str, body: &Value) {
+    ast::walk(body, &mut |node| {
+        if ast::node_type(node) != "FunctionCall" {
+            return;
+        }
+        let Some(mut callee) = node.get("expression") else {
+            return;
+        };
+        if ast::node_type(callee) == "FunctionCallOptions" {
+            callee = callee.get("expression").unwrap_or(callee);
+        }
+
+        let base_type = callee.get("expression").map(ast::type_string).unwrap_or_default();
+        let member = ast::str_field(callee, "memberName");
+        if ast::node_type(callee) == "MemberAccess" && base_type.starts_with("address") && LOW_LEVEL_CALLS.contains(&member) {
+            let id = format!("address.{}", member);
+            graph.nodes.entry(id.clone()).or_insert_with(|| GraphNode {
+                id: id.clone(),
+                label: format!(".{}()", member),
+                kind: NodeKind::LowLevelCall,
+                contract: None,
+                visibility: None,
+                dependency: false,
+                location: None,
+            });
+            graph.add_edge(from, &id, EdgeKind::LowLevel);
+            return;
+        }
+
+        let Some(target) = callee
+            .get("referencedDeclaration")
+            .and_then(Value::as_i64)
+            .and_then(|id| compilation.node(id))
+            .filter(|target| ast::node_type(target) == "FunctionDefinition")
+        else {
+            return;
+        };
+        let is_library = target
+            .get("scope")
+            .and_then(Value::as_i64)
+            .and_then(|id| compilation.node(id))
+            .is_some_and(|c| ast::str_field(c, "contractKind") == "library");
+        let kind = if is_library {
+            EdgeKind::Library
+        } else if ast::node_type(callee) == "MemberAccess" && base_type.starts_with("contract ") {
+            // `token.transfer(...)` or `this.f()`: a message call, not a jump
+            EdgeKind::External
+        } else {
+            EdgeKind::Internal
+        };
+        if let Some(to) = member_node(graph, compilation, target) {
+            graph.add_edge(from, &to, kind);
+        }
+    });
+}
+
+fn add_source(inheritance: &mut GraphBuilder, calls: &mut GraphBuilder, compilation: &Compilation, source_ast: &Value) {
+    for contract in ast::find_all(source_ast, "ContractDefinition") {
+        let name = contract_node(inheritance, compilation, contract);
+
+        let bases = contract.get("baseContracts").and_then(Value::as_array).into_iter().flatten();
+        for base in bases.filter_map(|b| b.pointer("/baseName/referencedDeclaration").and_then(Value::as_i64)) {
+            if let Some(base) = compilation.node(base) {
+                let base_name = contract_node(inheritance, compilation, base);
+                inheritance.add_edge(&name, &base_name, EdgeKind::Inherits);
+            }
+        }
+
+        let members = contract.get("nodes").and_then(Value::as_array).into_iter().flatten();
+        for member in members.filter(|n| matches!(ast::node_type(n), "FunctionDefinition" | "ModifierDefinition")) {
+            let Some(from) = member_node(calls, compilation, member) else {
+                continue;
+            };
+
+            let modifiers = member.get("modifiers").and_then(Value::as_array).into_iter().flatten();
+            for modifier in modifiers.filter_map(|m| m.pointer("/modifierName/referencedDeclaration").and_then(Value::as_i64)) {
+                let target = compilation.node(modifier).filter(|m| ast::node_type(m) == "ModifierDefinition");
+                if let Some(to) = target.and_then(|m| member_node(calls, compilation, m)) {
+                    calls.add_edge(&from, &to, EdgeKind::Modifier);
+                }
+            }
+
+            if let Some(body) = member.get("body").filter(|b| !b.is_null()) {
+                add_calls(calls, compilation, &from, body);
+            }
+        }
+    }
+}
+
+pub fn build_graphs(project_path: &Path, include_dependencies: bool) -> ContractGraphs {
+    let mut inheritance = GraphBuilder::default();
+    let mut calls = GraphBuilder::default();
+    let mut seen = HashSet::new();
+
+    // Newest build-info first; each project source is taken from the latest compile of it
+    for path in artifacts::build_info_files(project_path) {
+        let Some(build_info) = artifacts::read_build_info(&path, BuildInfoView::Sources) else {
+            continue;
+        };
+        let compilation = Compilation::new(&build_info);
+        let sources: Vec<&str> = compilation
+            .source_names()
+            .filter(|name| !artifacts::is_dependency_source(name))
+            .filter(|name| seen.insert(name.to_string()))
+            .collect();
+        for source in sources {
+            if let Some(source_ast) = compilation.source_ast(source) {
+                add_source(&mut inheritance, &mut calls, &compilation, source_ast);
+            }
+        }
+    }
+
+    ContractGraphs {
+        inheritance: inheritance.build(include_dependencies),
+        call_graph: calls.build(include_dependencies),
+    }
+}
+
+#[tauri::command]
+pub async fn get_contract_graphs(project_path: String, include_dependencies: Option<bool>) -> Result<ContractGraphs, AppError> {
+    let root = Path::new(&project_path);
+    if !root.join("artifacts").join("build-info").is_dir() {
+        return Err("No build info found. Compile the project first.".to_string().into());
+    }
+    Ok(build_graphs(root, include_dependencies.unwrap_or(true)))
+}
@@ -0,0 +1,348 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures_util::{SinkExt, StreamExt};
+use rand::RngCore;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::http::HeaderMap;
+use tauri::ipc::{CallbackFn, InvokeBody, InvokeError, InvokeResponse, InvokeResponseBody};
+use tauri::webview::InvokeRequest;
+use tauri::{AppHandle, Context, EventId, Listener, Manager, Url, WebviewUrl, Wry};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+// Headless mode runs the backend behind a hidden blank window and serves the command set
+// on 127.0.0.1, for e2e tests in CI and for scripting:
+// - `POST /invoke/<command>` with the arguments as a JSON object, named like the
+//   frontend's invoke (camelCase), answers `{"result": ...}` or `{"error": ...}`
+// - `GET /ws` upgrades to a WebSocket taking `{"id", "invoke", "args"}` and
+//   `{"subscribe": "<event>"}` messages; replies echo the id and events arrive as
+//   `{"event", "payload"}`
+// Every request needs `Authorization: Bearer <token>` (or `?token=` on the WebSocket URL).
+// Linux still needs a display for the hidden window; use xvfb-run in CI
+// Clear of the Hardhat node (8545), the RPC recorder (8546) and Nitro (8547)
+const DEFAULT_PORT: u16 = 8548;
+const PORT_ENV: &str = "HARDHAT_GUI_HEADLESS_PORT";
+const TOKEN_ENV: &str = "HARDHAT_GUI_HEADLESS_TOKEN";
+// The capability file grants plugin permissions to this window
+const WINDOW_LABEL: &str = "main";
+const MAX_BODY_BYTES: usize = 32 * 1024 * 1024;
+
+pub struct HeadlessConfig {
+    port: u16,
+    token: String,
+    generated_token: bool,
+}
+
+// `--headless` or HARDHAT_GUI_HEADLESS=1
+fn requested() -> Option<HeadlessConfig> {
+    let enabled = std::env::args().any(|arg| arg == "--headless")
+        || std::env::var("HARDHAT_GUI_HEADLESS").is_ok_and(|value| value == "1" || value == "true");
+    if !enabled {
+        return None;
+    }
+    let port = std::env::var(PORT_ENV).ok().and_then(|port| port.parse().ok()).unwrap_or(DEFAULT_PORT);
+    let (token, generated_token) = match std::env::var(TOKEN_ENV) {
+        Ok(token) if !token.is_empty() => (token, false),
+        _ => {
+            let mut bytes = [0u8; 24];
+            rand::thread_rng().fill_bytes(&mut bytes);
+            (bytes.iter().map(|b| format!("{:02x}", b)).collect(), true)
+        }
+    };
+    Some(HeadlessConfig { port, token, generated_token })
+}
+
+// Commands are dispatched through a webview, so one hidden window stays, showing nothing
+pub fn configure(mut context: Context<Wry>) -> (Context<Wry>, Option<HeadlessConfig>) {
+    let Some(config) = requested() else {
+        return (context, None);
+    };
+    let windows = &mut context.config_mut().app.windows;
+    windows.truncate(1);
+    if let Some(window) = windows.first_mut() {
+        window.label = WINDOW_LABEL.to_string();
+        window.url = WebviewUrl::External(Url::parse("about:blank").expect("valid URL"));
+        window.visible = false;
+        window.focus = false;
+    }
+    (context, Some(config))
+}
+
+pub fn start(app: &AppHandle, config: HeadlessConfig) -> Result<(), String> {
+    let listener = std::net::TcpListener::bind(("127.0.0.1", config.port))
+        .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+        .map_err(|e| format!("Failed to listen on port {}: {}", config.port, e))?;
+    // Scripts read these lines to find the server
+    println!("headless: listening on http://127.0.0.1:{}", config.port);
+    if config.generated_token {
+        println!("headless: token {}", config.token);
+    }
+
+    let app = app.clone();
+    let token: Arc<str> = config.token.into();
+    tauri::async_runtime::spawn(async move {
+        let listener = match TcpListener::from_std(listener) {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::error!(error = %e, "headless listener failed");
+                return;
+            }
+        };
+        while let Ok((stream, _)) = listener.accept().await {
+            tauri::async_runtime::spawn(handle_connection(app.clone(), token.clone(), stream));
+        }
+    });
+    Ok(())
+}
+
+// The URL every request claims to come from; it has to be local for the capability to apply
+fn local_url() -> Url {
+    let url = if cfg!(windows) { "http://tauri.localhost" } else { "tauri://localhost" };
+    Url::parse(url).expect("valid URL")
+}
+
+// Goes through the same handler, ACL and audit log as a call from the UI
+async fn invoke(app: &AppHandle, command: &str, args: Value) -> Result<Value, Value> {
+    let webview = app.get_webview(WINDOW_LABEL).ok_or_else(|| json!("The headless window is not open"))?;
+    let request = InvokeRequest {
+        cmd: command.to_string(),
+        callback: CallbackFn(0),
+        error: CallbackFn(1),
+        url: local_url(),
+        body: InvokeBody::Json(args),
+        headers: HeaderMap::new(),
+        invoke_key: app.invoke_key().to_string(),
+    };
+    let (sender, receiver) = oneshot::channel();
+    webview.on_message(
+        request,
+        Box::new(move |_webview, _command, response, _callback, _error| {
+            let _ = sender.send(response);
+        }),
+    );
+    match receiver.await {
+        Ok(InvokeResponse::Ok(InvokeResponseBody::Json(json))) => {
+            Ok(serde_json::from_str(&json).unwrap_or(Value::String(json)))
+        }
+        Ok(InvokeResponse::Ok(InvokeResponseBody::Raw(bytes))) => Ok(Value::from(bytes)),
+        Ok(InvokeResponse::Err(InvokeError(error))) => Err(error),
+        Err(_) => Err(json!(format!("{} was dropped without a response", command))),
+    }
+}
+
+fn reply(result: Result<Value, Value>) -> Value {
+    match result {
+        Ok(result) => json!({ "result": result }),
+        Err(error) => json!({ "error": error }),
+    }
+}
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+async fn read_request(reader: &mut BufReader<TcpStream>) -> Result<Option<HttpRequest>, String> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await.map_err(|e| format!("Failed to read request: {}", e))? == 0 {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_uppercase();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    let path = path.to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await.map_err(|e| format!("Failed to read request: {}", e))? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+    let content_length: usize = match headers.get("content-length") {
+        Some(length) => length.parse().map_err(|_| "Invalid Content-Length".to_string())?,
+        None => 0,
+    };
+    if content_length > MAX_BODY_BYTES {
+        return Err(format!("Request body of {} bytes is too large", content_length));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await.map_err(|e| format!("Failed to read request body: {}", e))?;
+    Ok(Some(HttpRequest { method, path, query, headers, body }))
+}
+
+fn http_response(status: &str, body: &Value) -> Vec<u8> {
+    let body = body.to_string();
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+    .into_bytes()
+}
+
+fn authorized(request: &HttpRequest, token: &str) -> bool {
+    let bearer = request.headers.get("authorization").and_then(|value| value.strip_prefix("Bearer "));
+    bearer.or(request.query.get("token").map(String::as_str)) == Some(token)
+}
+
+async fn handle_connection(app: AppHandle, token: Arc<str>, stream: TcpStream) {
+    let mut reader = BufReader::new(stream);
+    loop {
+        let request = match read_request(&mut reader).await {
+            Ok(Some(request)) => request,
+            Ok(None) => return,
+            Err(e) => {
+                let _ = reader.get_mut().write_all(&http_response("400 Bad Request", &json!({ "error": e }))).await;
+                return;
+            }
+        };
+        if !authorized(&request, &token) {
+            let response = http_response("401 Unauthorized", &json!({ "error": "Missing or wrong token" }));
+            let _ = reader.get_mut().write_all(&response).await;
+            return;
+        }
+
+        let response = match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/ws") => {
+                // The client waits for the handshake before sending frames, so nothing is buffered
+                upgrade(app, reader.into_inner(), &request).await;
+                return;
+            }
+            ("GET", "/health") => http_response("200 OK", &json!({ "ok": true })),
+            ("POST", path) if path.starts_with("/invoke/") => {
+                let command = &path["/invoke/".len()..];
+                let args = if request.body.is_empty() {
+                    Ok(json!({}))
+                } else {
+                    serde_json::from_slice::<Value>(&request.body).map_err(|e| format!("Invalid JSON body: {}", e))
+                };
+                match args {
+                    Ok(args) => match invoke(&app, command, args).await {
+                        Ok(result) => http_response("200 OK", &json!({ "result": result })),
+                        Err(error) => http_response("500 Internal Server Error", &json!({ "error": error })),
+                    },
+                    Err(e) => http_response("400 Bad Request", &json!({ "error": e })),
+                }
+            }
+            _ => http_response("404 Not Found", &json!({ "error": "Not found" })),
+        };
+        if reader.get_mut().write_all(&response).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SocketMessage {
+    id: Option<Value>,
+    invoke: Option<String>,
+    #[serde(default)]
+    args: Option<Value>,
+    subscribe: Option<String>,
+    unsubscribe: Option<String>,
+}
+
+// Tauri panics on event names outside this set
+fn valid_event(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || "-/:_".contains(c))
+}
+
+async fn upgrade(app: AppHandle, mut stream: TcpStream, request: &HttpRequest) {
+    let Some(key) = request.headers.get("sec-websocket-key") else {
+        let _ = stream.write_all(&http_response("400 Bad Request", &json!({ "error": "Expected a WebSocket upgrade" }))).await;
+        return;
+    };
+    let handshake = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(key.as_bytes())
+    );
+    if stream.write_all(handshake.as_bytes()).await.is_err() {
+        return;
+    }
+    let socket = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+    let (mut write, mut read) = socket.split();
+    let (outgoing, mut queued) = mpsc::unbounded_channel::<Value>();
+    let mut subscriptions: HashMap<String, EventId> = HashMap::new();
+
+    loop {
+        tokio::select! {
+            message = queued.recv() => match message {
+                Some(message) => if write.send(Message::Text(message.to_string())).await.is_err() { break },
+                None => break,
+            },
+            frame = read.next() => match frame {
+                Some(Ok(Message::Text(text))) => {
+                    let message = match serde_json::from_str::<SocketMessage>(&text) {
+                        Ok(message) => message,
+                        Err(e) => {
+                            let _ = outgoing.send(json!({ "error": format!("Invalid message: {}", e) }));
+                            continue;
+                        }
+                    };
+                    if let Some(event) = message.subscribe {
+                        if !valid_event(&event) {
+                            let _ = outgoing.send(json!({ "id": message.id, "error": format!("Invalid event name {}", event) }));
+                            continue;
+                        }
+                        if let Entry::Vacant(entry) = subscriptions.entry(event.clone()) {
+                            let sender = outgoing.clone();
+                            let id = app.listen_any(event.clone(), move |emitted| {
+                                let payload = serde_json::from_str::<Value>(emitted.payload()).unwrap_or(Value::Null);
+                                let _ = sender.send(json!({ "event": event, "payload": payload }));
+                            });
+                            entry.insert(id);
+                        }
+                        let _ = outgoing.send(json!({ "id": message.id, "result": true }));
+                    } else if let Some(event) = message.unsubscribe {
+                        let removed = subscriptions.remove(&event);
+                        if let Some(id) = removed {
+                            app.unlisten(id);
+                        }
+                        let _ = outgoing.send(json!({ "id": message.id, "result": removed.is_some() }));
+                    } else if let Some(command) = message.invoke {
+                        // Commands run concurrently; the id ties each reply to its request
+                        let app = app.clone();
+                        let sender = outgoing.clone();
+                        let args = message.args.unwrap_or_else(|| json!({}));
+                        tauri::async_runtime::spawn(async move {
+                            let mut reply = reply(invoke(&app, &command, args).await);
+                            reply["id"] = message.id.unwrap_or(Value::Null);
+                            let _ = sender.send(reply);
+                        });
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    for id in subscriptions.into_values() {
+        app.unlisten(id);
+    }
+}
@@ -0,0 +1,450 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::project;
+use crate::solidity::{self, SolcVersion};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HealthIssue {
+    pub check: String,
+    pub severity: Severity,
+    pub message: String,
+    pub fix: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HealthReport {
+    pub project_path: String,
+    pub healthy: bool,
+    pub error_count: usize,
+    pub warning_count: usize,
+    pub issues: Vec<HealthIssue>,
+}
+
+fn issue(check: &str, severity: Severity, message: String, fix: Option<&str>) -> HealthIssue {
+    HealthIssue {
+        check: check.to_string(),
+        severity,
+        message,
+        fix: fix.map(|f| f.to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn check_project_health(project_path: String) -> Result<HealthReport, AppError> {
+    let root = Path::new(&project_path);
+    if !root.is_dir() {
+        return Err(format!("Project directory not found: {}", project_path).into());
+    }
+
+    let config = project::read_hardhat_config(root);
+    let mut issues = Vec::new();
+
+    check_directories(root, config.is_some(), &mut issues);
+    check_dependencies(root, &mut issues)?;
+    check_env_gitignored(root, &mut issues);
+    check_stale_artifacts(root, &mut issues);
+    if let Some(config) = &config {
+        check_pragmas(root, config, &mut issues);
+        check_networks(root, config, &mut issues)?;
+    }
+
+    // Most severe problems first so the UI can show them at the top
+    issues.sort_by_key(|i| std::cmp::Reverse(i.severity));
+
+    let error_count = issues.iter().filter(|i| i.severity == Severity::Error).count();
+    let warning_count = issues.iter().filter(|i| i.severity == Severity::Warning).count();
+
+    Ok(HealthReport {
+        project_path,
+        healthy: error_count == 0,
+        error_count,
+        warning_count,
+        issues,
+    })
+}
+
+fn check_directories(root: &Path, has_config: bool, issues: &mut Vec<HealthIssue>) {
+    if !has_config {
+        issues.push(issue(
+            "config",
+            Severity::Error,
+            "No hardhat.config file found".to_string(),
+            Some("Create a hardhat.config.js or hardhat.config.ts in the project root"),
+        ));
+    }
+
+    if !root.join("package.json").exists() {
+        issues.push(issue(
+            "directories",
+            Severity::Error,
+            "package.json is missing".to_string(),
+            Some("Run `npm init -y` and install hardhat as a dev dependency"),
+        ));
+    } else if !root.join("node_modules").exists() {
+        issues.push(issue(
+            "directories",
+            Severity::Error,
+            "Dependencies are not installed (node_modules is missing)".to_string(),
+            Some("Run `npm install` in the project directory"),
+        ));
+    }
+
+    for (dir, severity) in [
+        ("contracts", Severity::Warning),
+        ("test", Severity::Info),
+        ("scripts", Severity::Info),
+    ] {
+        if !root.join(dir).is_dir() {
+            issues.push(issue(
+                "directories",
+                severity,
+                format!("The {}/ directory is missing", dir),
+                Some("Create the directory or point the project at the correct root"),
+            ));
+        }
+    }
+}
+
+// An x/X/* standing in for a whole version component (`1.x`, `^2.3.X`, `*`). Git, URL,
+// file and alias specs aren't ranges, whatever letters they contain
+fn has_wildcard(version: &str) -> bool {
+    if version.contains(':') || version.contains('/') {
+        return false;
+    }
+    version
+        .split(|c: char| c == '.' || c.is_whitespace() || "^~<>=|".contains(c))
+        .any(|part| matches!(part, "x" | "X" | "*"))
+}
+
+fn check_dependencies(root: &Path, issues: &mut Vec<HealthIssue>) -> Result<(), String> {
+    let Some(package) = project::read_package_json(root)? else {
+        return Ok(());
+    };
+
+    let mut has_hardhat = false;
+    for section in ["dependencies", "devDependencies"] {
+        let Some(deps) = package.get(section).and_then(|d| d.as_object()) else {
+            continue;
+        };
+
+        for (name, version) in deps {
+            if name == "hardhat" {
+                has_hardhat = true;
+            }
+
+            let version = version.as_str().unwrap_or("").trim();
+            let unpinned = version.is_empty()
+                || version == "*"
+                || version == "latest"
+                || has_wildcard(version)
+                || (version.starts_with('>') && !version.contains('<'));
+
+            if unpinned {
+                issues.push(issue(
+                    "dependencies",
+                    Severity::Warning,
+                    format!("{} uses an unpinned version ({:?}) in {}", name, version, section),
+                    Some("Pin the dependency to a specific version or caret range"),
+                ));
+            }
+        }
+    }
+
+    if !has_hardhat {
+        issues.push(issue(
+            "dependencies",
+            Severity::Warning,
+            "hardhat is not listed in package.json".to_string(),
+            Some("Run `npm install --save-dev hardhat`"),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_env_gitignored(root: &Path, issues: &mut Vec<HealthIssue>) {
+    if !root.join(".env").exists() {
+        return;
+    }
+
+    let gitignore = fs::read_to_string(root.join(".gitignore")).unwrap_or_default();
+    let ignored = gitignore.lines().map(str::trim).any(|line| {
+        matches!(line, ".env" | "/.env" | ".env*" | "*.env" | ".env.*")
+    });
+
+    if !ignored {
+        issues.push(issue(
+            "secrets",
+            Severity::Error,
+            ".env exists but is not listed in .gitignore".to_string(),
+            Some("Add `.env` to .gitignore so private keys and API keys are never committed"),
+        ));
+    }
+}
+
+fn newest_mtime(files: &[std::path::PathBuf]) -> Option<SystemTime> {
+    files
+        .iter()
+        .filter_map(|f| fs::metadata(f).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+fn check_stale_artifacts(root: &Path, issues: &mut Vec<HealthIssue>) {
+    let sources = project::collect_files(&root.join("contracts"), "sol");
+    if sources.is_empty() {
+        return;
+    }
+
+    // build-info is rewritten on every successful compile
+    let build_info = project::collect_files(&root.join("artifacts").join("build-info"), "json");
+    let Some(compiled_at) = newest_mtime(&build_info) else {
+        issues.push(issue(
+            "artifacts",
+            Severity::Info,
+            "Contracts have not been compiled yet".to_string(),
+            Some("Run `npx hardhat compile`"),
+        ));
+        return;
+    };
+
+    let stale: Vec<String> = sources
+        .iter()
+        .filter(|source| {
+            fs::metadata(source)
+                .and_then(|m| m.modified())
+                .map(|modified| modified > compiled_at)
+                .unwrap_or(false)
+        })
+        .map(|source| source.strip_prefix(root).unwrap_or(source).to_string_lossy().to_string())
+        .collect();
+
+    if !stale.is_empty() {
+        issues.push(issue(
+            "artifacts",
+            Severity::Warning,
+            format!("Artifacts are older than {} source file(s): {}", stale.len(), stale.join(", ")),
+            Some("Recompile the project with `npx hardhat compile`"),
+        ));
+    }
+}
+
+fn check_pragmas(root: &Path, config: &str, issues: &mut Vec<HealthIssue>) {
+    let versions = solidity::configured_compiler_versions(config);
+    if versions.is_empty() {
+        return;
+    }
+
+    for source in project::collect_files(&root.join("contracts"), "sol") {
+        let Some(pragma) = fs::read_to_string(&source)
+            .ok()
+            .and_then(|content| solidity::extract_pragma(&content))
+        else {
+            continue;
+        };
+
+        if !versions.iter().any(|v| solidity::pragma_matches(&pragma, *v)) {
+            let configured: Vec<String> = versions.iter().map(SolcVersion::to_string).collect();
+            issues.push(issue(
+                "pragmas",
+                Severity::Error,
+                format!(
+                    "{} requires solidity {} but the config only provides {}",
+                    source.strip_prefix(root).unwrap_or(&source).display(),
+                    pragma,
+                    configured.join(", ")
+                ),
+                Some("Add a matching compiler version to the `solidity` section of hardhat.config"),
+            ));
+        }
+    }
+}
+
+// JS comments removed, leaving "//" inside strings such as URLs alone
+fn strip_comments(source: &str) -> String {
+    let mut code = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut quote = None;
+    while let Some(c) = chars.next() {
+        match (quote, c, chars.peek()) {
+            (Some(open), c, _) => {
+                if c == '\\' {
+                    code.push(c);
+                    code.extend(chars.next());
+                    continue;
+                }
+                if c == open {
+                    quote = None;
+                }
+            }
+            (None, '"' | '\'' | '`', _) => quote = Some(c),
+            (None, '/', Some('/')) => {
+                while chars.next_if(|c| *c != '\n').is_some() {}
+                continue;
+            }
+            (None, '/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+                continue;
+            }
+            _ => {}
+        }
+        code.push(c);
+    }
+    code
+}
+
+// Keys of the config's `networks: { ... }` object. Only its top level counts, so a name
+// that appears in a comment, a string or another object isn't taken as defined
+fn defined_networks(config: &str) -> BTreeSet<String> {
+    let config = strip_comments(config);
+    let start = Regex::new(r#"\bnetworks["'`]?\s*:\s*\{"#).unwrap();
+    let key = Regex::new(r#"["'`]?([A-Za-z0-9_-]+)["'`]?\s*:"#).unwrap();
+
+    let mut names = BTreeSet::new();
+    for found in start.find_iter(&config) {
+        let mut top_level = String::new();
+        let mut depth = 1;
+        let mut quote = None;
+        for c in config[found.end()..].chars() {
+            match (quote, c) {
+                (Some(open), c) if c == open => quote = None,
+                (Some(_), _) => {}
+                (None, '"' | '\'' | '`') => quote = Some(c),
+                (None, '{' | '[' | '(') => depth += 1,
+                (None, '}' | ']' | ')') => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                break;
+            }
+            // Values are dropped along with everything nested, keeping keys and commas
+            if depth == 1 {
+                top_level.push(c);
+            }
+        }
+        let entries = top_level.split(',').filter_map(|entry| key.captures(entry));
+        names.extend(entries.map(|capture| capture[1].to_string()));
+    }
+    names
+}
+
+fn check_networks(root: &Path, config: &str, issues: &mut Vec<HealthIssue>) -> Result<(), String> {
+    let mut referenced = BTreeSet::new();
+    let network_flag = Regex::new(r"--network\s+([A-Za-z0-9_-]+)").unwrap();
+
+    if let Some(package) = project::read_package_json(root)? {
+        if let Some(scripts) = package.get("scripts").and_then(|s| s.as_object()) {
+            for script in scripts.values().filter_map(|s| s.as_str()) {
+                for capture in network_flag.captures_iter(script) {
+                    referenced.insert(capture[1].to_string());
+                }
+            }
+        }
+    }
+
+    let default_network = Regex::new(r#"defaultNetwork\s*:\s*["'`]([A-Za-z0-9_-]+)["'`]"#).unwrap();
+    if let Some(capture) = default_network.captures(config) {
+        referenced.insert(capture[1].to_string());
+    }
+
+    let defined = defined_networks(config);
+    for network in referenced {
+        if network == "hardhat" || network == "localhost" {
+            continue;
+        }
+        if !defined.contains(&network) {
+            issues.push(issue(
+                "networks",
+                Severity::Error,
+                format!("Network \"{}\" is used but not defined in hardhat.config", network),
+                Some("Add an entry for it under `networks` in hardhat.config"),
+            ));
+        }
+    }
+
+    // Networks usually pull RPC URLs and keys from the environment
+    let env_file = fs::read_to_string(root.join(".env")).unwrap_or_default();
+    let env_ref = Regex::new(r"process\.env\.([A-Z0-9_]+)").unwrap();
+    let missing: BTreeSet<String> = env_ref
+        .captures_iter(config)
+        .map(|c| c[1].to_string())
+        .filter(|var| {
+            std::env::var(var).is_err()
+                && !env_file.lines().any(|line| line.trim_start().starts_with(&format!("{}=", var)))
+        })
+        .collect();
+
+    if !missing.is_empty() {
+        issues.push(issue(
+            "networks",
+            Severity::Warning,
+            format!(
+                "hardhat.config reads environment variables that are not set: {}",
+                missing.into_iter().collect::<Vec<_>>().join(", ")
+            ),
+            Some("Define them in the project's .env file"),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards_are_whole_components() {
+        assert!(has_wildcard("1.x"));
+        assert!(has_wildcard("^2.3.X"));
+        assert!(has_wildcard("1.* || 2.x"));
+        assert!(!has_wildcard("^2.19.0"));
+        assert!(!has_wildcard("npm:hardhat-x@1.2.3"));
+        assert!(!has_wildcard("github:user/hardhat-extras"));
+        assert!(!has_wildcard("https://example.com/pkg-x.tgz"));
+        assert!(!has_wildcard("file:../lib-x"));
+    }
+
+    #[test]
+    fn networks_are_read_from_the_networks_object() {
+        let config = r#"
+            // sepolia: { url: "" } was removed
+            const note = "mainnet: soon";
+            module.exports = {
+              solidity: "0.8.24",
+              etherscan: { apiKey: { polygon: "key" } },
+              networks: {
+                "base-sepolia": { url: process.env.BASE_URL, accounts: [process.env.KEY] },
+                arbitrum: {
+                  url: "https://arb1.example/rpc", // optimism: no
+                  /* polygon: { } */
+                  forking: { optimism: true },
+                },
+              },
+            };
+        "#;
+        let names: Vec<String> = defined_networks(config).into_iter().collect();
+        assert_eq!(names, ["arbitrum", "base-sepolia"]);
+    }
+}
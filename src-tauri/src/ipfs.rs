@@ -0,0 +1,179 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use reqwest::multipart::{Form, Part};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::AppError;
+use crate::provider_keys::Provider;
+use crate::rpc;
+
+// Kubo's RPC API, as started by `ipfs daemon` or IPFS Desktop
+const LOCAL_API_URL: &str = "http://127.0.0.1:5001";
+const LOCAL_GATEWAY_URL: &str = "http://127.0.0.1:8080/ipfs";
+const PINATA_API_URL: &str = "https://api.pinata.cloud/pinning";
+const PINATA_GATEWAY_URL: &str = "https://gateway.pinata.cloud/ipfs";
+// A folder of NFT metadata or images; anything bigger is better uploaded with the IPFS CLI
+const MAX_UPLOAD_FILES: usize = 10_000;
+
+// web3.storage's token-based upload API has been shut down, so Pinata is the hosted option
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum IpfsTarget {
+    #[default]
+    Local,
+    Pinata,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct IpfsPin {
+    pub name: String,
+    pub cid: String,
+    pub files: usize,
+    // What goes into tokenURI / baseURI
+    pub uri: String,
+    pub gateway_url: String,
+}
+
+// Files under `path` with their names relative to its parent, so a folder keeps its name
+// as the root of the upload: metadata/1.json -> ipfs://<cid>/1.json
+fn collect_files(path: &Path) -> Result<Vec<(String, PathBuf)>, String> {
+    let base = path.parent().unwrap_or(Path::new(""));
+    let mut files = Vec::new();
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        if current.is_dir() {
+            let entries = fs::read_dir(&current).map_err(|e| format!("Failed to read {}: {}", current.display(), e))?;
+            pending.extend(entries.flatten().map(|entry| entry.path()).filter(|p| {
+                !p.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.'))
+            }));
+        } else {
+            let name = current.strip_prefix(base).unwrap_or(&current).to_string_lossy().replace('\\', "/");
+            files.push((name, current));
+        }
+        if files.len() > MAX_UPLOAD_FILES {
+            return Err(format!("{} has more than {} files", path.display(), MAX_UPLOAD_FILES));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+// Kubo only infers directories from explicit directory parts; Pinata takes file paths
+fn form(files: Vec<(String, Vec<u8>)>, target: IpfsTarget) -> Result<Form, String> {
+    let mut form = Form::new();
+    if target == IpfsTarget::Local {
+        let mut dirs: Vec<String> = files
+            .iter()
+            .flat_map(|(name, _)| {
+                let parts: Vec<&str> = name.split('/').collect();
+                (1..parts.len()).map(move |n| parts[..n].join("/"))
+            })
+            .collect();
+        dirs.sort();
+        dirs.dedup();
+        for dir in dirs {
+            let part = Part::bytes(Vec::new())
+                .file_name(dir)
+                .mime_str("application/x-directory")
+                .map_err(|e| format!("Failed to build upload: {}", e))?;
+            form = form.part("file", part);
+        }
+    }
+    for (name, content) in files {
+        form = form.part("file", Part::bytes(content).file_name(name));
+    }
+    Ok(form)
+}
+
+async fn upload(name: &str, files: Vec<(String, Vec<u8>)>, target: IpfsTarget, api_url: Option<&str>) -> Result<IpfsPin, String> {
+    let count = files.len();
+    let form = form(files, target)?;
+    let (cid, gateway) = match target {
+        IpfsTarget::Local => {
+            let url = format!("{}/api/v0/add", api_url.unwrap_or(LOCAL_API_URL).trim_end_matches('/'));
+            let response = rpc::client()
+                .post(url)
+                .query(&[("pin", "true"), ("cid-version", "1")])
+                .multipart(form)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to reach the IPFS node (is `ipfs daemon` running?): {}", e))?;
+            let status = response.status();
+            let body = response.text().await.map_err(|e| format!("Failed to read IPFS response: {}", e))?;
+            if !status.is_success() {
+                return Err(format!("IPFS add failed: {}", body.trim()));
+            }
+            // One JSON object per added file or directory; the root comes last
+            let root = body
+                .lines()
+                .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+                .find(|entry| entry.get("Name").and_then(Value::as_str) == Some(name))
+                .ok_or("IPFS returned no CID for the upload")?;
+            let cid = root.get("Hash").and_then(Value::as_str).unwrap_or_default().to_string();
+            (cid, LOCAL_GATEWAY_URL)
+        }
+        IpfsTarget::Pinata => {
+            let jwt = Provider::Pinata.key()?.ok_or("No PINATA_JWT is configured")?;
+            let form = form.text("pinataMetadata", json!({ "name": name }).to_string());
+            let response = rpc::client()
+                .post(format!("{}/pinFileToIPFS", PINATA_API_URL))
+                .bearer_auth(jwt)
+                .multipart(form)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to reach Pinata: {}", e))?;
+            let status = response.status();
+            let body: Value = response.json().await.unwrap_or(Value::Null);
+            if !status.is_success() {
+                let message = body.pointer("/error/details").or_else(|| body.get("error")).map(Value::to_string);
+                return Err(format!("Pinata upload failed: {}", message.unwrap_or_else(|| status.to_string())));
+            }
+            let cid = body.get("IpfsHash").and_then(Value::as_str).ok_or("Pinata returned no CID")?.to_string();
+            (cid, PINATA_GATEWAY_URL)
+        }
+    };
+    Ok(IpfsPin {
+        name: name.to_string(),
+        files: count,
+        uri: format!("ipfs://{}", cid),
+        gateway_url: format!("{}/{}", gateway, cid),
+        cid,
+    })
+}
+
+// A single file, or a folder uploaded as one directory CID
+#[tauri::command]
+pub async fn ipfs_add_path(path: String, target: Option<IpfsTarget>, api_url: Option<String>) -> Result<IpfsPin, AppError> {
+    let path = PathBuf::from(path);
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid path: {}", path.display()))?;
+    let mut files = Vec::new();
+    for (file_name, file) in collect_files(&path)? {
+        let content = fs::read(&file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+        files.push((file_name, content));
+    }
+    if files.is_empty() {
+        return Err(format!("{} has no files to upload", path.display()).into());
+    }
+    Ok(upload(&name, files, target.unwrap_or_default(), api_url.as_deref()).await?)
+}
+
+// Token metadata straight from the GUI, e.g. { name, description, image: "ipfs://..." }
+#[tauri::command]
+pub async fn ipfs_add_json(
+    name: String,
+    content: Value,
+    target: Option<IpfsTarget>,
+    api_url: Option<String>,
+) -> Result<IpfsPin, AppError> {
+    let name = if name.ends_with(".json") { name } else { format!("{}.json", name) };
+    if name.contains('/') || name.contains('\\') {
+        return Err(format!("Invalid file name: {}", name).into());
+    }
+    let bytes = serde_json::to_vec_pretty(&content).map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+    Ok(upload(&name, vec![(name.clone(), bytes)], target.unwrap_or_default(), api_url.as_deref()).await?)
+}
@@ -1,15 +1,103 @@
-use std::process::Command;
+use std::collections::BTreeMap;
 use std::path::Path;
-use serde::{Deserialize, Serialize};
-
-#[derive(Serialize, Deserialize)]
-pub struct HardhatStatus {
-    pub installed: bool,
-    pub version: Option<String>,
-    pub project_detected: bool,
-    pub project_path: Option<String>,
-    pub network_running: bool,
-}
+use serde_json::json;
+use tauri::AppHandle;
+
+use crate::error::AppError;
+use crate::messages::Message;
+
+mod abi;
+mod abi_diff;
+mod access_control;
+mod address_book;
+mod advisories;
+mod ansi;
+mod archive;
+mod artifacts;
+mod ast;
+mod audit;
+mod autocomplete;
+mod balances;
+mod benchmark;
+mod bridge;
+mod cancellation;
+mod chain_export;
+mod chain_index;
+mod chain_metrics;
+mod chain_stats;
+mod chainlist;
+mod command_index;
+mod console;
+mod contracts;
+mod dashboard;
+mod db;
+mod decode;
+mod deployments;
+mod devnets;
+mod diagnostics;
+mod discovery;
+mod error;
+mod etherscan;
+mod events;
+mod exclusion;
+mod explorer;
+mod extensions;
+mod external_abis;
+mod external_node;
+mod faucet;
+mod findings;
+mod fixtures;
+mod forms;
+mod fork_diff;
+mod gas_oracle;
+mod gates;
+mod genesis;
+mod graph;
+mod health;
+#[cfg(feature = "headless")]
+mod headless;
+mod ipfs;
+mod licenses;
+mod lint;
+mod logging;
+mod messages;
+mod metrics;
+mod mocks;
+mod mythril;
+mod networks;
+mod node_state;
+mod notifications;
+mod onboarding;
+mod presets;
+mod processes;
+mod progress;
+mod project;
+mod provider_keys;
+mod proxy_audit;
+mod queue;
+mod report;
+mod resolver;
+mod rpc;
+mod rpc_recorder;
+mod scaffold;
+mod scanner;
+mod scheduler;
+mod selectors;
+mod semgrep;
+mod simulate;
+mod solidity;
+mod status;
+mod storage_layout;
+mod store;
+mod stream;
+mod subgraph;
+mod tasks;
+mod tenderly;
+mod time_travel;
+mod transactions;
+mod updates;
+mod utils;
+mod walletconnect;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -17,507 +105,387 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+// Installs hardhat and the toolbox as dev dependencies of the project, with the package
+// manager the project already uses. `global` keeps `npm install -g` for use without a project
 #[tauri::command]
-async fn check_hardhat_status(project_path: Option<String>) -> Result<HardhatStatus, String> {
-    let mut status = HardhatStatus {
-        installed: false,
-        version: None,
-        project_detected: false,
-        project_path: None,
-        network_running: false,
-    };
-
-    // Check if Hardhat is installed globally
-    if let Ok(output) = Command::new("npx").args(["hardhat", "--version"]).output() {
-        if output.status.success() {
-            status.installed = true;
-            if let Ok(version_str) = String::from_utf8(output.stdout) {
-                status.version = Some(version_str.trim().to_string());
+async fn install_hardhat(
+    app: AppHandle,
+    project_path: Option<String>,
+    global: Option<bool>,
+    execution_id: Option<String>,
+) -> Result<Message, AppError> {
+    let (cmd, installed) = match project_path.filter(|_| !global.unwrap_or(false)) {
+        Some(project_path) => {
+            let root = Path::new(&project_path);
+            if !root.join("package.json").exists() {
+                let mut init = resolver::npm();
+                init.args(["init", "-y"]).current_dir(root);
+                let output = processes::output(init)
+                    .await
+                    .map_err(|e| Message::new("install.npm_failed", json!({ "error": e.to_string() })))?;
+                if !output.status.success() {
+                    let error = String::from_utf8_lossy(&output.stderr);
+                    return Err(Message::new("install.package_json_failed", json!({ "error": error })).into());
+                }
             }
+            let package_manager = project::PackageManager::detect(root);
+            let cmd = package_manager.add_dev(root, &["hardhat", "@nomicfoundation/hardhat-toolbox"]);
+            let installed = Message::new(
+                "install.succeeded",
+                json!({ "path": project_path, "package_manager": package_manager.binary() }),
+            );
+            (cmd, installed)
         }
-    }
-
-    // Check if we're in a Hardhat project or if a path was provided
-    let check_path = project_path.as_deref().unwrap_or(".");
-    let hardhat_config = Path::new(check_path).join("hardhat.config.js");
-    let hardhat_config_ts = Path::new(check_path).join("hardhat.config.ts");
-    
-    if hardhat_config.exists() || hardhat_config_ts.exists() {
-        status.project_detected = true;
-        status.project_path = Some(check_path.to_string());
-    }
-
-    // Check if network is running by trying to connect
-    status.network_running = check_network_connection().await;
-
-    Ok(status)
-}
-
-#[tauri::command]
-async fn install_hardhat() -> Result<String, String> {
-    let output = Command::new("npm")
-        .args(["install", "-g", "hardhat"])
-        .output()
-        .map_err(|e| format!("Failed to execute npm: {}", e))?;
+        None => {
+            let mut cmd = resolver::npm();
+            cmd.args(["install", "-g", "hardhat"]);
+            (cmd, Message::new("install.succeeded_global", json!({})))
+        }
+    };
+    let output = processes::run_tracked(&app, execution_id, "install", cmd).await?;
 
     if output.status.success() {
-        Ok("Hardhat installed successfully!".to_string())
+        status::refresh();
+        Ok(installed)
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Failed to install Hardhat: {}", error))
+        Err(Message::new("install.failed", json!({ "error": error })).into())
     }
 }
 
 #[tauri::command]
-async fn create_hardhat_project(project_path: String) -> Result<String, String> {
-    // Create directory if it doesn't exist
-    std::fs::create_dir_all(&project_path)
-        .map_err(|e| format!("Failed to create directory: {}", e))?;
-
-    // Try different approaches to create a Hardhat project non-interactively
-    
-    // First, try with environment variable to force non-interactive mode
-    let mut cmd = Command::new("npx");
-    cmd.args(["hardhat", "init"])
-        .current_dir(&project_path)
-        .env("CI", "true")  // This often forces non-interactive mode
-        .env("HARDHAT_CREATE_JAVASCRIPT_PROJECT_WITH_DEFAULTS", "true");
-    
-    let output = cmd.output()
-        .map_err(|e| format!("Failed to execute hardhat init: {}", e))?;
-
-    if output.status.success() {
-        Ok(format!("Hardhat project created successfully at {}", project_path))
-    } else {
-        let _error = String::from_utf8_lossy(&output.stderr);
-        
-        // If the first approach fails, try the template approach
-        let template_output = Command::new("npx")
-            .args(["create-hardhat"])
-            .current_dir(&project_path)
-            .env("CI", "true")
-            .output();
-            
-        match template_output {
-            Ok(out) if out.status.success() => {
-                Ok(format!("Hardhat project created successfully at {}", project_path))
-            },
-            _ => {
-                // Final fallback: Create a minimal project structure manually
-                create_minimal_hardhat_project(&project_path)?;
-                Ok(format!("Hardhat project created successfully at {} (using fallback method)", project_path))
-            }
-        }
-    }
-}
-
-#[tauri::command]
-async fn start_hardhat_network(project_path: String) -> Result<String, String> {
-    // This will start the network in the background
-    // Note: In a real implementation, you might want to use a more sophisticated
-    // process management approach
-    let _child = Command::new("npx")
-        .args(["hardhat", "node"])
-        .current_dir(&project_path)
-        .spawn()
-        .map_err(|e| format!("Failed to start Hardhat network: {}", e))?;
-
-    Ok("Hardhat network started successfully!".to_string())
-}
-
-async fn check_network_connection() -> bool {
-    // Try to make a simple HTTP request to the Hardhat network
-    // This is a simplified check - in a real implementation you might want to use reqwest
-    use std::net::TcpStream;
-    use std::time::Duration;
-    
-    match TcpStream::connect_timeout(
-        &"127.0.0.1:8545".parse().unwrap(),
-        Duration::from_secs(2)
-    ) {
-        Ok(_) => true,
-        Err(_) => false,
-    }
-}
-
-fn create_minimal_hardhat_project(project_path: &str) -> Result<(), String> {
-    use std::fs;
-    use std::path::Path;
-
-    let project_dir = Path::new(project_path);
-
-    // Create package.json
-    let package_json = r#"{
-  "name": "hardhat-project",
-  "version": "1.0.0",
-  "description": "A Hardhat project created by Hardhat GUI",
-  "scripts": {
-    "test": "npx hardhat test"
-  },
-  "devDependencies": {
-    "@nomicfoundation/hardhat-toolbox": "^5.0.0",
-    "hardhat": "^2.22.0"
-  }
-}
-"#;
-    fs::write(project_dir.join("package.json"), package_json)
-        .map_err(|e| format!("Failed to create package.json: {}", e))?;
-
-    // Create hardhat.config.js
-    let hardhat_config = r#"require("@nomicfoundation/hardhat-toolbox");
-
-/** @type import('hardhat/config').HardhatUserConfig */
-module.exports = {
-  solidity: "0.8.24",
-  networks: {
-    localhost: {
-      url: "http://127.0.0.1:8545"
-    }
-  }
-};
-"#;
-    fs::write(project_dir.join("hardhat.config.js"), hardhat_config)
-        .map_err(|e| format!("Failed to create hardhat.config.js: {}", e))?;
-
-    // Create contracts directory
-    fs::create_dir_all(project_dir.join("contracts"))
-        .map_err(|e| format!("Failed to create contracts directory: {}", e))?;
-
-    // Create a simple contract
-    let contract_content = r#"// SPDX-License-Identifier: MIT
-pragma solidity ^0.8.24;
-
-contract Lock {
-    uint public unlockTime;
-    address payable public owner;
-
-    event Withdrawal(uint amount, uint when);
-
-    constructor(uint _unlockTime) payable {
-        require(
-            block.timestamp < _unlockTime,
-            "Unlock time should be in the future"
-        );
-
-        unlockTime = _unlockTime;
-        owner = payable(msg.sender);
-    }
-
-    function withdraw() public {
-        require(block.timestamp >= unlockTime, "You can't withdraw yet");
-        require(msg.sender == owner, "You aren't the owner");
-
-        emit Withdrawal(address(this).balance, block.timestamp);
-
-        owner.transfer(address(this).balance);
-    }
-}
-"#;
-    fs::write(project_dir.join("contracts").join("Lock.sol"), contract_content)
-        .map_err(|e| format!("Failed to create Lock.sol: {}", e))?;
-
-    // Create test directory
-    fs::create_dir_all(project_dir.join("test"))
-        .map_err(|e| format!("Failed to create test directory: {}", e))?;
-
-    // Create scripts directory
-    fs::create_dir_all(project_dir.join("scripts"))
-        .map_err(|e| format!("Failed to create scripts directory: {}", e))?;
-
-    // Install dependencies
-    let install_output = Command::new("npm")
-        .args(["install"])
-        .current_dir(project_path)
-        .output()
-        .map_err(|e| format!("Failed to install dependencies: {}", e))?;
+async fn start_hardhat_network(
+    app: AppHandle,
+    project_path: String,
+    state: Option<String>,
+    backend: Option<devnets::NodeBackend>,
+    options: Option<devnets::NodeOptions>,
+) -> Result<Message, AppError> {
+    let backend = backend.unwrap_or_default();
+    let _slot = exclusion::acquire(&app, Path::new(&project_path), "start_hardhat_network", "node", &[exclusion::JobGroup::Node, exclusion::JobGroup::Rpc]).await;
+    let node = devnets::start_l1_node(&app, &project_path, backend, &options.unwrap_or_default())
+        .map_err(|e| Message::new("network.start_failed", json!({ "error": e.to_string() })))?;
+    let name = match backend {
+        devnets::NodeBackend::Hardhat => "Hardhat",
+        devnets::NodeBackend::Anvil => "Anvil",
+    };
 
-    if !install_output.status.success() {
-        let error = String::from_utf8_lossy(&install_output.stderr);
-        return Err(format!("Failed to install dependencies: {}", error));
+    // A saved state is written into the fresh node once it answers
+    if let Some(state) = state {
+        let url = &node.chains[0].rpc_url;
+        let info = node_state::restore_on_start(Path::new(&project_path), &state, url).await?;
+        return Ok(Message::new(
+            "network.started_with_state",
+            json!({ "backend": name, "state": info.name, "accounts": info.accounts, "block_number": info.block_number }),
+        ));
     }
 
-    Ok(())
-}
-
-#[tauri::command]
-async fn compile_contracts(project_path: String) -> Result<String, String> {
-    let output = Command::new("npx")
-        .args(["hardhat", "compile"])
-        .current_dir(&project_path)
-        .output()
-        .map_err(|e| format!("Failed to execute hardhat compile: {}", e))?;
-
-    if output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        Ok(format!("Compilation successful!\n{}", stdout))
-    } else {
-        let error = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Compilation failed: {}", error))
-    }
+    Ok(Message::new("network.started", json!({ "backend": name })))
 }
 
 #[tauri::command]
-async fn run_tests(project_path: String) -> Result<String, String> {
-    let output = Command::new("npx")
-        .args(["hardhat", "test"])
-        .current_dir(&project_path)
-        .output()
-        .map_err(|e| format!("Failed to execute hardhat test: {}", e))?;
+async fn compile_contracts(app: AppHandle, project_path: String, execution_id: Option<String>) -> Result<Message, AppError> {
+    error::require_project(&project_path)?;
+    let mut cmd = resolver::hardhat(Path::new(&project_path));
+    cmd.arg("compile");
+    let output = processes::run_tracked(&app, execution_id, "compile", cmd).await?;
+    gates::check_after_run(&app, &project_path);
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    
     if output.status.success() {
-        Ok(format!("Tests completed!\n{}", stdout))
+        Ok(Message::new("compile.succeeded", json!({ "output": stdout })))
     } else {
-        Err(format!("Tests failed: {}\n{}", stderr, stdout))
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        // solc's messages can land on either stream depending on the Hardhat version
+        let diagnostics = solidity::compiler_diagnostics(&format!("{}\n{}", stdout, stderr));
+        Err(AppError::CompileFailed { diagnostics, output: stderr })
     }
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct ContractInfo {
-    pub name: String,
-    pub path: String,
-    pub compiled: bool,
-    pub size: Option<u64>,
-}
-
 #[tauri::command]
-async fn list_contracts(project_path: String) -> Result<Vec<ContractInfo>, String> {
-    use std::fs;
-    
-    let contracts_dir = Path::new(&project_path).join("contracts");
-    let mut contracts = Vec::new();
-    
-    if !contracts_dir.exists() {
-        return Ok(contracts);
-    }
-    
-    let entries = fs::read_dir(&contracts_dir)
-        .map_err(|e| format!("Failed to read contracts directory: {}", e))?;
-    
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
-        let path = entry.path();
-        
-        if path.extension().and_then(|s| s.to_str()) == Some("sol") {
-            let name = path.file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("Unknown")
-                .to_string();
-            
-            let size = entry.metadata().ok().map(|m| m.len());
-            
-            // Check if contract is compiled by looking for artifacts
-            // Hardhat creates artifacts at: artifacts/contracts/{filename.sol}/{ContractName}.json
-            let artifacts_path = Path::new(&project_path)
-                .join("artifacts")
-                .join("contracts")
-                .join(path.file_name().unwrap())
-                .join(format!("{}.json", name));
-            
-            // Also check for dbg.json which is sometimes created
-            let artifacts_dbg_path = Path::new(&project_path)
-                .join("artifacts")
-                .join("contracts")
-                .join(path.file_name().unwrap())
-                .join(format!("{}.dbg.json", name));
-            
-            let is_compiled = artifacts_path.exists() || artifacts_dbg_path.exists();
-            
-            // Debug logging
-            println!("Contract: {}, Artifacts path: {:?}, Exists: {}", 
-                name, artifacts_path, is_compiled);
-            
-            contracts.push(ContractInfo {
-                name,
-                path: path.to_string_lossy().to_string(),
-                compiled: is_compiled,
-                size,
-            });
-        }
-    }
-    
-    Ok(contracts)
-}
-
-#[tauri::command]
-async fn deploy_contracts(project_path: String) -> Result<String, String> {
-    // First check if there are any ignition modules
-    let ignition_dir = Path::new(&project_path).join("ignition").join("modules");
-    
-    if !ignition_dir.exists() {
-        return Err("No Hardhat Ignition modules found. Please create deployment scripts in ignition/modules/".to_string());
-    }
-    
-    // Look for .js or .ts files in ignition/modules
-    let entries = std::fs::read_dir(&ignition_dir)
-        .map_err(|e| format!("Failed to read ignition modules: {}", e))?;
-    
-    let mut module_file = None;
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let path = entry.path();
-        if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
-            if ext == "js" || ext == "ts" {
-                module_file = Some(path);
-                break;
-            }
-        }
-    }
-    
-    let module_path = module_file.ok_or("No deployment modules found in ignition/modules/")?;
-    
-    let output = Command::new("npx")
-        .args(["hardhat", "ignition", "deploy", &module_path.to_string_lossy(), "--network", "localhost"])
-        .current_dir(&project_path)
-        .output()
-        .map_err(|e| format!("Failed to execute deployment: {}", e))?;
+async fn run_tests(app: AppHandle, project_path: String, execution_id: Option<String>) -> Result<Message, AppError> {
+    error::require_project(&project_path)?;
+    let mut cmd = resolver::hardhat(Path::new(&project_path));
+    cmd.arg("test");
+    let output = processes::run_tracked(&app, execution_id, "test", cmd).await?;
+    gates::check_after_run(&app, &project_path);
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
     
     if output.status.success() {
-        Ok(format!("Deployment successful!\n{}", stdout))
+        Ok(Message::new("test.succeeded", json!({ "output": stdout })))
     } else {
-        Err(format!("Deployment failed: {}\n{}", stderr, stdout))
+        Err(Message::new("test.failed", json!({ "stderr": stderr, "stdout": stdout })).into())
     }
 }
 
 #[tauri::command]
-async fn run_hardhat_task(project_path: String, task: String, args: Vec<String>) -> Result<String, String> {
-    let mut cmd_args = vec!["hardhat", &task];
-    for arg in &args {
-        cmd_args.push(arg);
-    }
+async fn deploy_contracts(app: AppHandle, project_path: String, env: Option<BTreeMap<String, String>>, network: Option<String>, execution_id: Option<String>) -> Result<Message, AppError> {
+    let module_path = project::find_ignition_module(Path::new(&project_path))?;
     
-    let output = Command::new("npx")
-        .args(&cmd_args)
-        .current_dir(&project_path)
-        .output()
-        .map_err(|e| format!("Failed to execute hardhat task: {}", e))?;
+    let mut cmd = resolver::hardhat(Path::new(&project_path));
+    cmd.args(["ignition", "deploy", &module_path.to_string_lossy()]);
+    let network = network.as_deref().unwrap_or("localhost");
+    gas_oracle::prefetch(network).await;
+    networks::apply_network(&mut cmd, network);
+    processes::apply_env(&mut cmd, &env.unwrap_or_default())?;
+    let output = processes::run_tracked(&app, execution_id, "deploy", cmd).await?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
     
     if output.status.success() {
-        Ok(stdout.to_string())
+        Ok(Message::new("deploy.succeeded", json!({ "output": stdout })))
     } else {
-        Err(format!("Task failed: {}\n{}", stderr, stdout))
+        Err(Message::new("deploy.failed", json!({ "stderr": stderr, "stdout": stdout })).into())
     }
 }
 
 #[tauri::command]
-async fn run_hardhat_console_command(project_path: String, command: String) -> Result<String, String> {
-    // Create a temporary script file with the command
-    use std::fs;
-    use std::path::Path;
-    
-    let script_content = format!(
-        r#"
-const hre = require("hardhat");
-const {{ ethers }} = require("hardhat");
+async fn run_hardhat_task(app: AppHandle, project_path: String, task: String, args: Vec<String>, env: Option<BTreeMap<String, String>>, execution_id: Option<String>) -> Result<Message, AppError> {
+    let mut cmd = resolver::hardhat(Path::new(&project_path));
+    cmd.arg(&task).args(&args);
+    processes::apply_env(&mut cmd, &env.unwrap_or_default())?;
+    let kind = if task == "verify" { "verify" } else { "task" };
+    let output = processes::run_tracked(&app, execution_id, kind, cmd).await?;
 
-// Make provider available globally for easier access
-const provider = hre.ethers.provider;
-
-// Custom serializer to handle BigInt and other special types
-function serializeResult(obj) {{
-    return JSON.stringify(obj, (key, value) => {{
-        if (typeof value === 'bigint') {{
-            return value.toString() + 'n';
-        }}
-        if (value && typeof value === 'object' && value.constructor && value.constructor.name === 'BigNumber') {{
-            return value.toString() + ' (BigNumber)';
-        }}
-        if (value && typeof value.address === 'string') {{
-            // Handle ethers Signer objects
-            return {{
-                address: value.address,
-                type: 'Signer'
-            }};
-        }}
-        if (value && value._isBigNumber) {{
-            return value.toString() + ' (BigNumber)';
-        }}
-        return value;
-    }}, 2);
-}}
-
-async function main() {{
-    try {{
-        const result = await eval(`(async () => {{ {} }})()`);
-        if (result !== undefined) {{
-            if (typeof result === 'bigint') {{
-                console.log(result.toString());
-            }} else if (Array.isArray(result)) {{
-                console.log('Array with', result.length, 'items:');
-                console.log(serializeResult(result));
-            }} else if (typeof result === 'object' && result !== null) {{
-                console.log(serializeResult(result));
-            }} else {{
-                console.log(result);
-            }}
-        }}
-    }} catch (error) {{
-        console.error("Error:", error.message);
-        if (error.stack) {{
-            console.error("Stack:", error.stack);
-        }}
-        process.exit(1);
-    }}
-}}
-
-main().catch((error) => {{
-    console.error("Fatal error:", error.message);
-    process.exit(1);
-}});
-"#, command
-    );
-    
-    let temp_script = Path::new(&project_path).join("temp_console_script.js");
-    fs::write(&temp_script, script_content)
-        .map_err(|e| format!("Failed to create temp script: {}", e))?;
-    
-    let output = Command::new("npx")
-        .args(["hardhat", "run", "temp_console_script.js", "--network", "localhost"])
-        .current_dir(&project_path)
-        .output()
-        .map_err(|e| format!("Failed to execute console command: {}", e))?;
-    
-    // Clean up temp file
-    let _ = fs::remove_file(&temp_script);
-    
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
     
     if output.status.success() {
-        Ok(stdout.to_string())
+        Ok(Message::new("task.succeeded", json!({ "task": task, "output": stdout })))
     } else {
-        Err(format!("Console command failed: {}\n{}", stderr, stdout))
+        Err(Message::new("task.failed", json!({ "stderr": stderr, "stdout": stdout })).into())
     }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let context = tauri::generate_context!();
+    #[cfg(feature = "headless")]
+    let (context, headless) = headless::configure(context);
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
-        .invoke_handler(tauri::generate_handler![
+        .plugin(tauri_plugin_notification::init())
+        .manage(console::ConsoleSessions::default())
+        .manage(processes::ProcessManager::default())
+        .manage(queue::JobQueue::default())
+        .manage(scheduler::Scheduler::default())
+        .manage(tasks::TaskRegistryCache::default())
+        .manage(autocomplete::AutocompleteCache::default())
+        .manage(chain_metrics::ChainMetrics::default())
+        .manage(notifications::Notifications::default())
+        .setup(move |app| {
+            // Logging is best effort; the app still works without a log file
+            if let Err(e) = logging::init(app.handle()) {
+                eprintln!("{}", e);
+            }
+            if let Err(e) = audit::init(app.handle()) {
+                tracing::warn!(error = %e, "audit log unavailable");
+            }
+            networks::init(app.handle())?;
+            status::init(app.handle());
+            updates::init(app.handle());
+            if let Err(e) = extensions::init(app.handle()) {
+                tracing::warn!(error = %e, "extensions unavailable");
+            }
+            #[cfg(feature = "headless")]
+            if let Some(config) = headless {
+                headless::start(app.handle(), config)?;
+            }
+            Ok(())
+        })
+        .invoke_handler(audit::audited(extensions::dispatch(tauri::generate_handler![
             greet,
-            check_hardhat_status,
+            status::check_hardhat_status,
             install_hardhat,
-            create_hardhat_project,
+            scaffold::create_hardhat_project,
             start_hardhat_network,
             compile_contracts,
             run_tests,
-            list_contracts,
+            contracts::list_contracts,
             deploy_contracts,
             run_hardhat_task,
-            run_hardhat_console_command
-        ])
-        .run(tauri::generate_context!())
+            console::run_hardhat_console_command,
+            health::check_project_health,
+            archive::export_project_archive,
+            tasks::list_hardhat_tasks,
+            tasks::create_hardhat_task,
+            tasks::run_hardhat_task_with_args,
+            console::open_console_session,
+            console::eval_console_session,
+            console::close_console_session,
+            console::get_console_history,
+            autocomplete::get_console_autocomplete,
+            processes::cancel_execution,
+            resolver::get_node_toolchain,
+            lint::run_solhint,
+            lint::set_solhint_rule,
+            lint::format_contracts,
+            mythril::run_mythril,
+            gates::get_gate_thresholds,
+            gates::save_gate_thresholds,
+            gates::evaluate_gates,
+            licenses::scan_licenses,
+            advisories::check_vulnerable_dependencies,
+            scanner::run_ast_scanner,
+            abi_diff::snapshot_abi,
+            abi_diff::list_abi_snapshots,
+            abi_diff::diff_abi,
+            storage_layout::snapshot_storage_layout,
+            storage_layout::diff_storage_layout,
+            proxy_audit::audit_proxies,
+            semgrep::run_semgrep,
+            access_control::get_access_control_matrix,
+            graph::get_contract_graphs,
+            report::generate_report,
+            explorer::get_transaction_details,
+            explorer::decode_calldata,
+            explorer::get_internal_transactions,
+            explorer::search_logs,
+            chain_stats::get_gas_history,
+            chain_stats::get_chain_summary,
+            events::decode_event_logs,
+            address_book::list_address_labels,
+            address_book::set_address_label,
+            address_book::remove_address_label,
+            explorer::get_accounts,
+            simulate::simulate_transaction,
+            chain_export::export_chain_data,
+            fork_diff::get_fork_diff,
+            chain_metrics::start_chain_metrics,
+            chain_metrics::stop_chain_metrics,
+            chain_metrics::get_chain_metrics_stream,
+            node_state::save_node_state,
+            node_state::list_node_states,
+            node_state::load_node_state,
+            node_state::delete_node_state,
+            node_state::prune_node_states,
+            notifications::get_notification_settings,
+            notifications::set_notification_enabled,
+            logging::get_app_logs,
+            logging::get_log_settings,
+            logging::set_log_filter,
+            metrics::get_operation_stats,
+            metrics::get_operation_history,
+            diagnostics::export_diagnostics,
+            audit::get_audit_log,
+            benchmark::run_benchmark,
+            dashboard::get_dashboard_state,
+            networks::list_network_profiles,
+            networks::save_network_profile,
+            networks::remove_network_profile,
+            networks::check_network_profile,
+            chainlist::list_public_chains,
+            chainlist::add_public_chain,
+            provider_keys::list_provider_keys,
+            provider_keys::set_provider_key,
+            provider_keys::remove_provider_key,
+            provider_keys::get_provider_urls,
+            faucet::request_testnet_funds,
+            gas_oracle::get_fee_suggestions,
+            balances::get_cross_network_balances,
+            devnets::start_l2_devnet,
+            devnets::list_local_nodes,
+            devnets::stop_local_node,
+            devnets::get_anvil_status,
+            devnets::install_anvil,
+            external_node::connect_external_node,
+            external_node::disconnect_external_node,
+            external_node::get_external_node,
+            external_node::get_node_capabilities,
+            tenderly::get_tenderly_settings,
+            tenderly::set_tenderly_settings,
+            tenderly::remove_tenderly_settings,
+            tenderly::export_to_tenderly,
+            etherscan::fetch_verified_contract,
+            external_abis::import_external_abi,
+            external_abis::list_external_abis,
+            external_abis::remove_external_abi,
+            external_abis::fetch_external_abi,
+            external_abis::call_external_contract,
+            ipfs::ipfs_add_path,
+            ipfs::ipfs_add_json,
+            subgraph::start_graph_node,
+            subgraph::stop_graph_node,
+            subgraph::build_subgraph,
+            subgraph::deploy_subgraph,
+            subgraph::get_subgraph_status,
+            walletconnect::walletconnect_pair,
+            walletconnect::walletconnect_wait_for_session,
+            walletconnect::get_walletconnect_session,
+            walletconnect::walletconnect_disconnect,
+            walletconnect::walletconnect_send_transaction,
+            walletconnect::walletconnect_sign_message,
+            walletconnect::walletconnect_deploy,
+            utils::convert_units,
+            utils::keccak256_hash,
+            utils::compute_signature_hashes,
+            utils::abi_encode,
+            utils::abi_decode,
+            utils::check_address,
+            transactions::build_raw_transaction,
+            transactions::send_raw_transaction,
+            genesis::export_genesis,
+            bridge::relay_bridge_messages,
+            bridge::bridge_deposit_eth,
+            bridge::bridge_withdraw_eth,
+            rpc_recorder::start_rpc_recorder,
+            rpc_recorder::stop_rpc_recorder,
+            rpc_recorder::get_rpc_recorder_status,
+            rpc_recorder::list_rpc_sessions,
+            rpc_recorder::get_rpc_session,
+            rpc_recorder::replay_rpc_session,
+            processes::get_execution_timeouts,
+            processes::set_execution_timeout,
+            ansi::format_output,
+            queue::enqueue_jobs,
+            queue::list_jobs,
+            queue::cancel_job,
+            queue::clear_finished_jobs,
+            presets::list_task_presets,
+            presets::save_task_preset,
+            presets::delete_task_preset,
+            presets::run_task_preset,
+            scheduler::start_schedule,
+            scheduler::stop_schedule,
+            scheduler::list_schedules,
+            scheduler::remove_schedule,
+            contracts::get_contract_ignore,
+            contracts::set_contract_ignore,
+            exclusion::list_job_slots,
+            artifacts::get_artifact,
+            deployments::get_deployment_history,
+            gates::get_gas_snapshots,
+            queue::start_execution,
+            cancellation::close_project,
+            extensions::list_extensions,
+            extensions::call_extension,
+            extensions::get_sidecar_configs,
+            extensions::save_sidecar_configs,
+            extensions::restart_extension,
+            onboarding::check_environment,
+            onboarding::install_tool,
+            discovery::discover_hardhat_projects,
+            forms::get_contract_forms,
+            mocks::list_mock_presets,
+            mocks::deploy_mock,
+            fixtures::list_fixtures,
+            fixtures::save_fixture,
+            fixtures::delete_fixture,
+            fixtures::build_fixture,
+            fixtures::load_fixture,
+            time_travel::time_travel,
+            command_index::get_command_index,
+            messages::get_message_catalog,
+            updates::check_for_updates,
+            updates::get_last_update_check
+        ])))
+        .run(context)
         .expect("error while running tauri application");
 }
@@ -0,0 +1,172 @@
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::AppError;
+use crate::project;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseCategory {
+    Permissive,
+    WeakCopyleft,
+    StrongCopyleft,
+    // UNLICENSED / proprietary: not licensed for reuse at all
+    Proprietary,
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DependencyLicense {
+    pub name: String,
+    pub version: Option<String>,
+    pub dev: bool,
+    pub license: Option<String>,
+    pub category: LicenseCategory,
+    pub flagged: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SourceLicense {
+    pub file: String,
+    pub license: Option<String>,
+    pub category: LicenseCategory,
+    pub flagged: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LicenseReport {
+    pub flagged_count: usize,
+    pub dependencies: Vec<DependencyLicense>,
+    pub sources: Vec<SourceLicense>,
+}
+
+fn classify_id(id: &str) -> LicenseCategory {
+    let id = id.trim_matches(|c| c == '(' || c == ')').trim().to_uppercase();
+    let id = id.trim_end_matches('+').trim_end_matches("-ONLY").trim_end_matches("-OR-LATER");
+
+    if id == "UNLICENSED" || id.starts_with("SEE LICENSE") || id == "PROPRIETARY" {
+        LicenseCategory::Proprietary
+    } else if id.starts_with("AGPL") || id.starts_with("GPL") || id.starts_with("SSPL") || id.starts_with("BUSL") {
+        LicenseCategory::StrongCopyleft
+    } else if id.starts_with("LGPL") || id.starts_with("MPL") || id.starts_with("EPL") || id.starts_with("CDDL") {
+        LicenseCategory::WeakCopyleft
+    } else if ["MIT", "ISC", "BSD", "APACHE", "UNLICENSE", "0BSD", "CC0", "ZLIB", "WTFPL", "BLUEOAK", "PYTHON"]
+        .iter()
+        .any(|p| id.starts_with(p))
+    {
+        LicenseCategory::Permissive
+    } else {
+        LicenseCategory::Unknown
+    }
+}
+
+// SPDX expressions: with OR the consumer picks the friendliest option, with AND
+// every term applies so the most restrictive one wins
+pub fn classify(expression: Option<&str>) -> LicenseCategory {
+    let Some(expression) = expression.map(str::trim).filter(|e| !e.is_empty()) else {
+        return LicenseCategory::Unknown;
+    };
+
+    expression
+        .split(" OR ")
+        .map(|alternative| {
+            alternative
+                .split(" AND ")
+                .map(classify_id)
+                .max()
+                .unwrap_or(LicenseCategory::Unknown)
+        })
+        .min()
+        .unwrap_or(LicenseCategory::Unknown)
+}
+
+// `license` is usually a string, but old packages use {type} or a `licenses` array
+fn package_license(manifest: &Value) -> Option<String> {
+    match manifest.get("license") {
+        Some(Value::String(license)) => return Some(license.clone()),
+        Some(Value::Object(obj)) => return obj.get("type").and_then(Value::as_str).map(str::to_string),
+        _ => {}
+    }
+    let licenses: Vec<&str> = manifest
+        .get("licenses")?
+        .as_array()?
+        .iter()
+        .filter_map(|l| l.get("type").and_then(Value::as_str))
+        .collect();
+    (!licenses.is_empty()).then(|| licenses.join(" OR "))
+}
+
+fn scan_dependencies(root: &Path) -> Result<Vec<DependencyLicense>, String> {
+    let Some(package) = project::read_package_json(root)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut dependencies = Vec::new();
+    for (section, dev) in [("dependencies", false), ("devDependencies", true)] {
+        let Some(deps) = package.get(section).and_then(Value::as_object) else {
+            continue;
+        };
+        for name in deps.keys() {
+            let manifest: Option<Value> = fs::read_to_string(root.join("node_modules").join(name).join("package.json"))
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok());
+            let license = manifest.as_ref().and_then(package_license);
+            let category = classify(license.as_deref());
+            dependencies.push(DependencyLicense {
+                name: name.clone(),
+                version: manifest
+                    .as_ref()
+                    .and_then(|m| m.get("version"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                dev,
+                license,
+                category,
+                flagged: category != LicenseCategory::Permissive,
+            });
+        }
+    }
+
+    dependencies.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(dependencies)
+}
+
+// Covers the project's own contracts plus vendored libraries under lib/ (forge-style)
+fn scan_sources(root: &Path) -> Vec<SourceLicense> {
+    let re = Regex::new(r"SPDX-License-Identifier:\s*([^\s*]+(?:\s+(?:AND|OR|WITH)\s+[^\s*]+)*)").unwrap();
+
+    let mut files = project::collect_files(&root.join("contracts"), "sol");
+    files.extend(project::collect_files(&root.join("lib"), "sol"));
+
+    files
+        .into_iter()
+        .map(|path| {
+            let license = fs::read_to_string(&path)
+                .ok()
+                .and_then(|source| re.captures(&source).map(|c| c[1].to_string()));
+            let category = classify(license.as_deref());
+            SourceLicense {
+                file: path.strip_prefix(root).unwrap_or(&path).to_string_lossy().to_string(),
+                license,
+                category,
+                flagged: category != LicenseCategory::Permissive,
+            }
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn scan_licenses(project_path: String) -> Result<LicenseReport, AppError> {
+    let root = Path::new(&project_path);
+    let dependencies = scan_dependencies(root)?;
+    let sources = scan_sources(root);
+
+    let flagged_count = dependencies.iter().filter(|d| d.flagged).count()
+        + sources.iter().filter(|s| s.flagged).count();
+
+    Ok(LicenseReport { flagged_count, dependencies, sources })
+}
@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+use crate::error::AppError;
+use crate::findings::{self, AnalysisReport, Finding, SourceLocation};
+use crate::health::Severity;
+use crate::processes;
+use crate::project;
+use crate::resolver;
+
+const SOLHINT_CONFIG: &str = ".solhint.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LintDiagnostic {
+    pub line: u32,
+    pub column: u32,
+    pub severity: Severity,
+    pub rule: String,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FileDiagnostics {
+    pub file: String,
+    pub diagnostics: Vec<LintDiagnostic>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LintReport {
+    pub installed_solhint: bool,
+    pub created_config: bool,
+    pub error_count: usize,
+    pub warning_count: usize,
+    pub files: Vec<FileDiagnostics>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SolhintEntry {
+    file_path: String,
+    line: Option<u32>,
+    column: Option<u32>,
+    severity: Option<String>,
+    rule_id: Option<String>,
+    message: Option<String>,
+}
+
+fn read_solhint_config(root: &Path) -> Result<Option<Value>, String> {
+    let path = root.join(SOLHINT_CONFIG);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", SOLHINT_CONFIG, e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {}: {}", SOLHINT_CONFIG, e))
+}
+
+fn write_solhint_config(root: &Path, config: &Value) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize {}: {}", SOLHINT_CONFIG, e))?;
+    fs::write(root.join(SOLHINT_CONFIG), content + "\n")
+        .map_err(|e| format!("Failed to write {}: {}", SOLHINT_CONFIG, e))
+}
+
+// The json formatter prints one array of reports plus a trailing `conclusion`
+// object; anything solhint logs before it (update notices etc.) is skipped
+// Plugins and npx can print around the report, and messages may contain brackets, so
+// the report is the first line-leading array that parses on its own
+fn solhint_entries(stdout: &str) -> Result<Vec<Value>, String> {
+    let mut error = None;
+    let mut offset = 0;
+    for line in stdout.split_inclusive('\n') {
+        let start = offset + (line.len() - line.trim_start().len());
+        offset += line.len();
+        if !stdout[start..].starts_with('[') {
+            continue;
+        }
+        match serde_json::Deserializer::from_str(&stdout[start..]).into_iter::<Vec<Value>>().next() {
+            Some(Ok(entries)) => return Ok(entries),
+            Some(Err(e)) => error = Some(e),
+            None => {}
+        }
+    }
+    Err(match error {
+        Some(e) => format!("Failed to parse solhint output: {}", e),
+        None => "Solhint produced no JSON output".to_string(),
+    })
+}
+
+fn parse_solhint_output(stdout: &str) -> Result<Vec<FileDiagnostics>, String> {
+    let entries = solhint_entries(stdout)?;
+
+    let mut files: BTreeMap<String, Vec<LintDiagnostic>> = BTreeMap::new();
+    for entry in entries {
+        let Ok(entry) = serde_json::from_value::<SolhintEntry>(entry) else {
+            continue;
+        };
+        let severity = match entry.severity.as_deref() {
+            Some("Error") => Severity::Error,
+            Some("Warning") => Severity::Warning,
+            _ => Severity::Info,
+        };
+        files.entry(entry.file_path).or_default().push(LintDiagnostic {
+            line: entry.line.unwrap_or(0),
+            column: entry.column.unwrap_or(0),
+            severity,
+            rule: entry.rule_id.unwrap_or_default(),
+            message: entry.message.unwrap_or_default(),
+        });
+    }
+
+    Ok(files
+        .into_iter()
+        .map(|(file, mut diagnostics)| {
+            diagnostics.sort_by_key(|d| (d.line, d.column));
+            FileDiagnostics { file, diagnostics }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn run_solhint(app: AppHandle, project_path: String) -> Result<LintReport, AppError> {
+    let root = Path::new(&project_path);
+    let installed_solhint = project::ensure_dev_dependency(&app, &project_path, "solhint", &["solhint"]).await?;
+
+    let created_config = read_solhint_config(root)?.is_none();
+    if created_config {
+        write_solhint_config(root, &json!({ "extends": "solhint:recommended" }))?;
+    }
+
+    let mut cmd = resolver::npx();
+    cmd.args(["solhint", "--formatter", "json", "contracts/**/*.sol"])
+        .current_dir(&project_path);
+    let output = processes::run_tracked(&app, None, "lint", cmd).await?;
+
+    // Solhint exits non-zero whenever it reports errors, so only missing JSON is a failure
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let files = parse_solhint_output(&stdout).map_err(|e| {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        format!("{}\n{}", e, stderr)
+    })?;
+
+    let count = |severity: Severity| {
+        files
+            .iter()
+            .flat_map(|f| &f.diagnostics)
+            .filter(|d| d.severity == severity)
+            .count()
+    };
+
+    // Also kept in the shared findings format so it shows up in the report bundle
+    let lint_findings = files
+        .iter()
+        .flat_map(|f| {
+            f.diagnostics.iter().map(|d| Finding {
+                tool: "solhint".to_string(),
+                rule: d.rule.clone(),
+                swc_id: None,
+                title: d.rule.clone(),
+                severity: d.severity,
+                description: d.message.clone(),
+                contract: None,
+                function: None,
+                locations: vec![SourceLocation { file: f.file.clone(), line: Some(d.line) }],
+            })
+        })
+        .collect();
+    findings::record(root, AnalysisReport::new("solhint", lint_findings))?;
+
+    Ok(LintReport {
+        installed_solhint,
+        created_config,
+        error_count: count(Severity::Error),
+        warning_count: count(Severity::Warning),
+        files,
+    })
+}
+
+// `severity` of None turns the rule off; otherwise "error" or "warn"
+#[tauri::command]
+pub async fn set_solhint_rule(
+    project_path: String,
+    rule: String,
+    severity: Option<String>,
+) -> Result<Value, AppError> {
+    let root = Path::new(&project_path);
+    let level = match severity.as_deref() {
+        None | Some("off") => "off",
+        Some("warn") | Some("warning") => "warn",
+        Some("error") => "error",
+        Some(other) => return Err(format!("Unknown solhint severity: {}", other).into()),
+    };
+
+    let mut config = read_solhint_config(root)?.unwrap_or_else(|| json!({ "extends": "solhint:recommended" }));
+    let rules = config
+        .as_object_mut()
+        .ok_or_else(|| format!("{} must contain a JSON object", SOLHINT_CONFIG))?
+        .entry("rules")
+        .or_insert_with(|| json!({}));
+    let rules = rules
+        .as_object_mut()
+        .ok_or_else(|| format!("\"rules\" in {} must be an object", SOLHINT_CONFIG))?;
+
+    // Rules configured with options (["error", {...}]) keep their options
+    match rules.get_mut(&rule) {
+        Some(Value::Array(parts)) if !parts.is_empty() => parts[0] = json!(level),
+        _ => {
+            rules.insert(rule, json!(level));
+        }
+    }
+
+    write_solhint_config(root, &config)?;
+    Ok(config)
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FormatReport {
+    pub installed_prettier: bool,
+    pub check_only: bool,
+    // Files that differ from prettier's output; in write mode these were rewritten
+    pub changed_files: Vec<String>,
+}
+
+async fn run_prettier(app: &AppHandle, project_path: &str, flag: &str, paths: &[String]) -> Result<std::process::Output, AppError> {
+    let mut cmd = resolver::npx();
+    cmd.args(["prettier", "--plugin=prettier-plugin-solidity", flag])
+        .args(paths)
+        .current_dir(project_path);
+    processes::run_tracked(app, None, "format", cmd).await
+}
+
+#[tauri::command]
+pub async fn format_contracts(
+    app: AppHandle,
+    project_path: String,
+    paths: Option<Vec<String>>,
+    check: Option<bool>,
+) -> Result<FormatReport, AppError> {
+    let check_only = check.unwrap_or(false);
+    let paths = paths
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| vec!["contracts/**/*.sol".to_string()]);
+
+    let installed_prettier = project::ensure_dev_dependency(
+        &app,
+        &project_path,
+        "prettier",
+        &["prettier", "prettier-plugin-solidity"],
+    )
+    .await?;
+
+    // --list-different exits 1 when files differ and 2 on real errors (bad syntax, no matches)
+    let listed = run_prettier(&app, &project_path, "--list-different", &paths).await?;
+    if listed.status.code() != Some(0) && listed.status.code() != Some(1) {
+        let stderr = String::from_utf8_lossy(&listed.stderr);
+        return Err(format!("Prettier failed: {}", stderr).into());
+    }
+
+    let changed_files: Vec<String> = String::from_utf8_lossy(&listed.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if !check_only && !changed_files.is_empty() {
+        let written = run_prettier(&app, &project_path, "--write", &changed_files).await?;
+        if !written.status.success() {
+            let stderr = String::from_utf8_lossy(&written.stderr);
+            return Err(format!("Prettier failed: {}", stderr).into());
+        }
+    }
+
+    Ok(FormatReport { installed_prettier, check_only, changed_files })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_report_between_other_output() {
+        let stdout = "[solhint] loading plugins\n[{\"filePath\":\"contracts/A.sol\",\"line\":3,\"column\":1,\"severity\":\"Warning\",\"ruleId\":\"no-unused-vars\",\"message\":\"Variable [x] is unused\"}]\nDone [1 warning]\n";
+        let files = parse_solhint_output(stdout).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].file, "contracts/A.sol");
+        assert_eq!(files[0].diagnostics[0].message, "Variable [x] is unused");
+        assert_eq!(files[0].diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn reports_missing_output() {
+        assert_eq!(parse_solhint_output("npm ERR! missing script\n").err().as_deref(), Some("Solhint produced no JSON output"));
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::error::AppError;
+use crate::store;
+
+const LOG_FILE_PREFIX: &str = "hardhat-gui.log";
+const DEFAULT_FILTER: &str = "info,tauri_app_lib=debug";
+// Daily files older than this are deleted by the appender
+const MAX_LOG_FILES: usize = 7;
+const DEFAULT_LOG_LIMIT: usize = 500;
+
+struct Logging {
+    dir: PathBuf,
+    filter: reload::Handle<EnvFilter, Registry>,
+    // Dropping the guard stops the background writer, so it lives as long as the app
+    _guard: WorkerGuard,
+}
+
+static LOGGING: OnceLock<Logging> = OnceLock::new();
+
+// Epoch milliseconds, so get_app_logs can compare against `since` without parsing dates
+struct EpochMillis;
+
+impl FormatTime for EpochMillis {
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        write!(w, "{}", store::timestamp_millis())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LogEntry {
+    pub timestamp: u64,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    // Structured fields other than the message, e.g. kind and exit_code
+    pub fields: Value,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LogSettings {
+    pub directory: String,
+    // EnvFilter directives, e.g. "info,tauri_app_lib::rpc=trace"
+    pub filter: String,
+}
+
+fn level_rank(level: &str) -> u8 {
+    match level.to_ascii_uppercase().as_str() {
+        "TRACE" => 0,
+        "DEBUG" => 1,
+        "INFO" => 2,
+        "WARN" => 3,
+        _ => 4,
+    }
+}
+
+// JSON lines in a daily rolling file in the app log dir. RUST_LOG overrides the default
+// filter; set_log_filter changes it at runtime
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to resolve log directory: {}", e))?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create log directory: {}", e))?;
+
+    let appender = tracing_appender::rolling::Builder::new()
+        .rotation(tracing_appender::rolling::Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir)
+        .map_err(|e| format!("Failed to open log file: {}", e))?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let (filter, handle) = reload::Layer::new(filter);
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .flatten_event(true)
+                .with_timer(EpochMillis)
+                .with_writer(writer),
+        )
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .try_init()
+        .map_err(|e| format!("Failed to initialize logging: {}", e))?;
+
+    let _ = LOGGING.set(Logging { dir, filter: handle, _guard: guard });
+    Ok(())
+}
+
+// Rolling log files, oldest first
+pub fn log_files() -> Vec<PathBuf> {
+    let Some(logging) = LOGGING.get() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&logging.dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.file_name().is_some_and(|n| n.to_string_lossy().starts_with(LOG_FILE_PREFIX)))
+        .collect();
+    // Daily files are suffixed with their date, so name order is time order
+    files.sort();
+    files
+}
+
+fn logging() -> Result<&'static Logging, String> {
+    LOGGING.get().ok_or_else(|| "Logging is not initialized".to_string())
+}
+
+fn parse_entry(line: &str) -> Option<LogEntry> {
+    let Value::Object(mut fields) = serde_json::from_str(line).ok()? else {
+        return None;
+    };
+    let mut take = |key: &str| fields.remove(key).and_then(|v| v.as_str().map(str::to_string));
+    let timestamp = take("timestamp")?.parse().ok()?;
+    Some(LogEntry {
+        timestamp,
+        level: take("level").unwrap_or_default(),
+        target: take("target").unwrap_or_default(),
+        message: take("message").unwrap_or_default(),
+        fields: Value::Object(fields),
+    })
+}
+
+// Newest entries last, at most `limit` of them. `level` is the minimum level and
+// `filter` a case-insensitive match on target, message or fields
+#[tauri::command]
+pub async fn get_app_logs(
+    level: Option<String>,
+    since: Option<u64>,
+    filter: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<LogEntry>, AppError> {
+    logging()?;
+    let min_level = level_rank(level.as_deref().unwrap_or("trace"));
+    let filter = filter.map(|f| f.to_lowercase()).filter(|f| !f.is_empty());
+
+    let mut entries = Vec::new();
+    for path in log_files() {
+        let Ok(file) = fs::File::open(&path) else {
+            continue;
+        };
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            let Some(entry) = parse_entry(&line) else {
+                continue;
+            };
+            if level_rank(&entry.level) < min_level || since.is_some_and(|since| entry.timestamp < since) {
+                continue;
+            }
+            if let Some(filter) = &filter {
+                let haystack = format!("{} {} {}", entry.target, entry.message, entry.fields).to_lowercase();
+                if !haystack.contains(filter.as_str()) {
+                    continue;
+                }
+            }
+            entries.push(entry);
+        }
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_LOG_LIMIT);
+    let skip = entries.len().saturating_sub(limit);
+    Ok(entries.split_off(skip))
+}
+
+#[tauri::command]
+pub async fn get_log_settings() -> Result<LogSettings, AppError> {
+    let logging = logging()?;
+    let filter = logging
+        .filter
+        .with_current(|filter| filter.to_string())
+        .map_err(|e| format!("Failed to read log filter: {}", e))?;
+    Ok(LogSettings { directory: logging.dir.to_string_lossy().to_string(), filter })
+}
+
+// Per-module levels use EnvFilter syntax: "warn,tauri_app_lib::processes=debug"
+#[tauri::command]
+pub async fn set_log_filter(filter: String) -> Result<LogSettings, AppError> {
+    let parsed = EnvFilter::try_new(&filter).map_err(|e| format!("Invalid log filter {:?}: {}", filter, e))?;
+    logging()?
+        .filter
+        .reload(parsed)
+        .map_err(|e| format!("Failed to apply log filter: {}", e))?;
+    tracing::info!(filter = %filter, "log filter changed");
+    get_log_settings().await
+}
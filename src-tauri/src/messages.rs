@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::AppError;
+
+// English text for every message key. Placeholders are `{param}`; the frontend renders
+// its own translation of a key with the same params and falls back to `text`.
+//
+// Scope: keyed messages cover the project workflow commands only, i.e. install_hardhat,
+// start_hardhat_network, compile_contracts, run_tests, deploy_contracts, run_hardhat_task,
+// create_hardhat_project, the task commands (list/run/create), the preset commands and the
+// console commands, plus the typed AppError kinds every command can return. Any other
+// command's plain-string error arrives as `error.other` with the English text in
+// `params.message`; the frontend shows it untranslated. A module joins the catalog by
+// returning `Message`s instead of strings
+const CATALOG: &[(&str, &str)] = &[
+    ("error.not_installed", "{tool} is not installed or not on PATH"),
+    ("error.project_not_found", "No Hardhat project found at {path}"),
+    ("error.compile_failed", "Compilation failed: {output}"),
+    ("error.rpc_error", "{message}"),
+    ("error.rpc_unavailable", "{message}"),
+    ("error.process_timeout", "{process_kind} timed out after {seconds}s (execution {execution_id})\n{output}"),
+    ("error.cancelled", "Execution {execution_id} was cancelled"),
+    // Errors that are still plain strings
+    ("error.other", "{message}"),
+    ("install.npm_failed", "Failed to execute npm: {error}"),
+    ("install.package_json_failed", "Failed to create package.json: {error}"),
+    ("install.failed", "Failed to install Hardhat: {error}"),
+    ("install.succeeded", "Hardhat installed in {path} with {package_manager}"),
+    ("install.succeeded_global", "Hardhat installed globally"),
+    ("network.start_failed", "Failed to start local network: {error}"),
+    ("network.started", "{backend} network started successfully!"),
+    (
+        "network.started_with_state",
+        "{backend} network started with state {state} ({accounts} accounts at block {block_number})",
+    ),
+    ("compile.succeeded", "Compilation successful!\n{output}"),
+    ("test.succeeded", "Tests completed!\n{output}"),
+    ("test.failed", "Tests failed: {stderr}\n{stdout}"),
+    ("deploy.succeeded", "Deployment successful!\n{output}"),
+    ("deploy.failed", "Deployment failed: {stderr}\n{stdout}"),
+    ("task.succeeded", "Task {task} completed\n{output}"),
+    ("task.failed", "Task failed: {stderr}\n{stdout}"),
+    ("task.unknown", "Unknown hardhat task: {task}"),
+    ("task.list_failed", "Failed to list hardhat tasks: {error}"),
+    ("task.unknown_param", "Task {task} has no parameter named {param}"),
+    ("task.flag_value", "Flag {param} expects true or false, got {value}"),
+    ("task.missing_param", "Missing required parameter: {param}"),
+    ("task.missing_argument", "Missing required argument: {param}"),
+    ("task.argument_gap", "{param} cannot be set while the earlier argument {previous} is empty"),
+    ("task.invalid_value", "Invalid value for {param} (expected {param_type}): {value}"),
+    ("task.no_config", "No hardhat.config file found in project"),
+    ("task.invalid_name", "Invalid task name: {name}"),
+    ("task.invalid_param_name", "Invalid parameter name \"{param}\": Hardhat expects camelCase names"),
+    ("task.unknown_param_type", "Unknown parameter type \"{param_type}\" for {param}"),
+    ("task.file_exists", "{path} already exists"),
+    ("task.read_failed", "Failed to read {path}: {error}"),
+    ("task.write_failed", "Failed to write {path}: {error}"),
+    ("preset.succeeded", "Preset {name} completed\n{output}"),
+    ("preset.failed", "Preset {name} failed: {stderr}\n{stdout}"),
+    ("preset.not_found", "Preset \"{name}\" not found"),
+    ("preset.name_required", "Preset name cannot be empty"),
+    ("console.start_failed", "Failed to start console session: {error}"),
+    ("console.no_session", "No console session is open for this project"),
+    ("console.eval_failed", "Failed to evaluate console command: {error}"),
+    ("console.command_failed", "Console command failed: {stderr}\n{stdout}"),
+    ("project.created", "Hardhat project created successfully at {path}"),
+    ("project.created_builtin", "Hardhat project created successfully at {path} (using the built-in template)"),
+    ("project.already_exists", "{path} already has a Hardhat config"),
+];
+
+// A user-facing message: a stable key and its params, plus the English rendering so
+// callers that don't localize can show `text` as before
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Message {
+    pub key: String,
+    pub params: BTreeMap<String, Value>,
+    pub text: String,
+}
+
+impl Message {
+    // `params` is a JSON object, e.g. `json!({ "path": project_path })`
+    pub fn new(key: &str, params: Value) -> Self {
+        let params: BTreeMap<String, Value> = match params {
+            Value::Object(map) => map.into_iter().collect(),
+            _ => BTreeMap::new(),
+        };
+        let text = match template(key) {
+            Some(template) => render(template, &params),
+            None => key.to_string(),
+        };
+        Message { key: key.to_string(), params, text }
+    }
+}
+
+impl From<Message> for AppError {
+    fn from(message: Message) -> Self {
+        AppError::Message(message)
+    }
+}
+
+pub fn template(key: &str) -> Option<&'static str> {
+    CATALOG.iter().find(|(k, _)| *k == key).map(|(_, template)| *template)
+}
+
+// Single pass, so braces inside param values are never substituted again. Unknown
+// placeholders are left as they are
+pub fn render(template: &str, params: &BTreeMap<String, Value>) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}').map(|end| (&after[..end], end)) {
+            Some((name, end)) if params.contains_key(name) => {
+                match &params[name] {
+                    Value::String(value) => text.push_str(value),
+                    Value::Null => {}
+                    value => text.push_str(&value.to_string()),
+                }
+                rest = &after[end + 1..];
+            }
+            _ => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+// The English catalog, for building and checking translations
+#[tauri::command]
+pub async fn get_message_catalog() -> Result<BTreeMap<String, String>, AppError> {
+    Ok(CATALOG.iter().map(|(key, template)| (key.to_string(), template.to_string())).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn params(value: Value) -> BTreeMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn render_substitutes_params() {
+        let params = params(json!({ "tool": "npx", "count": 3, "ok": true, "missing": null }));
+        assert_eq!(render("{tool} ran {count} times: {ok}", &params), "npx ran 3 times: true");
+        assert_eq!(render("[{missing}]", &params), "[]");
+    }
+
+    #[test]
+    fn render_leaves_unknown_and_unclosed_placeholders() {
+        let params = params(json!({ "tool": "npx" }));
+        assert_eq!(render("{other} {tool} {", &params), "{other} npx {");
+        assert_eq!(render("{{tool}}", &params), "{npx}");
+    }
+
+    #[test]
+    fn render_does_not_expand_braces_in_values() {
+        let params = params(json!({ "a": "{b}", "b": "x" }));
+        assert_eq!(render("{a} {b}", &params), "{b} x");
+    }
+
+    #[test]
+    fn new_renders_the_catalog_template() {
+        let message = Message::new("error.project_not_found", json!({ "path": "/tmp/app" }));
+        assert_eq!(message.text, "No Hardhat project found at /tmp/app");
+        assert_eq!(Message::new("unknown.key", json!({})).text, "unknown.key");
+    }
+
+    #[test]
+    fn catalog_keys_are_unique() {
+        for (index, (key, _)) in CATALOG.iter().enumerate() {
+            assert!(!CATALOG[index + 1..].iter().any(|(other, _)| other == key), "duplicate key {}", key);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use rusqlite::{params, Connection, Row};
+use serde::{Deserialize, Serialize};
+
+use crate::db;
+use crate::error::AppError;
+
+// Runs compared against the overall average to spot slowdowns
+const RECENT_RUNS: usize = 10;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Success,
+    Failure,
+    Cancelled,
+    TimedOut,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OperationRecord {
+    // compile, test, deploy, rpc_sync, ...
+    pub kind: String,
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub outcome: Outcome,
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OperationStats {
+    pub kind: String,
+    pub count: usize,
+    pub successes: usize,
+    pub failures: usize,
+    pub avg_ms: u64,
+    pub min_ms: u64,
+    pub max_ms: u64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub recent_avg_ms: u64,
+    pub last_run_at: u64,
+}
+
+// Timings never leave the machine; failures to write them are ignored
+pub fn record(
+    project_path: &Path,
+    kind: &str,
+    started_at: u64,
+    duration: Duration,
+    outcome: Outcome,
+    exit_code: Option<i32>,
+) {
+    let record = OperationRecord {
+        kind: kind.to_string(),
+        started_at,
+        duration_ms: duration.as_millis() as u64,
+        outcome,
+        exit_code,
+    };
+    if let Err(e) = db::with(project_path, |conn| insert(conn, &record)) {
+        tracing::debug!(error = %e, "failed to record operation timing");
+    }
+}
+
+pub fn insert(conn: &Connection, record: &OperationRecord) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO operations (kind, started_at, duration_ms, outcome, exit_code) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![record.kind, record.started_at, record.duration_ms, db::enum_to_sql(&record.outcome), record.exit_code],
+    )?;
+    Ok(())
+}
+
+const COLUMNS: &str = "kind, started_at, duration_ms, outcome, exit_code";
+
+fn from_row(row: &Row) -> rusqlite::Result<OperationRecord> {
+    Ok(OperationRecord {
+        kind: row.get(0)?,
+        started_at: row.get(1)?,
+        duration_ms: row.get(2)?,
+        outcome: db::enum_from_sql(row.get(3)?)?,
+        exit_code: row.get(4)?,
+    })
+}
+
+// Oldest first; `limit` keeps the newest runs
+fn query(project_path: &Path, kind: Option<&str>, since: Option<u64>, limit: Option<usize>) -> Result<Vec<OperationRecord>, String> {
+    let mut records = db::with(project_path, |conn| {
+        let sql = format!(
+            "SELECT {} FROM operations WHERE (?1 IS NULL OR kind = ?1) AND started_at >= ?2 ORDER BY id DESC LIMIT ?3",
+            COLUMNS
+        );
+        let limit = limit.map_or(-1, |limit| limit as i64);
+        conn.prepare_cached(&sql)?
+            .query_map(params![kind, since.unwrap_or(0), limit], from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()
+    })?;
+    records.reverse();
+    Ok(records)
+}
+
+pub fn last_run(project_path: &Path, kind: &str) -> Option<OperationRecord> {
+    query(project_path, Some(kind), None, Some(1)).ok()?.pop()
+}
+
+pub fn percentile(sorted: &[u64], p: usize) -> u64 {
+    sorted.get(sorted.len().saturating_sub(1) * p / 100).copied().unwrap_or(0)
+}
+
+fn stats(kind: String, runs: &[&OperationRecord]) -> OperationStats {
+    let mut durations: Vec<u64> = runs.iter().map(|r| r.duration_ms).collect();
+    let recent = &durations[durations.len().saturating_sub(RECENT_RUNS)..];
+    let recent_avg_ms = recent.iter().sum::<u64>() / recent.len().max(1) as u64;
+    let avg_ms = durations.iter().sum::<u64>() / durations.len().max(1) as u64;
+    durations.sort_unstable();
+    OperationStats {
+        kind,
+        count: runs.len(),
+        successes: runs.iter().filter(|r| r.outcome == Outcome::Success).count(),
+        failures: runs.iter().filter(|r| r.outcome != Outcome::Success).count(),
+        avg_ms,
+        min_ms: durations.first().copied().unwrap_or(0),
+        max_ms: durations.last().copied().unwrap_or(0),
+        p50_ms: percentile(&durations, 50),
+        p95_ms: percentile(&durations, 95),
+        recent_avg_ms,
+        last_run_at: runs.last().map_or(0, |r| r.started_at),
+    }
+}
+
+#[tauri::command]
+pub async fn get_operation_stats(
+    project_path: String,
+    kind: Option<String>,
+    since: Option<u64>,
+) -> Result<Vec<OperationStats>, AppError> {
+    let records = query(Path::new(&project_path), kind.as_deref(), since, None)?;
+    let mut by_kind: BTreeMap<String, Vec<&OperationRecord>> = BTreeMap::new();
+    for record in &records {
+        by_kind.entry(record.kind.clone()).or_default().push(record);
+    }
+    Ok(by_kind.into_iter().map(|(kind, runs)| stats(kind, &runs)).collect())
+}
+
+#[tauri::command]
+pub async fn get_operation_history(
+    project_path: String,
+    kind: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<OperationRecord>, AppError> {
+    Ok(query(Path::new(&project_path), kind.as_deref(), None, Some(limit.unwrap_or(100)))?)
+}
@@ -0,0 +1,445 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::address_book;
+use crate::error::AppError;
+use crate::networks;
+use crate::processes;
+use crate::project;
+use crate::resolver;
+use crate::rpc;
+
+// The mocks are real sources in the project, compiled and deployed by Ignition like the
+// user's own modules, so they land in the deployment registry. They live apart from
+// ignition/modules/ so deploy_contracts never picks one up, and use their own deployment
+// id so resetting them never touches the user's deployments
+const SOURCES_DIR: &str = "contracts/hardhat-gui-mocks";
+const MODULES_DIR: &str = "ignition/hardhat-gui-mocks";
+
+const MOCK_ERC20: &str = r#"// SPDX-License-Identifier: MIT
+// Written by Hardhat GUI for local testing: anyone can mint and burn
+pragma solidity ^0.8.0;
+
+contract MockERC20 {
+    string public name;
+    string public symbol;
+    uint8 public immutable decimals;
+    uint256 public totalSupply;
+    mapping(address => uint256) public balanceOf;
+    mapping(address => mapping(address => uint256)) public allowance;
+
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+
+    constructor(string memory name_, string memory symbol_, uint8 decimals_, uint256 initialSupply) {
+        name = name_;
+        symbol = symbol_;
+        decimals = decimals_;
+        _mint(msg.sender, initialSupply);
+    }
+
+    function mint(address to, uint256 amount) external {
+        _mint(to, amount);
+    }
+
+    function burn(address from, uint256 amount) external {
+        balanceOf[from] -= amount;
+        totalSupply -= amount;
+        emit Transfer(from, address(0), amount);
+    }
+
+    function approve(address spender, uint256 amount) external returns (bool) {
+        allowance[msg.sender][spender] = amount;
+        emit Approval(msg.sender, spender, amount);
+        return true;
+    }
+
+    function transfer(address to, uint256 amount) external returns (bool) {
+        _transfer(msg.sender, to, amount);
+        return true;
+    }
+
+    function transferFrom(address from, address to, uint256 amount) external returns (bool) {
+        uint256 allowed = allowance[from][msg.sender];
+        if (allowed != type(uint256).max) {
+            allowance[from][msg.sender] = allowed - amount;
+        }
+        _transfer(from, to, amount);
+        return true;
+    }
+
+    function _transfer(address from, address to, uint256 amount) internal {
+        balanceOf[from] -= amount;
+        balanceOf[to] += amount;
+        emit Transfer(from, to, amount);
+    }
+
+    function _mint(address to, uint256 amount) internal {
+        totalSupply += amount;
+        balanceOf[to] += amount;
+        emit Transfer(address(0), to, amount);
+    }
+}
+"#;
+
+const MOCK_WETH: &str = r#"// SPDX-License-Identifier: MIT
+// Written by Hardhat GUI for local testing: WETH9's interface
+pragma solidity ^0.8.0;
+
+contract MockWETH {
+    string public constant name = "Wrapped Ether";
+    string public constant symbol = "WETH";
+    uint8 public constant decimals = 18;
+    mapping(address => uint256) public balanceOf;
+    mapping(address => mapping(address => uint256)) public allowance;
+
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Deposit(address indexed to, uint256 value);
+    event Withdrawal(address indexed from, uint256 value);
+
+    receive() external payable {
+        deposit();
+    }
+
+    function deposit() public payable {
+        balanceOf[msg.sender] += msg.value;
+        emit Deposit(msg.sender, msg.value);
+    }
+
+    function withdraw(uint256 amount) external {
+        balanceOf[msg.sender] -= amount;
+        payable(msg.sender).transfer(amount);
+        emit Withdrawal(msg.sender, amount);
+    }
+
+    function totalSupply() external view returns (uint256) {
+        return address(this).balance;
+    }
+
+    function approve(address spender, uint256 amount) external returns (bool) {
+        allowance[msg.sender][spender] = amount;
+        emit Approval(msg.sender, spender, amount);
+        return true;
+    }
+
+    function transfer(address to, uint256 amount) external returns (bool) {
+        return transferFrom(msg.sender, to, amount);
+    }
+
+    function transferFrom(address from, address to, uint256 amount) public returns (bool) {
+        if (from != msg.sender && allowance[from][msg.sender] != type(uint256).max) {
+            allowance[from][msg.sender] -= amount;
+        }
+        balanceOf[from] -= amount;
+        balanceOf[to] += amount;
+        emit Transfer(from, to, amount);
+        return true;
+    }
+}
+"#;
+
+const MOCK_PRICE_FEED: &str = r#"// SPDX-License-Identifier: MIT
+// Written by Hardhat GUI for local testing: Chainlink's AggregatorV3Interface, and
+// anyone can post a new answer
+pragma solidity ^0.8.0;
+
+contract MockPriceFeed {
+    uint8 public immutable decimals;
+    string public description;
+    uint256 public constant version = 4;
+
+    uint80 public latestRound;
+    mapping(uint80 => int256) public answers;
+    mapping(uint80 => uint256) public timestamps;
+
+    event AnswerUpdated(int256 indexed current, uint256 indexed roundId, uint256 updatedAt);
+
+    constructor(uint8 decimals_, string memory description_, int256 initialAnswer) {
+        decimals = decimals_;
+        description = description_;
+        updateAnswer(initialAnswer);
+    }
+
+    function updateAnswer(int256 answer) public {
+        latestRound += 1;
+        answers[latestRound] = answer;
+        timestamps[latestRound] = block.timestamp;
+        emit AnswerUpdated(answer, latestRound, block.timestamp);
+    }
+
+    function latestAnswer() external view returns (int256) {
+        return answers[latestRound];
+    }
+
+    function getRoundData(uint80 roundId)
+        public
+        view
+        returns (uint80, int256, uint256, uint256, uint80)
+    {
+        require(timestamps[roundId] != 0, "No data present");
+        return (roundId, answers[roundId], timestamps[roundId], timestamps[roundId], roundId);
+    }
+
+    function latestRoundData() external view returns (uint80, int256, uint256, uint256, uint80) {
+        return getRoundData(latestRound);
+    }
+}
+"#;
+
+// Amounts are in whole units (tokens, dollars) and scaled by `decimals` here
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MockSpec {
+    Erc20 {
+        name: String,
+        symbol: String,
+        decimals: u8,
+        // Minted to the deployer
+        initial_supply: String,
+    },
+    Weth,
+    PriceFeed {
+        // "ETH / USD", as Chainlink names its feeds
+        description: String,
+        decimals: u8,
+        answer: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MockPreset {
+    pub label: String,
+    pub spec: MockSpec,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MockDeployment {
+    pub label: String,
+    pub module_id: String,
+    pub contract_name: String,
+    pub address: String,
+    pub chain_id: u64,
+}
+
+fn erc20(name: &str, symbol: &str, decimals: u8) -> MockPreset {
+    MockPreset {
+        label: symbol.to_string(),
+        spec: MockSpec::Erc20 {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            decimals,
+            initial_supply: "1000000".to_string(),
+        },
+    }
+}
+
+fn presets() -> Vec<MockPreset> {
+    vec![
+        erc20("USD Coin", "USDC", 6),
+        erc20("Tether USD", "USDT", 6),
+        erc20("Dai Stablecoin", "DAI", 18),
+        MockPreset { label: "WETH".to_string(), spec: MockSpec::Weth },
+        MockPreset {
+            label: "ETH / USD feed".to_string(),
+            spec: MockSpec::PriceFeed { description: "ETH / USD".to_string(), decimals: 8, answer: "3000".to_string() },
+        },
+    ]
+}
+
+// "ETH / USD" -> "ETH_USD"; Ignition ids take letters, digits and underscores
+fn identifier(text: &str) -> String {
+    text.split(|c: char| !c.is_ascii_alphanumeric()).filter(|part| !part.is_empty()).collect::<Vec<_>>().join("_")
+}
+
+// "1.5" with 6 decimals -> "1500000"
+fn scale(amount: &str, decimals: u8) -> Result<String, String> {
+    let amount = amount.trim();
+    let (sign, digits) = match amount.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", amount),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let valid = !whole.is_empty() && whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit());
+    if !valid || fraction.len() > decimals as usize {
+        return Err(format!("Invalid amount {} for {} decimals", amount, decimals));
+    }
+    let scaled = format!("{}{:0<width$}", whole, fraction, width = decimals as usize);
+    let scaled = scaled.trim_start_matches('0');
+    Ok(if scaled.is_empty() { "0".to_string() } else { format!("{}{}", sign, scaled) })
+}
+
+impl MockSpec {
+    fn contract_name(&self) -> &'static str {
+        match self {
+            MockSpec::Erc20 { .. } => "MockERC20",
+            MockSpec::Weth => "MockWETH",
+            MockSpec::PriceFeed { .. } => "MockPriceFeed",
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            MockSpec::Erc20 { .. } => MOCK_ERC20,
+            MockSpec::Weth => MOCK_WETH,
+            MockSpec::PriceFeed { .. } => MOCK_PRICE_FEED,
+        }
+    }
+
+    // One module per mock, so several tokens can sit side by side
+    fn module_id(&self) -> String {
+        match self {
+            MockSpec::Erc20 { symbol, .. } => format!("MockERC20_{}", identifier(symbol)),
+            MockSpec::Weth => "MockWETH".to_string(),
+            MockSpec::PriceFeed { description, .. } => format!("MockPriceFeed_{}", identifier(description)),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            MockSpec::Erc20 { symbol, .. } => format!("Mock {}", symbol),
+            MockSpec::Weth => "Mock WETH".to_string(),
+            MockSpec::PriceFeed { description, .. } => format!("Mock {} feed", description),
+        }
+    }
+
+    // Constructor arguments as JavaScript; integers as BigInt literals
+    fn arguments(&self) -> Result<Vec<String>, String> {
+        let string = |text: &str| serde_json::to_string(text).unwrap_or_default();
+        Ok(match self {
+            MockSpec::Erc20 { name, symbol, decimals, initial_supply } => vec![
+                string(name),
+                string(symbol),
+                decimals.to_string(),
+                format!("{}n", scale(initial_supply, *decimals)?),
+            ],
+            MockSpec::Weth => Vec::new(),
+            MockSpec::PriceFeed { description, decimals, answer } => {
+                vec![decimals.to_string(), string(description), format!("{}n", scale(answer, *decimals)?)]
+            }
+        })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            MockSpec::Erc20 { symbol, .. } if identifier(symbol).is_empty() => {
+                Err("The token symbol needs at least one letter or digit".to_string())
+            }
+            MockSpec::Erc20 { initial_supply, .. } if initial_supply.trim().starts_with('-') => {
+                Err("The initial supply can't be negative".to_string())
+            }
+            MockSpec::PriceFeed { description, .. } if identifier(description).is_empty() => {
+                Err("The feed description needs at least one letter or digit".to_string())
+            }
+            MockSpec::Erc20 { decimals, .. } | MockSpec::PriceFeed { decimals, .. } if *decimals > 36 => {
+                Err(format!("{} decimals is more than any real token uses", decimals))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn write_if_changed(path: &Path, content: &str) -> Result<(), String> {
+    if fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+        return Ok(());
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// ESM projects ("type": "module") can't `require` from a .js file
+fn module_extension(root: &Path) -> &'static str {
+    let esm = project::read_package_json(root)
+        .ok()
+        .flatten()
+        .is_some_and(|json| json.get("type").and_then(|kind| kind.as_str()) == Some("module"));
+    if esm {
+        "cjs"
+    } else {
+        "js"
+    }
+}
+
+fn write_module(root: &Path, spec: &MockSpec) -> Result<PathBuf, String> {
+    let contract = spec.contract_name();
+    write_if_changed(&root.join(SOURCES_DIR).join(format!("{}.sol", contract)), spec.source())?;
+
+    let module_id = spec.module_id();
+    let fully_qualified = format!("{}/{}.sol:{}", SOURCES_DIR, contract, contract);
+    let module = format!(
+        "// Written by Hardhat GUI; redeploying the mock rewrites this file\nconst {{ buildModule }} = require(\"@nomicfoundation/hardhat-ignition/modules\");\n\nmodule.exports = buildModule({}, (m) => {{\n  const mock = m.contract({}, [{}], {{ id: {} }});\n  return {{ mock }};\n}});\n",
+        serde_json::to_string(&module_id).unwrap_or_default(),
+        serde_json::to_string(&fully_qualified).unwrap_or_default(),
+        spec.arguments()?.join(", "),
+        serde_json::to_string(contract).unwrap_or_default(),
+    );
+    let path = root.join(MODULES_DIR).join(format!("{}.{}", module_id, module_extension(root)));
+    write_if_changed(&path, &module)?;
+    Ok(path)
+}
+
+fn deployment_id(chain_id: u64) -> String {
+    format!("chain-{}-mocks", chain_id)
+}
+
+fn deployed_addresses(root: &Path, chain_id: u64) -> BTreeMap<String, String> {
+    let path = root.join("ignition").join("deployments").join(deployment_id(chain_id)).join("deployed_addresses.json");
+    fs::read_to_string(path).ok().and_then(|content| serde_json::from_str(&content).ok()).unwrap_or_default()
+}
+
+// A restarted node has none of the recorded mocks, and Ignition would otherwise report
+// them as already deployed
+async fn reset_if_stale(root: &Path, chain_id: u64) -> Result<(), AppError> {
+    for address in deployed_addresses(root, chain_id).values() {
+        if rpc::code_at(rpc::LOCAL_RPC_URL, address).await? == "0x" {
+            let dir = root.join("ignition").join("deployments").join(deployment_id(chain_id));
+            return Ok(fs::remove_dir_all(&dir).map_err(|e| format!("Failed to reset the mock deployments: {}", e))?);
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_mock_presets() -> Result<Vec<MockPreset>, AppError> {
+    Ok(presets())
+}
+
+// Deploys to the local node only; mocks have no business on a real network
+#[tauri::command]
+pub async fn deploy_mock(
+    app: AppHandle,
+    project_path: String,
+    spec: MockSpec,
+    execution_id: Option<String>,
+) -> Result<MockDeployment, AppError> {
+    let root = Path::new(&project_path);
+    spec.validate()?;
+    let chain_id = rpc::chain_id(rpc::LOCAL_RPC_URL).await?;
+    let module_path = write_module(root, &spec)?;
+    reset_if_stale(root, chain_id).await?;
+
+    let mut cmd = resolver::hardhat(root);
+    cmd.args(["ignition", "deploy", &module_path.to_string_lossy(), "--deployment-id", &deployment_id(chain_id)]);
+    networks::apply_network(&mut cmd, "localhost");
+    let output = processes::run_tracked(&app, execution_id, "deploy", cmd).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        return Err(format!("Mock deployment failed: {}\n{}", stderr, stdout).into());
+    }
+
+    let module_id = spec.module_id();
+    let contract_name = spec.contract_name().to_string();
+    let address = deployed_addresses(root, chain_id)
+        .remove(&format!("{}#{}", module_id, contract_name))
+        .ok_or_else(|| format!("Ignition finished but recorded no address for {}", module_id))?;
+    let label = spec.label();
+    address_book::set_address_label(project_path.clone(), address.clone(), label.clone()).await?;
+    Ok(MockDeployment { label, module_id, contract_name, address, chain_id })
+}
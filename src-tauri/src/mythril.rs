@@ -0,0 +1,229 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppError;
+use crate::findings::{self, AnalysisReport, Finding, SourceLocation};
+use crate::health::Severity;
+use crate::processes;
+use crate::project;
+use crate::resolver;
+use crate::solidity;
+use crate::store;
+
+const DOCKER_IMAGE: &str = "mythril/myth";
+const SOLC_SETTINGS_FILE: &str = "mythril-solc.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MythrilProgress {
+    pub execution_id: String,
+    pub contract: String,
+    pub runner: String,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Deserialize)]
+struct MythrilOutput {
+    success: Option<bool>,
+    error: Option<String>,
+    #[serde(default)]
+    issues: Vec<MythrilIssue>,
+}
+
+#[derive(Deserialize)]
+struct MythrilIssue {
+    title: String,
+    #[serde(rename = "swc-id")]
+    swc_id: Option<String>,
+    severity: Option<String>,
+    description: Option<String>,
+    contract: Option<String>,
+    function: Option<String>,
+    filename: Option<String>,
+    lineno: Option<u32>,
+}
+
+enum Runner {
+    Local,
+    Python,
+    Docker,
+}
+
+impl Runner {
+    fn name(&self) -> &'static str {
+        match self {
+            Runner::Local => "local",
+            Runner::Python => "pip",
+            Runner::Docker => "docker",
+        }
+    }
+
+    fn command(&self, project_path: &str) -> Command {
+        match self {
+            Runner::Local => resolver::command("myth"),
+            Runner::Python => {
+                let mut cmd = python();
+                cmd.args(["-m", "mythril"]);
+                cmd
+            }
+            Runner::Docker => {
+                let mut cmd = resolver::command("docker");
+                cmd.args(["run", "--rm", "-v", &format!("{}:/src", project_path), "-w", "/src", DOCKER_IMAGE]);
+                cmd
+            }
+        }
+    }
+}
+
+fn python() -> Command {
+    resolver::command(if cfg!(windows) { "python" } else { "python3" })
+}
+
+async fn python_has_mythril() -> bool {
+    let mut command = python();
+    command.args(["-c", "import mythril"]);
+    processes::output(command).await.is_ok_and(|output| output.status.success())
+}
+
+// Prefer an existing install, then Docker; pip is only used when the caller opts in
+// because it pulls in a large dependency tree
+async fn resolve_runner(app: &AppHandle, install: bool) -> Result<Runner, AppError> {
+    if resolver::locate("myth").is_some() {
+        return Ok(Runner::Local);
+    }
+    if python_has_mythril().await {
+        return Ok(Runner::Python);
+    }
+    if resolver::locate("docker").is_some() {
+        return Ok(Runner::Docker);
+    }
+    if !install {
+        return Err("Mythril is not installed. Install it with `pip install mythril`, install Docker, or retry with install enabled".to_string().into());
+    }
+
+    let mut cmd = python();
+    cmd.args(["-m", "pip", "install", "--user", "mythril"]);
+    let output = processes::run_tracked(app, None, "install", cmd).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to install mythril: {}", stderr).into());
+    }
+    Ok(Runner::Python)
+}
+
+// Hardhat resolves imports from node_modules; solc needs explicit remappings for that
+fn write_solc_settings(project_path: &Path) -> Result<String, String> {
+    let mut remappings = Vec::new();
+    if let Ok(entries) = fs::read_dir(project_path.join("node_modules")) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with('.') && entry.path().is_dir() {
+                remappings.push(format!("{}/=node_modules/{}/", name, name));
+            }
+        }
+    }
+    remappings.sort();
+
+    let settings = serde_json::json!({ "remappings": remappings });
+    store::save_json(project_path, SOLC_SETTINGS_FILE, &settings)?;
+    Ok(format!("{}/{}", store::STORE_DIR, SOLC_SETTINGS_FILE))
+}
+
+fn to_finding(issue: MythrilIssue) -> Finding {
+    let severity = match issue.severity.as_deref() {
+        Some("High") => Severity::Error,
+        Some("Medium") => Severity::Warning,
+        _ => Severity::Info,
+    };
+    Finding {
+        tool: "mythril".to_string(),
+        rule: issue.swc_id.clone().map(|id| format!("SWC-{}", id)).unwrap_or_else(|| issue.title.clone()),
+        swc_id: issue.swc_id,
+        title: issue.title,
+        severity,
+        description: issue.description.unwrap_or_default(),
+        contract: issue.contract,
+        function: issue.function,
+        locations: issue
+            .filename
+            .map(|file| vec![SourceLocation { file, line: issue.lineno }])
+            .unwrap_or_default(),
+    }
+}
+
+#[tauri::command]
+pub async fn run_mythril(
+    app: AppHandle,
+    project_path: String,
+    contract: String,
+    timeout_secs: Option<u64>,
+    install: Option<bool>,
+    execution_id: Option<String>,
+) -> Result<AnalysisReport, AppError> {
+    let root = Path::new(&project_path);
+    if !root.join(&contract).is_file() {
+        return Err(format!("Contract file not found: {}", contract).into());
+    }
+
+    let runner = resolve_runner(&app, install.unwrap_or(false)).await?;
+    let solc_settings = write_solc_settings(root)?;
+    let timeout = timeout_secs.unwrap_or(300).to_string();
+
+    let mut cmd = runner.command(&project_path);
+    cmd.args(["analyze", &contract, "-o", "json", "--execution-timeout", &timeout, "--solc-json", &solc_settings])
+        .current_dir(&project_path);
+
+    // Mythril picks a default solc otherwise, which rarely matches the project
+    if let Some(version) = project::read_hardhat_config(root)
+        .map(|config| solidity::configured_compiler_versions(&config))
+        .and_then(|versions| versions.last().copied())
+    {
+        cmd.args(["--solv", &version.to_string()]);
+    }
+
+    // Analysis can run for minutes with no output, so report elapsed time meanwhile
+    let execution_id = execution_id.unwrap_or_else(|| format!("mythril-{}", store::timestamp_millis()));
+    let progress = {
+        let app = app.clone();
+        let progress = MythrilProgress {
+            execution_id: execution_id.clone(),
+            contract: contract.clone(),
+            runner: runner.name().to_string(),
+            elapsed_ms: 0,
+        };
+        tauri::async_runtime::spawn(async move {
+            let started = Instant::now();
+            loop {
+                let _ = app.emit(
+                    "mythril-progress",
+                    MythrilProgress { elapsed_ms: started.elapsed().as_millis() as u64, ..progress.clone() },
+                );
+                tokio::time::sleep(Duration::from_secs(2)).await;
+            }
+        })
+    };
+
+    let output = processes::run_tracked(&app, Some(execution_id), "mythril", cmd).await;
+    progress.abort();
+    let output = output?;
+
+    // Mythril exits non-zero when it finds issues, so the JSON decides success
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = stdout.lines().rev().find(|line| line.trim_start().starts_with('{'));
+    let parsed: MythrilOutput = json
+        .and_then(|line| serde_json::from_str(line).ok())
+        .ok_or_else(|| {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            format!("Mythril produced no results: {}\n{}", stderr, stdout)
+        })?;
+
+    if parsed.success == Some(false) {
+        return Err(format!("Mythril failed: {}", parsed.error.unwrap_or_default()).into());
+    }
+
+    Ok(findings::record(root, AnalysisReport::new("mythril", parsed.issues.into_iter().map(to_finding).collect()))?)
+}
@@ -0,0 +1,244 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::chain_index;
+use crate::error::AppError;
+use crate::gas_oracle;
+use crate::provider_keys;
+use crate::rpc::{self, RpcPolicy};
+use crate::store;
+
+const PROFILES_FILE: &str = "network-profiles.json";
+const LOCALHOST: &str = "localhost";
+
+// Profiles belong to the app, not a project, so they live in the app data dir
+static PROFILES_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+// Where transactions sent on this network get signed
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SignerSource {
+    // Unlocked accounts of the node itself (eth_accounts)
+    NodeAccounts,
+    // Names of environment variables, never the secrets themselves
+    EnvPrivateKey { variable: String },
+    EnvMnemonic { variable: String },
+    // The user's own wallet, over a WalletConnect session
+    WalletConnect,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NetworkProfile {
+    pub name: String,
+    pub rpc_url: String,
+    pub chain_id: u64,
+    pub currency_symbol: String,
+    pub explorer_url: Option<String>,
+    pub signer: SignerSource,
+    #[serde(default)]
+    pub rpc_policy: RpcPolicy,
+    #[serde(default)]
+    pub created_at: u64,
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct NetworkCheck {
+    pub name: String,
+    pub reachable: bool,
+    pub chain_id: Option<u64>,
+    pub chain_id_matches: bool,
+    pub block_number: Option<u64>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+fn localhost() -> NetworkProfile {
+    NetworkProfile {
+        name: LOCALHOST.to_string(),
+        rpc_url: rpc::LOCAL_RPC_URL.to_string(),
+        chain_id: 31337,
+        currency_symbol: "ETH".to_string(),
+        explorer_url: None,
+        signer: SignerSource::NodeAccounts,
+        rpc_policy: RpcPolicy::default(),
+        created_at: 0,
+        updated_at: 0,
+    }
+}
+
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?;
+    let _ = PROFILES_PATH.set(dir.join(PROFILES_FILE));
+    // A broken profiles file is reported when the profiles are listed, not at startup
+    if let Ok(profiles) = load_profiles() {
+        register_policies(&profiles);
+    }
+    Ok(())
+}
+
+// The rpc module only sees URLs, so policies are keyed by each profile's endpoint
+fn register_policies(profiles: &[NetworkProfile]) {
+    let by_url: HashMap<String, RpcPolicy> = profiles
+        .iter()
+        .map(|p| (provider_keys::expand(&p.rpc_url), p.rpc_policy.clone()))
+        .collect();
+    rpc::set_policies(by_url);
+}
+
+fn profiles_path() -> Result<&'static PathBuf, String> {
+    PROFILES_PATH.get().ok_or_else(|| "Network profiles are not initialized".to_string())
+}
+
+// Saved profiles plus the built-in localhost one, unless the user replaced it
+pub fn load_profiles() -> Result<Vec<NetworkProfile>, String> {
+    let path = profiles_path()?;
+    let mut profiles: Vec<NetworkProfile> = if path.exists() {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", PROFILES_FILE, e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", PROFILES_FILE, e))?
+    } else {
+        Vec::new()
+    };
+    if !profiles.iter().any(|p| p.name == LOCALHOST) {
+        profiles.insert(0, localhost());
+    }
+    Ok(profiles)
+}
+
+fn save_profiles(profiles: &[NetworkProfile]) -> Result<(), String> {
+    let path = profiles_path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(profiles)
+        .map_err(|e| format!("Failed to serialize {}: {}", PROFILES_FILE, e))?;
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, content).map_err(|e| format!("Failed to write {}: {}", PROFILES_FILE, e))?;
+    fs::rename(&temp, path).map_err(|e| format!("Failed to write {}: {}", PROFILES_FILE, e))?;
+    register_policies(profiles);
+    Ok(())
+}
+
+pub fn find_profile(name: &str) -> Option<NetworkProfile> {
+    load_profiles().ok()?.into_iter().find(|p| p.name == name)
+}
+
+// Hardhat only accepts networks defined in hardhat.config, so a profile is passed by
+// name and its endpoint exported for configs that read it:
+// `url: process.env.HARDHAT_GUI_RPC_URL`
+pub fn apply_network(cmd: &mut Command, network: &str) {
+    cmd.args(["--network", network]);
+    cmd.envs(profile_env(network));
+}
+
+// Provider keys and fee suggestions go along too, for configs that read them from process.env
+pub fn profile_env(network: &str) -> BTreeMap<String, String> {
+    let mut env = provider_keys::env();
+    if let Some(profile) = find_profile(network) {
+        env.insert("HARDHAT_GUI_NETWORK".to_string(), profile.name);
+        env.insert("HARDHAT_GUI_RPC_URL".to_string(), provider_keys::expand(&profile.rpc_url));
+        env.insert("HARDHAT_GUI_CHAIN_ID".to_string(), profile.chain_id.to_string());
+        env.extend(gas_oracle::env(network));
+    }
+    env
+}
+
+fn validate(profile: &NetworkProfile) -> Result<(), String> {
+    let valid_name = !profile.name.is_empty()
+        && profile.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid_name {
+        return Err(format!("Invalid network name {:?}: use letters, digits, '-' and '_'", profile.name));
+    }
+    if !["http://", "https://", "ws://", "wss://"].iter().any(|s| profile.rpc_url.starts_with(s)) {
+        return Err(format!("Invalid RPC URL for {}: {}", profile.name, profile.rpc_url));
+    }
+    if profile.chain_id == 0 {
+        return Err(format!("Network {} needs a chain id", profile.name));
+    }
+    let variable = match &profile.signer {
+        SignerSource::NodeAccounts | SignerSource::WalletConnect => None,
+        SignerSource::EnvPrivateKey { variable } | SignerSource::EnvMnemonic { variable } => Some(variable),
+    };
+    if variable.is_some_and(|v| v.is_empty() || v.contains('=')) {
+        return Err(format!("Invalid signer variable for {}", profile.name));
+    }
+    profile.rpc_policy.validate().map_err(|e| format!("{} ({})", e, profile.name))
+}
+
+#[tauri::command]
+pub async fn list_network_profiles() -> Result<Vec<NetworkProfile>, AppError> {
+    Ok(load_profiles()?)
+}
+
+// Creates the profile or replaces the one with the same name
+#[tauri::command]
+pub async fn save_network_profile(mut profile: NetworkProfile) -> Result<NetworkProfile, AppError> {
+    validate(&profile)?;
+    let mut profiles = load_profiles()?;
+    let now = store::timestamp_millis();
+    profile.updated_at = now;
+    match profiles.iter_mut().find(|p| p.name == profile.name) {
+        Some(existing) => {
+            profile.created_at = existing.created_at;
+            *existing = profile.clone();
+        }
+        None => {
+            profile.created_at = now;
+            profiles.push(profile.clone());
+        }
+    }
+    save_profiles(&profiles)?;
+    Ok(profile)
+}
+
+// Removing localhost restores the built-in default
+#[tauri::command]
+pub async fn remove_network_profile(name: String) -> Result<bool, AppError> {
+    let mut profiles = load_profiles()?;
+    let before = profiles.len();
+    profiles.retain(|p| p.name != name);
+    let removed = profiles.len() != before;
+    if removed {
+        save_profiles(&profiles)?;
+    }
+    Ok(removed)
+}
+
+// Confirms the endpoint answers and serves the chain the profile claims
+#[tauri::command]
+pub async fn check_network_profile(name: String) -> Result<NetworkCheck, AppError> {
+    let profile = find_profile(&name).ok_or_else(|| format!("Unknown network profile: {}", name))?;
+    let url = provider_keys::expand(&profile.rpc_url);
+    let started = Instant::now();
+    let mut check = NetworkCheck {
+        name,
+        reachable: false,
+        chain_id: None,
+        chain_id_matches: false,
+        block_number: None,
+        latency_ms: None,
+        error: None,
+    };
+    match rpc::chain_id(&url).await {
+        Ok(chain_id) => {
+            check.latency_ms = Some(started.elapsed().as_millis() as u64);
+            check.reachable = true;
+            check.chain_id = Some(chain_id);
+            check.chain_id_matches = chain_id == profile.chain_id;
+            check.block_number = chain_index::block_number(&url).await.ok();
+        }
+        Err(e) => check.error = Some(e.to_string()),
+    }
+    Ok(check)
+}
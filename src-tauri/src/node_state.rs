@@ -0,0 +1,244 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+use crate::chain_index;
+use crate::error::AppError;
+use crate::exclusion::{self, JobGroup};
+use crate::fork_diff;
+use crate::rpc;
+use crate::store;
+
+const STATES_DIR: &str = "node-states";
+// `hardhat node` takes a few seconds to compile and bind before a state can be loaded
+const NODE_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StoredAccount {
+    pub address: String,
+    // Hex quantities, as returned by the node
+    pub balance: String,
+    pub nonce: String,
+    pub code: String,
+    // Slot -> 32-byte word, both 0x-prefixed
+    #[serde(default)]
+    pub storage: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NodeStateInfo {
+    pub name: String,
+    pub created_at: u64,
+    pub chain_id: u64,
+    pub block_number: u64,
+    pub accounts: usize,
+    pub slots: usize,
+    // False when some transactions couldn't be traced or predate the chain index,
+    // so part of the state wasn't captured
+    pub complete: bool,
+    #[serde(default)]
+    pub size_bytes: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct NodeState {
+    info: NodeStateInfo,
+    accounts: Vec<StoredAccount>,
+}
+
+fn states_dir(project_path: &Path) -> PathBuf {
+    store::project_store_dir(project_path).join(STATES_DIR)
+}
+
+fn state_path(project_path: &Path, name: &str) -> Result<PathBuf, String> {
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("Invalid state name {:?}: use letters, digits, '-' and '_'", name));
+    }
+    Ok(states_dir(project_path).join(format!("{}.json", name)))
+}
+
+fn read_state(project_path: &Path, name: &str) -> Result<NodeState, String> {
+    let path = state_path(project_path, name)?;
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read state {}: {}", name, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse state {}: {}", name, e))
+}
+
+// The fork block, or genesis on a plain local chain, is what the saved changes build on
+pub async fn chain_base(url: &str) -> Result<(u64, String), AppError> {
+    let fork = rpc::call(url, "hardhat_metadata", json!([]))
+        .await
+        .ok()
+        .and_then(|metadata| metadata.get("forkedNetwork").cloned())
+        .filter(|fork| !fork.is_null());
+    if let Some(fork) = fork {
+        let block = fork.get("forkBlockNumber").and_then(Value::as_u64).unwrap_or(0);
+        let hash = fork.get("forkBlockHash").and_then(Value::as_str).unwrap_or_default();
+        return Ok((block, hash.to_string()));
+    }
+    let genesis = rpc::call(url, "eth_getBlockByNumber", json!(["0x0", false])).await?;
+    Ok((0, genesis.get("hash").and_then(Value::as_str).unwrap_or_default().to_string()))
+}
+
+// Storage positions are quantities, so without the leading zeros tracers pad them with
+fn slot_position(slot: &str) -> String {
+    let digits = slot.trim_start_matches("0x").trim_start_matches('0');
+    format!("0x{}", if digits.is_empty() { "0" } else { digits })
+}
+
+pub async fn read_account(url: &str, address: &str, slots: &[String]) -> Result<StoredAccount, AppError> {
+    let balance = rpc::call(url, "eth_getBalance", json!([address, "latest"])).await?;
+    let nonce = rpc::call(url, "eth_getTransactionCount", json!([address, "latest"])).await?;
+    let mut storage = BTreeMap::new();
+    for slot in slots {
+        storage.insert(slot.clone(), format!("0x{}", rpc::storage_at(url, address, &slot_position(slot)).await?));
+    }
+    Ok(StoredAccount {
+        address: address.to_string(),
+        balance: balance.as_str().unwrap_or("0x0").to_string(),
+        nonce: nonce.as_str().unwrap_or("0x0").to_string(),
+        code: rpc::code_at(url, address).await?,
+        storage,
+    })
+}
+
+// Writes the saved accounts into a running node and mines up to the saved height.
+// Timestamps aren't restored: the node's clock only moves forward
+pub async fn restore(project_path: &Path, name: &str, url: &str) -> Result<NodeStateInfo, AppError> {
+    let state = read_state(project_path, name)?;
+    let chain_id = rpc::chain_id(url).await?;
+    if chain_id != state.info.chain_id {
+        return Err(format!(
+            "State {} was saved on chain {}, the node is chain {}",
+            name, state.info.chain_id, chain_id
+        ).into());
+    }
+
+    for account in &state.accounts {
+        rpc::call(url, "hardhat_setBalance", json!([account.address, account.balance])).await?;
+        rpc::call(url, "hardhat_setCode", json!([account.address, account.code])).await?;
+        // Nonces can't go down; a fresh node is at zero anyway
+        let _ = rpc::call(url, "hardhat_setNonce", json!([account.address, account.nonce])).await;
+        for (slot, value) in &account.storage {
+            rpc::call(url, "hardhat_setStorageAt", json!([account.address, slot_position(slot), value])).await?;
+        }
+    }
+
+    let head = chain_index::block_number(url).await?;
+    if state.info.block_number > head {
+        rpc::call(url, "hardhat_mine", json!([format!("0x{:x}", state.info.block_number - head)])).await?;
+    }
+    Ok(state.info)
+}
+
+// Polls until a freshly spawned node answers, then restores the state into it
+pub async fn restore_on_start(project_path: &Path, name: &str, url: &str) -> Result<NodeStateInfo, AppError> {
+    let started = std::time::Instant::now();
+    while chain_index::block_number(url).await.is_err() {
+        if started.elapsed() > NODE_STARTUP_TIMEOUT {
+            return Err(format!(
+                "Node did not come up within {}s; state {} was not loaded",
+                NODE_STARTUP_TIMEOUT.as_secs(),
+                name
+            ).into());
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    restore(project_path, name, url).await
+}
+
+pub fn state_exists(project_path: &Path, name: &str) -> bool {
+    state_path(project_path, name).is_ok_and(|path| path.exists())
+}
+
+// Saves every account and slot the local transactions wrote, with their current values
+pub async fn capture(root: &Path, name: &str, url: &str) -> Result<NodeStateInfo, AppError> {
+    let path = state_path(root, name)?;
+
+    let (base_block, base_hash) = chain_base(url).await?;
+    let changes = fork_diff::local_changes(root, url, base_block, &base_hash).await?;
+    let mut accounts = Vec::new();
+    for (address, slots) in changes.touched_slots() {
+        accounts.push(read_account(url, &address, &slots).await?);
+    }
+
+    let mut info = NodeStateInfo {
+        name: name.to_string(),
+        created_at: store::timestamp_millis(),
+        chain_id: rpc::chain_id(url).await?,
+        block_number: chain_index::block_number(url).await?,
+        accounts: accounts.len(),
+        slots: accounts.iter().map(|a| a.storage.len()).sum(),
+        complete: changes.complete && changes.untraced_transactions == 0,
+        size_bytes: 0,
+    };
+    let content = serde_json::to_string(&NodeState { info: info.clone(), accounts })
+        .map_err(|e| format!("Failed to serialize state: {}", e))?;
+    info.size_bytes = content.len() as u64;
+
+    fs::create_dir_all(states_dir(root)).map_err(|e| format!("Failed to create state directory: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write state: {}", e))?;
+    Ok(info)
+}
+
+#[tauri::command]
+pub async fn save_node_state(app: AppHandle, project_path: String, name: String, rpc_url: Option<String>) -> Result<NodeStateInfo, AppError> {
+    let root = Path::new(&project_path);
+    let _slot = exclusion::acquire(&app, root, "save_node_state", "node-state", &[JobGroup::Node, JobGroup::Rpc]).await;
+    capture(root, &name, &rpc_url.unwrap_or_else(rpc::default_url)).await
+}
+
+#[tauri::command]
+pub async fn list_node_states(project_path: String) -> Result<Vec<NodeStateInfo>, AppError> {
+    let root = Path::new(&project_path);
+    let Ok(entries) = fs::read_dir(states_dir(root)) else {
+        return Ok(Vec::new());
+    };
+
+    let mut states = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|e| e != "json") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if let Ok(state) = read_state(root, name) {
+            let mut info = state.info;
+            info.size_bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            states.push(info);
+        }
+    }
+    states.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+    Ok(states)
+}
+
+#[tauri::command]
+pub async fn load_node_state(app: AppHandle, project_path: String, name: String, rpc_url: Option<String>) -> Result<NodeStateInfo, AppError> {
+    let _slot = exclusion::acquire(&app, Path::new(&project_path), "load_node_state", "node-state", &[JobGroup::Node, JobGroup::Rpc]).await;
+    restore(Path::new(&project_path), &name, &rpc_url.unwrap_or_else(rpc::default_url)).await
+}
+
+#[tauri::command]
+pub async fn delete_node_state(project_path: String, name: String) -> Result<(), AppError> {
+    let path = state_path(Path::new(&project_path), &name)?;
+    Ok(fs::remove_file(&path).map_err(|e| format!("Failed to delete state {}: {}", name, e))?)
+}
+
+// Keeps the `keep` newest states; returns the names of the deleted ones
+#[tauri::command]
+pub async fn prune_node_states(project_path: String, keep: usize) -> Result<Vec<String>, AppError> {
+    let states = list_node_states(project_path.clone()).await?;
+    let mut deleted = Vec::new();
+    for state in states.into_iter().skip(keep) {
+        delete_node_state(project_path.clone(), state.name.clone()).await?;
+        deleted.push(state.name);
+    }
+    Ok(deleted)
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::error::AppError;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    DeploySucceeded,
+    DeployFailed,
+    TestsPassed,
+    TestsFailed,
+    VerificationSucceeded,
+    VerificationFailed,
+    NodeStopped,
+    CommandTimedOut,
+}
+
+const ALL_EVENTS: [NotificationEvent; 8] = [
+    NotificationEvent::DeploySucceeded,
+    NotificationEvent::DeployFailed,
+    NotificationEvent::TestsPassed,
+    NotificationEvent::TestsFailed,
+    NotificationEvent::VerificationSucceeded,
+    NotificationEvent::VerificationFailed,
+    NotificationEvent::NodeStopped,
+    NotificationEvent::CommandTimedOut,
+];
+
+// Passing test runs are the common case and would be noise, so they start disabled
+pub struct Notifications {
+    enabled: Mutex<BTreeMap<NotificationEvent, bool>>,
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        let enabled = ALL_EVENTS
+            .into_iter()
+            .map(|event| (event, event != NotificationEvent::TestsPassed))
+            .collect();
+        Self { enabled: Mutex::new(enabled) }
+    }
+}
+
+// Shows an OS notification unless the user turned this event type off
+pub fn notify(app: &AppHandle, event: NotificationEvent, title: &str, body: &str) {
+    let enabled = app.state::<Notifications>().enabled.lock().unwrap().get(&event).copied().unwrap_or(true);
+    if enabled {
+        let _ = app.notification().builder().title(title).body(body).show();
+    }
+}
+
+// Called by run_tracked when a command finishes; only kinds worth interrupting for notify
+pub fn on_execution_finished(app: &AppHandle, kind: &str, success: bool, timed_out: bool) {
+    if timed_out {
+        let body = format!("The {} command was stopped", kind);
+        notify(app, NotificationEvent::CommandTimedOut, "Command timed out", &body);
+        return;
+    }
+    let (event, title) = match (kind, success) {
+        ("deploy", true) => (NotificationEvent::DeploySucceeded, "Deployment finished"),
+        ("deploy", false) => (NotificationEvent::DeployFailed, "Deployment failed"),
+        ("test", true) => (NotificationEvent::TestsPassed, "Tests passed"),
+        ("test", false) => (NotificationEvent::TestsFailed, "Tests failed"),
+        ("verify", true) => (NotificationEvent::VerificationSucceeded, "Contract verified"),
+        ("verify", false) => (NotificationEvent::VerificationFailed, "Verification failed"),
+        _ => return,
+    };
+    notify(app, event, title, "Open Hardhat GUI for the full output");
+}
+
+#[tauri::command]
+pub async fn get_notification_settings(
+    notifications: State<'_, Notifications>,
+) -> Result<BTreeMap<NotificationEvent, bool>, AppError> {
+    Ok(notifications.enabled.lock().unwrap().clone())
+}
+
+#[tauri::command]
+pub async fn set_notification_enabled(
+    notifications: State<'_, Notifications>,
+    event: NotificationEvent,
+    enabled: bool,
+) -> Result<BTreeMap<NotificationEvent, bool>, AppError> {
+    let mut settings = notifications.enabled.lock().unwrap();
+    settings.insert(event, enabled);
+    Ok(settings.clone())
+}
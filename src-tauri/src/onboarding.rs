@@ -0,0 +1,269 @@
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::error::AppError;
+use crate::processes;
+use crate::resolver;
+use crate::status;
+
+// Hardhat 2.22 and later refuse to run on anything older
+const MIN_NODE_MAJOR: u64 = 18;
+const FNM_INSTALL_SCRIPT: &str = "curl -fsSL https://fnm.vercel.app/install | bash -s -- --skip-shell";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Tool {
+    Node,
+    Npm,
+    Git,
+}
+
+impl Tool {
+    fn binary(self) -> &'static str {
+        match self {
+            Tool::Node => "node",
+            Tool::Npm => "npm",
+            Tool::Git => "git",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Installer {
+    Winget,
+    Brew,
+    // Git only
+    Apt,
+    // A Node runtime managed by fnm in the user's home, no admin rights needed
+    Fnm,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct InstallOption {
+    pub installer: Installer,
+    // What will run, shown before the user agrees to it
+    pub commands: Vec<String>,
+    pub requires_admin: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ToolCheck {
+    pub tool: Tool,
+    pub path: Option<String>,
+    pub version: Option<String>,
+    // Found and new enough
+    pub ok: bool,
+    pub problem: Option<String>,
+    pub install_options: Vec<InstallOption>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EnvironmentReport {
+    pub platform: String,
+    pub ready: bool,
+    pub checks: Vec<ToolCheck>,
+}
+
+// Sent before each step of an install; the step's output streams as execution events
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OnboardingProgress {
+    pub tool: Tool,
+    pub installer: Installer,
+    pub step: usize,
+    pub steps: usize,
+    pub description: String,
+    pub execution_id: String,
+}
+
+struct Step {
+    description: String,
+    program: &'static str,
+    args: Vec<String>,
+}
+
+impl Step {
+    fn new(description: &str, program: &'static str, args: &[&str]) -> Self {
+        Step {
+            description: description.to_string(),
+            program,
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    fn display(&self) -> String {
+        std::iter::once(self.program.to_string()).chain(self.args.iter().cloned()).collect::<Vec<_>>().join(" ")
+    }
+
+    // Resolved when the step runs, so a binary installed by an earlier step is found
+    fn command(&self) -> Command {
+        let mut cmd = resolver::command(self.program);
+        cmd.args(&self.args);
+        cmd
+    }
+}
+
+fn winget_id(tool: Tool) -> &'static str {
+    match tool {
+        Tool::Node | Tool::Npm => "OpenJS.NodeJS.LTS",
+        Tool::Git => "Git.Git",
+    }
+}
+
+// Every installer offered for Node.js brings npm with it
+fn steps(tool: Tool, installer: Installer) -> Result<Vec<Step>, String> {
+    let steps = match (installer, tool) {
+        (Installer::Winget, _) => vec![Step::new(
+            &format!("Installing {} with winget", winget_id(tool)),
+            "winget",
+            &["install", "--id", winget_id(tool), "-e", "--silent", "--accept-package-agreements", "--accept-source-agreements"],
+        )],
+        (Installer::Brew, Tool::Git) => vec![Step::new("Installing git with Homebrew", "brew", &["install", "git"])],
+        (Installer::Brew, _) => vec![Step::new("Installing Node.js with Homebrew", "brew", &["install", "node"])],
+        (Installer::Apt, Tool::Git) => {
+            vec![Step::new("Installing git with apt", "pkexec", &["apt-get", "install", "-y", "git"])]
+        }
+        // Distribution packages lag far behind (Ubuntu 22.04 ships Node 12), so they would
+        // leave the version check failing; fnm installs a current LTS instead
+        (Installer::Apt, _) => {
+            return Err(format!("apt's Node.js is older than {}; install it with fnm", MIN_NODE_MAJOR))
+        }
+        (Installer::Fnm, Tool::Git) => return Err("fnm only manages Node.js".to_string()),
+        (Installer::Fnm, _) => {
+            let mut steps = Vec::new();
+            if resolver::locate("fnm").is_none() {
+                steps.push(if cfg!(windows) {
+                    Step::new("Installing fnm with winget", "winget", &["install", "--id", "Schniz.fnm", "-e", "--silent"])
+                } else if resolver::locate("brew").is_some() {
+                    Step::new("Installing fnm with Homebrew", "brew", &["install", "fnm"])
+                } else {
+                    Step::new("Installing fnm", "bash", &["-c", FNM_INSTALL_SCRIPT])
+                });
+            }
+            steps.push(Step::new("Installing the latest Node.js LTS", "fnm", &["install", "--lts"]));
+            steps.push(Step::new("Making it the default Node.js", "fnm", &["default", "lts-latest"]));
+            steps
+        }
+    };
+    Ok(steps)
+}
+
+fn available_installers(tool: Tool) -> Vec<Installer> {
+    let has = |binary: &str| resolver::locate(binary).is_some();
+    let mut installers = Vec::new();
+    if cfg!(windows) && has("winget") {
+        installers.push(Installer::Winget);
+    }
+    if !cfg!(windows) && has("brew") {
+        installers.push(Installer::Brew);
+    }
+    if tool == Tool::Git && cfg!(target_os = "linux") && has("apt-get") && has("pkexec") {
+        installers.push(Installer::Apt);
+    }
+    let fnm_usable = has("fnm") || (cfg!(windows) && has("winget")) || (!cfg!(windows) && has("curl") && has("bash"));
+    if tool != Tool::Git && fnm_usable {
+        installers.push(Installer::Fnm);
+    }
+    installers
+}
+
+fn install_options(tool: Tool) -> Vec<InstallOption> {
+    available_installers(tool)
+        .into_iter()
+        .filter_map(|installer| {
+            let steps = steps(tool, installer).ok()?;
+            Some(InstallOption {
+                installer,
+                commands: steps.iter().map(Step::display).collect(),
+                requires_admin: matches!(installer, Installer::Apt),
+            })
+        })
+        .collect()
+}
+
+fn major(version: &str) -> Option<u64> {
+    version.trim().trim_start_matches('v').split('.').next()?.parse().ok()
+}
+
+async fn check(tool: Tool) -> ToolCheck {
+    let path = resolver::locate(tool.binary());
+    let mut version = None;
+    if path.is_some() {
+        let mut cmd = resolver::command(tool.binary());
+        cmd.arg("--version");
+        if let Ok(output) = processes::output(cmd).await {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            // git prints "git version 2.43.0"
+            version = stdout.split_whitespace().last().map(String::from).filter(|_| output.status.success());
+        }
+    }
+
+    let problem = match (&path, &version, tool) {
+        (None, _, _) => Some(format!("{} was not found", tool.binary())),
+        (Some(_), None, _) => Some(format!("{} was found but `{} --version` failed", tool.binary(), tool.binary())),
+        (Some(_), Some(version), Tool::Node) if major(version).is_some_and(|major| major < MIN_NODE_MAJOR) => {
+            Some(format!("Node.js {} is too old; Hardhat needs {} or later", version, MIN_NODE_MAJOR))
+        }
+        _ => None,
+    };
+    ToolCheck {
+        tool,
+        path: path.map(|p| p.to_string_lossy().to_string()),
+        version,
+        ok: problem.is_none(),
+        install_options: if problem.is_some() { install_options(tool) } else { Vec::new() },
+        problem,
+    }
+}
+
+async fn report() -> EnvironmentReport {
+    let mut checks = Vec::new();
+    for tool in [Tool::Node, Tool::Npm, Tool::Git] {
+        checks.push(check(tool).await);
+    }
+    EnvironmentReport {
+        platform: std::env::consts::OS.to_string(),
+        ready: checks.iter().all(|check| check.ok),
+        checks,
+    }
+}
+
+#[tauri::command]
+pub async fn check_environment() -> Result<EnvironmentReport, AppError> {
+    resolver::refresh();
+    Ok(report().await)
+}
+
+// Runs the installer's steps one after another; each streams its output as a tracked
+// execution and can be cancelled like any other
+#[tauri::command]
+pub async fn install_tool(app: AppHandle, tool: Tool, installer: Installer) -> Result<EnvironmentReport, AppError> {
+    if !available_installers(tool).contains(&installer) {
+        return Err(format!("That installer is not available for {} on this system", tool.binary()).into());
+    }
+    let steps = steps(tool, installer)?;
+    let manager = app.state::<processes::ProcessManager>();
+    for (i, step) in steps.iter().enumerate() {
+        let execution_id = manager.new_execution_id();
+        let progress = OnboardingProgress {
+            tool,
+            installer,
+            step: i + 1,
+            steps: steps.len(),
+            description: step.description.clone(),
+            execution_id: execution_id.clone(),
+        };
+        let _ = app.emit("onboarding-progress", &progress);
+
+        let output = processes::run_tracked(&app, Some(execution_id), "setup", step.command()).await?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("{} failed: {}", step.description, stderr.trim()).into());
+        }
+        resolver::refresh();
+    }
+    status::refresh();
+    Ok(report().await)
+}
@@ -0,0 +1,146 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::AppHandle;
+
+use crate::error::AppError;
+use crate::messages::Message;
+use crate::networks;
+use crate::processes;
+use crate::resolver;
+use crate::store;
+
+const PRESETS_FILE: &str = "presets.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PresetTarget {
+    Task {
+        task: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    Script {
+        script: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TaskPreset {
+    pub name: String,
+    pub description: Option<String>,
+    pub target: PresetTarget,
+    pub network: Option<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub created_at: u64,
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+impl TaskPreset {
+    pub fn command(&self, project_path: &str) -> Command {
+        let mut cmd = resolver::hardhat(Path::new(project_path));
+
+        match &self.target {
+            PresetTarget::Task { task, args } => {
+                // --network is a global hardhat flag, so it goes before the task name
+                if let Some(network) = &self.network {
+                    networks::apply_network(&mut cmd, network);
+                }
+                cmd.arg(task).args(args);
+            }
+            PresetTarget::Script { script } => {
+                cmd.args(["run", script]);
+                if let Some(network) = &self.network {
+                    networks::apply_network(&mut cmd, network);
+                }
+            }
+        }
+
+        cmd.envs(&self.env);
+        cmd
+    }
+}
+
+pub fn load_presets(project_path: &str) -> Result<Vec<TaskPreset>, String> {
+    store::load_json(Path::new(project_path), PRESETS_FILE)
+}
+
+pub fn find_preset(project_path: &str, name: &str) -> Result<TaskPreset, Message> {
+    load_presets(project_path)
+        .map_err(|error| Message::new("error.other", json!({ "message": error })))?
+        .into_iter()
+        .find(|preset| preset.name == name)
+        .ok_or_else(|| Message::new("preset.not_found", json!({ "name": name })))
+}
+
+#[tauri::command]
+pub async fn list_task_presets(project_path: String) -> Result<Vec<TaskPreset>, AppError> {
+    Ok(load_presets(&project_path)?)
+}
+
+#[tauri::command]
+pub async fn save_task_preset(project_path: String, preset: TaskPreset) -> Result<TaskPreset, AppError> {
+    if preset.name.trim().is_empty() {
+        return Err(Message::new("preset.name_required", json!({})).into());
+    }
+    processes::validate_env(&preset.env)?;
+
+    let mut presets = load_presets(&project_path)?;
+    let now = store::timestamp_millis();
+    let mut preset = TaskPreset { updated_at: now, ..preset };
+
+    // Saving under an existing name replaces that preset but keeps its creation time
+    match presets.iter_mut().find(|p| p.name == preset.name) {
+        Some(existing) => {
+            preset.created_at = existing.created_at;
+            *existing = preset.clone();
+        }
+        None => {
+            preset.created_at = now;
+            presets.push(preset.clone());
+        }
+    }
+
+    store::save_json(Path::new(&project_path), PRESETS_FILE, &presets)?;
+    Ok(preset)
+}
+
+#[tauri::command]
+pub async fn delete_task_preset(project_path: String, name: String) -> Result<bool, AppError> {
+    let mut presets = load_presets(&project_path)?;
+    let before = presets.len();
+    presets.retain(|preset| preset.name != name);
+
+    if presets.len() == before {
+        return Ok(false);
+    }
+    store::save_json(Path::new(&project_path), PRESETS_FILE, &presets)?;
+    Ok(true)
+}
+
+#[tauri::command]
+pub async fn run_task_preset(
+    app: AppHandle,
+    project_path: String,
+    name: String,
+    execution_id: Option<String>,
+) -> Result<Message, AppError> {
+    let preset = find_preset(&project_path, &name)?;
+
+    let output = processes::run_tracked(&app, execution_id, "preset", preset.command(&project_path)).await?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if output.status.success() {
+        Ok(Message::new("preset.succeeded", json!({ "name": name, "output": stdout })))
+    } else {
+        Err(Message::new("preset.failed", json!({ "name": name, "stderr": stderr, "stdout": stdout })).into())
+    }
+}
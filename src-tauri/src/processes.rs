@@ -0,0 +1,511 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Output, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager, State};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::process::Child;
+use tokio_util::sync::CancellationToken;
+
+use crate::ansi;
+use crate::artifacts;
+use crate::audit;
+use crate::cancellation;
+use crate::deployments;
+use crate::error::AppError;
+use crate::exclusion;
+use crate::metrics::{self, Outcome};
+use crate::notifications;
+use crate::progress::{self, ProgressUpdate};
+use crate::store;
+use crate::stream::{self, ExecutionUpdate, FinishStatus, LineStream};
+
+// Plain-text output of a finished command, kept in memory for diagnostic bundles
+// and the dashboard
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecentOutput {
+    pub execution_id: String,
+    pub kind: String,
+    pub project_path: Option<String>,
+    pub finished_at: u64,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+const RECENT_OUTPUT_LIMIT: usize = 20;
+// Per stream; the end of the output is where errors usually are, so that's what is kept
+const RECENT_OUTPUT_BYTES: usize = 64 * 1024;
+
+fn tail(text: &str) -> String {
+    let mut start = text.len().saturating_sub(RECENT_OUTPUT_BYTES);
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    text[start..].to_string()
+}
+
+// The child itself is owned by the task waiting on it; cancelling its execution token
+// wakes that task
+struct TrackedProcess {
+    kind: String,
+}
+
+// Seconds before a command of the given kind is killed. Kinds without an entry run
+// until they exit on their own.
+fn default_timeouts() -> HashMap<String, u64> {
+    [("install", 900), ("compile", 600), ("test", 1800), ("coverage", 1800), ("deploy", 600), ("setup", 1800), ("console", 300)]
+        .into_iter()
+        .map(|(kind, secs)| (kind.to_string(), secs))
+        .collect()
+}
+
+pub struct ProcessManager {
+    next_id: AtomicU64,
+    running: Mutex<HashMap<String, TrackedProcess>>,
+    timeouts: Mutex<HashMap<String, u64>>,
+    recent: Mutex<VecDeque<RecentOutput>>,
+}
+
+impl Default for ProcessManager {
+    fn default() -> Self {
+        Self {
+            next_id: AtomicU64::new(0),
+            running: Mutex::new(HashMap::new()),
+            timeouts: Mutex::new(default_timeouts()),
+            recent: Mutex::new(VecDeque::new()),
+        }
+    }
+}
+
+impl ProcessManager {
+    pub fn new_execution_id(&self) -> String {
+        format!("exec-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    pub fn timeout_for(&self, kind: &str) -> Option<Duration> {
+        self.timeouts.lock().unwrap().get(kind).map(|secs| Duration::from_secs(*secs))
+    }
+
+    fn remember(&self, mut output: RecentOutput) {
+        output.stdout = tail(&output.stdout);
+        output.stderr = tail(&output.stderr);
+        let mut recent = self.recent.lock().unwrap();
+        if recent.len() == RECENT_OUTPUT_LIMIT {
+            recent.pop_front();
+        }
+        recent.push_back(output);
+    }
+
+    // Oldest first
+    pub fn recent_outputs(&self) -> Vec<RecentOutput> {
+        self.recent.lock().unwrap().iter().cloned().collect()
+    }
+
+    // Plain-text stdout of the latest run of `kind` in the project, if still kept
+    fn last_stdout(&self, project_path: Option<&str>, kind: &str) -> Option<String> {
+        let recent = self.recent.lock().unwrap();
+        let output = recent.iter().rev().find(|o| o.kind == kind && o.project_path.as_deref() == project_path)?;
+        Some(output.stdout.clone())
+    }
+
+    pub fn running_count(&self) -> usize {
+        self.running.lock().unwrap().len()
+    }
+
+    pub fn is_running(&self, execution_id: &str) -> bool {
+        self.running.lock().unwrap().contains_key(execution_id)
+    }
+}
+
+// npx/hardhat spawn their own children, so killing only the direct child would leave
+// the actual compile or test run behind. Each tracked command gets its own process
+// group (Unix) / job tree (Windows) and the whole tree is terminated.
+#[cfg(unix)]
+pub fn isolate(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+#[cfg(not(unix))]
+pub fn isolate(_command: &mut Command) {}
+
+// For children that were started with `isolate`
+pub async fn kill_process_tree(pid: u32) {
+    let pid = pid.to_string();
+
+    #[cfg(unix)]
+    let _ = tokio::process::Command::new("kill").args(["-KILL", &format!("-{}", pid)]).status().await;
+
+    #[cfg(windows)]
+    let _ = tokio::process::Command::new("taskkill").args(["/PID", &pid, "/T", "/F"]).status().await;
+}
+
+async fn kill_tree(child: &mut Child) {
+    if let Some(pid) = child.id() {
+        kill_process_tree(pid).await;
+    }
+
+    let _ = child.kill().await;
+}
+
+// Waits for the child to exit, killing its tree when the deadline passes or the
+// execution is cancelled. The flag is true when the deadline was hit
+async fn wait_or_kill(
+    child: &mut Child,
+    deadline: Option<Instant>,
+    cancel: &CancellationToken,
+) -> std::io::Result<(ExitStatus, bool)> {
+    let expired = async {
+        match deadline {
+            Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+            None => std::future::pending().await,
+        }
+    };
+    let timed_out = tokio::select! {
+        status = child.wait() => return status.map(|status| (status, false)),
+        _ = expired => true,
+        _ = cancel.cancelled() => false,
+    };
+    kill_tree(child).await;
+    child.wait().await.map(|status| (status, timed_out))
+}
+
+// Reads in chunks into a shared buffer so whatever arrived before a timeout can
+// still be reported, forwarding each chunk to the UI as it arrives
+fn read_into<R: AsyncRead + Unpin + Send + 'static>(
+    pipe: Option<R>,
+    buf: Arc<Mutex<Vec<u8>>>,
+    mut lines: LineStream,
+) -> JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let Some(mut pipe) = pipe else { return };
+        let mut chunk = [0u8; 8192];
+        while let Ok(n) = pipe.read(&mut chunk).await {
+            if n == 0 {
+                break;
+            }
+            buf.lock().unwrap().extend_from_slice(&chunk[..n]);
+            lines.push(&chunk[..n]);
+        }
+        lines.flush();
+    })
+}
+
+fn finished(app: &AppHandle, execution_id: &str, kind: &str, status: FinishStatus, exit_code: Option<i32>, summary: String) {
+    stream::emit(app, execution_id, kind, ExecutionUpdate::Finished { status, exit_code, summary });
+}
+
+// Variables are set on the child only; the project's .env and the GUI's own
+// environment are left untouched
+pub fn validate_env(env: &BTreeMap<String, String>) -> Result<(), String> {
+    for (key, value) in env {
+        if key.is_empty() || key.contains('=') || key.contains('\0') || value.contains('\0') {
+            return Err(format!("Invalid environment variable: {:?}", key));
+        }
+    }
+    Ok(())
+}
+
+pub fn apply_env(command: &mut Command, env: &BTreeMap<String, String>) -> Result<(), String> {
+    validate_env(env)?;
+    command.envs(env);
+    Ok(())
+}
+
+// Async `Command::output()` for short helper commands that don't need tracking (version
+// probes, `docker rm`); the child is killed if the caller is dropped
+pub async fn output(mut command: Command) -> std::io::Result<Output> {
+    command.stdin(Stdio::null());
+    tokio::process::Command::from(command).kill_on_drop(true).output().await
+}
+
+// Drop-in replacement for `Command::output()` that registers the child under an
+// execution id so it can be cancelled while it runs
+pub async fn run_tracked(
+    app: &AppHandle,
+    execution_id: Option<String>,
+    kind: &str,
+    command: Command,
+) -> Result<Output, AppError> {
+    run_tracked_with_input(app, execution_id, kind, command, None).await
+}
+
+// `input` is written to the child's stdin, which is then closed. Payloads go this way
+// rather than through arguments or the environment, which are size-limited and visible
+// to other processes
+pub async fn run_tracked_with_input(
+    app: &AppHandle,
+    execution_id: Option<String>,
+    kind: &str,
+    mut command: Command,
+    input: Option<Vec<u8>>,
+) -> Result<Output, AppError> {
+    let manager = app.state::<ProcessManager>();
+    let execution_id = execution_id.unwrap_or_else(|| manager.new_execution_id());
+    // Commands run from the project root, which is where their timings are kept
+    let project_path = command.get_current_dir().map(Path::to_path_buf);
+    let started_at = store::timestamp_millis();
+    let started = Instant::now();
+    let track = |outcome: Outcome, exit_code: Option<i32>| {
+        if let Some(project_path) = &project_path {
+            metrics::record(project_path, kind, started_at, started.elapsed(), outcome, exit_code);
+        }
+        let project = project_path.as_ref().map(|p| p.to_string_lossy().to_string());
+        audit::record_outcome(kind, &execution_id, project, outcome, exit_code);
+    };
+    let recent_output = |exit_code: Option<i32>, timed_out: bool, stdout: &str, stderr: &str| RecentOutput {
+        execution_id: execution_id.clone(),
+        kind: kind.to_string(),
+        project_path: project_path.as_ref().map(|p| p.to_string_lossy().to_string()),
+        finished_at: store::timestamp_millis(),
+        exit_code,
+        timed_out,
+        stdout: stdout.to_string(),
+        stderr: stderr.to_string(),
+    };
+
+    // Registered before queueing so an execution waiting for its slot can be cancelled
+    // too, by id or by closing its project
+    let execution = cancellation::execution(project_path.as_deref(), &execution_id);
+    let cancel = &execution.token;
+    manager
+        .running
+        .lock()
+        .unwrap()
+        .insert(execution_id.clone(), TrackedProcess { kind: kind.to_string() });
+
+    let _slot = match &project_path {
+        Some(project_path) => {
+            let groups = exclusion::groups_for(kind);
+            tokio::select! {
+                slot = exclusion::acquire(app, project_path, &execution_id, kind, groups) => slot,
+                _ = cancel.cancelled() => {
+                    manager.running.lock().unwrap().remove(&execution_id);
+                    track(Outcome::Cancelled, None);
+                    finished(app, &execution_id, kind, FinishStatus::Cancelled, None, "Cancelled before it started".to_string());
+                    return Err(AppError::Cancelled { execution_id });
+                }
+            }
+        }
+        None => None,
+    };
+
+    isolate(&mut command);
+    command
+        .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let program = command.get_program().to_string_lossy().to_string();
+    let spawned = tokio::process::Command::from(command).kill_on_drop(true).spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            manager.running.lock().unwrap().remove(&execution_id);
+            let error = match e.kind() {
+                std::io::ErrorKind::NotFound => AppError::NotInstalled { tool: program },
+                _ => AppError::Other(format!("Failed to start {}: {}", kind, e)),
+            };
+            finished(app, &execution_id, kind, FinishStatus::Failed, None, error.to_string());
+            return Err(error);
+        }
+    };
+
+    tracing::info!(execution_id = %execution_id, kind, "execution started");
+    stream::emit(
+        app,
+        &execution_id,
+        kind,
+        ExecutionUpdate::Started { project_path: project_path.as_ref().map(|p| p.to_string_lossy().to_string()) },
+    );
+
+    let stdout_buf = Arc::new(Mutex::new(Vec::new()));
+    let stderr_buf = Arc::new(Mutex::new(Vec::new()));
+    let project = project_path.as_ref().map(|p| p.to_string_lossy().to_string());
+    // The last run's output gives totals the tools don't print up front
+    let reporter = progress::reporter(kind, manager.last_stdout(project.as_deref(), kind).as_deref());
+    let stdout_lines = LineStream::stdout(app, &execution_id, kind, reporter);
+    let stdout = read_into(child.stdout.take(), stdout_buf.clone(), stdout_lines);
+    let stderr = read_into(child.stderr.take(), stderr_buf.clone(), LineStream::stderr(app, &execution_id, kind));
+    // Written from its own task so a child that doesn't read its input can't stall the
+    // wait below; dropping the pipe afterwards closes it
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        tauri::async_runtime::spawn(async move {
+            let _ = stdin.write_all(&input).await;
+        });
+    }
+
+    let timeout = manager.timeout_for(kind);
+    let deadline = timeout.map(|t| Instant::now() + t);
+    let waited = wait_or_kill(&mut child, deadline, cancel)
+        .await
+        .map_err(|e| format!("Failed to wait for {}: {}", kind, e));
+
+    manager.running.lock().unwrap().remove(&execution_id);
+    let (status, timed_out) = match waited {
+        Ok(waited) => waited,
+        Err(e) => {
+            finished(app, &execution_id, kind, FinishStatus::Failed, None, e.clone());
+            return Err(e.into());
+        }
+    };
+
+    // The readers finish once the pipes close, so every chunk is out before `finished`
+    let _ = stdout.await;
+    let _ = stderr.await;
+    let stdout = std::mem::take(&mut *stdout_buf.lock().unwrap());
+    let stderr = std::mem::take(&mut *stderr_buf.lock().unwrap());
+
+    if cancel.is_cancelled() {
+        tracing::info!(execution_id = %execution_id, kind, "execution cancelled");
+        track(Outcome::Cancelled, None);
+        finished(app, &execution_id, kind, FinishStatus::Cancelled, None, "Cancelled".to_string());
+        return Err(AppError::Cancelled { execution_id });
+    }
+
+    if timed_out {
+        let timeout_secs = timeout.map_or(0, |t| t.as_secs());
+        tracing::warn!(execution_id = %execution_id, kind, timeout_secs, "execution timed out");
+        track(Outcome::TimedOut, None);
+        notifications::on_execution_finished(app, kind, false, true);
+        let summary = format!("Timed out after {}s", timeout_secs);
+        finished(app, &execution_id, kind, FinishStatus::TimedOut, None, summary);
+        let stdout = ansi::strip(&String::from_utf8_lossy(&stdout));
+        let stderr = ansi::strip(&String::from_utf8_lossy(&stderr));
+        manager.remember(recent_output(None, true, &stdout, &stderr));
+        return Err(AppError::ProcessTimeout {
+            execution_id,
+            kind: kind.to_string(),
+            seconds: timeout_secs,
+            output: format!("{}{}", stdout, stderr),
+        });
+    }
+
+    // Styled segments already went to the UI; callers get plain text without escape codes
+    let stdout = ansi::strip(&String::from_utf8_lossy(&stdout));
+    let stderr = ansi::strip(&String::from_utf8_lossy(&stderr));
+
+    tracing::info!(execution_id = %execution_id, kind, exit_code = status.code(), "execution finished");
+    let summary = stream::summary(kind, status.success(), &stdout, &stderr);
+    let finish_status = if status.success() { FinishStatus::Succeeded } else { FinishStatus::Failed };
+    finished(app, &execution_id, kind, finish_status, status.code(), summary);
+    let outcome = if status.success() { Outcome::Success } else { Outcome::Failure };
+    track(outcome, status.code());
+    if let Some(project_path) = &project_path {
+        if exclusion::groups_for(kind).contains(&exclusion::JobGroup::Build) {
+            artifacts::invalidate(project_path);
+        }
+        if kind == "deploy" && status.success() {
+            deployments::record_history(project_path);
+        }
+    }
+    notifications::on_execution_finished(app, kind, status.success(), false);
+
+    manager.remember(recent_output(status.code(), false, &stdout, &stderr));
+
+    Ok(Output {
+        status,
+        stdout: stdout.into_bytes(),
+        stderr: stderr.into_bytes(),
+    })
+}
+
+// Long-running servers such as local nodes. They are registered like any execution, so
+// they count as running and cancel_execution stops them, but nothing waits for their
+// output; it goes to the GUI's own stdout. `on_exit` runs unless the process was cancelled
+pub fn spawn_service<F>(app: &AppHandle, kind: &str, mut command: Command, on_exit: F) -> Result<String, String>
+where
+    F: FnOnce(&AppHandle, Option<i32>) + Send + 'static,
+{
+    let manager = app.state::<ProcessManager>();
+    let execution_id = manager.new_execution_id();
+
+    let execution = cancellation::execution(command.get_current_dir(), &execution_id);
+    isolate(&mut command);
+    command.stdin(Stdio::null());
+    let mut child = tokio::process::Command::from(command)
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", kind, e))?;
+    manager
+        .running
+        .lock()
+        .unwrap()
+        .insert(execution_id.clone(), TrackedProcess { kind: kind.to_string() });
+
+    tracing::info!(execution_id = %execution_id, kind, "service started");
+    stream::emit(app, &execution_id, kind, ExecutionUpdate::Started { project_path: None });
+
+    let app = app.clone();
+    let id = execution_id.clone();
+    let kind = kind.to_string();
+    tauri::async_runtime::spawn(async move {
+        let status = wait_or_kill(&mut child, None, &execution.token).await.ok().map(|(status, _)| status);
+        app.state::<ProcessManager>().running.lock().unwrap().remove(&id);
+        let exit_code = status.and_then(|s| s.code());
+        tracing::info!(execution_id = %id, kind, exit_code, "service exited");
+        if execution.token.is_cancelled() {
+            finished(&app, &id, &kind, FinishStatus::Cancelled, exit_code, "Stopped".to_string());
+            return;
+        }
+        let status = if exit_code == Some(0) { FinishStatus::Succeeded } else { FinishStatus::Failed };
+        let summary = match exit_code {
+            Some(code) => format!("Exited with code {}", code),
+            None => "Terminated by a signal".to_string(),
+        };
+        finished(&app, &id, &kind, status, exit_code, summary);
+        on_exit(&app, exit_code);
+    });
+
+    Ok(execution_id)
+}
+
+#[tauri::command]
+pub async fn cancel_execution(
+    app: AppHandle,
+    processes: State<'_, ProcessManager>,
+    execution_id: String,
+) -> Result<bool, AppError> {
+    let kind = processes.running.lock().unwrap().get(&execution_id).map(|tracked| tracked.kind.clone());
+    // Work other than processes can be registered under an execution id too
+    if !cancellation::cancel(&execution_id) {
+        return Ok(false);
+    }
+    let Some(kind) = kind else {
+        return Ok(true);
+    };
+
+    // `finished` follows once the process tree is gone
+    let update = ExecutionUpdate::Progress(ProgressUpdate::phase("cancelling", "Cancelling"));
+    stream::emit(&app, &execution_id, &kind, update);
+
+    Ok(true)
+}
+
+#[tauri::command]
+pub async fn get_execution_timeouts(
+    processes: State<'_, ProcessManager>,
+) -> Result<HashMap<String, u64>, AppError> {
+    Ok(processes.timeouts.lock().unwrap().clone())
+}
+
+// `None` (or 0) removes the limit for that command kind
+#[tauri::command]
+pub async fn set_execution_timeout(
+    processes: State<'_, ProcessManager>,
+    kind: String,
+    seconds: Option<u64>,
+) -> Result<HashMap<String, u64>, AppError> {
+    let mut timeouts = processes.timeouts.lock().unwrap();
+    match seconds.filter(|secs| *secs > 0) {
+        Some(secs) => timeouts.insert(kind, secs),
+        None => timeouts.remove(&kind),
+    };
+    Ok(timeouts.clone())
+}
@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProgressUpdate {
+    // Short snake_case step name, e.g. `compiling` or `running_tests`
+    pub phase: String,
+    pub message: String,
+    pub completed: Option<u64>,
+    pub total: Option<u64>,
+    // 0-100, only when the total is known
+    pub percent: Option<u8>,
+}
+
+impl ProgressUpdate {
+    pub fn phase(phase: &str, message: impl Into<String>) -> Self {
+        Self {
+            phase: phase.to_string(),
+            message: message.into(),
+            completed: None,
+            total: None,
+            percent: None,
+        }
+    }
+
+    fn done(phase: &str, message: impl Into<String>, completed: Option<u64>) -> Self {
+        Self { completed, total: completed, percent: Some(100), ..Self::phase(phase, message) }
+    }
+
+    // Totals from a previous run are estimates, so a running step never shows 100%
+    fn counted(phase: &str, message: impl Into<String>, completed: u64, total: Option<u64>) -> Self {
+        let percent = total.filter(|total| *total > 0).map(|total| (completed * 100 / total).min(99) as u8);
+        Self { completed: Some(completed), total, percent, ..Self::phase(phase, message) }
+    }
+}
+
+// Reads one execution's stdout line by line (plain text, trimmed) and reports whenever
+// the command moves on. Hardhat, mocha, Ignition and npm print nothing machine-readable,
+// so each reporter knows the lines of one tool
+pub trait ProgressReporter: Send {
+    fn line(&mut self, line: &str) -> Option<ProgressUpdate>;
+}
+
+// `previous` is the stdout of the last run of the same kind in the project. It supplies
+// totals a tool doesn't print up front, like the number of tests
+pub fn reporter(kind: &str, previous: Option<&str>) -> Option<Box<dyn ProgressReporter>> {
+    match kind {
+        "compile" => Some(Box::new(CompileProgress::default())),
+        "test" => Some(Box::new(TestProgress::new(previous))),
+        "coverage" => Some(Box::new(CoverageProgress { tests: TestProgress::new(previous) })),
+        "deploy" => Some(Box::new(DeployProgress::default())),
+        "install" => Some(Box::new(InstallProgress)),
+        _ => None,
+    }
+}
+
+fn leading_number(text: &str) -> Option<u64> {
+    text.split_whitespace().next()?.parse().ok()
+}
+
+// Mocha's closing counts ("12 passing (3s)", "1 failing", "2 pending") added up
+pub fn test_count(output: &str) -> Option<u64> {
+    let counts: Vec<u64> = output
+        .lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            let count = words.next()?.parse().ok()?;
+            words.next().filter(|word| ["passing", "failing", "pending"].contains(word))?;
+            Some(count)
+        })
+        .collect();
+    (!counts.is_empty()).then(|| counts.iter().sum())
+}
+
+// "Compiling 12 files with Solc 0.8.24", then "Compiled 12 Solidity files successfully"
+#[derive(Default)]
+struct CompileProgress {
+    total: Option<u64>,
+}
+
+impl CompileProgress {
+    fn compile_line(&mut self, line: &str) -> Option<ProgressUpdate> {
+        if line.starts_with("Downloading compiler") {
+            return Some(ProgressUpdate::phase("downloading_compiler", line));
+        }
+        if let Some(rest) = line.strip_prefix("Compiling ") {
+            self.total = leading_number(rest);
+            return Some(ProgressUpdate::counted("compiling", line, 0, self.total));
+        }
+        if line.starts_with("Compiled ") || line.starts_with("Nothing to compile") {
+            return Some(ProgressUpdate::done("compiled", line, self.total));
+        }
+        None
+    }
+}
+
+impl ProgressReporter for CompileProgress {
+    fn line(&mut self, line: &str) -> Option<ProgressUpdate> {
+        self.compile_line(line)
+    }
+}
+
+// `hardhat test` compiles first, then mocha prints a ✔ line per passing test and a
+// numbered line ("1) title") per failing one
+struct TestProgress {
+    compile: CompileProgress,
+    finished: u64,
+    expected: Option<u64>,
+    done: bool,
+}
+
+impl TestProgress {
+    fn new(previous: Option<&str>) -> Self {
+        Self {
+            compile: CompileProgress::default(),
+            finished: 0,
+            expected: previous.and_then(test_count),
+            done: false,
+        }
+    }
+}
+
+impl ProgressReporter for TestProgress {
+    fn line(&mut self, line: &str) -> Option<ProgressUpdate> {
+        // Failure details are repeated, numbered, after the counts
+        if self.done {
+            return None;
+        }
+        if let Some(update) = self.compile.compile_line(line) {
+            return Some(update);
+        }
+        let mut words = line.split_whitespace();
+        if leading_number(line).is_some() && words.nth(1) == Some("passing") {
+            self.done = true;
+            return Some(ProgressUpdate::done("finished", line, Some(self.finished)));
+        }
+        let passed = line.strip_prefix(['✔', '✓', '√']);
+        let failed = line
+            .split_once(") ")
+            .filter(|(number, _)| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+            .map(|(_, title)| title);
+        let title = passed.or(failed)?;
+        self.finished += 1;
+        Some(ProgressUpdate::counted("running_tests", title.trim(), self.finished, self.expected))
+    }
+}
+
+// solidity-coverage instruments and compiles, runs the test suite, then prints its table
+struct CoverageProgress {
+    tests: TestProgress,
+}
+
+impl ProgressReporter for CoverageProgress {
+    fn line(&mut self, line: &str) -> Option<ProgressUpdate> {
+        if line.starts_with("Instrumenting for coverage") {
+            return Some(ProgressUpdate::phase("instrumenting", line));
+        }
+        if line.starts_with("File ") && line.contains("% Stmts") {
+            return Some(ProgressUpdate::phase("reporting", "Writing coverage reports"));
+        }
+        if line.starts_with("Istanbul reports written") {
+            return Some(ProgressUpdate::done("finished", line, None));
+        }
+        self.tests.line(line)
+    }
+}
+
+// Ignition: "Deploying [ Module ]", "Batch #1", one "Executed Module#Future" per future,
+// then "Deployed Addresses"
+#[derive(Default)]
+struct DeployProgress {
+    executed: u64,
+}
+
+impl ProgressReporter for DeployProgress {
+    fn line(&mut self, line: &str) -> Option<ProgressUpdate> {
+        if line.starts_with("Deploying [") {
+            return Some(ProgressUpdate::phase("deploying", line));
+        }
+        if line.starts_with("Batch #") {
+            return Some(ProgressUpdate { completed: Some(self.executed), ..ProgressUpdate::phase("batch", line) });
+        }
+        if line.starts_with("Executed ") {
+            self.executed += 1;
+            return Some(ProgressUpdate::counted("executing", line, self.executed, None));
+        }
+        if line.starts_with("Deployed Addresses") {
+            return Some(ProgressUpdate::done("deployed", line, Some(self.executed)));
+        }
+        None
+    }
+}
+
+// npm is silent until its closing "added 12 packages ..." or "up to date ..."
+struct InstallProgress;
+
+impl ProgressReporter for InstallProgress {
+    fn line(&mut self, line: &str) -> Option<ProgressUpdate> {
+        if line.starts_with("added ") || line.starts_with("up to date") || line.starts_with("removed ") {
+            return Some(ProgressUpdate::done("installed", line, None));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(kind: &str, previous: Option<&str>, output: &str) -> Vec<ProgressUpdate> {
+        let mut reporter = reporter(kind, previous).unwrap();
+        output.lines().filter_map(|line| reporter.line(line.trim())).collect()
+    }
+
+    fn phases(updates: &[ProgressUpdate]) -> Vec<&str> {
+        updates.iter().map(|update| update.phase.as_str()).collect()
+    }
+
+    #[test]
+    fn counts_mocha_results() {
+        assert_eq!(test_count("  12 passing (3s)\n  1 failing\n  2 pending\n"), Some(15));
+        assert_eq!(test_count("Compiled 3 Solidity files successfully\n"), None);
+    }
+
+    #[test]
+    fn reports_compile_progress() {
+        let updates = run(
+            "compile",
+            None,
+            "Downloading compiler 0.8.24\nCompiling 12 files with Solc 0.8.24\nCompiled 12 Solidity files successfully",
+        );
+        assert_eq!(phases(&updates), ["downloading_compiler", "compiling", "compiled"]);
+        assert_eq!((updates[1].total, updates[1].percent), (Some(12), Some(0)));
+        assert_eq!((updates[2].completed, updates[2].percent), (Some(12), Some(100)));
+    }
+
+    #[test]
+    fn reports_tests_against_the_previous_total() {
+        let output = "Nothing to compile\n  Token\n    ✔ mints\n    1) burns\n    ✔ transfers\n  2 passing (1s)\n  1 failing\n  1) Token\n       burns:\n";
+        let updates = run("test", Some("  4 passing (2s)\n"), output);
+        assert_eq!(phases(&updates), ["compiled", "running_tests", "running_tests", "running_tests", "finished"]);
+        assert_eq!(updates[2].message, "burns");
+        assert_eq!((updates[3].completed, updates[3].total, updates[3].percent), (Some(3), Some(4), Some(75)));
+        assert_eq!((updates[4].completed, updates[4].percent), (Some(3), Some(100)));
+    }
+
+    #[test]
+    fn running_tests_stay_below_complete() {
+        let updates = run("test", Some("  1 passing\n"), "✔ one\n✔ two");
+        assert_eq!(updates.last().unwrap().percent, Some(99));
+    }
+
+    #[test]
+    fn reports_coverage_phases() {
+        let output = "Instrumenting for coverage...\n✔ mints\n  1 passing\nFile  | % Stmts | % Branch\nIstanbul reports written to ./coverage/";
+        assert_eq!(
+            phases(&run("coverage", None, output)),
+            ["instrumenting", "running_tests", "finished", "reporting", "finished"]
+        );
+    }
+
+    #[test]
+    fn reports_ignition_deployments() {
+        let output = "Deploying [ TokenModule ]\nBatch #1\nExecuted TokenModule#Token\nBatch #2\nExecuted TokenModule#Vault\nDeployed Addresses\nTokenModule#Token - 0x5FbDB2315678afecb367f032d93F642f64180aa3";
+        let updates = run("deploy", None, output);
+        assert_eq!(phases(&updates), ["deploying", "batch", "executing", "batch", "executing", "deployed"]);
+        assert_eq!(updates[3].completed, Some(1));
+        assert_eq!(updates[5].completed, Some(2));
+    }
+
+    #[test]
+    fn reports_npm_completion() {
+        assert_eq!(phases(&run("install", None, "npm WARN deprecated\nadded 12 packages in 3s")), ["installed"]);
+        assert!(reporter("task", None).is_none());
+    }
+}
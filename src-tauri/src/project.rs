@@ -0,0 +1,208 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use tauri::AppHandle;
+
+use crate::error::AppError;
+use crate::processes;
+use crate::resolver;
+
+// Config file names Hardhat accepts, in the order it resolves them
+pub const CONFIG_FILE_NAMES: [&str; 4] = [
+    "hardhat.config.js",
+    "hardhat.config.ts",
+    "hardhat.config.cjs",
+    "hardhat.config.mjs",
+];
+
+pub fn find_hardhat_config(project_path: &Path) -> Option<PathBuf> {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| project_path.join(name))
+        .find(|path| path.exists())
+}
+
+pub fn read_hardhat_config(project_path: &Path) -> Option<String> {
+    find_hardhat_config(project_path).and_then(|path| fs::read_to_string(path).ok())
+}
+
+pub fn read_package_json(project_path: &Path) -> Result<Option<serde_json::Value>, String> {
+    let path = project_path.join("package.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read package.json: {}", e))?;
+    let json = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse package.json: {}", e))?;
+
+    Ok(Some(json))
+}
+
+// Recursively collect files with the given extension, skipping dependency folders
+pub fn collect_files(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_files_into(dir, extension, &mut files);
+    files.sort();
+    files
+}
+
+fn collect_files_into(dir: &Path, extension: &str, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        // Symlinked directories aren't followed; workspace links in node_modules can loop
+        if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            if path.file_name().and_then(|s| s.to_str()) == Some("node_modules") {
+                continue;
+            }
+            collect_files_into(&path, extension, files);
+        } else if path.extension().and_then(|s| s.to_str()) == Some(extension) {
+            files.push(path);
+        }
+    }
+}
+
+pub fn find_ignition_module(project_path: &Path) -> Result<PathBuf, String> {
+    let ignition_dir = project_path.join("ignition").join("modules");
+
+    if !ignition_dir.exists() {
+        return Err("No Hardhat Ignition modules found. Please create deployment scripts in ignition/modules/".to_string());
+    }
+
+    // Look for .js or .ts files in ignition/modules
+    let entries = fs::read_dir(&ignition_dir)
+        .map_err(|e| format!("Failed to read ignition modules: {}", e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let path = entry.path();
+        if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
+            if ext == "js" || ext == "ts" {
+                return Ok(path);
+            }
+        }
+    }
+
+    Err("No deployment modules found in ignition/modules/".to_string())
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PackageManager {
+    Npm,
+    Yarn,
+    Pnpm,
+    Bun,
+}
+
+impl PackageManager {
+    // package.json's "packageManager" field (corepack) wins over lockfiles; npm otherwise
+    pub fn detect(project_path: &Path) -> Self {
+        let declared = read_package_json(project_path).ok().flatten().and_then(|json| {
+            let field = json.get("packageManager")?.as_str()?.to_string();
+            field.split('@').next().map(str::to_string)
+        });
+        let from_field = match declared.as_deref() {
+            Some("yarn") => Some(PackageManager::Yarn),
+            Some("pnpm") => Some(PackageManager::Pnpm),
+            Some("bun") => Some(PackageManager::Bun),
+            Some("npm") => Some(PackageManager::Npm),
+            _ => None,
+        };
+        let lockfiles = [
+            ("pnpm-lock.yaml", PackageManager::Pnpm),
+            ("yarn.lock", PackageManager::Yarn),
+            ("bun.lockb", PackageManager::Bun),
+            ("bun.lock", PackageManager::Bun),
+            ("package-lock.json", PackageManager::Npm),
+        ];
+        from_field
+            .or_else(|| lockfiles.iter().find(|(file, _)| project_path.join(file).exists()).map(|(_, pm)| *pm))
+            .unwrap_or(PackageManager::Npm)
+    }
+
+    pub fn binary(self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Bun => "bun",
+        }
+    }
+
+    pub fn install(self, project_path: &Path) -> Command {
+        let mut cmd = resolver::command(self.binary());
+        cmd.arg("install").current_dir(project_path);
+        cmd
+    }
+
+    pub fn add_dev(self, project_path: &Path, packages: &[&str]) -> Command {
+        let mut cmd = resolver::command(self.binary());
+        match self {
+            PackageManager::Npm => cmd.args(["install", "--save-dev"]),
+            PackageManager::Yarn => cmd.args(["add", "--dev"]),
+            PackageManager::Pnpm => cmd.args(["add", "--save-dev"]),
+            PackageManager::Bun => cmd.args(["add", "--dev"]),
+        };
+        cmd.args(packages).current_dir(project_path);
+        cmd
+    }
+}
+
+pub fn has_local_bin(project_path: &Path, name: &str) -> bool {
+    let bin_dir = project_path.join("node_modules").join(".bin");
+    bin_dir.join(name).exists() || bin_dir.join(format!("{}.cmd", name)).exists()
+}
+
+// Installs dev tooling (solhint, prettier, ...) into the project the first time a
+// feature needs it. Returns whether an install was performed.
+pub async fn ensure_dev_dependency(
+    app: &AppHandle,
+    project_path: &str,
+    bin: &str,
+    packages: &[&str],
+) -> Result<bool, AppError> {
+    let root = Path::new(project_path);
+    let node_modules = root.join("node_modules");
+    if has_local_bin(root, bin) && packages.iter().all(|p| node_modules.join(p).exists()) {
+        return Ok(false);
+    }
+
+    let cmd = PackageManager::detect(root).add_dev(root, packages);
+    let output = processes::run_tracked(app, None, "install", cmd).await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to install {}: {}", packages.join(" "), stderr).into());
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn collect_files_does_not_follow_symlinked_directories() {
+        let root = std::env::temp_dir().join(format!("hardhat-gui-collect-{}", std::process::id()));
+        let contracts = root.join("contracts");
+        fs::create_dir_all(contracts.join("lib")).unwrap();
+        fs::write(contracts.join("Token.sol"), "").unwrap();
+        fs::write(contracts.join("lib").join("Math.sol"), "").unwrap();
+        // A link back up the tree, like a workspace package linking its own root
+        std::os::unix::fs::symlink(&contracts, contracts.join("lib").join("loop")).unwrap();
+
+        let files = collect_files(&contracts, "sol");
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(files, vec![contracts.join("Token.sol"), contracts.join("lib").join("Math.sol")]);
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+// Keys live in the OS keychain (Keychain, Credential Manager, Secret Service),
+// never in the app data dir or a project
+pub const KEYRING_SERVICE: &str = "hardhat-gui";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Provider {
+    Infura,
+    Alchemy,
+    // Not an RPC provider; one key serves every Etherscan-family explorer via the v2 API
+    Etherscan,
+    // JWT for pinning to IPFS
+    Pinata,
+    // Project id for the WalletConnect relay
+    WalletConnect,
+}
+
+const PROVIDERS: [Provider; 5] =
+    [Provider::Infura, Provider::Alchemy, Provider::Etherscan, Provider::Pinata, Provider::WalletConnect];
+
+// chain id, Infura network, Alchemy network
+const PROVIDER_NETWORKS: [(u64, &str, &str); 11] = [
+    (1, "mainnet", "eth-mainnet"),
+    (11155111, "sepolia", "eth-sepolia"),
+    (17000, "holesky", "eth-holesky"),
+    (8453, "base-mainnet", "base-mainnet"),
+    (84532, "base-sepolia", "base-sepolia"),
+    (42161, "arbitrum-mainnet", "arb-mainnet"),
+    (421614, "arbitrum-sepolia", "arb-sepolia"),
+    (10, "optimism-mainnet", "opt-mainnet"),
+    (11155420, "optimism-sepolia", "opt-sepolia"),
+    (137, "polygon-mainnet", "polygon-mainnet"),
+    (80002, "polygon-amoy", "polygon-amoy"),
+];
+
+#[derive(Serialize, Deserialize)]
+pub struct ProviderKeyInfo {
+    pub provider: Provider,
+    pub configured: bool,
+    // First and last characters only, enough to tell two keys apart
+    pub hint: Option<String>,
+    pub env_var: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ProviderUrls {
+    pub provider: Provider,
+    pub chain_id: u64,
+    // With the key filled in, for the GUI's own connections
+    pub http_url: String,
+    pub ws_url: String,
+    // For network profiles; the GUI fills in the key when it connects
+    pub profile_url: String,
+    // JS expression for hardhat.config; the key comes from the environment at run time
+    pub config_snippet: String,
+    pub env_var: String,
+}
+
+impl Provider {
+    fn env_var(self) -> &'static str {
+        match self {
+            Provider::Infura => "INFURA_API_KEY",
+            Provider::Alchemy => "ALCHEMY_API_KEY",
+            Provider::Etherscan => "ETHERSCAN_API_KEY",
+            Provider::Pinata => "PINATA_JWT",
+            Provider::WalletConnect => "WALLETCONNECT_PROJECT_ID",
+        }
+    }
+
+    fn entry(self) -> Result<Entry, String> {
+        Entry::new(KEYRING_SERVICE, &format!("provider:{}", self.env_var()))
+            .map_err(|e| format!("Failed to open keychain: {}", e))
+    }
+
+    pub fn key(self) -> Result<Option<String>, String> {
+        match self.entry()?.get_password() {
+            Ok(key) => Ok(Some(key)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(format!("Failed to read {} key from keychain: {}", self.env_var(), e)),
+        }
+    }
+
+    // URLs with `{}` where the key goes
+    fn templates(self, chain_id: u64) -> Option<(String, String)> {
+        let (_, infura, alchemy) = PROVIDER_NETWORKS.iter().find(|(id, _, _)| *id == chain_id)?;
+        Some(match self {
+            Provider::Infura => (
+                format!("https://{}.infura.io/v3/{{}}", infura),
+                format!("wss://{}.infura.io/ws/v3/{{}}", infura),
+            ),
+            Provider::Alchemy => (
+                format!("https://{}.g.alchemy.com/v2/{{}}", alchemy),
+                format!("wss://{}.g.alchemy.com/v2/{{}}", alchemy),
+            ),
+            Provider::Etherscan | Provider::Pinata | Provider::WalletConnect => return None,
+        })
+    }
+}
+
+pub fn hint(key: &str) -> String {
+    if key.len() <= 8 {
+        return "…".to_string();
+    }
+    format!("{}…{}", &key[..4], &key[key.len() - 4..])
+}
+
+// Environment for hardhat runs, so configs can use process.env.INFURA_API_KEY
+pub fn env() -> BTreeMap<String, String> {
+    PROVIDERS
+        .iter()
+        .filter_map(|provider| Some((provider.env_var().to_string(), provider.key().ok()??)))
+        .collect()
+}
+
+// Fills `${INFURA_API_KEY}` style placeholders, as written by get_provider_urls
+pub fn expand(url: &str) -> String {
+    let mut url = url.to_string();
+    for provider in PROVIDERS {
+        let placeholder = format!("${{{}}}", provider.env_var());
+        if url.contains(&placeholder) {
+            if let Ok(Some(key)) = provider.key() {
+                url = url.replace(&placeholder, &key);
+            }
+        }
+    }
+    url
+}
+
+#[tauri::command]
+pub async fn list_provider_keys() -> Result<Vec<ProviderKeyInfo>, AppError> {
+    PROVIDERS
+        .iter()
+        .map(|provider| {
+            let key = provider.key()?;
+            Ok(ProviderKeyInfo {
+                provider: *provider,
+                configured: key.is_some(),
+                hint: key.as_deref().map(hint),
+                env_var: provider.env_var().to_string(),
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn set_provider_key(provider: Provider, key: String) -> Result<ProviderKeyInfo, AppError> {
+    let key = key.trim();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.') {
+        return Err(format!("Invalid {} key", provider.env_var()).into());
+    }
+    provider
+        .entry()?
+        .set_password(key)
+        .map_err(|e| format!("Failed to store {} key in keychain: {}", provider.env_var(), e))?;
+    Ok(ProviderKeyInfo {
+        provider,
+        configured: true,
+        hint: Some(hint(key)),
+        env_var: provider.env_var().to_string(),
+    })
+}
+
+#[tauri::command]
+pub async fn remove_provider_key(provider: Provider) -> Result<bool, AppError> {
+    match provider.entry()?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(format!("Failed to remove {} key from keychain: {}", provider.env_var(), e).into()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_provider_urls(provider: Provider, chain_id: u64) -> Result<ProviderUrls, AppError> {
+    let (http, ws) = provider
+        .templates(chain_id)
+        .ok_or_else(|| format!("{} has no endpoint for chain {}", provider.env_var(), chain_id))?;
+    let key = provider
+        .key()?
+        .ok_or_else(|| format!("No {} is configured", provider.env_var()))?;
+    let env_var = provider.env_var().to_string();
+    Ok(ProviderUrls {
+        provider,
+        chain_id,
+        http_url: http.replace("{}", &key),
+        ws_url: ws.replace("{}", &key),
+        profile_url: http.replace("{}", &format!("${{{}}}", env_var)),
+        config_snippet: format!("`{}`", http.replace("{}", &format!("${{process.env.{}}}", env_var))),
+        env_var,
+    })
+}
@@ -0,0 +1,312 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::artifacts;
+use crate::deployments::{self, Deployment};
+use crate::error::AppError;
+use crate::health::Severity;
+use crate::rpc;
+use crate::storage_layout;
+
+// EIP-1967 slots: keccak256("eip1967.proxy.<name>") - 1
+const IMPLEMENTATION_SLOT: &str = "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc";
+const ADMIN_SLOT: &str = "0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103";
+const BEACON_SLOT: &str = "0xa3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50";
+// OpenZeppelin 5.x Initializable namespace (ERC-7201)
+const INITIALIZABLE_SLOT: &str = "0xf0c57e16840df040f15088dc2f81fe391c3923bec73e23a9662efc9c229c6a00";
+
+const OWNER_SELECTOR: &str = "0x8da5cb5b";
+const IMPLEMENTATION_SELECTOR: &str = "0x5c60da1b";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyKind {
+    Transparent,
+    Uups,
+    Beacon,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UpgradeAuthority {
+    pub address: String,
+    // Contract owners are usually multisigs or timelocks; an EOA is a single key
+    pub is_contract: bool,
+    // Set when the authority was found through an intermediate contract (ProxyAdmin, beacon)
+    pub via: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProxyRisk {
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProxyReport {
+    pub deployment_id: String,
+    pub future_id: String,
+    pub address: String,
+    pub kind: ProxyKind,
+    pub implementation: Option<String>,
+    pub implementation_contract: Option<String>,
+    pub upgrade_authority: Option<UpgradeAuthority>,
+    pub proxy_initialized: Option<bool>,
+    pub implementation_initializers_disabled: Option<bool>,
+    pub risks: Vec<ProxyRisk>,
+}
+
+// A deployment whose proxy slots could not be read, so it could not be classified
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProxyCheckError {
+    pub deployment_id: String,
+    pub future_id: String,
+    pub address: String,
+    pub check: String,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ProxyAuditSummary {
+    pub chain_id: u64,
+    pub proxy_count: usize,
+    pub at_risk_count: usize,
+    pub proxies: Vec<ProxyReport>,
+    pub errors: Vec<ProxyCheckError>,
+}
+
+async fn owner_of(url: &str, address: &str) -> Option<String> {
+    rpc::eth_call(url, address, OWNER_SELECTOR).await.ok().and_then(|r| rpc::word_to_address(&r))
+}
+
+async fn authority(url: &str, address: String, via: Option<String>) -> UpgradeAuthority {
+    let is_contract = rpc::code_at(url, &address).await.is_ok_and(|code| code.len() > 2);
+    UpgradeAuthority { address, is_contract, via }
+}
+
+fn read_byte(word: &str, offset: usize, size: usize) -> Option<u64> {
+    let end = word.len().checked_sub(offset * 2)?;
+    let start = end.checked_sub(size * 2)?;
+    u64::from_str_radix(&word[start..end], 16).ok()
+}
+
+// `disabled` is set when _disableInitializers() ran, i.e. the counter holds its type's max
+struct Initialized {
+    version: u64,
+    disabled: bool,
+}
+
+impl Initialized {
+    // 4.x counts in a uint8, 5.x in a uint64
+    fn new(version: u64, bytes: usize) -> Self {
+        Initialized { version, disabled: version == u64::MAX >> (64 - bytes * 8) }
+    }
+}
+
+// The `_initialized` counter: 5.x keeps it in a namespaced slot, 4.x in regular
+// storage at the position the compiler assigned. Ok(None) when it can't be located
+async fn initialized_version(url: &str, project_path: &Path, address: &str, contract: Option<&str>) -> Result<Option<Initialized>, AppError> {
+    let namespaced = rpc::storage_at(url, address, INITIALIZABLE_SLOT).await?;
+    let Some(version) = read_byte(&namespaced, 0, 8) else {
+        return Ok(None);
+    };
+    if version != 0 {
+        return Ok(Some(Initialized::new(version, 8)));
+    }
+
+    // A zero only means "uninitialized" for contracts that are Initializable at all,
+    // which both major versions advertise through the Initialized event
+    let Some(contract) = contract else {
+        return Ok(None);
+    };
+    let initializable = artifacts::load_artifacts(project_path)
+        .iter()
+        .find(|a| a.contract_name == contract)
+        .is_some_and(|a| a.abi.iter().any(|item| item.is_event() && item.name == "Initialized"));
+    if !initializable {
+        return Ok(None);
+    }
+
+    let layout = storage_layout::current_layout(project_path, contract).ok();
+    let Some(entry) = layout.as_ref().and_then(|l| l.storage.iter().find(|e| e.label == "_initialized")) else {
+        // 5.x with nothing written to the namespaced slot yet
+        return Ok(Some(Initialized::new(0, 8)));
+    };
+    let Ok(slot) = entry.slot.parse::<u128>() else {
+        return Ok(None);
+    };
+    let word = rpc::storage_at(url, address, &format!("0x{:x}", slot)).await?;
+    Ok(read_byte(&word, entry.offset as usize, 1).map(|version| Initialized::new(version, 1)))
+}
+
+// A failed read only loses the check it belongs to; the rest of the audit goes on
+async fn inspect(url: &str, project_path: &Path, deployment: &Deployment, registry: &[Deployment]) -> Result<Option<ProxyReport>, ProxyCheckError> {
+    let address = deployment.address.as_str();
+    let failed = |check: &str, e: AppError| ProxyCheckError {
+        deployment_id: deployment.deployment_id.clone(),
+        future_id: deployment.future_id.clone(),
+        address: deployment.address.clone(),
+        check: check.to_string(),
+        message: e.to_string(),
+    };
+    let implementation_word = rpc::storage_at(url, address, IMPLEMENTATION_SLOT)
+        .await
+        .map_err(|e| failed("implementation_slot", e))?;
+    let beacon = rpc::word_to_address(&rpc::storage_at(url, address, BEACON_SLOT).await.map_err(|e| failed("beacon_slot", e))?);
+
+    let (kind, implementation, upgrade_authority) = if let Some(beacon) = beacon {
+        let implementation = rpc::eth_call(url, &beacon, IMPLEMENTATION_SELECTOR)
+            .await
+            .ok()
+            .and_then(|r| rpc::word_to_address(&r));
+        let owner = match owner_of(url, &beacon).await {
+            Some(owner) => Some(authority(url, owner, Some(beacon)).await),
+            None => None,
+        };
+        (ProxyKind::Beacon, implementation, owner)
+    } else if let Some(implementation) = rpc::word_to_address(&implementation_word) {
+        let admin_word = rpc::storage_at(url, address, ADMIN_SLOT).await.map_err(|e| failed("admin_slot", e))?;
+        match rpc::word_to_address(&admin_word) {
+            // Transparent proxies are administered by a ProxyAdmin whose owner can upgrade
+            Some(admin) => {
+                let authority = match owner_of(url, &admin).await {
+                    Some(owner) => authority(url, owner, Some(admin)).await,
+                    None => authority(url, admin, None).await,
+                };
+                (ProxyKind::Transparent, Some(implementation), Some(authority))
+            }
+            // UUPS: upgradeTo lives on the implementation, usually gated by owner()
+            None => {
+                let owner = match owner_of(url, address).await {
+                    Some(owner) => Some(authority(url, owner, None).await),
+                    None => None,
+                };
+                (ProxyKind::Uups, Some(implementation), owner)
+            }
+        }
+    } else {
+        return Ok(None);
+    };
+
+    let implementation_contract = implementation.as_ref().and_then(|imp| {
+        registry
+            .iter()
+            .find(|d| d.address.eq_ignore_ascii_case(imp))
+            .map(|d| d.contract_name.clone())
+    });
+
+    let mut risks = Vec::new();
+    let mut unreadable = |what: &str, e: AppError| {
+        risks.push(ProxyRisk {
+            severity: Severity::Error,
+            message: format!("Could not read the {} initializer state: {}", what, e),
+        });
+        None
+    };
+    let contract = implementation_contract.as_deref();
+    let proxy_version = initialized_version(url, project_path, address, contract)
+        .await
+        .unwrap_or_else(|e| unreadable("proxy's", e));
+    let implementation_version = match &implementation {
+        Some(imp) => initialized_version(url, project_path, imp, contract)
+            .await
+            .unwrap_or_else(|e| unreadable("implementation's", e)),
+        None => None,
+    };
+
+    if proxy_version.as_ref().is_some_and(|v| v.version == 0) {
+        risks.push(ProxyRisk {
+            severity: Severity::Error,
+            message: "Proxy is not initialized; anyone can call its initializer and take ownership".to_string(),
+        });
+    }
+    match &implementation_version {
+        Some(v) if v.version == 0 => risks.push(ProxyRisk {
+            severity: Severity::Error,
+            message: "Implementation initializers are not disabled; anyone can initialize the implementation directly".to_string(),
+        }),
+        Some(v) if !v.disabled => risks.push(ProxyRisk {
+            severity: Severity::Warning,
+            message: "Implementation was initialized directly instead of calling _disableInitializers()".to_string(),
+        }),
+        _ => {}
+    }
+    match &upgrade_authority {
+        Some(a) if !a.is_contract => risks.push(ProxyRisk {
+            severity: Severity::Warning,
+            message: format!("Upgrades are controlled by a single externally owned account ({})", a.address),
+        }),
+        None => risks.push(ProxyRisk {
+            severity: Severity::Info,
+            message: "Could not determine who can upgrade this proxy (no owner())".to_string(),
+        }),
+        _ => {}
+    }
+
+    Ok(Some(ProxyReport {
+        deployment_id: deployment.deployment_id.clone(),
+        future_id: deployment.future_id.clone(),
+        address: deployment.address.clone(),
+        kind,
+        implementation,
+        implementation_contract,
+        upgrade_authority,
+        proxy_initialized: proxy_version.map(|v| v.version != 0),
+        implementation_initializers_disabled: implementation_version.map(|v| v.disabled),
+        risks,
+    }))
+}
+
+#[tauri::command]
+pub async fn audit_proxies(project_path: String, rpc_url: Option<String>) -> Result<ProxyAuditSummary, AppError> {
+    let root = Path::new(&project_path);
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let chain_id = rpc::chain_id(url).await?;
+
+    let registry: Vec<Deployment> = deployments::load_deployments(root)
+        .into_iter()
+        .filter(|d| d.chain_id.is_none_or(|id| id == chain_id))
+        .collect();
+
+    let mut proxies = Vec::new();
+    let mut errors = Vec::new();
+    for deployment in &registry {
+        match inspect(url, root, deployment, &registry).await {
+            Ok(Some(report)) => proxies.push(report),
+            Ok(None) => {}
+            Err(e) => errors.push(e),
+        }
+    }
+
+    Ok(ProxyAuditSummary {
+        chain_id,
+        proxy_count: proxies.len(),
+        at_risk_count: proxies
+            .iter()
+            .filter(|p| p.risks.iter().any(|r| r.severity != Severity::Info))
+            .count(),
+        proxies,
+        errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_counter_types_max_means_disabled() {
+        assert!(Initialized::new(255, 1).disabled);
+        assert!(!Initialized::new(1, 1).disabled);
+        assert!(!Initialized::new(255, 8).disabled);
+        assert!(Initialized::new(u64::MAX, 8).disabled);
+    }
+
+    #[test]
+    fn reads_bytes_from_the_right_of_a_word() {
+        let word = format!("0x{}ff01", "0".repeat(60));
+        assert_eq!(read_byte(&word, 0, 1), Some(1));
+        assert_eq!(read_byte(&word, 1, 1), Some(255));
+        assert_eq!(read_byte(&word, 0, 8), Some(0xff01));
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::cancellation;
+use crate::error::AppError;
+use crate::gas_oracle;
+use crate::networks;
+use crate::processes;
+use crate::project::{self, PackageManager};
+use crate::resolver;
+use crate::store;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobSpec {
+    Compile,
+    Test,
+    // Needs solidity-coverage, which hardhat-toolbox includes
+    Coverage,
+    Deploy {
+        module: Option<String>,
+        network: Option<String>,
+        #[serde(default)]
+        env: BTreeMap<String, String>,
+    },
+    Task {
+        task: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default)]
+        env: BTreeMap<String, String>,
+    },
+    Script {
+        script: String,
+        network: Option<String>,
+        #[serde(default)]
+        env: BTreeMap<String, String>,
+    },
+    // The project's own dependencies
+    Install,
+}
+
+impl JobSpec {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JobSpec::Compile => "compile",
+            JobSpec::Test => "test",
+            JobSpec::Coverage => "coverage",
+            JobSpec::Deploy { .. } => "deploy",
+            JobSpec::Task { .. } => "task",
+            JobSpec::Script { .. } => "script",
+            JobSpec::Install => "install",
+        }
+    }
+
+    pub fn command(&self, project_path: &str) -> Result<Command, String> {
+        let root = Path::new(project_path);
+        if let JobSpec::Install = self {
+            return Ok(PackageManager::detect(root).install(root));
+        }
+
+        let mut cmd = resolver::hardhat(root);
+
+        match self {
+            JobSpec::Compile => {
+                cmd.arg("compile");
+            }
+            JobSpec::Test => {
+                cmd.arg("test");
+            }
+            JobSpec::Coverage => {
+                cmd.arg("coverage");
+            }
+            JobSpec::Deploy { module, network, env } => {
+                let module = match module {
+                    Some(module) => module.clone(),
+                    None => project::find_ignition_module(root)?
+                        .to_string_lossy()
+                        .to_string(),
+                };
+                let network = network.as_deref().unwrap_or("localhost");
+                cmd.args(["ignition", "deploy", &module]);
+                networks::apply_network(&mut cmd, network);
+                processes::apply_env(&mut cmd, env)?;
+            }
+            JobSpec::Task { task, args, env } => {
+                cmd.arg(task).args(args);
+                processes::apply_env(&mut cmd, env)?;
+            }
+            JobSpec::Script { script, network, env } => {
+                cmd.args(["run", script]);
+                networks::apply_network(&mut cmd, network.as_deref().unwrap_or("localhost"));
+                processes::apply_env(&mut cmd, env)?;
+            }
+            JobSpec::Install => {}
+        }
+
+        Ok(cmd)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+    Skipped,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Job {
+    pub id: String,
+    pub batch_id: String,
+    pub project_path: String,
+    pub spec: JobSpec,
+    pub status: JobStatus,
+    pub log: String,
+    pub exit_code: Option<i32>,
+    pub queued_at: u64,
+    pub started_at: Option<u64>,
+    pub finished_at: Option<u64>,
+    pub duration_ms: Option<u64>,
+}
+
+// All jobs run one at a time through a single worker, so two npx processes never
+// fight over the same cache/ and artifacts/ directories
+#[derive(Default)]
+pub struct JobQueue {
+    next_id: AtomicU64,
+    jobs: Mutex<Vec<Job>>,
+    worker_running: AtomicBool,
+}
+
+impl JobQueue {
+    fn next_id(&self, prefix: &str) -> String {
+        format!("{}-{}", prefix, self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn update<F: FnOnce(&mut Job)>(&self, app: &AppHandle, id: &str, f: F) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.iter_mut().find(|job| job.id == id) {
+            f(job);
+            let _ = app.emit("job-updated", job.clone());
+        }
+    }
+
+    fn is_cancelled(&self, id: &str) -> bool {
+        self.jobs.lock().unwrap().iter().any(|job| job.id == id && job.status == JobStatus::Cancelled)
+    }
+
+    // Claims the oldest queued job, or releases the worker slot when there is none.
+    // Both happen under the jobs lock so an enqueue can't slip in between.
+    fn claim_next(&self) -> Option<Job> {
+        let mut jobs = self.jobs.lock().unwrap();
+        match jobs.iter_mut().find(|job| job.status == JobStatus::Queued) {
+            Some(job) => {
+                job.status = JobStatus::Running;
+                job.started_at = Some(store::timestamp_millis());
+                Some(job.clone())
+            }
+            None => {
+                self.worker_running.store(false, Ordering::SeqCst);
+                None
+            }
+        }
+    }
+}
+
+async fn run_worker(app: AppHandle) {
+    let queue = app.state::<JobQueue>();
+
+    while let Some(job) = queue.claim_next() {
+        let _ = app.emit("job-updated", job.clone());
+        let started = Instant::now();
+
+        // Registered before anything is awaited, so cancel_job can stop a job that is
+        // still preparing. Once the process runs, its own execution token takes over. A
+        // cancel that came between the claim and this shows in the job's status
+        let execution = cancellation::execution(Some(Path::new(&job.project_path)), &job.id);
+        if queue.is_cancelled(&job.id) {
+            execution.token.cancel();
+        }
+        if let JobSpec::Deploy { network: Some(network), .. } = &job.spec {
+            execution.token.run_until_cancelled(gas_oracle::prefetch(network)).await;
+        }
+        let result = match job.spec.command(&job.project_path) {
+            _ if execution.token.is_cancelled() => Err(AppError::Cancelled { execution_id: job.id.clone() }),
+            Ok(cmd) => {
+                let run = processes::run_tracked(&app, Some(job.id.clone()), job.spec.kind(), cmd);
+                tokio::pin!(run);
+                // Biased so the run registers its execution before a cancellation is handled
+                tokio::select! {
+                    biased;
+                    result = &mut run => result,
+                    _ = execution.token.cancelled() => {
+                        cancellation::cancel(&job.id);
+                        run.await
+                    }
+                }
+            }
+            Err(e) => Err(e.into()),
+        };
+        drop(execution);
+
+        let (status, log, exit_code) = match result {
+            Ok(output) => {
+                let log = format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                let status = if output.status.success() { JobStatus::Succeeded } else { JobStatus::Failed };
+                (status, log, output.status.code())
+            }
+            Err(e @ AppError::Cancelled { .. }) => (JobStatus::Cancelled, e.to_string(), None),
+            Err(e) => (JobStatus::Failed, e.to_string(), None),
+        };
+        // A job cancelled while it ran stays cancelled, whatever the process did after
+        let status = if queue.is_cancelled(&job.id) { JobStatus::Cancelled } else { status };
+
+        queue.update(&app, &job.id, |j| {
+            j.status = status;
+            j.log = log;
+            j.exit_code = exit_code;
+            j.finished_at = Some(store::timestamp_millis());
+            j.duration_ms = Some(started.elapsed().as_millis() as u64);
+        });
+
+        // compile → test → deploy is a pipeline: don't deploy what failed to build
+        if status != JobStatus::Succeeded {
+            let skipped: Vec<String> = queue
+                .jobs
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|j| j.batch_id == job.batch_id && j.status == JobStatus::Queued)
+                .map(|j| j.id.clone())
+                .collect();
+            for id in skipped {
+                queue.update(&app, &id, |j| j.status = JobStatus::Skipped);
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn enqueue_jobs(
+    app: AppHandle,
+    queue: State<'_, JobQueue>,
+    project_path: String,
+    jobs: Vec<JobSpec>,
+) -> Result<Vec<Job>, AppError> {
+    if jobs.is_empty() {
+        return Err("No jobs to enqueue".to_string().into());
+    }
+
+    let batch_id = queue.next_id("batch");
+    let queued_at = store::timestamp_millis();
+    let new_jobs: Vec<Job> = jobs
+        .into_iter()
+        .map(|spec| Job {
+            id: queue.next_id("job"),
+            batch_id: batch_id.clone(),
+            project_path: project_path.clone(),
+            spec,
+            status: JobStatus::Queued,
+            log: String::new(),
+            exit_code: None,
+            queued_at,
+            started_at: None,
+            finished_at: None,
+            duration_ms: None,
+        })
+        .collect();
+
+    queue.jobs.lock().unwrap().extend(new_jobs.iter().cloned());
+    for job in &new_jobs {
+        let _ = app.emit("job-updated", job.clone());
+    }
+
+    if !queue.worker_running.swap(true, Ordering::SeqCst) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move { run_worker(app).await });
+    }
+
+    Ok(new_jobs)
+}
+
+#[tauri::command]
+pub async fn list_jobs(
+    queue: State<'_, JobQueue>,
+    project_path: Option<String>,
+) -> Result<Vec<Job>, AppError> {
+    let jobs = queue.jobs.lock().unwrap();
+    Ok(jobs
+        .iter()
+        .filter(|job| project_path.as_ref().is_none_or(|p| &job.project_path == p))
+        .cloned()
+        .collect())
+}
+
+#[tauri::command]
+pub async fn cancel_job(
+    app: AppHandle,
+    queue: State<'_, JobQueue>,
+    processes: State<'_, processes::ProcessManager>,
+    job_id: String,
+) -> Result<bool, AppError> {
+    let status = queue
+        .jobs
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|job| job.id == job_id)
+        .map(|job| job.status);
+
+    match status {
+        Some(JobStatus::Queued) => {
+            queue.update(&app, &job_id, |j| j.status = JobStatus::Cancelled);
+            Ok(true)
+        }
+        // The worker registers the job's execution id as soon as it claims the job, so
+        // this reaches it whether it is still preparing or already running
+        Some(JobStatus::Running) => {
+            queue.update(&app, &job_id, |j| j.status = JobStatus::Cancelled);
+            processes::cancel_execution(app.clone(), processes, job_id).await?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+#[tauri::command]
+pub async fn clear_finished_jobs(queue: State<'_, JobQueue>) -> Result<usize, AppError> {
+    let mut jobs = queue.jobs.lock().unwrap();
+    let before = jobs.len();
+    jobs.retain(|job| matches!(job.status, JobStatus::Queued | JobStatus::Running));
+    Ok(before - jobs.len())
+}
+
+// Starts a job outside the queue and returns its execution id straight away; output,
+// progress and the result arrive as `execution-event`s for that id
+#[tauri::command]
+pub async fn start_execution(app: AppHandle, project_path: String, spec: JobSpec) -> Result<String, AppError> {
+    let cmd = spec.command(&project_path)?;
+    let execution_id = app.state::<processes::ProcessManager>().new_execution_id();
+
+    let id = execution_id.clone();
+    tauri::async_runtime::spawn(async move {
+        if let JobSpec::Deploy { network: Some(network), .. } = &spec {
+            gas_oracle::prefetch(network).await;
+        }
+        if let Err(e) = processes::run_tracked(&app, Some(id.clone()), spec.kind(), cmd).await {
+            tracing::debug!(execution_id = %id, error = %e, "background execution ended with an error");
+        }
+    });
+
+    Ok(execution_id)
+}
@@ -0,0 +1,327 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::artifacts;
+use crate::error::AppError;
+use crate::findings::{self, AnalysisReport};
+use crate::gates;
+use crate::health::Severity;
+use crate::processes;
+use crate::resolver;
+use crate::store;
+
+const REPORTS_DIR: &str = "reports";
+const COVERAGE_FILE: &str = "coverage.json";
+// EIP-170 runtime code size limit
+const MAX_CONTRACT_SIZE: usize = 24_576;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    Markdown,
+    Pdf,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReportBundle {
+    pub path: String,
+    pub format: ReportFormat,
+    // Sections that had data; the rest are listed in the report as not run
+    pub included: Vec<String>,
+}
+
+#[derive(Default)]
+struct Coverage {
+    covered: usize,
+    total: usize,
+}
+
+impl Coverage {
+    fn add(&mut self, counts: impl Iterator<Item = u64>) {
+        for count in counts {
+            self.total += 1;
+            self.covered += (count > 0) as usize;
+        }
+    }
+
+    fn percent(&self) -> String {
+        if self.total == 0 {
+            return "-".to_string();
+        }
+        format!("{:.1}%", self.covered as f64 * 100.0 / self.total as f64)
+    }
+}
+
+// "2024-05-01 14:03 UTC" without pulling in a date crate (civil-from-days)
+fn format_timestamp(millis: u64) -> String {
+    let secs = millis / 1000;
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    format!("{:04}-{:02}-{:02} {:02}:{:02} UTC", year, month, day, secs % 86_400 / 3600, secs % 3600 / 60)
+}
+
+// Table cells can't contain raw pipes or newlines
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn severity_label(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "High",
+        Severity::Warning => "Medium",
+        Severity::Info => "Low",
+    }
+}
+
+fn write_findings(out: &mut String, reports: &BTreeMap<String, AnalysisReport>) {
+    let _ = writeln!(out, "## Static analysis\n");
+    let _ = writeln!(out, "| Tool | Run at | High | Medium | Low |\n|---|---|---|---|---|");
+    for report in reports.values() {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} |",
+            report.tool,
+            format_timestamp(report.generated_at),
+            report.error_count,
+            report.warning_count,
+            report.info_count
+        );
+    }
+    out.push('\n');
+
+    for report in reports.values().filter(|r| !r.findings.is_empty()) {
+        let _ = writeln!(out, "### {}\n", report.tool);
+        let _ = writeln!(out, "| Severity | Finding | Location | Rule |\n|---|---|---|---|");
+        for finding in &report.findings {
+            let location = finding
+                .locations
+                .first()
+                .map(|l| match l.line {
+                    Some(line) => format!("{}:{}", l.file, line),
+                    None => l.file.clone(),
+                })
+                .unwrap_or_default();
+            let rule = match &finding.swc_id {
+                Some(swc) => format!("{} (SWC-{})", finding.rule, swc),
+                None => finding.rule.clone(),
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                severity_label(finding.severity),
+                cell(&finding.title),
+                cell(&location),
+                cell(&rule)
+            );
+        }
+        out.push('\n');
+    }
+}
+
+// solidity-coverage writes Istanbul's per-file counters to coverage.json
+fn write_coverage(out: &mut String, coverage: &Value) -> bool {
+    let Some(files) = coverage.as_object().filter(|files| !files.is_empty()) else {
+        return false;
+    };
+
+    let counts = |file: &Value, key: &str| -> Vec<u64> {
+        file.get(key)
+            .and_then(Value::as_object)
+            .map(|counters| {
+                counters
+                    .values()
+                    .flat_map(|v| match v {
+                        Value::Array(branches) => branches.iter().filter_map(Value::as_u64).collect(),
+                        other => other.as_u64().into_iter().collect::<Vec<_>>(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let _ = writeln!(out, "## Test coverage\n");
+    let _ = writeln!(out, "| File | Statements | Branches | Functions | Lines |\n|---|---|---|---|---|");
+    let mut totals: [Coverage; 4] = Default::default();
+    for (name, file) in files {
+        let mut row: [Coverage; 4] = Default::default();
+        for (i, key) in ["s", "b", "f", "l"].iter().enumerate() {
+            row[i].add(counts(file, key).into_iter());
+            totals[i].add(counts(file, key).into_iter());
+        }
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} |",
+            cell(name),
+            row[0].percent(),
+            row[1].percent(),
+            row[2].percent(),
+            row[3].percent()
+        );
+    }
+    let _ = writeln!(
+        out,
+        "| **All files** | **{}** | **{}** | **{}** | **{}** |\n",
+        totals[0].percent(),
+        totals[1].percent(),
+        totals[2].percent(),
+        totals[3].percent()
+    );
+    true
+}
+
+fn write_gas(out: &mut String, gas: &BTreeMap<String, u64>, limits: &BTreeMap<String, u64>) {
+    let _ = writeln!(out, "## Gas usage\n");
+    let _ = writeln!(out, "| Function | Max gas | Threshold |\n|---|---|---|");
+    for (function, max) in gas {
+        let threshold = match limits.get(function) {
+            Some(limit) if max > limit => format!("{} (exceeded)", limit),
+            Some(limit) => limit.to_string(),
+            None => "-".to_string(),
+        };
+        let _ = writeln!(out, "| {} | {} | {} |", cell(function), max, threshold);
+    }
+    out.push('\n');
+}
+
+fn write_sizes(out: &mut String, project_path: &Path) -> bool {
+    let mut contracts: Vec<(String, usize)> = artifacts::load_artifacts(project_path)
+        .into_iter()
+        .filter(|a| !artifacts::is_dependency_source(&a.source_name) && a.deployed_size > 0)
+        .map(|a| (a.contract_name.clone(), a.deployed_size))
+        .collect();
+    if contracts.is_empty() {
+        return false;
+    }
+    contracts.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+
+    let _ = writeln!(out, "## Contract sizes\n");
+    let _ = writeln!(out, "| Contract | Runtime size (bytes) | Of 24 KiB limit |\n|---|---|---|");
+    for (name, size) in contracts {
+        let share = size as f64 * 100.0 / MAX_CONTRACT_SIZE as f64;
+        let flag = if size > MAX_CONTRACT_SIZE { " (over limit)" } else { "" };
+        let _ = writeln!(out, "| {} | {} | {:.1}%{} |", name, size, share, flag);
+    }
+    out.push('\n');
+    true
+}
+
+fn render(project_path: &Path, included: &mut Vec<String>) -> Result<String, String> {
+    let name = project_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "project".to_string());
+    let mut out = format!(
+        "# Security & quality report: {}\n\nGenerated {}\n\n",
+        name,
+        format_timestamp(store::timestamp_millis())
+    );
+    let mut missing = Vec::new();
+
+    let reports = findings::latest_reports(project_path)?;
+    if reports.is_empty() {
+        missing.push("static analysis (run the scanner, solhint, semgrep or mythril)");
+    } else {
+        write_findings(&mut out, &reports);
+        included.push("analysis".to_string());
+    }
+
+    match artifacts::read_json(&project_path.join(COVERAGE_FILE)) {
+        Some(coverage) if write_coverage(&mut out, &coverage) => included.push("coverage".to_string()),
+        _ => missing.push("test coverage (run `npx hardhat coverage`)"),
+    }
+
+    let thresholds = gates::load_thresholds(project_path)?;
+    match gates::load_gas_report(project_path).filter(|gas| !gas.is_empty()) {
+        Some(gas) => {
+            write_gas(&mut out, &gas, &thresholds.function_gas);
+            included.push("gas".to_string());
+        }
+        None => missing.push("gas usage (run the tests with hardhat-gas-reporter enabled)"),
+    }
+
+    if write_sizes(&mut out, project_path) {
+        included.push("sizes".to_string());
+    } else {
+        missing.push("contract sizes (compile the project)");
+    }
+
+    if !thresholds.is_empty() {
+        let gates = gates::evaluate(project_path, &thresholds);
+        let _ = writeln!(
+            out,
+            "## Quality gates\n\n{} ({} violation(s))\n",
+            if gates.passed { "Passed" } else { "**Failed**" },
+            gates.violations.len()
+        );
+        included.push("gates".to_string());
+    }
+
+    if !missing.is_empty() {
+        let _ = writeln!(out, "## Not included\n");
+        for item in missing {
+            let _ = writeln!(out, "- No results for {}", item);
+        }
+    }
+
+    Ok(out)
+}
+
+#[tauri::command]
+pub async fn generate_report(
+    app: AppHandle,
+    project_path: String,
+    format: Option<ReportFormat>,
+    output_path: Option<String>,
+) -> Result<ReportBundle, AppError> {
+    let root = Path::new(&project_path);
+    let format = format.unwrap_or(ReportFormat::Markdown);
+    let extension = if format == ReportFormat::Pdf { "pdf" } else { "md" };
+    let path = match output_path {
+        Some(path) => PathBuf::from(path),
+        None => root
+            .join(REPORTS_DIR)
+            .join(format!("report-{}.{}", store::timestamp_millis(), extension)),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create report directory: {}", e))?;
+    }
+
+    let mut included = Vec::new();
+    let markdown = render(root, &mut included)?;
+    let markdown_path = path.with_extension("md");
+    fs::write(&markdown_path, markdown).map_err(|e| format!("Failed to write report: {}", e))?;
+
+    if format == ReportFormat::Pdf {
+        // PDF rendering is delegated to pandoc; the Markdown stays next to it either way
+        if resolver::locate("pandoc").is_none() {
+            return Err(format!(
+                "PDF export requires pandoc. The Markdown report was written to {}",
+                markdown_path.display()
+            ).into());
+        }
+        let mut cmd = resolver::command("pandoc");
+        cmd.arg(&markdown_path).arg("-o").arg(&path);
+        let output = processes::run_tracked(&app, None, "report", cmd).await?;
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to render PDF: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ).into());
+        }
+    }
+
+    Ok(ReportBundle { path: path.to_string_lossy().to_string(), format, included })
+}
@@ -0,0 +1,224 @@
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+// Apps launched from Finder/Explorer/a desktop launcher don't inherit the login
+// shell's PATH, so node installed through nvm/volta/fnm is invisible to a bare
+// `Command::new("npx")`. Every spawn site goes through here instead.
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ToolchainInfo {
+    pub node: Option<String>,
+    pub npm: Option<String>,
+    pub npx: Option<String>,
+    pub search_path: Vec<String>,
+}
+
+struct Toolchain {
+    dirs: Vec<PathBuf>,
+    path: OsString,
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn env_dir(name: &str) -> Option<PathBuf> {
+    env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from)
+}
+
+fn version_key(name: &str) -> Vec<u64> {
+    name.trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+// nvm's default alias can be "18", "v18.17.0" or "lts/*"; pick the newest installed
+// version matching it, or the newest installed version overall
+fn nvm_dir(home: &Path) -> Option<PathBuf> {
+    if let Some(bin) = env_dir("NVM_BIN") {
+        return Some(bin);
+    }
+    let root = env_dir("NVM_DIR").unwrap_or_else(|| home.join(".nvm"));
+    let alias = fs::read_to_string(root.join("alias").join("default")).unwrap_or_default();
+    let alias = alias.trim().trim_start_matches('v');
+
+    let mut versions: Vec<String> = fs::read_dir(root.join("versions").join("node"))
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    versions.sort_by_key(|v| version_key(v));
+
+    let matching = versions.iter().rev().find(|v| {
+        let v = v.trim_start_matches('v');
+        !alias.is_empty() && (v == alias || v.starts_with(&format!("{}.", alias)))
+    });
+    let version = matching.or(versions.last())?;
+    Some(root.join("versions").join("node").join(version).join("bin"))
+}
+
+// fnm's own install script puts the fnm binary in its root as well
+fn fnm_roots(home: &Path) -> Vec<PathBuf> {
+    let candidates = [
+        env_dir("FNM_DIR"),
+        Some(home.join(".local").join("share").join("fnm")),
+        Some(home.join("Library").join("Application Support").join("fnm")),
+        env_dir("APPDATA").map(|d| d.join("fnm")),
+        Some(home.join(".fnm")),
+    ];
+    candidates.into_iter().flatten().collect()
+}
+
+fn fnm_dir(home: &Path) -> Option<PathBuf> {
+    if let Some(multishell) = env_dir("FNM_MULTISHELL_PATH") {
+        return Some(if cfg!(windows) { multishell } else { multishell.join("bin") });
+    }
+    fnm_roots(home).into_iter().find_map(|root| {
+        let default = root.join("aliases").join("default");
+        let bin = if cfg!(windows) { default } else { default.join("bin") };
+        bin.is_dir().then_some(bin)
+    })
+}
+
+// Where foundryup installs anvil, forge and cast
+pub fn foundry_bin() -> Option<PathBuf> {
+    let root = env_dir("FOUNDRY_DIR").or_else(|| home_dir().map(|home| home.join(".foundry")))?;
+    Some(root.join("bin"))
+}
+
+fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let home = home_dir();
+
+    // Version managers first, matching how their shell hooks prepend to PATH
+    if let Some(home) = &home {
+        let volta = env_dir("VOLTA_HOME").unwrap_or_else(|| home.join(".volta"));
+        dirs.push(volta.join("bin"));
+        dirs.extend(nvm_dir(home));
+        dirs.extend(fnm_dir(home));
+        dirs.extend(fnm_roots(home));
+    }
+    dirs.extend(env_dir("NVM_SYMLINK"));
+    dirs.extend(foundry_bin());
+
+    if let Some(path) = env::var_os("PATH") {
+        dirs.extend(env::split_paths(&path));
+    }
+
+    if cfg!(windows) {
+        dirs.extend(env_dir("APPDATA").map(|d| d.join("npm")));
+        dirs.extend(env_dir("ProgramFiles").map(|d| d.join("nodejs")));
+        dirs.extend(env_dir("ProgramFiles").map(|d| d.join("Git").join("cmd")));
+        // winget's shims for portable packages such as fnm
+        dirs.extend(env_dir("LOCALAPPDATA").map(|d| d.join("Microsoft").join("WinGet").join("Links")));
+    } else {
+        dirs.extend(["/opt/homebrew/bin", "/usr/local/bin", "/usr/bin", "/bin"].map(PathBuf::from));
+    }
+
+    let mut seen = Vec::new();
+    dirs.retain(|dir| {
+        if seen.contains(dir) || !dir.is_dir() {
+            return false;
+        }
+        seen.push(dir.clone());
+        true
+    });
+    dirs
+}
+
+static TOOLCHAIN: RwLock<Option<Arc<Toolchain>>> = RwLock::new(None);
+
+fn scan() -> Toolchain {
+    let dirs = search_dirs();
+    let path = env::join_paths(&dirs).unwrap_or_else(|_| env::var_os("PATH").unwrap_or_default());
+    Toolchain { dirs, path }
+}
+
+fn toolchain() -> Arc<Toolchain> {
+    if let Some(toolchain) = TOOLCHAIN.read().unwrap().as_ref() {
+        return toolchain.clone();
+    }
+    TOOLCHAIN.write().unwrap().get_or_insert_with(|| Arc::new(scan())).clone()
+}
+
+// Directories are only scanned once; installing Node or git has to scan again
+pub fn refresh() {
+    *TOOLCHAIN.write().unwrap() = Some(Arc::new(scan()));
+}
+
+fn binary_names(tool: &str) -> Vec<String> {
+    if cfg!(windows) {
+        vec![format!("{}.cmd", tool), format!("{}.exe", tool), format!("{}.bat", tool)]
+    } else {
+        vec![tool.to_string()]
+    }
+}
+
+pub fn locate(tool: &str) -> Option<PathBuf> {
+    let names = binary_names(tool);
+    toolchain()
+        .dirs
+        .iter()
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .find(|candidate| candidate.is_file())
+}
+
+// Falls back to the bare name so a missing install surfaces as the usual spawn error.
+// PATH is replaced with the resolved search path because npm/npx shell out to node.
+pub fn command(tool: &str) -> Command {
+    let program = locate(tool).map(PathBuf::into_os_string).unwrap_or_else(|| tool.into());
+    let mut cmd = Command::new(program);
+    cmd.env("PATH", &toolchain().path);
+    cmd
+}
+
+pub fn npx() -> Command {
+    command("npx")
+}
+
+pub fn npm() -> Command {
+    command("npm")
+}
+
+// The project's own Hardhat when it is installed, so the version pinned in package.json
+// is the one that runs; npx, which may pick up a global install, only as a fallback
+pub fn hardhat(project_path: &Path) -> Command {
+    let bin_dir = project_path.join("node_modules").join(".bin");
+    let local = binary_names("hardhat").into_iter().map(|name| bin_dir.join(name)).find(|bin| bin.is_file());
+    let mut cmd = match local {
+        Some(local) => {
+            let mut cmd = Command::new(local);
+            cmd.env("PATH", &toolchain().path);
+            cmd
+        }
+        None => {
+            let mut cmd = npx();
+            cmd.arg("hardhat");
+            cmd
+        }
+    };
+    cmd.current_dir(project_path);
+    cmd
+}
+
+#[tauri::command]
+pub async fn get_node_toolchain() -> Result<ToolchainInfo, AppError> {
+    let display = |tool: &str| locate(tool).map(|p| p.to_string_lossy().to_string());
+    Ok(ToolchainInfo {
+        node: display("node"),
+        npm: display("npm"),
+        npx: display("npx"),
+        search_path: toolchain().dirs.iter().map(|d| d.to_string_lossy().to_string()).collect(),
+    })
+}
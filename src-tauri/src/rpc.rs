@@ -0,0 +1,292 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::AppError;
+
+pub const LOCAL_RPC_URL: &str = "http://127.0.0.1:8545";
+
+// Set when the GUI is pointed at an external node instead of a managed one
+static EXTERNAL_URL: Mutex<Option<String>> = Mutex::new(None);
+// Methods a node was found not to serve, by URL; calls to them fail without a round trip
+static UNSUPPORTED: OnceLock<Mutex<HashMap<String, BTreeSet<String>>>> = OnceLock::new();
+// Timeout and retry settings by URL, from the network profiles
+static POLICIES: OnceLock<Mutex<HashMap<String, RpcPolicy>>> = OnceLock::new();
+
+fn unsupported() -> &'static Mutex<HashMap<String, BTreeSet<String>>> {
+    UNSUPPORTED.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn policies() -> &'static Mutex<HashMap<String, RpcPolicy>> {
+    POLICIES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// Forked nodes fetch state from an upstream provider on first touch, so calls can be
+// slow or fail transiently (rate limits, dropped connections). Only calls that are safe
+// to repeat are retried
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RpcPolicy {
+    // Per attempt
+    pub timeout_ms: u64,
+    // Attempts after the first one
+    pub retries: u32,
+    // The wait before retry n is random, up to backoff_ms * 2^n capped at max_backoff_ms
+    pub backoff_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RpcPolicy {
+    fn default() -> Self {
+        Self { timeout_ms: 30_000, retries: 2, backoff_ms: 250, max_backoff_ms: 4_000 }
+    }
+}
+
+impl RpcPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.timeout_ms < 100 {
+            return Err("RPC timeout must be at least 100ms".to_string());
+        }
+        if self.retries > 10 {
+            return Err("At most 10 RPC retries are allowed".to_string());
+        }
+        if self.max_backoff_ms < self.backoff_ms {
+            return Err("The maximum RPC backoff can't be shorter than the initial one".to_string());
+        }
+        Ok(())
+    }
+
+    fn backoff(&self, retry: u32) -> Duration {
+        let cap = self.backoff_ms.saturating_mul(1 << retry.min(16)).min(self.max_backoff_ms);
+        Duration::from_millis(rand::thread_rng().gen_range(0..=cap))
+    }
+}
+
+// Replaces all policies; URLs without one use the default
+pub fn set_policies(by_url: HashMap<String, RpcPolicy>) {
+    *policies().lock().unwrap() = by_url;
+}
+
+pub fn policy(url: &str) -> RpcPolicy {
+    policies().lock().unwrap().get(url).cloned().unwrap_or_default()
+}
+
+// The node commands talk to when no RPC URL is given
+pub fn default_url() -> String {
+    EXTERNAL_URL.lock().unwrap().clone().unwrap_or_else(|| LOCAL_RPC_URL.to_string())
+}
+
+pub fn external_url() -> Option<String> {
+    EXTERNAL_URL.lock().unwrap().clone()
+}
+
+pub fn set_external_url(url: Option<String>) {
+    *EXTERNAL_URL.lock().unwrap() = url;
+}
+
+pub fn set_unsupported(url: &str, methods: BTreeSet<String>) {
+    unsupported().lock().unwrap().insert(url.to_string(), methods);
+}
+
+// Shared HTTP client; also used for the few non-RPC lookups
+pub fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("failed to build HTTP client")
+    })
+}
+
+struct SendError {
+    message: String,
+    timed_out: bool,
+    retryable: bool,
+}
+
+async fn send_once(url: &str, method: &str, params: &Value, timeout: Duration) -> Result<Result<Value, Value>, SendError> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+    let failed = |e: reqwest::Error| SendError {
+        message: e.to_string(),
+        timed_out: e.is_timeout(),
+        retryable: e.is_timeout() || e.is_connect() || e.is_request(),
+    };
+
+    let response = client().post(url).json(&request).timeout(timeout).send().await.map_err(failed)?;
+    let status = response.status();
+    // Providers answer rate limits and overload with bare HTTP errors
+    if status.as_u16() == 429 || status.is_server_error() {
+        return Err(SendError {
+            message: format!("HTTP {}", status),
+            timed_out: false,
+            retryable: status.as_u16() == 429 || status.as_u16() >= 502,
+        });
+    }
+    let response: Value = response.json().await.map_err(|e| SendError {
+        message: format!("invalid response: {}", e),
+        timed_out: e.is_timeout(),
+        retryable: e.is_timeout(),
+    })?;
+
+    match response.get("error") {
+        Some(error) => Ok(Err(error.clone())),
+        None => Ok(Ok(response.get("result").cloned().unwrap_or(Value::Null))),
+    }
+}
+
+// Sending a transaction twice or repeating a cheat code isn't harmless
+fn is_repeatable(method: &str) -> bool {
+    !["eth_send", "hardhat_", "anvil_", "evm_", "personal_", "wallet_"]
+        .iter()
+        .any(|prefix| method.starts_with(prefix))
+}
+
+// Forked nodes pass upstream rate limits on as ordinary JSON-RPC errors
+fn is_rate_limited(error: &Value) -> bool {
+    let message = error.get("message").and_then(Value::as_str).unwrap_or_default().to_lowercase();
+    error.get("code").and_then(Value::as_i64) == Some(-32005)
+        || message.contains("rate limit")
+        || message.contains("too many requests")
+}
+
+// The JSON-RPC error object is returned as is, so callers can tell error kinds apart.
+// Transport failures, timeouts and rate limits are retried according to the URL's policy
+async fn send(url: &str, method: &str, params: &Value) -> Result<Result<Value, Value>, AppError> {
+    let policy = policy(url);
+    let timeout = Duration::from_millis(policy.timeout_ms);
+    let retries = if is_repeatable(method) { policy.retries } else { 0 };
+    let started = Instant::now();
+    let mut attempt = 0;
+    loop {
+        let reason = match send_once(url, method, params, timeout).await {
+            Ok(Err(error)) if attempt < retries && is_rate_limited(&error) => "rate limited".to_string(),
+            Ok(response) => return Ok(response),
+            Err(e) if attempt < retries && e.retryable => e.message,
+            Err(e) => {
+                let attempts = attempt + 1;
+                let message = if e.timed_out {
+                    format!("RPC request {} timed out after {}ms ({} attempt{})", method, policy.timeout_ms, attempts, plural(attempts))
+                } else {
+                    format!("RPC request {} failed after {} attempt{}: {}", method, attempts, plural(attempts), e.message)
+                };
+                return Err(AppError::RpcUnavailable {
+                    method: method.to_string(),
+                    attempts,
+                    timed_out: e.timed_out,
+                    elapsed_ms: started.elapsed().as_millis() as u64,
+                    message,
+                });
+            }
+        };
+        let delay = policy.backoff(attempt);
+        attempt += 1;
+        tracing::debug!(method, attempt, reason = %reason, delay_ms = delay.as_millis() as u64, "retrying rpc call");
+        tokio::time::sleep(delay).await;
+    }
+}
+
+fn plural(count: u32) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+fn is_method_not_found(error: &Value) -> bool {
+    let message = error.get("message").and_then(Value::as_str).unwrap_or_default().to_lowercase();
+    error.get("code").and_then(Value::as_i64) == Some(-32601)
+        || message.contains("method not found")
+        || message.contains("does not exist")
+        || message.contains("not supported")
+        || message.contains("not available")
+}
+
+fn rpc_error(url: &str, method: &str, error: &Value) -> AppError {
+    let message = error.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error");
+    tracing::debug!(url, method, error = message, "rpc error");
+    AppError::RpcError {
+        method: method.to_string(),
+        code: error.get("code").and_then(Value::as_i64),
+        message: format!("RPC error from {}: {}", method, message),
+    }
+}
+
+// Anvil serves most hardhat_* methods as anvil_*, and only newer releases alias the
+// hardhat names, so those are retried under the anvil name
+pub async fn call(url: &str, method: &str, params: Value) -> Result<Value, AppError> {
+    if unsupported().lock().unwrap().get(url).is_some_and(|methods| methods.contains(method)) {
+        return Err(AppError::RpcError {
+            method: method.to_string(),
+            code: Some(-32601),
+            message: format!("{} is not supported by the node at {}", method, url),
+        });
+    }
+    match send(url, method, &params).await? {
+        Ok(result) => Ok(result),
+        Err(error) if method.starts_with("hardhat_") && is_method_not_found(&error) => {
+            let anvil_method = method.replacen("hardhat_", "anvil_", 1);
+            send(url, &anvil_method, &params).await?.map_err(|_| rpc_error(url, method, &error))
+        }
+        Err(error) => Err(rpc_error(url, method, &error)),
+    }
+}
+
+// Probes with deliberately invalid params, so nothing on the node changes: a node that
+// knows the method rejects the params, one that doesn't reports it as unknown
+pub async fn supports(url: &str, method: &str) -> Result<bool, AppError> {
+    let probe = json!(["hardhat-gui-probe"]);
+    let known = |response: &Result<Value, Value>| response.as_ref().err().is_none_or(|e| !is_method_not_found(e));
+    if known(&send(url, method, &probe).await?) {
+        return Ok(true);
+    }
+    if method.starts_with("hardhat_") {
+        let anvil_method = method.replacen("hardhat_", "anvil_", 1);
+        return Ok(known(&send(url, &anvil_method, &probe).await?));
+    }
+    Ok(false)
+}
+
+pub fn parse_quantity(value: &Value) -> Option<u64> {
+    u64::from_str_radix(value.as_str()?.trim_start_matches("0x"), 16).ok()
+}
+
+pub async fn chain_id(url: &str) -> Result<u64, AppError> {
+    let result = call(url, "eth_chainId", json!([])).await?;
+    parse_quantity(&result).ok_or_else(|| AppError::RpcError {
+        method: "eth_chainId".to_string(),
+        code: None,
+        message: format!("Invalid chain id: {}", result),
+    })
+}
+
+// 32-byte storage word as a 64-char hex string without the 0x prefix
+pub async fn storage_at(url: &str, address: &str, slot: &str) -> Result<String, AppError> {
+    let result = call(url, "eth_getStorageAt", json!([address, slot, "latest"])).await?;
+    let word = result.as_str().unwrap_or_default().trim_start_matches("0x");
+    Ok(format!("{:0>64}", word))
+}
+
+pub async fn code_at(url: &str, address: &str) -> Result<String, AppError> {
+    let result = call(url, "eth_getCode", json!([address, "latest"])).await?;
+    Ok(result.as_str().unwrap_or("0x").to_string())
+}
+
+pub async fn eth_call(url: &str, to: &str, data: &str) -> Result<String, AppError> {
+    let result = call(url, "eth_call", json!([{ "to": to, "data": data }, "latest"])).await?;
+    Ok(result.as_str().unwrap_or("0x").to_string())
+}
+
+// The address held in the low 20 bytes of a storage word or ABI-encoded return value
+pub fn word_to_address(word: &str) -> Option<String> {
+    let word = word.trim_start_matches("0x");
+    if word.len() < 40 || word.chars().all(|c| c == '0') {
+        return None;
+    }
+    Some(format!("0x{}", &word[word.len() - 40..]))
+}
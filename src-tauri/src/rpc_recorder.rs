@@ -0,0 +1,408 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+
+use crate::cancellation;
+use crate::error::AppError;
+use crate::rpc;
+use crate::store;
+
+const SESSIONS_DIR: &str = "rpc-sessions";
+const DEFAULT_PORT: u16 = 8546;
+// Largest request body the proxy accepts; batches of calldata stay far below this
+const MAX_BODY_BYTES: usize = 32 * 1024 * 1024;
+// Mismatches listed in a replay report; the rest are only counted
+const MAX_MISMATCHES: usize = 100;
+
+struct Recorder {
+    project_path: PathBuf,
+    session: String,
+    upstream: String,
+    port: u16,
+    started_at: u64,
+    calls: u64,
+    // Stops the listener and every open connection; a child of the project's token
+    stop: CancellationToken,
+}
+
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecordedCall {
+    pub seq: u64,
+    pub timestamp: u64,
+    pub duration_ms: u64,
+    // A single JSON-RPC request or a batch, exactly as the client sent it
+    pub request: Value,
+    pub response: Option<Value>,
+    // Set when the node couldn't be reached or didn't answer with JSON
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RecorderStatus {
+    pub running: bool,
+    pub proxy_url: Option<String>,
+    pub upstream: Option<String>,
+    pub session: Option<String>,
+    pub started_at: Option<u64>,
+    pub calls: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RpcSessionSummary {
+    pub name: String,
+    pub path: String,
+    pub calls: usize,
+    pub started_at: Option<u64>,
+    pub size_bytes: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReplayMismatch {
+    pub seq: u64,
+    pub methods: Vec<String>,
+    pub recorded: Option<Value>,
+    pub replayed: Option<Value>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReplayReport {
+    pub session: String,
+    pub target: String,
+    pub calls: usize,
+    pub matched: usize,
+    pub mismatched: usize,
+    // The first MAX_MISMATCHES differences, in session order
+    pub mismatches: Vec<ReplayMismatch>,
+}
+
+struct HttpRequest {
+    method: String,
+    body: Vec<u8>,
+    keep_alive: bool,
+}
+
+fn sessions_dir(project_path: &Path) -> PathBuf {
+    store::project_store_dir(project_path).join(SESSIONS_DIR)
+}
+
+fn session_file(name: &str) -> String {
+    format!("{}/{}.jsonl", SESSIONS_DIR, name)
+}
+
+fn sanitize(name: &str) -> String {
+    let name: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' }).collect();
+    name.trim_matches('-').to_string()
+}
+
+fn methods(request: &Value) -> Vec<String> {
+    let method = |call: &Value| call.get("method").and_then(Value::as_str).unwrap_or("?").to_string();
+    match request {
+        Value::Array(calls) => calls.iter().map(method).collect(),
+        call => vec![method(call)],
+    }
+}
+
+fn status() -> RecorderStatus {
+    match RECORDER.lock().unwrap().as_ref() {
+        Some(recorder) => RecorderStatus {
+            running: true,
+            proxy_url: Some(format!("http://127.0.0.1:{}", recorder.port)),
+            upstream: Some(recorder.upstream.clone()),
+            session: Some(recorder.session.clone()),
+            started_at: Some(recorder.started_at),
+            calls: recorder.calls,
+        },
+        None => RecorderStatus { running: false, proxy_url: None, upstream: None, session: None, started_at: None, calls: 0 },
+    }
+}
+
+// Headers and body of one HTTP/1.1 request; None once the client closed the connection
+async fn read_request(reader: &mut BufReader<TcpStream>) -> Result<Option<HttpRequest>, String> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await.map_err(|e| format!("Failed to read request: {}", e))? == 0 {
+        return Ok(None);
+    }
+    let method = line.split_whitespace().next().unwrap_or_default().to_uppercase();
+    let mut content_length = 0;
+    let mut keep_alive = !line.trim_end().ends_with("HTTP/1.0");
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await.map_err(|e| format!("Failed to read request: {}", e))? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        match name.trim().to_lowercase().as_str() {
+            "content-length" => content_length = value.trim().parse().map_err(|_| "Invalid Content-Length".to_string())?,
+            "connection" => keep_alive = !value.trim().eq_ignore_ascii_case("close"),
+            _ => {}
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(format!("Request body of {} bytes is too large", content_length));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await.map_err(|e| format!("Failed to read request body: {}", e))?;
+    Ok(Some(HttpRequest { method, body, keep_alive }))
+}
+
+// Browser dapps send a CORS preflight first, so every response allows any origin
+fn http_response(status: &str, body: &[u8]) -> Vec<u8> {
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: POST, OPTIONS\r\nAccess-Control-Allow-Headers: *\r\n\r\n",
+        status,
+        body.len()
+    )
+    .into_bytes();
+    response.extend_from_slice(body);
+    response
+}
+
+async fn forward(upstream: &str, body: &[u8]) -> Result<Value, String> {
+    let response = rpc::client()
+        .post(upstream)
+        .header("Content-Type", "application/json")
+        .body(body.to_vec())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach {}: {}", upstream, e))?;
+    response.json().await.map_err(|e| format!("Invalid response from {}: {}", upstream, e))
+}
+
+// Forwards the call and appends it to the session; recording failures never break the proxy
+async fn record(app: &AppHandle, upstream: &str, body: &[u8]) -> Vec<u8> {
+    let started = Instant::now();
+    let timestamp = store::timestamp_millis();
+    let request = serde_json::from_slice::<Value>(body).unwrap_or_else(|_| Value::String(String::from_utf8_lossy(body).to_string()));
+    let result = forward(upstream, body).await;
+
+    let (response_body, status) = match &result {
+        Ok(response) => (serde_json::to_vec(response).unwrap_or_default(), "200 OK"),
+        Err(e) => {
+            let error = json!({ "jsonrpc": "2.0", "id": request.get("id").cloned().unwrap_or(Value::Null), "error": { "code": -32603, "message": e } });
+            (serde_json::to_vec(&error).unwrap_or_default(), "502 Bad Gateway")
+        }
+    };
+
+    let target = {
+        let mut recorder = RECORDER.lock().unwrap();
+        recorder.as_mut().map(|recorder| {
+            recorder.calls += 1;
+            (recorder.project_path.clone(), recorder.session.clone(), recorder.calls)
+        })
+    };
+    if let Some((project_path, session, seq)) = target {
+        let call = RecordedCall {
+            seq,
+            timestamp,
+            duration_ms: started.elapsed().as_millis() as u64,
+            request,
+            response: result.as_ref().ok().cloned(),
+            error: result.err(),
+        };
+        if let Err(e) = store::append_jsonl(&project_path, &session_file(&session), &call) {
+            tracing::warn!(error = %e, "failed to record rpc call");
+        }
+        let _ = app.emit("rpc-recorded", &call);
+    }
+    http_response(status, &response_body)
+}
+
+async fn handle_connection(app: AppHandle, upstream: String, stream: TcpStream) {
+    let mut reader = BufReader::new(stream);
+    loop {
+        let request = match read_request(&mut reader).await {
+            Ok(Some(request)) => request,
+            Ok(None) => return,
+            Err(e) => {
+                let body = json!({ "jsonrpc": "2.0", "id": null, "error": { "code": -32600, "message": e } }).to_string();
+                let _ = reader.get_mut().write_all(&http_response("400 Bad Request", body.as_bytes())).await;
+                return;
+            }
+        };
+        let response = match request.method.as_str() {
+            "OPTIONS" => http_response("204 No Content", b""),
+            "POST" => record(&app, &upstream, &request.body).await,
+            _ => http_response("405 Method Not Allowed", b""),
+        };
+        if reader.get_mut().write_all(&response).await.is_err() || !request.keep_alive {
+            return;
+        }
+    }
+}
+
+// Starts a JSON-RPC proxy on 127.0.0.1:`port` in front of the node. Point a dapp or
+// wallet at the proxy URL and every request/response pair is appended to the session
+#[tauri::command]
+pub async fn start_rpc_recorder(
+    app: AppHandle,
+    project_path: String,
+    port: Option<u16>,
+    upstream_url: Option<String>,
+    session: Option<String>,
+) -> Result<RecorderStatus, AppError> {
+    if RECORDER.lock().unwrap().is_some() {
+        return Err("The RPC recorder is already running; stop it first".to_string().into());
+    }
+    let root = PathBuf::from(&project_path);
+    let upstream = upstream_url.unwrap_or_else(rpc::default_url);
+    let session = sanitize(&session.unwrap_or_else(|| format!("session-{}", store::timestamp_millis())));
+    if session.is_empty() {
+        return Err("Invalid session name".to_string().into());
+    }
+    fs::create_dir_all(sessions_dir(&root)).map_err(|e| format!("Failed to create {} directory: {}", SESSIONS_DIR, e))?;
+
+    let listener = TcpListener::bind(("127.0.0.1", port.unwrap_or(DEFAULT_PORT)))
+        .await
+        .map_err(|e| format!("Failed to listen on port {}: {}", port.unwrap_or(DEFAULT_PORT), e))?;
+    let port = listener.local_addr().map_err(|e| format!("Failed to read proxy address: {}", e))?.port();
+    let stop = cancellation::project(&root).child_token();
+    let stopped = stop.clone();
+
+    {
+        let mut recorder = RECORDER.lock().unwrap();
+        if recorder.is_some() {
+            return Err("The RPC recorder is already running; stop it first".to_string().into());
+        }
+        *recorder = Some(Recorder {
+            project_path: root,
+            session,
+            upstream: upstream.clone(),
+            port,
+            started_at: store::timestamp_millis(),
+            calls: 0,
+            stop,
+        });
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let accept = async {
+            while let Ok((stream, _)) = listener.accept().await {
+                let connection = handle_connection(app.clone(), upstream.clone(), stream);
+                let stopped = stopped.clone();
+                tauri::async_runtime::spawn(async move { stopped.run_until_cancelled(connection).await });
+            }
+        };
+        stopped.run_until_cancelled(accept).await;
+        // Closing the project stops the recorder without going through stop_rpc_recorder
+        let mut recorder = RECORDER.lock().unwrap();
+        if recorder.as_ref().is_some_and(|recorder| recorder.stop.is_cancelled()) {
+            *recorder = None;
+        }
+    });
+    Ok(status())
+}
+
+#[tauri::command]
+pub async fn stop_rpc_recorder() -> Result<RecorderStatus, AppError> {
+    let recorder = RECORDER.lock().unwrap().take().ok_or("The RPC recorder is not running")?;
+    recorder.stop.cancel();
+    Ok(RecorderStatus {
+        running: false,
+        proxy_url: None,
+        upstream: Some(recorder.upstream),
+        session: Some(recorder.session),
+        started_at: Some(recorder.started_at),
+        calls: recorder.calls,
+    })
+}
+
+#[tauri::command]
+pub async fn get_rpc_recorder_status() -> Result<RecorderStatus, AppError> {
+    Ok(status())
+}
+
+#[tauri::command]
+pub async fn list_rpc_sessions(project_path: String) -> Result<Vec<RpcSessionSummary>, AppError> {
+    let root = Path::new(&project_path);
+    let dir = sessions_dir(root);
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut sessions = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "jsonl") {
+            continue;
+        }
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let calls: Vec<RecordedCall> = store::read_jsonl(root, &session_file(&name))?;
+        sessions.push(RpcSessionSummary {
+            started_at: calls.first().map(|call| call.timestamp),
+            calls: calls.len(),
+            size_bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+            path: path.to_string_lossy().to_string(),
+            name,
+        });
+    }
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.started_at));
+    Ok(sessions)
+}
+
+#[tauri::command]
+pub async fn get_rpc_session(project_path: String, session: String) -> Result<Vec<RecordedCall>, AppError> {
+    let root = Path::new(&project_path);
+    if !store::project_store_dir(root).join(session_file(&session)).is_file() {
+        return Err(format!("No recorded session named {}", session).into());
+    }
+    Ok(store::read_jsonl(root, &session_file(&session))?)
+}
+
+// Re-sends every recorded request, in order, and compares the responses. Hashes, block
+// numbers and timestamps only line up when the node starts from the state the session
+// was recorded against (e.g. a restored node state snapshot)
+#[tauri::command]
+pub async fn replay_rpc_session(
+    project_path: String,
+    session: String,
+    rpc_url: Option<String>,
+    preserve_timing: Option<bool>,
+) -> Result<ReplayReport, AppError> {
+    let calls = get_rpc_session(project_path, session.clone()).await?;
+    let target = rpc_url.unwrap_or_else(rpc::default_url);
+    let preserve_timing = preserve_timing.unwrap_or(false);
+
+    let mut report = ReplayReport { session, target: target.clone(), calls: calls.len(), matched: 0, mismatched: 0, mismatches: Vec::new() };
+    let mut previous: Option<u64> = None;
+    for call in calls {
+        if preserve_timing {
+            if let Some(previous) = previous {
+                tokio::time::sleep(Duration::from_millis(call.timestamp.saturating_sub(previous))).await;
+            }
+            previous = Some(call.timestamp);
+        }
+        let body = serde_json::to_vec(&call.request).map_err(|e| format!("Failed to serialize request {}: {}", call.seq, e))?;
+        let replayed = forward(&target, &body).await;
+        if replayed.as_ref().ok() == call.response.as_ref() {
+            report.matched += 1;
+            continue;
+        }
+        report.mismatched += 1;
+        if report.mismatches.len() < MAX_MISMATCHES {
+            report.mismatches.push(ReplayMismatch {
+                seq: call.seq,
+                methods: methods(&call.request),
+                recorded: call.response,
+                replayed: replayed.as_ref().ok().cloned(),
+                error: replayed.err(),
+            });
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,282 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::error::AppError;
+use crate::messages::Message;
+use crate::processes;
+use crate::project::{self, PackageManager};
+use crate::resolver;
+use crate::status;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectTemplate {
+    #[default]
+    Javascript,
+    Typescript,
+    TypescriptViem,
+    // Only a hardhat.config.js, like `hardhat init`'s last option
+    Empty,
+}
+
+impl ProjectTemplate {
+    // Hardhat 2's own switches for a non-interactive `hardhat init`
+    fn init_env(self, version: (u64, u64)) -> Option<&'static str> {
+        match self {
+            ProjectTemplate::Javascript => Some("HARDHAT_CREATE_JAVASCRIPT_PROJECT_WITH_DEFAULTS"),
+            ProjectTemplate::Typescript => Some("HARDHAT_CREATE_TYPESCRIPT_PROJECT_WITH_DEFAULTS"),
+            // The viem template arrived in 2.19
+            ProjectTemplate::TypescriptViem if version >= (2, 19) => {
+                Some("HARDHAT_CREATE_TYPESCRIPT_VIEM_PROJECT_WITH_DEFAULTS")
+            }
+            _ => None,
+        }
+    }
+
+    // The package and the major that still targets Hardhat 2
+    fn toolbox(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            ProjectTemplate::Javascript | ProjectTemplate::Typescript => {
+                Some(("@nomicfoundation/hardhat-toolbox", "^5.0.0"))
+            }
+            ProjectTemplate::TypescriptViem => Some(("@nomicfoundation/hardhat-toolbox-viem", "^3.0.0")),
+            ProjectTemplate::Empty => None,
+        }
+    }
+
+    fn typescript(self) -> bool {
+        matches!(self, ProjectTemplate::Typescript | ProjectTemplate::TypescriptViem)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CreationPhase {
+    Scaffold,
+    Install,
+    Verify,
+}
+
+// Sent on `project-creation-progress` as each step starts; a step that runs a command
+// streams its output as execution events under `execution_id`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CreationProgress {
+    pub project_path: String,
+    pub phase: CreationPhase,
+    pub message: String,
+    pub execution_id: Option<String>,
+}
+
+const SOLIDITY_VERSION: &str = "0.8.24";
+// The built-in configs are written for Hardhat 2; a bare `hardhat` would now install 3
+const HARDHAT_VERSION: &str = "^2.22.0";
+
+const LOCK_CONTRACT: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.24;
+
+contract Lock {
+    uint public unlockTime;
+    address payable public owner;
+
+    event Withdrawal(uint amount, uint when);
+
+    constructor(uint _unlockTime) payable {
+        require(
+            block.timestamp < _unlockTime,
+            "Unlock time should be in the future"
+        );
+
+        unlockTime = _unlockTime;
+        owner = payable(msg.sender);
+    }
+
+    function withdraw() public {
+        require(block.timestamp >= unlockTime, "You can't withdraw yet");
+        require(msg.sender == owner, "You aren't the owner");
+
+        emit Withdrawal(address(this).balance, block.timestamp);
+
+        owner.transfer(address(this).balance);
+    }
+}
+"#;
+
+const TSCONFIG: &str = r#"{
+  "compilerOptions": {
+    "target": "es2020",
+    "module": "commonjs",
+    "esModuleInterop": true,
+    "forceConsistentCasingInFileNames": true,
+    "strict": true,
+    "skipLibCheck": true,
+    "resolveJsonModule": true
+  }
+}
+"#;
+
+fn hardhat_config(template: ProjectTemplate) -> String {
+    let networks = "  networks: {\n    localhost: {\n      url: \"http://127.0.0.1:8545\"\n    }\n  }";
+    match template.toolbox().map(|(toolbox, _)| toolbox) {
+        Some(toolbox) if template.typescript() => format!(
+            "import type {{ HardhatUserConfig }} from \"hardhat/config\";\nimport \"{}\";\n\nconst config: HardhatUserConfig = {{\n  solidity: \"{}\",\n{}\n}};\n\nexport default config;\n",
+            toolbox, SOLIDITY_VERSION, networks
+        ),
+        Some(toolbox) => format!(
+            "require(\"{}\");\n\n/** @type import('hardhat/config').HardhatUserConfig */\nmodule.exports = {{\n  solidity: \"{}\",\n{}\n}};\n",
+            toolbox, SOLIDITY_VERSION, networks
+        ),
+        None => format!(
+            "/** @type import('hardhat/config').HardhatUserConfig */\nmodule.exports = {{\n  solidity: \"{}\",\n}};\n",
+            SOLIDITY_VERSION
+        ),
+    }
+}
+
+// `hardhat --version` prints a bare version, e.g. "2.22.10"
+async fn hardhat_version(root: &Path) -> Option<(u64, u64)> {
+    let mut cmd = resolver::hardhat(root);
+    cmd.arg("--version");
+    let output = processes::output(cmd).await.ok().filter(|output| output.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut parts = stdout.split_whitespace().last()?.split('.');
+    Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+}
+
+struct Creation<'a> {
+    app: &'a AppHandle,
+    root: &'a Path,
+    template: ProjectTemplate,
+}
+
+impl Creation<'_> {
+    fn report(&self, phase: CreationPhase, message: impl Into<String>, execution_id: Option<String>) {
+        let progress = CreationProgress {
+            project_path: self.root.to_string_lossy().to_string(),
+            phase,
+            message: message.into(),
+            execution_id,
+        };
+        let _ = self.app.emit("project-creation-progress", &progress);
+    }
+
+    async fn run(&self, phase: CreationPhase, message: &str, cmd: Command) -> Result<Output, AppError> {
+        let execution_id = self.app.state::<processes::ProcessManager>().new_execution_id();
+        self.report(phase, message, Some(execution_id.clone()));
+        processes::run_tracked(self.app, Some(execution_id), "install", cmd).await
+    }
+
+    fn write(&self, name: &str, content: &str) -> Result<(), String> {
+        let path = self.root.join(name);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        fs::write(&path, content).map_err(|e| format!("Failed to create {}: {}", name, e))?;
+        self.report(CreationPhase::Scaffold, format!("Created {}", name), None);
+        Ok(())
+    }
+
+    // Hardhat 2.17 moved project creation from a bare `hardhat` to `hardhat init`. Hardhat 3's
+    // `--init` is interactive only, so it isn't driven from here
+    async fn hardhat_init(&self) -> Result<bool, AppError> {
+        self.report(CreationPhase::Scaffold, "Checking the Hardhat version", None);
+        let Some(version) = hardhat_version(self.root).await else {
+            return Ok(false);
+        };
+        let Some(env) = self.template.init_env(version).filter(|_| version.0 == 2) else {
+            return Ok(false);
+        };
+        let mut cmd = resolver::hardhat(self.root);
+        if version >= (2, 17) {
+            cmd.arg("init");
+        }
+        cmd.env(env, "true");
+        // init installs the template's dependencies itself
+        let message = format!("Running hardhat {}.{} init and installing dependencies", version.0, version.1);
+        let output = self.run(CreationPhase::Scaffold, &message, cmd).await?;
+        Ok(output.status.success() && project::find_hardhat_config(self.root).is_some())
+    }
+
+    async fn scaffold(&self) -> Result<(), AppError> {
+        let (root, template) = (self.root, self.template);
+        if !root.join("package.json").exists() {
+            let package_json = json!({
+                "name": root.file_name().map(|n| n.to_string_lossy().to_lowercase().replace(' ', "-")).unwrap_or_else(|| "hardhat-project".to_string()),
+                "version": "1.0.0",
+                "description": "A Hardhat project created by Hardhat GUI",
+                "scripts": { "test": "hardhat test" },
+            });
+            let content = serde_json::to_string_pretty(&package_json).map_err(|e| format!("Failed to create package.json: {}", e))?;
+            self.write("package.json", &(content + "\n"))?;
+        }
+
+        let config_name = if template.typescript() { "hardhat.config.ts" } else { "hardhat.config.js" };
+        self.write(config_name, &hardhat_config(template))?;
+        if template.typescript() {
+            self.write("tsconfig.json", TSCONFIG)?;
+        }
+        if template != ProjectTemplate::Empty {
+            self.write("contracts/Lock.sol", LOCK_CONTRACT)?;
+            for dir in ["test", "scripts", "ignition/modules"] {
+                fs::create_dir_all(root.join(dir)).map_err(|e| format!("Failed to create {} directory: {}", dir, e))?;
+            }
+        }
+
+        let mut packages = vec![format!("hardhat@{}", HARDHAT_VERSION)];
+        packages.extend(template.toolbox().map(|(toolbox, version)| format!("{}@{}", toolbox, version)));
+        if template.typescript() {
+            packages.extend(["typescript", "ts-node", "@types/node"].map(String::from));
+        }
+        let packages: Vec<&str> = packages.iter().map(String::as_str).collect();
+        let package_manager = PackageManager::detect(root);
+        let message = format!("Installing {} with {}", packages.join(", "), package_manager.binary());
+        let output = self.run(CreationPhase::Install, &message, package_manager.add_dev(root, &packages)).await?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Failed to install dependencies: {}", error).into());
+        }
+        Ok(())
+    }
+
+    // The project only counts as created once its own Hardhat runs
+    async fn verify(&self) -> Result<(), AppError> {
+        if !project::has_local_bin(self.root, "hardhat") {
+            return Err("Hardhat is missing from node_modules after the install".to_string().into());
+        }
+        let mut cmd = resolver::hardhat(self.root);
+        cmd.arg("--version");
+        let output = self.run(CreationPhase::Verify, "Checking that the project's Hardhat runs", cmd).await?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("The new project's Hardhat failed to run: {}", error.trim()).into());
+        }
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub async fn create_hardhat_project(
+    app: AppHandle,
+    project_path: String,
+    template: Option<ProjectTemplate>,
+) -> Result<Message, AppError> {
+    let root = Path::new(&project_path);
+    fs::create_dir_all(root).map_err(|e| format!("Failed to create directory: {}", e))?;
+    if project::find_hardhat_config(root).is_some() {
+        return Err(Message::new("project.already_exists", json!({ "path": project_path })).into());
+    }
+
+    let creation = Creation { app: &app, root, template: template.unwrap_or_default() };
+    let built_in = !creation.hardhat_init().await?;
+    if built_in {
+        creation.scaffold().await?;
+    }
+    creation.verify().await?;
+    status::refresh();
+    let key = if built_in { "project.created_builtin" } else { "project.created" };
+    Ok(Message::new(key, json!({ "path": project_path })))
+}
@@ -0,0 +1,275 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::artifacts::{self, SourceUnit};
+use crate::ast;
+use crate::error::AppError;
+use crate::findings::{self, AnalysisReport, Finding, SourceLocation};
+use crate::health::Severity;
+
+const LOW_LEVEL_CALLS: [&str; 4] = ["call", "delegatecall", "staticcall", "send"];
+
+// Everything the rules need to know about the function being scanned
+struct FunctionScope<'a> {
+    unit: &'a SourceUnit,
+    contract: &'a str,
+    function: &'a Value,
+    state_vars: &'a HashSet<i64>,
+}
+
+impl FunctionScope<'_> {
+    fn name(&self) -> String {
+        match ast::str_field(self.function, "kind") {
+            "constructor" => "constructor".to_string(),
+            "fallback" | "receive" => ast::str_field(self.function, "kind").to_string(),
+            _ => ast::str_field(self.function, "name").to_string(),
+        }
+    }
+
+    fn is_entry_point(&self) -> bool {
+        matches!(ast::str_field(self.function, "visibility"), "public" | "external")
+            && !matches!(ast::str_field(self.function, "stateMutability"), "view" | "pure")
+    }
+
+    fn parameter_ids(&self) -> HashSet<i64> {
+        self.function
+            .pointer("/parameters/parameters")
+            .and_then(Value::as_array)
+            .map(|params| params.iter().filter_map(ast::id).collect())
+            .unwrap_or_default()
+    }
+
+    fn has_modifier(&self, needle: &str) -> bool {
+        self.function
+            .get("modifiers")
+            .and_then(Value::as_array)
+            .is_some_and(|modifiers| {
+                modifiers.iter().any(|m| {
+                    let name = m
+                        .pointer("/modifierName/name")
+                        .or_else(|| m.pointer("/modifierName/namePath"))
+                        .and_then(Value::as_str)
+                        .unwrap_or_default();
+                    name.to_lowercase().contains(needle)
+                })
+            })
+    }
+
+    fn finding(&self, rule: &str, swc_id: &str, severity: Severity, title: &str, description: String, node: &Value) -> Finding {
+        Finding {
+            tool: "ast-scanner".to_string(),
+            rule: rule.to_string(),
+            swc_id: Some(swc_id.to_string()),
+            title: title.to_string(),
+            severity,
+            description,
+            contract: Some(self.contract.to_string()),
+            function: Some(self.name()),
+            locations: vec![SourceLocation {
+                file: self.unit.source_name.clone(),
+                line: artifacts::src_line(ast::str_field(node, "src"), &self.unit.content),
+            }],
+        }
+    }
+}
+
+// `addr.call(...)`, `addr.call{value: v}(...)` etc.; returns the member name and the
+// expression the call is made on
+fn low_level_call(call: &Value) -> Option<(&str, &Value)> {
+    let mut callee = call.get("expression")?;
+    if ast::node_type(callee) == "FunctionCallOptions" {
+        callee = callee.get("expression")?;
+    }
+    if ast::node_type(callee) != "MemberAccess" {
+        return None;
+    }
+    let member = ast::str_field(callee, "memberName");
+    let target = callee.get("expression")?;
+    (LOW_LEVEL_CALLS.contains(&member) && ast::type_string(target).starts_with("address"))
+        .then_some((member, target))
+}
+
+// Calls into another contract that can run arbitrary code (and so re-enter)
+fn is_external_call(call: &Value) -> bool {
+    if low_level_call(call).is_some_and(|(member, _)| member != "send" && member != "staticcall") {
+        return true;
+    }
+    let Some(callee) = call.get("expression") else {
+        return false;
+    };
+    let callee = if ast::node_type(callee) == "FunctionCallOptions" {
+        callee.get("expression").unwrap_or(callee)
+    } else {
+        callee
+    };
+    let signature = ast::type_string(callee);
+    ast::node_type(callee) == "MemberAccess"
+        && callee.get("expression").is_some_and(|base| ast::type_string(base).starts_with("contract "))
+        && signature.contains(" external")
+        && !signature.contains(" view")
+        && !signature.contains(" pure")
+}
+
+// The storage variable an assignment ultimately writes to, through mappings/arrays/structs
+fn written_state_var(target: &Value, state_vars: &HashSet<i64>) -> bool {
+    let mut node = target;
+    loop {
+        match ast::node_type(node) {
+            "IndexAccess" => node = &node["baseExpression"],
+            "MemberAccess" => node = &node["expression"],
+            "Identifier" => {
+                return node
+                    .get("referencedDeclaration")
+                    .and_then(Value::as_i64)
+                    .is_some_and(|id| state_vars.contains(&id))
+            }
+            _ => return false,
+        }
+    }
+}
+
+fn is_tx_origin(node: &Value) -> bool {
+    ast::node_type(node) == "MemberAccess"
+        && ast::str_field(node, "memberName") == "origin"
+        && node.get("expression").is_some_and(|e| ast::str_field(e, "name") == "tx")
+}
+
+fn scan_function(scope: &FunctionScope, findings: &mut Vec<Finding>) {
+    let Some(body) = scope.function.get("body").filter(|b| !b.is_null()) else {
+        return;
+    };
+    let params = scope.parameter_ids();
+    let mut external_calls = Vec::new();
+    let mut state_writes = Vec::new();
+
+    ast::walk(body, &mut |node| match ast::node_type(node) {
+        "BinaryOperation" => {
+            let operator = ast::str_field(node, "operator");
+            if (operator == "==" || operator == "!=")
+                && (is_tx_origin(&node["leftExpression"]) || is_tx_origin(&node["rightExpression"]))
+            {
+                findings.push(scope.finding(
+                    "tx-origin-auth",
+                    "115",
+                    Severity::Error,
+                    "Authorization through tx.origin",
+                    "tx.origin is compared for authorization; a malicious contract called by the owner can pass this check. Use msg.sender instead.".to_string(),
+                    node,
+                ));
+            }
+        }
+        "FunctionCall" => {
+            if let Some(name) = node.get("expression").map(|e| ast::str_field(e, "name")) {
+                if name == "selfdestruct" || name == "suicide" {
+                    findings.push(scope.finding(
+                        "selfdestruct",
+                        "106",
+                        Severity::Warning,
+                        "Use of selfdestruct",
+                        "selfdestruct is deprecated and, where it still works, lets the contract be destroyed; make sure it is unreachable or strictly access controlled.".to_string(),
+                        node,
+                    ));
+                }
+            }
+
+            if let Some(("delegatecall", target)) = low_level_call(node) {
+                let user_supplied = ast::node_type(target) == "Identifier"
+                    && target
+                        .get("referencedDeclaration")
+                        .and_then(Value::as_i64)
+                        .is_some_and(|id| params.contains(&id));
+                if user_supplied && scope.is_entry_point() {
+                    findings.push(scope.finding(
+                        "delegatecall-user-address",
+                        "112",
+                        Severity::Error,
+                        "delegatecall to a caller-supplied address",
+                        "The delegatecall target comes straight from a function argument, so any caller can run arbitrary code in this contract's storage context.".to_string(),
+                        node,
+                    ));
+                }
+            }
+
+            if is_external_call(node) {
+                external_calls.push(ast::src_start(node));
+            }
+        }
+        "ExpressionStatement" => {
+            // A bare `addr.call(...)` statement throws the success flag away
+            if let Some((member, _)) = node.get("expression").and_then(low_level_call) {
+                findings.push(scope.finding(
+                    "unchecked-low-level-call",
+                    "104",
+                    Severity::Warning,
+                    "Unchecked low-level call result",
+                    format!("The return value of .{}() is ignored, so a failed call goes unnoticed.", member),
+                    node,
+                ));
+            }
+        }
+        "Assignment" if written_state_var(&node["leftHandSide"], scope.state_vars) => {
+            state_writes.push(ast::src_start(node));
+        }
+        "UnaryOperation" => {
+            let operator = ast::str_field(node, "operator");
+            if matches!(operator, "++" | "--" | "delete") && written_state_var(&node["subExpression"], scope.state_vars) {
+                state_writes.push(ast::src_start(node));
+            }
+        }
+        _ => {}
+    });
+
+    // Checks-effects-interactions: storage written after an external call without a guard
+    if scope.is_entry_point() && !scope.has_modifier("nonreentrant") {
+        if let Some(first_call) = external_calls.iter().min() {
+            if state_writes.iter().any(|write| write > first_call) {
+                findings.push(scope.finding(
+                    "missing-reentrancy-guard",
+                    "107",
+                    Severity::Warning,
+                    "State written after external call without reentrancy guard",
+                    "This function updates storage after making an external call and has no nonReentrant modifier; the callee can re-enter before the update lands.".to_string(),
+                    scope.function,
+                ));
+            }
+        }
+    }
+}
+
+pub fn scan(project_path: &Path) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    // Dependencies are someone else's audit; only the project's own sources are scanned
+    for unit in artifacts::load_source_units(project_path)
+        .iter()
+        .filter(|unit| !artifacts::is_dependency_source(&unit.source_name))
+    {
+        for contract in ast::find_all(&unit.ast, "ContractDefinition") {
+            let name = ast::str_field(contract, "name");
+            let state_vars: HashSet<i64> = ast::find_all(contract, "VariableDeclaration")
+                .into_iter()
+                .filter(|v| v.get("stateVariable").and_then(Value::as_bool) == Some(true))
+                .filter_map(ast::id)
+                .collect();
+
+            let functions = contract.get("nodes").and_then(Value::as_array).into_iter().flatten();
+            for function in functions.filter(|n| ast::node_type(n) == "FunctionDefinition") {
+                let scope = FunctionScope { unit, contract: name, function, state_vars: &state_vars };
+                scan_function(&scope, &mut findings);
+            }
+        }
+    }
+
+    findings
+}
+
+#[tauri::command]
+pub async fn run_ast_scanner(project_path: String) -> Result<AnalysisReport, AppError> {
+    let root = Path::new(&project_path);
+    if !root.join("artifacts").join("build-info").is_dir() {
+        return Err("No build info found. Compile the project first.".to_string().into());
+    }
+    Ok(findings::record(root, AnalysisReport::new("ast-scanner", scan(root)))?)
+}
@@ -0,0 +1,266 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio_util::sync::CancellationToken;
+
+use crate::cancellation;
+use crate::error::AppError;
+use crate::presets;
+use crate::processes::{self, ProcessManager};
+use crate::rpc;
+use crate::store;
+
+const HISTORY_LIMIT: usize = 50;
+const OUTPUT_LIMIT: usize = 4096;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduleAction {
+    Preset {
+        name: String,
+    },
+    // Lightweight node actions like `evm_mine` don't need a hardhat process at all
+    Rpc {
+        method: String,
+        #[serde(default)]
+        params: serde_json::Value,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduleTrigger {
+    Interval { every_ms: u64 },
+    At { timestamp_ms: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ScheduleRun {
+    pub schedule_id: String,
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub success: bool,
+    pub output: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ScheduleInfo {
+    pub id: String,
+    pub project_path: String,
+    pub action: ScheduleAction,
+    pub trigger: ScheduleTrigger,
+    pub max_runs: Option<u32>,
+    pub active: bool,
+    pub run_count: u32,
+    pub history: VecDeque<ScheduleRun>,
+}
+
+struct ScheduleEntry {
+    info: ScheduleInfo,
+    // Child of the project's token, so closing the project stops its schedules too
+    cancel: CancellationToken,
+}
+
+#[derive(Default)]
+pub struct Scheduler {
+    next_id: AtomicU64,
+    schedules: Mutex<HashMap<String, ScheduleEntry>>,
+}
+
+impl Scheduler {
+    fn record(&self, app: &AppHandle, run: ScheduleRun) -> bool {
+        let mut schedules = self.schedules.lock().unwrap();
+        let Some(entry) = schedules.get_mut(&run.schedule_id) else {
+            return false;
+        };
+
+        let _ = app.emit("schedule-run", run.clone());
+        entry.info.run_count += 1;
+        entry.info.history.push_front(run);
+        entry.info.history.truncate(HISTORY_LIMIT);
+
+        let exhausted = entry.info.max_runs.is_some_and(|max| entry.info.run_count >= max);
+        if exhausted {
+            entry.info.active = false;
+        }
+        !exhausted
+    }
+
+    fn finish(&self, id: &str) {
+        if let Some(entry) = self.schedules.lock().unwrap().get_mut(id) {
+            entry.info.active = false;
+        }
+    }
+}
+
+// Stopping a schedule cancels a running preset through its own execution id, so the
+// process group is killed and the run is reported and recorded like any cancelled command
+async fn execute(
+    app: &AppHandle,
+    project_path: &str,
+    action: &ScheduleAction,
+    cancel: &CancellationToken,
+) -> (bool, String) {
+    match action {
+        ScheduleAction::Preset { name } => {
+            let preset = match presets::find_preset(project_path, name) {
+                Ok(preset) => preset,
+                Err(e) => return (false, e.text),
+            };
+            let execution_id = app.state::<ProcessManager>().new_execution_id();
+            let run = processes::run_tracked(app, Some(execution_id.clone()), "schedule", preset.command(project_path));
+            tokio::pin!(run);
+            // Biased so the run registers its execution before a cancellation is handled
+            let result = tokio::select! {
+                biased;
+                result = &mut run => result,
+                _ = cancel.cancelled() => {
+                    cancellation::cancel(&execution_id);
+                    run.await
+                }
+            };
+            match result {
+                Ok(output) => {
+                    let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+                    text.push_str(&String::from_utf8_lossy(&output.stderr));
+                    (output.status.success(), text)
+                }
+                Err(e) => (false, e.to_string()),
+            }
+        }
+        ScheduleAction::Rpc { method, params } => {
+            let params = if params.is_null() { serde_json::json!([]) } else { params.clone() };
+            match cancel.run_until_cancelled(rpc::call(&rpc::default_url(), method, params)).await {
+                Some(Ok(result)) => (true, result.to_string()),
+                Some(Err(e)) => (false, e.to_string()),
+                None => (false, "Cancelled".to_string()),
+            }
+        }
+    }
+}
+
+async fn run_schedule(app: AppHandle, info: ScheduleInfo, cancel: CancellationToken) {
+    let scheduler = app.state::<Scheduler>();
+
+    loop {
+        let delay = match info.trigger {
+            ScheduleTrigger::Interval { every_ms } => every_ms,
+            ScheduleTrigger::At { timestamp_ms } => timestamp_ms.saturating_sub(store::timestamp_millis()),
+        };
+        if cancel.run_until_cancelled(tokio::time::sleep(Duration::from_millis(delay))).await.is_none() {
+            break;
+        }
+
+        let started_at = store::timestamp_millis();
+        let started = Instant::now();
+        let (success, mut output) = execute(&app, &info.project_path, &info.action, &cancel).await;
+        if cancel.is_cancelled() {
+            break;
+        }
+        if output.len() > OUTPUT_LIMIT {
+            let mut cut = output.len() - OUTPUT_LIMIT;
+            while !output.is_char_boundary(cut) {
+                cut += 1;
+            }
+            output.drain(..cut);
+        }
+
+        let keep_going = scheduler.record(
+            &app,
+            ScheduleRun {
+                schedule_id: info.id.clone(),
+                started_at,
+                duration_ms: started.elapsed().as_millis() as u64,
+                success,
+                output,
+            },
+        );
+
+        if !keep_going || matches!(info.trigger, ScheduleTrigger::At { .. }) {
+            break;
+        }
+    }
+
+    scheduler.finish(&info.id);
+}
+
+#[tauri::command]
+pub async fn start_schedule(
+    app: AppHandle,
+    scheduler: State<'_, Scheduler>,
+    project_path: String,
+    action: ScheduleAction,
+    trigger: ScheduleTrigger,
+    max_runs: Option<u32>,
+) -> Result<ScheduleInfo, AppError> {
+    if let ScheduleTrigger::Interval { every_ms } = trigger {
+        if every_ms < 100 {
+            return Err("Schedule interval must be at least 100ms".to_string().into());
+        }
+    }
+    if let ScheduleAction::Preset { name } = &action {
+        presets::find_preset(&project_path, name)?;
+    }
+
+    let info = ScheduleInfo {
+        id: format!("schedule-{}", scheduler.next_id.fetch_add(1, Ordering::Relaxed) + 1),
+        project_path,
+        action,
+        trigger,
+        max_runs,
+        active: true,
+        run_count: 0,
+        history: VecDeque::new(),
+    };
+
+    // Register before spawning so the first run always finds its entry
+    let mut schedules = scheduler.schedules.lock().unwrap();
+    let cancel = cancellation::project(Path::new(&info.project_path)).child_token();
+    schedules.insert(info.id.clone(), ScheduleEntry { info: info.clone(), cancel: cancel.clone() });
+    tauri::async_runtime::spawn(run_schedule(app.clone(), info.clone(), cancel));
+
+    Ok(info)
+}
+
+#[tauri::command]
+pub async fn stop_schedule(scheduler: State<'_, Scheduler>, schedule_id: String) -> Result<bool, AppError> {
+    let mut schedules = scheduler.schedules.lock().unwrap();
+    let Some(entry) = schedules.get_mut(&schedule_id) else {
+        return Ok(false);
+    };
+
+    let was_active = entry.info.active;
+    entry.cancel.cancel();
+    entry.info.active = false;
+    Ok(was_active)
+}
+
+#[tauri::command]
+pub async fn list_schedules(
+    scheduler: State<'_, Scheduler>,
+    project_path: Option<String>,
+) -> Result<Vec<ScheduleInfo>, AppError> {
+    let schedules = scheduler.schedules.lock().unwrap();
+    let mut list: Vec<ScheduleInfo> = schedules
+        .values()
+        .filter(|entry| project_path.as_ref().is_none_or(|p| &entry.info.project_path == p))
+        .map(|entry| entry.info.clone())
+        .collect();
+    // Ids are "schedule-N", so order by N rather than as strings
+    list.sort_by_key(|info| info.id.rsplit('-').next().and_then(|n| n.parse::<u64>().ok()));
+    Ok(list)
+}
+
+#[tauri::command]
+pub async fn remove_schedule(scheduler: State<'_, Scheduler>, schedule_id: String) -> Result<bool, AppError> {
+    let removed = scheduler.schedules.lock().unwrap().remove(&schedule_id);
+    if let Some(entry) = &removed {
+        entry.cancel.cancel();
+    }
+    Ok(removed.is_some())
+}
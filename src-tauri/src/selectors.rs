@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::decode::{self, AbiSource, DecodedCall};
+use crate::rpc;
+use crate::store;
+
+const CACHE_FILE: &str = "selectors.json";
+const LOOKUP_URL: &str = "https://api.openchain.xyz/signature-database/v1/lookup";
+
+// Text signatures for a 4-byte selector from the public signature database. Answers,
+// including "unknown", are cached so each selector is only looked up once
+pub async fn lookup(project_path: &Path, selector: &str) -> Vec<String> {
+    let mut cache: BTreeMap<String, Vec<String>> = store::load_json(project_path, CACHE_FILE).unwrap_or_default();
+    if let Some(signatures) = cache.get(selector) {
+        return signatures.clone();
+    }
+
+    let response = rpc::client()
+        .get(LOOKUP_URL)
+        .query(&[("function", selector), ("filter", "true")])
+        .timeout(Duration::from_secs(5))
+        .send()
+        .await;
+    let json = match response {
+        Ok(response) => response.json::<Value>().await.ok(),
+        Err(_) => None,
+    };
+    let Some(json) = json else {
+        // Offline: don't cache, so the lookup is retried next time
+        return Vec::new();
+    };
+
+    let signatures: Vec<String> = json
+        .pointer("/result/function")
+        .and_then(|functions| functions.get(selector))
+        .and_then(Value::as_array)
+        .map(|matches| {
+            matches
+                .iter()
+                .filter_map(|m| m.get("name").and_then(Value::as_str).map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    cache.insert(selector.to_string(), signatures.clone());
+    let _ = store::save_json(project_path, CACHE_FILE, &cache);
+    signatures
+}
+
+// Fills in a call the project's ABIs couldn't decode. Selectors collide, so the first
+// candidate whose parameters actually decode the calldata wins
+pub async fn resolve(project_path: &Path, call: &mut DecodedCall, input: &str) {
+    if call.function.is_some() {
+        return;
+    }
+    let Some(bytes) = decode::from_hex(input) else {
+        return;
+    };
+    for signature in lookup(project_path, &call.selector).await {
+        let Some((_, params)) = decode::parse_signature(&signature) else {
+            continue;
+        };
+        if let Some(args) = decode::decode_params(&params, &bytes[4..]) {
+            call.function = Some(signature);
+            call.source = Some(AbiSource::SelectorDatabase);
+            call.args = args;
+            return;
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use tauri::AppHandle;
+
+use crate::error::AppError;
+use crate::findings::{self, AnalysisReport, Finding, SourceLocation};
+use crate::health::Severity;
+use crate::processes;
+use crate::resolver;
+
+const DOCKER_IMAGE: &str = "semgrep/semgrep";
+const DEFAULT_RULESET: &str = "p/smart-contracts";
+// Organization-specific rules checked into the project are picked up automatically
+const LOCAL_RULE_PATHS: [&str; 3] = [".semgrep", ".semgrep.yml", ".semgrep.yaml"];
+
+#[derive(Deserialize)]
+struct SemgrepOutput {
+    #[serde(default)]
+    results: Vec<SemgrepResult>,
+    #[serde(default)]
+    errors: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct SemgrepResult {
+    check_id: String,
+    path: String,
+    start: SemgrepPosition,
+    extra: SemgrepExtra,
+}
+
+#[derive(Deserialize)]
+struct SemgrepPosition {
+    line: u32,
+}
+
+#[derive(Deserialize)]
+struct SemgrepExtra {
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    severity: String,
+}
+
+fn to_finding(result: SemgrepResult) -> Finding {
+    let severity = match result.extra.severity.as_str() {
+        "ERROR" => Severity::Error,
+        "WARNING" => Severity::Warning,
+        _ => Severity::Info,
+    };
+    Finding {
+        tool: "semgrep".to_string(),
+        title: result.check_id.rsplit('.').next().unwrap_or(&result.check_id).replace('-', " "),
+        rule: result.check_id,
+        swc_id: None,
+        severity,
+        description: result.extra.message,
+        contract: None,
+        function: None,
+        locations: vec![SourceLocation { file: result.path, line: Some(result.start.line) }],
+    }
+}
+
+#[tauri::command]
+pub async fn run_semgrep(
+    app: AppHandle,
+    project_path: String,
+    rulesets: Option<Vec<String>>,
+    execution_id: Option<String>,
+) -> Result<AnalysisReport, AppError> {
+    let root = Path::new(&project_path);
+    let mut configs = rulesets.filter(|r| !r.is_empty()).unwrap_or_else(|| vec![DEFAULT_RULESET.to_string()]);
+    configs.extend(
+        LOCAL_RULE_PATHS
+            .iter()
+            .filter(|path| root.join(path).exists())
+            .map(|path| path.to_string()),
+    );
+
+    let mut cmd = if resolver::locate("semgrep").is_some() {
+        resolver::command("semgrep")
+    } else if resolver::locate("docker").is_some() {
+        let mut cmd = resolver::command("docker");
+        cmd.args(["run", "--rm", "-v", &format!("{}:/src", project_path), "-w", "/src", DOCKER_IMAGE, "semgrep"]);
+        cmd
+    } else {
+        return Err("Semgrep is not installed. Install it with `pip install semgrep` or install Docker".to_string().into());
+    };
+
+    cmd.args(["scan", "--json", "--metrics=off"]).current_dir(&project_path);
+    for config in &configs {
+        cmd.args(["--config", config]);
+    }
+    cmd.arg("contracts");
+
+    let output = processes::run_tracked(&app, execution_id, "semgrep", cmd).await?;
+
+    // Exit code 1 just means findings were reported; the JSON is authoritative
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: SemgrepOutput = serde_json::from_str(stdout.trim()).map_err(|_| {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        format!("Semgrep failed: {}", stderr)
+    })?;
+
+    if parsed.results.is_empty() && !parsed.errors.is_empty() && !output.status.success() {
+        let messages: Vec<String> = parsed
+            .errors
+            .iter()
+            .filter_map(|e| e.get("message").and_then(|m| m.as_str()).map(str::to_string))
+            .collect();
+        return Err(format!("Semgrep failed: {}", messages.join("\n")).into());
+    }
+
+    Ok(findings::record(root, AnalysisReport::new("semgrep", parsed.results.into_iter().map(to_finding).collect()))?)
+}
@@ -0,0 +1,268 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::address_book::AddressBook;
+use crate::decode::{self, AbiIndex, DecodedCall};
+use crate::error::AppError;
+use crate::events::{self, DecodedLog};
+use crate::explorer::{self, CallFrame};
+use crate::rpc;
+use crate::transactions::{self, TxType};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SimulationBase {
+    // A mined (or pending) transaction, re-run with its original fields unless overridden
+    Transaction { hash: String },
+    Draft {
+        from: String,
+        to: Option<String>,
+        #[serde(default)]
+        data: Option<String>,
+        // Wei as a decimal string
+        #[serde(default)]
+        value: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulationState {
+    // Sends the transaction on top of the current state inside evm_snapshot/evm_revert,
+    // so it gets a real receipt and logs
+    #[default]
+    Snapshot,
+    // Traces the call against the state right before the original transaction's block,
+    // without touching the node. Only a trace and return data, no receipt
+    ParentBlock,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct SimulationOverrides {
+    pub from: Option<String>,
+    pub gas: Option<u64>,
+    // Wei as a decimal string
+    pub value: Option<String>,
+    // Raw calldata; takes precedence over `args`
+    pub data: Option<String>,
+    // New arguments for the decoded function, in the shapes decode_calldata returns
+    pub args: Option<Vec<Value>>,
+    pub tx_type: Option<TxType>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SimulatedRequest {
+    pub from: String,
+    pub to: Option<String>,
+    pub value: String,
+    pub gas: Option<u64>,
+    pub data: String,
+    pub block: Option<u64>,
+    // None leaves the fee fields, and so the type, to the node
+    #[serde(default)]
+    pub tx_type: Option<TxType>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SimulationResult {
+    pub request: SimulatedRequest,
+    pub state: SimulationState,
+    pub decoded_input: Option<DecodedCall>,
+    pub success: bool,
+    // Revert reason or RPC error when the call failed
+    pub error: Option<String>,
+    pub output: Option<String>,
+    pub gas_used: Option<u64>,
+    pub logs: Vec<DecodedLog>,
+    pub call_tree: Option<CallFrame>,
+    pub trace_error: Option<String>,
+    pub labels: BTreeMap<String, String>,
+}
+
+fn str_value(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+fn rpc_request(request: &SimulatedRequest) -> Result<Value, String> {
+    let mut tx = Map::new();
+    tx.insert("from".to_string(), json!(request.from));
+    if let Some(to) = &request.to {
+        tx.insert("to".to_string(), json!(to));
+    }
+    tx.insert("data".to_string(), json!(request.data));
+    tx.insert("value".to_string(), json!(decode::decimal_to_quantity(&request.value)?));
+    if let Some(gas) = request.gas {
+        tx.insert("gas".to_string(), json!(format!("0x{:x}", gas)));
+    }
+    Ok(Value::Object(tx))
+}
+
+// Swaps the arguments of the decoded function while keeping its selector
+fn reencode(index: &AbiIndex, to: Option<&str>, data: &str, args: &[Value]) -> Result<String, String> {
+    let call = index
+        .decode_call(to, data)
+        .ok_or("Only contract calls can have their arguments changed")?;
+    let signature = call
+        .function
+        .ok_or_else(|| format!("No known ABI for selector {}", call.selector))?;
+    let (_, params) = decode::parse_signature(&signature).ok_or_else(|| format!("Invalid signature: {}", signature))?;
+    let encoded = decode::encode_params(&params, args)?;
+    Ok(format!("{}{}", call.selector, &decode::to_hex(&encoded)[2..]))
+}
+
+pub async fn base_request(url: &str, base: &SimulationBase) -> Result<SimulatedRequest, AppError> {
+    match base {
+        SimulationBase::Transaction { hash } => {
+            let tx = rpc::call(url, "eth_getTransactionByHash", json!([hash])).await?;
+            if tx.is_null() {
+                return Err(format!("Transaction {} not found", hash).into());
+            }
+            Ok(SimulatedRequest {
+                from: str_value(&tx, "from").unwrap_or_default(),
+                to: str_value(&tx, "to"),
+                value: decode::quantity_to_decimal(tx.get("value").and_then(Value::as_str).unwrap_or("0x0")),
+                gas: tx.get("gas").and_then(rpc::parse_quantity),
+                data: str_value(&tx, "input").unwrap_or_else(|| "0x".to_string()),
+                block: tx.get("blockNumber").and_then(rpc::parse_quantity),
+                tx_type: transactions::tx_type_of(&tx),
+            })
+        }
+        SimulationBase::Draft { from, to, data, value } => Ok(SimulatedRequest {
+            from: from.clone(),
+            to: to.clone(),
+            value: value.clone().unwrap_or_else(|| "0".to_string()),
+            gas: None,
+            data: data.clone().unwrap_or_else(|| "0x".to_string()),
+            block: None,
+            tx_type: None,
+        }),
+    }
+}
+
+async fn run_on_snapshot(url: &str, request: &SimulatedRequest, index: &AbiIndex, result: &mut SimulationResult) -> Result<(), AppError> {
+    let mut tx = rpc_request(request)?;
+    transactions::apply_tx_type(url, &mut tx, request.tx_type).await?;
+    let snapshot = rpc::call(url, "evm_snapshot", json!([])).await?;
+    // Lets the simulation send as any address, including contracts and the original sender
+    let _ = rpc::call(url, "hardhat_impersonateAccount", json!([request.from])).await;
+
+    let sent = rpc::call(url, "eth_sendTransaction", json!([tx])).await;
+    match sent.as_ref().ok().and_then(Value::as_str) {
+        Some(hash) => {
+            let receipt = rpc::call(url, "eth_getTransactionReceipt", json!([hash])).await.unwrap_or(Value::Null);
+            result.success = receipt.get("status").and_then(rpc::parse_quantity) == Some(1);
+            result.gas_used = receipt.get("gasUsed").and_then(rpc::parse_quantity);
+            result.logs = receipt
+                .get("logs")
+                .and_then(Value::as_array)
+                .map(|logs| events::decode_logs(index, logs))
+                .unwrap_or_default();
+            match explorer::call_tree(url, hash, index).await {
+                Ok(frame) => {
+                    result.output = frame.output.clone();
+                    result.error = frame.error.clone();
+                    result.call_tree = Some(frame);
+                }
+                Err(e) => result.trace_error = Some(e.to_string()),
+            }
+        }
+        // Hardhat rejects reverting transactions instead of mining them
+        None => result.error = Some(sent.err().unwrap_or_else(|| "Node returned no transaction hash".to_string().into()).to_string()),
+    }
+
+    let _ = rpc::call(url, "hardhat_stopImpersonatingAccount", json!([request.from])).await;
+    let reverted = rpc::call(url, "evm_revert", json!([snapshot])).await?;
+    if reverted != Value::Bool(true) {
+        return Err("Failed to revert the simulation snapshot; the node state may include the simulated transaction".to_string().into());
+    }
+    Ok(())
+}
+
+async fn run_at_parent_block(url: &str, request: &SimulatedRequest, index: &AbiIndex, result: &mut SimulationResult) -> Result<(), String> {
+    let block = request
+        .block
+        .ok_or("Only mined transactions can be simulated at their parent block")?
+        .saturating_sub(1);
+    let tag = format!("0x{:x}", block);
+    let tx = rpc_request(request)?;
+
+    match rpc::call(url, "eth_call", json!([tx, tag])).await {
+        Ok(output) => {
+            result.success = true;
+            result.output = output.as_str().map(str::to_string);
+        }
+        Err(e) => result.error = Some(e.to_string()),
+    }
+    if let Ok(gas) = rpc::call(url, "eth_estimateGas", json!([tx, tag])).await {
+        result.gas_used = rpc::parse_quantity(&gas);
+    }
+    match rpc::call(url, "debug_traceCall", json!([tx, tag, { "tracer": "callTracer" }])).await {
+        Ok(trace) => result.call_tree = Some(explorer::parse_trace(trace, index)?),
+        Err(e) => result.trace_error = Some(e.to_string()),
+    }
+    Ok(())
+}
+
+// Re-runs a transaction with changed gas, value, sender or arguments to see why it
+// reverted or what a fix would do. The node is left as it was
+#[tauri::command]
+pub async fn simulate_transaction(
+    project_path: String,
+    base: SimulationBase,
+    overrides: Option<SimulationOverrides>,
+    state: Option<SimulationState>,
+    rpc_url: Option<String>,
+) -> Result<SimulationResult, AppError> {
+    let root = Path::new(&project_path);
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let overrides = overrides.unwrap_or_default();
+    let state = state.unwrap_or_default();
+    let index = AbiIndex::load(root);
+
+    let mut request = base_request(url, &base).await?;
+    if let Some(from) = overrides.from {
+        request.from = from;
+    }
+    if overrides.gas.is_some() {
+        request.gas = overrides.gas;
+    }
+    if overrides.tx_type.is_some() {
+        request.tx_type = overrides.tx_type;
+    }
+    if let Some(value) = overrides.value {
+        request.value = value;
+    }
+    if let Some(data) = overrides.data {
+        if decode::from_hex(&data).is_none() {
+            return Err("Calldata must be 0x-prefixed hex".to_string().into());
+        }
+        request.data = data;
+    } else if let Some(args) = overrides.args {
+        request.data = reencode(&index, request.to.as_deref(), &request.data, &args)?;
+    }
+
+    let mut result = SimulationResult {
+        decoded_input: index.decode_call(request.to.as_deref(), &request.data),
+        request,
+        state,
+        success: false,
+        error: None,
+        output: None,
+        gas_used: None,
+        logs: Vec::new(),
+        call_tree: None,
+        trace_error: None,
+        labels: BTreeMap::new(),
+    };
+    let request = result.request.clone();
+    match state {
+        SimulationState::Snapshot => run_on_snapshot(url, &request, &index, &mut result).await?,
+        SimulationState::ParentBlock => run_at_parent_block(url, &request, &index, &mut result).await?,
+    }
+
+    result.labels = AddressBook::load(root, Some(url)).await.labels_for(&result);
+    Ok(result)
+}
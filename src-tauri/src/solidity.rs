@@ -0,0 +1,253 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SolcVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl SolcVersion {
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().trim_start_matches('v');
+        // Drop build metadata such as "0.8.24+commit.e11b9ed9"
+        let text = text.split(['+', '-']).next()?;
+        let mut parts = text.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map(|p| p.parse()).transpose().ok()?.unwrap_or(0);
+        let patch = parts.next().map(|p| p.parse()).transpose().ok()?.unwrap_or(0);
+        Some(SolcVersion { major, minor, patch })
+    }
+}
+
+impl fmt::Display for SolcVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+// Returns the version constraint of the first `pragma solidity ...;` in a source file
+pub fn extract_pragma(source: &str) -> Option<String> {
+    let re = Regex::new(r"pragma\s+solidity\s+([^;]+);").unwrap();
+    re.captures(source).map(|c| c[1].trim().to_string())
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CompilerDiagnostic {
+    // "error" or "warning"
+    pub severity: String,
+    // solc's diagnostic type, e.g. "TypeError", "ParserError", "Warning"
+    pub error_type: String,
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+}
+
+// solc diagnostics as Hardhat prints them:
+//   TypeError: Member "foo" not found ...
+//     --> contracts/Lock.sol:20:9:
+pub fn compiler_diagnostics(output: &str) -> Vec<CompilerDiagnostic> {
+    let header = Regex::new(r"^\s*(\w*Error|Warning):\s*(.+)$").unwrap();
+    let location = Regex::new(r"^\s*-->\s*(.+?):(\d+):(\d+):?\s*$").unwrap();
+    let mut diagnostics: Vec<CompilerDiagnostic> = Vec::new();
+    // The location line comes after its header, before the next diagnostic
+    let mut open = false;
+    for line in output.lines() {
+        if let Some(c) = header.captures(line) {
+            let error_type = c[1].to_string();
+            diagnostics.push(CompilerDiagnostic {
+                severity: if error_type == "Warning" { "warning" } else { "error" }.to_string(),
+                error_type,
+                message: c[2].trim().to_string(),
+                file: None,
+                line: None,
+                column: None,
+            });
+            open = true;
+        } else if let (true, Some(c)) = (open, location.captures(line)) {
+            if let Some(last) = diagnostics.last_mut() {
+                last.file = Some(c[1].to_string());
+                last.line = c[2].parse().ok();
+                last.column = c[3].parse().ok();
+            }
+            open = false;
+        }
+    }
+    diagnostics
+}
+
+// Compiler versions pinned in a hardhat config, e.g. `solidity: "0.8.24"` or
+// `compilers: [{ version: "0.8.20" }]`
+pub fn configured_compiler_versions(config: &str) -> Vec<SolcVersion> {
+    let re = Regex::new(r#"(?:solidity|version)\s*:\s*["'`](\d+\.\d+\.\d+)["'`]"#).unwrap();
+    let mut versions: Vec<SolcVersion> = re
+        .captures_iter(config)
+        .filter_map(|c| SolcVersion::parse(&c[1]))
+        .collect();
+    versions.sort();
+    versions.dedup();
+    versions
+}
+
+// Checks a version against a pragma constraint such as "^0.8.0", ">=0.7.0 <0.9.0"
+// or "0.8.19 || ^0.8.24"
+pub fn pragma_matches(pragma: &str, version: SolcVersion) -> bool {
+    pragma
+        .split("||")
+        .any(|range| range_matches(range.trim(), version))
+}
+
+fn range_matches(range: &str, version: SolcVersion) -> bool {
+    if let Some((low, high)) = range.split_once(" - ") {
+        return match (SolcVersion::parse(low), SolcVersion::parse(high)) {
+            (Some(low), Some(high)) => version >= low && version <= high,
+            _ => false,
+        };
+    }
+
+    // Glue operators to their versions so "> = 0.8.0" style spacing still parses
+    let normalized = Regex::new(r"(\^|~|>=|<=|>|<|=)\s+")
+        .unwrap()
+        .replace_all(range, "$1");
+
+    normalized
+        .split_whitespace()
+        .all(|comparator| comparator_matches(comparator, version))
+}
+
+fn comparator_matches(comparator: &str, version: SolcVersion) -> bool {
+    let (op, rest) = ["^", "~", ">=", "<=", ">", "<", "="]
+        .iter()
+        .find_map(|op| comparator.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("", comparator));
+
+    let Some(target) = SolcVersion::parse(rest) else {
+        return false;
+    };
+    let components = rest.split('.').count();
+
+    match op {
+        "^" => {
+            // The first non-zero component is the one that may not change; "^0.0.3" only
+            // accepts 0.0.3 itself
+            let upper = if target.major > 0 {
+                SolcVersion { major: target.major + 1, minor: 0, patch: 0 }
+            } else if target.minor > 0 || components < 3 {
+                SolcVersion { major: 0, minor: target.minor + 1, patch: 0 }
+            } else {
+                SolcVersion { major: 0, minor: 0, patch: target.patch + 1 }
+            };
+            version >= target && version < upper
+        }
+        "~" => {
+            let upper = SolcVersion { major: target.major, minor: target.minor + 1, patch: 0 };
+            version >= target && version < upper
+        }
+        ">=" => version >= target,
+        "<=" => version <= target,
+        ">" => version > target,
+        "<" => version < target,
+        // A partial exact version like "0.8" accepts any patch release
+        _ if components < 3 => version.major == target.major && version.minor == target.minor,
+        _ => version == target,
+    }
+}
+
+// Paths named by `import "...";` / `import {A} from "...";` statements
+pub fn extract_imports(source: &str) -> Vec<String> {
+    let re = Regex::new(r#"(?m)^\s*import\s+(?:[^"';]*?\bfrom\s+)?["']([^"']+)["']"#).unwrap();
+    re.captures_iter(source).map(|c| c[1].to_string()).collect()
+}
+
+// Resolves an import the way Hardhat does: relative to the importing file, otherwise
+// as a package path under node_modules
+pub fn resolve_import(project_path: &Path, from_file: &Path, import: &str) -> Option<PathBuf> {
+    let path = if import.starts_with("./") || import.starts_with("../") {
+        from_file.parent()?.join(import)
+    } else if project_path.join(import).is_file() {
+        project_path.join(import)
+    } else {
+        project_path.join("node_modules").join(import)
+    };
+    path.canonicalize().ok()
+}
+
+// Every file reachable from `entry` through imports, including `entry` itself
+pub fn import_closure(project_path: &Path, entry: &Path) -> Vec<PathBuf> {
+    let mut seen = BTreeSet::new();
+    let mut pending = vec![entry.canonicalize().unwrap_or_else(|_| entry.to_path_buf())];
+
+    while let Some(file) = pending.pop() {
+        if seen.contains(&file) {
+            continue;
+        }
+        if let Ok(source) = fs::read_to_string(&file) {
+            pending.extend(
+                extract_imports(&source)
+                    .iter()
+                    .filter_map(|import| resolve_import(project_path, &file, import)),
+            );
+        }
+        seen.insert(file);
+    }
+    seen.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pragma: &str, version: &str) -> bool {
+        pragma_matches(pragma, SolcVersion::parse(version).unwrap())
+    }
+
+    #[test]
+    fn parses_versions_with_build_metadata() {
+        assert_eq!(SolcVersion::parse("v0.8.24+commit.e11b9ed9"), Some(SolcVersion { major: 0, minor: 8, patch: 24 }));
+        assert_eq!(SolcVersion::parse("0.8"), Some(SolcVersion { major: 0, minor: 8, patch: 0 }));
+        assert_eq!(SolcVersion::parse("latest"), None);
+    }
+
+    #[test]
+    fn caret_keeps_the_first_non_zero_component() {
+        assert!(matches("^0.8.0", "0.8.24"));
+        assert!(!matches("^0.8.0", "0.9.0"));
+        assert!(!matches("^0.8.20", "0.8.19"));
+        assert!(matches("^1.2.3", "1.9.0"));
+        assert!(!matches("^1.2.3", "2.0.0"));
+        assert!(matches("^0.0.3", "0.0.3"));
+        assert!(!matches("^0.0.3", "0.0.4"));
+        assert!(matches("^0.0", "0.0.9"));
+        assert!(!matches("^0.0", "0.1.0"));
+    }
+
+    #[test]
+    fn matches_ranges_and_alternatives() {
+        assert!(matches("~0.8.17", "0.8.30"));
+        assert!(!matches("~0.8.17", "0.9.0"));
+        assert!(matches(">=0.7.0 <0.9.0", "0.8.24"));
+        assert!(!matches(">=0.7.0 <0.9.0", "0.9.0"));
+        assert!(matches("> = 0.8.0", "0.8.0"));
+        assert!(matches("0.8.0 - 0.8.4", "0.8.4"));
+        assert!(!matches("0.8.0 - 0.8.4", "0.8.5"));
+        assert!(matches("0.8.19 || ^0.8.24", "0.8.25"));
+        assert!(!matches("0.8.19 || ^0.8.24", "0.8.20"));
+        assert!(matches("0.8", "0.8.7"));
+        assert!(!matches("=0.8.7", "0.8.8"));
+    }
+
+    #[test]
+    fn reads_pragmas_and_configured_versions() {
+        assert_eq!(extract_pragma("// SPDX\npragma solidity >=0.8.0 <0.9.0;\n").as_deref(), Some(">=0.8.0 <0.9.0"));
+        let config = "solidity: { compilers: [{ version: \"0.8.20\" }, { version: '0.7.6' }, { version: \"0.8.20\" }] }";
+        let versions: Vec<String> = configured_compiler_versions(config).iter().map(|v| v.to_string()).collect();
+        assert_eq!(versions, ["0.7.6", "0.8.20"]);
+    }
+}
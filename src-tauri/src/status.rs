@@ -0,0 +1,187 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::mpsc;
+
+use crate::devnets;
+use crate::error::AppError;
+use crate::processes::{self, ProcessManager};
+use crate::project;
+use crate::resolver;
+use crate::rpc;
+use crate::store;
+
+const NODE_POLL: Duration = Duration::from_secs(3);
+// Short of the poll interval, so a hung node doesn't hold up the next probe
+const NODE_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct HardhatStatus {
+    pub installed: bool,
+    pub version: Option<String>,
+    // Installed in the project's node_modules rather than found through npx
+    #[serde(default)]
+    pub local: bool,
+    pub project_detected: bool,
+    pub project_path: Option<String>,
+    pub network_running: bool,
+    // When `npx hardhat --version` last finished; None while the first probe is running
+    #[serde(default)]
+    pub checked_at: Option<u64>,
+}
+
+enum StatusMessage {
+    Project(Option<String>),
+    // Re-probe the install, e.g. after `npm install -g hardhat`
+    Refresh,
+}
+
+struct StatusActor {
+    sender: mpsc::UnboundedSender<StatusMessage>,
+    status: Arc<Mutex<HardhatStatus>>,
+}
+
+static ACTOR: OnceLock<StatusActor> = OnceLock::new();
+
+fn detect_project(status: &mut HardhatStatus, project_path: Option<&str>) {
+    let check_path = project_path.unwrap_or(".");
+    status.project_detected = project::find_hardhat_config(Path::new(check_path)).is_some();
+    status.project_path = status.project_detected.then(|| check_path.to_string());
+}
+
+async fn probe_install(status: &mut HardhatStatus) {
+    status.installed = false;
+    status.version = None;
+    let root = Path::new(status.project_path.as_deref().unwrap_or("."));
+    status.local = project::has_local_bin(root, "hardhat");
+    let mut version = resolver::hardhat(root);
+    version.arg("--version");
+    if let Ok(output) = processes::output(version).await {
+        if output.status.success() {
+            status.installed = true;
+            if let Ok(version_str) = String::from_utf8(output.stdout) {
+                status.version = Some(version_str.trim().to_string());
+            }
+        }
+    }
+    status.checked_at = Some(store::timestamp_millis());
+}
+
+// An attached external node, otherwise the local node the GUI started last, otherwise
+// the default Hardhat port for a node started outside the GUI
+async fn probe_node(app: &AppHandle) -> bool {
+    let url = rpc::external_url()
+        .or_else(|| devnets::active_url(&app.state::<ProcessManager>()))
+        .unwrap_or_else(|| rpc::LOCAL_RPC_URL.to_string());
+    matches!(tokio::time::timeout(NODE_PROBE_TIMEOUT, rpc::chain_id(&url)).await, Ok(Ok(_)))
+}
+
+// Reports changes to the config (project detection) and package.json (the local
+// Hardhat install) in the project root; nothing below the root matters here
+fn watch_project(project_path: &str, changes: mpsc::UnboundedSender<bool>) -> Option<RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else { return };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        let names: Vec<String> = event
+            .paths
+            .iter()
+            .filter_map(|path| path.file_name().map(|name| name.to_string_lossy().to_string()))
+            .collect();
+        if names.iter().any(|name| project::CONFIG_FILE_NAMES.contains(&name.as_str())) {
+            let _ = changes.send(false);
+        }
+        if names.iter().any(|name| name == "package.json" || name == "package-lock.json") {
+            let _ = changes.send(true);
+        }
+    })
+    .map_err(|e| tracing::warn!(error = %e, "status watcher unavailable"))
+    .ok()?;
+    watcher.watch(Path::new(project_path), RecursiveMode::NonRecursive).ok()?;
+    Some(watcher)
+}
+
+fn publish(app: &AppHandle, shared: &Mutex<HardhatStatus>, next: &HardhatStatus) {
+    let mut current = shared.lock().unwrap();
+    if *current != *next {
+        *current = next.clone();
+        let _ = app.emit("status-changed", next.clone());
+    }
+}
+
+async fn run(app: AppHandle, mut messages: mpsc::UnboundedReceiver<StatusMessage>, shared: Arc<Mutex<HardhatStatus>>) {
+    let (changes_tx, mut changes) = mpsc::unbounded_channel();
+    let mut project_path: Option<String> = None;
+    let mut watcher: Option<RecommendedWatcher> = None;
+    let mut node_poll = tokio::time::interval(NODE_POLL);
+
+    let mut status = shared.lock().unwrap().clone();
+    probe_install(&mut status).await;
+    publish(&app, &shared, &status);
+
+    loop {
+        tokio::select! {
+            Some(message) = messages.recv() => match message {
+                StatusMessage::Project(path) => {
+                    let switched = path != project_path;
+                    if switched || watcher.is_none() {
+                        watcher = watch_project(path.as_deref().unwrap_or("."), changes_tx.clone());
+                        project_path = path;
+                    }
+                    detect_project(&mut status, project_path.as_deref());
+                    // Each project has its own Hardhat
+                    if switched {
+                        probe_install(&mut status).await;
+                    }
+                }
+                StatusMessage::Refresh => probe_install(&mut status).await,
+            },
+            Some(install_changed) = changes.recv() => {
+                detect_project(&mut status, project_path.as_deref());
+                if install_changed {
+                    probe_install(&mut status).await;
+                }
+            }
+            _ = node_poll.tick() => status.network_running = probe_node(&app).await,
+        }
+        publish(&app, &shared, &status);
+    }
+}
+
+// Starts the actor that owns Hardhat status; it emits `status-changed` whenever the
+// install, project detection or node reachability changes
+pub fn init(app: &AppHandle) {
+    let (sender, messages) = mpsc::unbounded_channel();
+    let status = Arc::new(Mutex::new(HardhatStatus::default()));
+    if ACTOR.set(StatusActor { sender, status: status.clone() }).is_err() {
+        return;
+    }
+    tauri::async_runtime::spawn(run(app.clone(), messages, status));
+}
+
+// The project the monitor is watching, once one has been detected
+pub fn project_path() -> Option<String> {
+    ACTOR.get().and_then(|actor| actor.status.lock().unwrap().project_path.clone())
+}
+
+pub fn refresh() {
+    if let Some(actor) = ACTOR.get() {
+        let _ = actor.sender.send(StatusMessage::Refresh);
+    }
+}
+
+// Returns the cached status straight away. Switching projects re-checks detection
+// inline (a few file lookups) so the answer is right for the path that was asked about
+#[tauri::command]
+pub async fn check_hardhat_status(project_path: Option<String>) -> Result<HardhatStatus, AppError> {
+    let actor = ACTOR.get().ok_or("Status monitor is not running")?;
+    let mut status = actor.status.lock().unwrap().clone();
+    detect_project(&mut status, project_path.as_deref());
+    let _ = actor.sender.send(StatusMessage::Project(project_path));
+    Ok(status)
+}
@@ -0,0 +1,406 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::artifacts::{self, BuildInfoView};
+use crate::deployments;
+use crate::error::AppError;
+use crate::health::Severity;
+use crate::store;
+
+const SNAPSHOTS_FILE: &str = "storage-layouts.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StorageEntry {
+    pub label: String,
+    pub slot: String,
+    pub offset: u32,
+    #[serde(rename = "type")]
+    pub type_id: String,
+    #[serde(default)]
+    pub contract: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StorageType {
+    pub label: String,
+    #[serde(rename = "numberOfBytes")]
+    pub number_of_bytes: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct StorageLayout {
+    #[serde(default)]
+    pub storage: Vec<StorageEntry>,
+    // solc emits `null` for contracts without storage
+    #[serde(default)]
+    pub types: Option<BTreeMap<String, StorageType>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LayoutSnapshot {
+    pub contract: String,
+    pub label: String,
+    pub created_at: u64,
+    pub layout: StorageLayout,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LayoutSource {
+    Snapshot { contract: String, label: Option<String> },
+    Deployment { deployment_id: String, future_id: String },
+    // Looked up in the Ignition deployments by address
+    Address { address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LayoutIssue {
+    pub severity: Severity,
+    pub kind: String,
+    pub slot: String,
+    pub offset: u32,
+    pub old: Option<String>,
+    pub new: Option<String>,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LayoutDiff {
+    pub contract: String,
+    pub baseline: String,
+    pub upgrade_safe: bool,
+    pub issues: Vec<LayoutIssue>,
+}
+
+impl StorageLayout {
+    fn type_of(&self, entry: &StorageEntry) -> Option<&StorageType> {
+        self.types.as_ref()?.get(&entry.type_id)
+    }
+
+    fn describe(&self, entry: &StorageEntry) -> String {
+        let type_label = self.type_of(entry).map_or(entry.type_id.as_str(), |t| t.label.as_str());
+        format!("{} {}", type_label, entry.label)
+    }
+
+    fn slots(&self, entry: &StorageEntry) -> u128 {
+        let bytes: u128 = self
+            .type_of(entry)
+            .and_then(|t| t.number_of_bytes.parse().ok())
+            .unwrap_or(32);
+        bytes.div_ceil(32).max(1)
+    }
+}
+
+const MISSING_LAYOUT: &str = "Storage layout missing from build info. Add `outputSelection: { \"*\": { \"*\": [\"storageLayout\"] } }` to the solidity settings in your Hardhat config and recompile.";
+
+fn layout_from_output(output: &Value) -> Result<StorageLayout, String> {
+    let layout = output.get("storageLayout").ok_or(MISSING_LAYOUT)?;
+    serde_json::from_value(layout.clone()).map_err(|e| format!("Failed to parse storage layout: {}", e))
+}
+
+pub fn current_layout(project_path: &Path, contract: &str) -> Result<StorageLayout, String> {
+    for path in artifacts::build_info_files(project_path) {
+        let Some(build_info) = artifacts::read_build_info(&path, BuildInfoView::Contracts) else {
+            continue;
+        };
+        if let Some((_, output)) = artifacts::contract_output(&build_info, contract) {
+            return layout_from_output(&output);
+        }
+    }
+    Err(format!("No build info found for {}. Compile the project first.", contract))
+}
+
+// Ignition keeps the build-info each deployed future was compiled with, referenced
+// from artifacts/<future-id>.dbg.json
+fn deployment_layout(project_path: &Path, deployment_id: &str, future_id: &str) -> Result<StorageLayout, String> {
+    let artifacts_dir = project_path
+        .join("ignition")
+        .join("deployments")
+        .join(deployment_id)
+        .join("artifacts");
+    let not_found = || format!("No artifact recorded for {} in deployment {}", future_id, deployment_id);
+
+    let artifact = artifacts::read_json(&artifacts_dir.join(format!("{}.json", future_id))).ok_or_else(not_found)?;
+    let debug = artifacts::read_json(&artifacts_dir.join(format!("{}.dbg.json", future_id))).ok_or_else(not_found)?;
+    let contract = artifact.get("contractName").and_then(Value::as_str).ok_or_else(not_found)?;
+    let build_info_path = debug.get("buildInfo").and_then(Value::as_str).ok_or_else(not_found)?;
+
+    let build_info = artifacts::read_build_info(&artifacts_dir.join(build_info_path), BuildInfoView::Contracts)
+        .ok_or_else(|| format!("Build info for deployment {} is missing", deployment_id))?;
+    let (_, output) = artifacts::contract_output(&build_info, contract).ok_or_else(not_found)?;
+    layout_from_output(&output)
+}
+
+fn load_snapshots(project_path: &Path) -> Result<Vec<LayoutSnapshot>, String> {
+    store::load_json(project_path, SNAPSHOTS_FILE)
+}
+
+fn resolve_baseline(project_path: &Path, source: &LayoutSource) -> Result<(String, StorageLayout), String> {
+    match source {
+        LayoutSource::Snapshot { contract, label } => {
+            let snapshot = load_snapshots(project_path)?
+                .into_iter()
+                .filter(|s| &s.contract == contract && label.as_ref().is_none_or(|l| &s.label == l))
+                .max_by_key(|s| s.created_at)
+                .ok_or_else(|| format!("No storage layout snapshot found for {}", contract))?;
+            Ok((format!("snapshot {}", snapshot.label), snapshot.layout))
+        }
+        LayoutSource::Deployment { deployment_id, future_id } => {
+            let layout = deployment_layout(project_path, deployment_id, future_id)?;
+            Ok((format!("deployment {} ({})", deployment_id, future_id), layout))
+        }
+        LayoutSource::Address { address } => {
+            let deployment = deployments::load_deployments(project_path)
+                .into_iter()
+                .find(|d| d.address.eq_ignore_ascii_case(address))
+                .ok_or_else(|| format!("No deployment found at {}", address))?;
+            let layout = deployment_layout(project_path, &deployment.deployment_id, &deployment.future_id)?;
+            Ok((format!("{} at {}", deployment.future_id, deployment.address), layout))
+        }
+    }
+}
+
+fn is_gap(entry: &StorageEntry) -> bool {
+    entry.label.starts_with("__gap")
+}
+
+fn slot(entry: &StorageEntry) -> u128 {
+    parse_slot(&entry.slot)
+}
+
+fn parse_slot(slot: &str) -> u128 {
+    slot.parse().unwrap_or(0)
+}
+
+fn issue(severity: Severity, kind: &str, entry: &StorageEntry, old: Option<String>, new: Option<String>, message: String) -> LayoutIssue {
+    LayoutIssue {
+        severity,
+        kind: kind.to_string(),
+        slot: entry.slot.clone(),
+        offset: entry.offset,
+        old,
+        new,
+        message,
+    }
+}
+
+// Contract-typed variables are stored as addresses, so swapping between the two is safe
+fn same_type(old: &StorageLayout, o: &StorageEntry, new: &StorageLayout, n: &StorageEntry) -> bool {
+    let label = |layout: &StorageLayout, e: &StorageEntry| {
+        let label = layout.type_of(e).map_or(e.type_id.clone(), |t| t.label.clone());
+        if label.starts_with("contract ") { "address".to_string() } else { label }
+    };
+    label(old, o) == label(new, n)
+}
+
+// Index of the declaring contract in the inheritance chain. Storage is laid out base
+// first, so the order in which contracts appear is their linearized order
+fn inheritance_position(layout: &StorageLayout, contract: &str) -> Option<usize> {
+    let mut seen: Vec<&str> = Vec::new();
+    for entry in &layout.storage {
+        if !seen.contains(&entry.contract.as_str()) {
+            seen.push(&entry.contract);
+        }
+    }
+    seen.iter().position(|c| *c == contract)
+}
+
+// Contracts are paired by position rather than name, so renaming BoxV1 to BoxV2 still
+// compares each variable against its own counterpart
+fn same_contract(old: &StorageLayout, o: &StorageEntry, new: &StorageLayout, n: &StorageEntry) -> bool {
+    inheritance_position(old, &o.contract) == inheritance_position(new, &n.contract)
+}
+
+pub fn diff(old: &StorageLayout, new: &StorageLayout) -> Vec<LayoutIssue> {
+    let mut issues = Vec::new();
+    let at = |layout: &StorageLayout, s: &str, offset: u32| -> Option<StorageEntry> {
+        layout.storage.iter().find(|e| e.slot == s && e.offset == offset).cloned()
+    };
+
+    for o in old.storage.iter().filter(|e| !is_gap(e)) {
+        let moved = new.storage.iter().find(|n| n.label == o.label && same_contract(old, o, new, n));
+        match at(new, &o.slot, o.offset) {
+            None => match moved {
+                Some(m) => issues.push(issue(Severity::Error, "reordered", o, Some(old.describe(o)), Some(new.describe(m)),
+                    format!("{} moved from slot {} to slot {}", o.label, o.slot, m.slot))),
+                None => issues.push(issue(Severity::Warning, "removed", o, Some(old.describe(o)), None,
+                    format!("{} was removed; its slot still holds the old value", o.label))),
+            },
+            Some(n) if n.label == o.label => {
+                if !same_type(old, o, new, &n) {
+                    issues.push(issue(Severity::Error, "type_changed", o, Some(old.describe(o)), Some(new.describe(&n)),
+                        format!("{} changed type; existing data will be misinterpreted", o.label)));
+                }
+            }
+            Some(n) if is_gap(&n) => issues.push(issue(Severity::Error, "replaced_by_gap", o, Some(old.describe(o)), Some(new.describe(&n)),
+                format!("{} was replaced by a storage gap", o.label))),
+            Some(n) => match moved {
+                Some(m) => issues.push(issue(Severity::Error, "reordered", o, Some(old.describe(o)), Some(new.describe(m)),
+                    format!("{} moved from slot {} to slot {}; {} now occupies its slot", o.label, o.slot, m.slot, n.label))),
+                // Safe if intended, but indistinguishable from two variables swapping places
+                None if same_type(old, o, new, &n) => issues.push(issue(Severity::Warning, "renamed", o, Some(old.describe(o)), Some(new.describe(&n)),
+                    format!("{} was renamed to {}; make sure it still holds the same value", o.label, n.label))),
+                None => issues.push(issue(Severity::Error, "replaced", o, Some(old.describe(o)), Some(new.describe(&n)),
+                    format!("{} was replaced by {} of a different type", o.label, n.label))),
+            },
+        }
+    }
+
+    // Gaps may shrink as long as everything after them stays put
+    let mut old_gaps = Vec::new();
+    for o in old.storage.iter().filter(|e| is_gap(e)) {
+        let old_end = slot(o) + old.slots(o);
+        old_gaps.push((slot(o), old_end));
+        match new.storage.iter().find(|n| is_gap(n) && same_contract(old, o, new, n)) {
+            Some(n) if slot(n) + new.slots(n) == old_end => {
+                let used = slot(n).saturating_sub(slot(o));
+                if used > 0 {
+                    issues.push(issue(Severity::Info, "gap_consumed", o, Some(old.describe(o)), Some(new.describe(n)),
+                        format!("{} new slot(s) taken from the {} gap", used, o.contract)));
+                }
+            }
+            Some(n) => issues.push(issue(Severity::Error, "gap_resized", o, Some(old.describe(o)), Some(new.describe(n)),
+                format!("The {} gap now ends at slot {} instead of {}", o.contract, slot(n) + new.slots(n), old_end))),
+            None => issues.push(issue(Severity::Warning, "gap_removed", o, Some(old.describe(o)), None,
+                format!("The {} storage gap was removed", o.contract))),
+        }
+    }
+
+    let old_end = old.storage.iter().map(|e| slot(e) + old.slots(e)).max().unwrap_or(0);
+    for n in new.storage.iter().filter(|e| !is_gap(e)) {
+        if old.storage.iter().any(|o| o.slot == n.slot && o.offset == n.offset && !is_gap(o))
+            || old.storage.iter().any(|o| o.label == n.label && same_contract(old, o, new, n))
+        {
+            continue;
+        }
+        let in_gap = old_gaps.iter().any(|(start, end)| (*start..*end).contains(&slot(n)));
+        if slot(n) >= old_end {
+            issues.push(issue(Severity::Info, "appended", n, None, Some(new.describe(n)),
+                format!("{} appended at slot {}", n.label, n.slot)));
+        } else if !in_gap {
+            issues.push(issue(Severity::Error, "inserted", n, None, Some(new.describe(n)),
+                format!("{} was inserted at slot {}, shifting existing storage", n.label, n.slot)));
+        }
+    }
+
+    issues.sort_by_key(|i| (parse_slot(&i.slot), i.offset));
+    issues
+}
+
+#[tauri::command]
+pub async fn snapshot_storage_layout(project_path: String, contract: String, label: Option<String>) -> Result<LayoutSnapshot, AppError> {
+    let root = Path::new(&project_path);
+    let layout = current_layout(root, &contract)?;
+    let created_at = store::timestamp_millis();
+    let snapshot = LayoutSnapshot {
+        label: label.filter(|l| !l.trim().is_empty()).unwrap_or_else(|| created_at.to_string()),
+        contract,
+        created_at,
+        layout,
+    };
+
+    let mut snapshots = load_snapshots(root)?;
+    snapshots.retain(|s| !(s.contract == snapshot.contract && s.label == snapshot.label));
+    snapshots.push(snapshot.clone());
+    store::save_json(root, SNAPSHOTS_FILE, &snapshots)?;
+    Ok(snapshot)
+}
+
+#[tauri::command]
+pub async fn diff_storage_layout(project_path: String, old: LayoutSource, new_contract: String) -> Result<LayoutDiff, AppError> {
+    let root = Path::new(&project_path);
+    let (baseline, old_layout) = resolve_baseline(root, &old)?;
+    let new_layout = current_layout(root, &new_contract)?;
+
+    let issues = diff(&old_layout, &new_layout);
+    Ok(LayoutDiff {
+        contract: new_contract,
+        baseline,
+        upgrade_safe: !issues.iter().any(|i| i.severity == Severity::Error),
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(entries: &[(&str, &str, u32, &str, &str)]) -> StorageLayout {
+        let mut types = BTreeMap::new();
+        types.insert("t_uint256".to_string(), StorageType { label: "uint256".to_string(), number_of_bytes: "32".to_string() });
+        types.insert("t_address".to_string(), StorageType { label: "address".to_string(), number_of_bytes: "20".to_string() });
+        types.insert("t_array(t_uint256)50_storage".to_string(), StorageType { label: "uint256[50]".to_string(), number_of_bytes: "1600".to_string() });
+        types.insert("t_array(t_uint256)49_storage".to_string(), StorageType { label: "uint256[49]".to_string(), number_of_bytes: "1568".to_string() });
+        StorageLayout {
+            storage: entries
+                .iter()
+                .map(|(contract, label, offset, slot, type_id)| StorageEntry {
+                    label: label.to_string(),
+                    slot: slot.to_string(),
+                    offset: *offset,
+                    type_id: type_id.to_string(),
+                    contract: contract.to_string(),
+                })
+                .collect(),
+            types: Some(types),
+        }
+    }
+
+    fn kinds(issues: &[LayoutIssue]) -> Vec<(&str, Severity)> {
+        issues.iter().map(|i| (i.kind.as_str(), i.severity)).collect()
+    }
+
+    #[test]
+    fn identical_layouts_after_a_contract_rename_have_no_issues() {
+        let old = layout(&[("Box.sol:BoxV1", "value", 0, "0", "t_uint256"), ("Box.sol:BoxV1", "__gap", 0, "1", "t_array(t_uint256)50_storage")]);
+        let new = layout(&[("Box.sol:BoxV2", "value", 0, "0", "t_uint256"), ("Box.sol:BoxV2", "__gap", 0, "1", "t_array(t_uint256)50_storage")]);
+        assert!(diff(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn swapped_variables_are_reordered_even_when_the_contract_was_renamed() {
+        let old = layout(&[("Box.sol:BoxV1", "a", 0, "0", "t_uint256"), ("Box.sol:BoxV1", "b", 0, "1", "t_uint256")]);
+        let new = layout(&[("Box.sol:BoxV2", "b", 0, "0", "t_uint256"), ("Box.sol:BoxV2", "a", 0, "1", "t_uint256")]);
+        let issues = diff(&old, &new);
+        assert_eq!(kinds(&issues), vec![("reordered", Severity::Error), ("reordered", Severity::Error)]);
+    }
+
+    #[test]
+    fn a_rename_in_the_same_slot_is_a_warning() {
+        let old = layout(&[("Box.sol:Box", "owner", 0, "0", "t_address")]);
+        let new = layout(&[("Box.sol:Box", "admin", 0, "0", "t_address")]);
+        assert_eq!(kinds(&diff(&old, &new)), vec![("renamed", Severity::Warning)]);
+    }
+
+    #[test]
+    fn type_changes_and_shifted_variables_are_errors() {
+        let old = layout(&[("Box.sol:Box", "a", 0, "0", "t_uint256"), ("Box.sol:Box", "b", 0, "1", "t_uint256")]);
+        let new = layout(&[("Box.sol:Box", "a", 0, "0", "t_address"), ("Box.sol:Box", "c", 0, "1", "t_uint256"), ("Box.sol:Box", "b", 0, "2", "t_uint256")]);
+        let issues = diff(&old, &new);
+        assert_eq!(kinds(&issues), vec![("type_changed", Severity::Error), ("reordered", Severity::Error)]);
+        assert_eq!(issues[1].new.as_deref(), Some("uint256 b"));
+    }
+
+    #[test]
+    fn variables_taken_from_a_gap_are_safe() {
+        let old = layout(&[("Box.sol:BoxV1", "a", 0, "0", "t_uint256"), ("Box.sol:BoxV1", "__gap", 0, "1", "t_array(t_uint256)50_storage")]);
+        let new = layout(&[
+            ("Box.sol:BoxV2", "a", 0, "0", "t_uint256"),
+            ("Box.sol:BoxV2", "b", 0, "1", "t_uint256"),
+            ("Box.sol:BoxV2", "__gap", 0, "2", "t_array(t_uint256)49_storage"),
+        ]);
+        let issues = diff(&old, &new);
+        assert_eq!(kinds(&issues), vec![("gap_consumed", Severity::Info)]);
+        assert!(!issues.iter().any(|i| i.severity == Severity::Error));
+    }
+
+    #[test]
+    fn appended_variables_are_info() {
+        let old = layout(&[("Box.sol:Box", "a", 0, "0", "t_uint256")]);
+        let new = layout(&[("Box.sol:Box", "a", 0, "0", "t_uint256"), ("Box.sol:Box", "b", 0, "1", "t_uint256")]);
+        assert_eq!(kinds(&diff(&old, &new)), vec![("appended", Severity::Info)]);
+    }
+}
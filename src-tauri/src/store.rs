@@ -0,0 +1,81 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// Per-project data written by the GUI (registry, settings, history) lives here,
+// next to the project's own files so it travels with the project
+pub const STORE_DIR: &str = ".hardhat-gui";
+
+pub fn project_store_dir(project_path: &Path) -> PathBuf {
+    project_path.join(STORE_DIR)
+}
+
+pub fn timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+pub fn load_json<T: DeserializeOwned + Default>(project_path: &Path, name: &str) -> Result<T, String> {
+    let path = project_store_dir(project_path).join(name);
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", name, e))
+}
+
+pub fn save_json<T: Serialize>(project_path: &Path, name: &str, value: &T) -> Result<(), String> {
+    let dir = project_store_dir(project_path);
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {} directory: {}", STORE_DIR, e))?;
+
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+
+    // Write to a temp file first so a crash never leaves a half-written store file
+    let temp = dir.join(format!("{}.tmp", name));
+    fs::write(&temp, content).map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    fs::rename(&temp, dir.join(name)).map_err(|e| format!("Failed to write {}: {}", name, e))
+}
+
+// Append-only logs are stored as one JSON document per line
+pub fn append_jsonl<T: Serialize>(project_path: &Path, name: &str, record: &T) -> Result<(), String> {
+    let dir = project_store_dir(project_path);
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {} directory: {}", STORE_DIR, e))?;
+
+    let line = serde_json::to_string(record)
+        .map_err(|e| format!("Failed to serialize {} entry: {}", name, e))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(name))
+        .map_err(|e| format!("Failed to open {}: {}", name, e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("Failed to write {}: {}", name, e))
+}
+
+pub fn read_jsonl<T: DeserializeOwned>(project_path: &Path, name: &str) -> Result<Vec<T>, String> {
+    let path = project_store_dir(project_path).join(name);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+
+    // A torn last line from a crash shouldn't make the whole log unreadable
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::ansi::{self, StyledSegment};
+use crate::progress::{ProgressReporter, ProgressUpdate};
+
+// Every tracked execution reports on the single `execution-event` channel, in order:
+// started, any number of stdout/stderr/progress updates, then exactly one finished
+pub const EVENT: &str = "execution-event";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FinishStatus {
+    Succeeded,
+    Failed,
+    Cancelled,
+    TimedOut,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExecutionUpdate {
+    Started {
+        project_path: Option<String>,
+    },
+    Stdout {
+        segments: Vec<StyledSegment>,
+    },
+    Stderr {
+        segments: Vec<StyledSegment>,
+    },
+    Progress(ProgressUpdate),
+    Finished {
+        status: FinishStatus,
+        exit_code: Option<i32>,
+        summary: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExecutionEvent {
+    pub execution_id: String,
+    pub kind: String,
+    #[serde(flatten)]
+    pub update: ExecutionUpdate,
+}
+
+pub fn emit(app: &AppHandle, execution_id: &str, kind: &str, update: ExecutionUpdate) {
+    let event = ExecutionEvent {
+        execution_id: execution_id.to_string(),
+        kind: kind.to_string(),
+        update,
+    };
+    let _ = app.emit(EVENT, event);
+}
+
+// Forwards one stream of a child as complete lines, so escape codes and UTF-8
+// sequences are never split between two events
+pub struct LineStream {
+    app: AppHandle,
+    execution_id: String,
+    kind: String,
+    stderr: bool,
+    pending: Vec<u8>,
+    // Fed stdout only; the tools print progress there and warnings on stderr
+    reporter: Option<Box<dyn ProgressReporter>>,
+}
+
+impl LineStream {
+    pub fn stdout(app: &AppHandle, execution_id: &str, kind: &str, reporter: Option<Box<dyn ProgressReporter>>) -> Self {
+        Self {
+            app: app.clone(),
+            execution_id: execution_id.to_string(),
+            kind: kind.to_string(),
+            stderr: false,
+            pending: Vec::new(),
+            reporter,
+        }
+    }
+
+    pub fn stderr(app: &AppHandle, execution_id: &str, kind: &str) -> Self {
+        Self { stderr: true, ..Self::stdout(app, execution_id, kind, None) }
+    }
+
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.pending.extend_from_slice(chunk);
+        let Some(end) = self.pending.iter().rposition(|b| *b == b'\n') else {
+            return;
+        };
+        let lines: Vec<u8> = self.pending.drain(..=end).collect();
+        self.send(&lines);
+    }
+
+    pub fn flush(&mut self) {
+        if !self.pending.is_empty() {
+            let rest = std::mem::take(&mut self.pending);
+            self.send(&rest);
+        }
+    }
+
+    fn send(&mut self, bytes: &[u8]) {
+        let text = String::from_utf8_lossy(bytes);
+        let segments = ansi::parse(&text);
+        let update = if self.stderr {
+            ExecutionUpdate::Stderr { segments }
+        } else {
+            ExecutionUpdate::Stdout { segments }
+        };
+        emit(&self.app, &self.execution_id, &self.kind, update);
+
+        let Some(reporter) = self.reporter.as_mut() else {
+            return;
+        };
+        for line in ansi::strip(&text).lines() {
+            if let Some(progress) = reporter.line(line.trim()) {
+                emit(&self.app, &self.execution_id, &self.kind, ExecutionUpdate::Progress(progress));
+            }
+        }
+    }
+}
+
+fn find_line(text: &str, predicate: impl Fn(&str) -> bool) -> Option<&str> {
+    text.lines().map(str::trim).find(|line| predicate(line))
+}
+
+fn last_line(text: &str) -> Option<&str> {
+    text.lines().map(str::trim).rfind(|line| !line.is_empty())
+}
+
+// One line for the finished event, from the plain-text output
+pub fn summary(kind: &str, success: bool, stdout: &str, stderr: &str) -> String {
+    let found = match kind {
+        "compile" => find_line(stdout, |line| line.starts_with("Compiled ") || line.starts_with("Nothing to compile")),
+        "test" | "coverage" => {
+            let counts: Vec<&str> = stdout
+                .lines()
+                .map(str::trim)
+                .filter(|line| {
+                    let mut words = line.split_whitespace();
+                    words.next().is_some_and(|count| count.parse::<u64>().is_ok())
+                        && words.next().is_some_and(|word| ["passing", "failing", "pending"].contains(&word))
+                })
+                .collect();
+            if !counts.is_empty() {
+                return counts.join(", ");
+            }
+            None
+        }
+        "deploy" => {
+            let deployed = stdout.lines().filter(|line| line.contains(" - 0x")).count();
+            if success && deployed > 0 {
+                return format!("Deployed {} contract{}", deployed, if deployed == 1 { "" } else { "s" });
+            }
+            None
+        }
+        "install" => find_line(stdout, |line| line.starts_with("added ") || line.starts_with("up to date")),
+        _ => None,
+    };
+    let fallback = if success { last_line(stdout) } else { last_line(stderr).or_else(|| last_line(stdout)) };
+    found.or(fallback).unwrap_or_default().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_known_commands() {
+        assert_eq!(
+            summary("compile", true, "Compiling 2 files\nCompiled 2 Solidity files successfully\n", ""),
+            "Compiled 2 Solidity files successfully"
+        );
+        assert_eq!(summary("test", false, "  ✔ mints\n  3 passing (1s)\n  1 failing\n", "boom"), "3 passing (1s), 1 failing");
+        assert_eq!(
+            summary("deploy", true, "Deployed Addresses\n\nM#A - 0x5FbDB2315678afecb367f032d93F642f64180aa3\nM#B - 0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512\n", ""),
+            "Deployed 2 contracts"
+        );
+        assert_eq!(summary("install", true, "added 12 packages in 3s\n\nfound 0 vulnerabilities\n", ""), "added 12 packages in 3s");
+    }
+
+    #[test]
+    fn falls_back_to_the_last_line() {
+        assert_eq!(summary("task", true, "first\nlast\n\n", "ignored"), "last");
+        assert_eq!(summary("task", false, "stdout\n", "Error: boom\n"), "Error: boom");
+        assert_eq!(summary("task", false, "stdout only\n", ""), "stdout only");
+        assert_eq!(summary("deploy", false, "M#A - 0x5FbDB2315678afecb367f032d93F642f64180aa3\n", ""), "M#A - 0x5FbDB2315678afecb367f032d93F642f64180aa3");
+        assert_eq!(summary("compile", true, "", ""), "");
+    }
+}
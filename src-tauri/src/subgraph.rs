@@ -0,0 +1,268 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+use crate::error::AppError;
+use crate::processes;
+use crate::resolver;
+use crate::rpc;
+use crate::store;
+
+const SUBGRAPH_DIR: &str = "subgraph";
+const COMPOSE_FILE: &str = "graph-node/docker-compose.yml";
+const SUBGRAPH_FILE: &str = "subgraph.json";
+const COMPOSE_PROJECT: &str = "hardhat-gui-graph";
+const ADMIN_URL: &str = "http://127.0.0.1:8020/";
+const STATUS_URL: &str = "http://127.0.0.1:8030/graphql";
+const QUERY_URL: &str = "http://127.0.0.1:8000/subgraphs/name";
+// Published away from 5001 so it doesn't clash with a local IPFS daemon
+const IPFS_URL: &str = "http://127.0.0.1:15001";
+
+// graph-node reaches the node through the Docker host. On Linux that only works when the
+// node listens beyond loopback (`--hostname 0.0.0.0`)
+const COMPOSE_TEMPLATE: &str = r#"services:
+  graph-node:
+    image: graphprotocol/graph-node
+    ports:
+      - "8000:8000"
+      - "8020:8020"
+      - "8030:8030"
+    depends_on:
+      - ipfs
+      - postgres
+    extra_hosts:
+      - "host.docker.internal:host-gateway"
+    environment:
+      postgres_host: postgres
+      postgres_user: graph-node
+      postgres_pass: let-me-in
+      postgres_db: graph-node
+      ipfs: "ipfs:5001"
+      ethereum: "{network}:{rpc_url}"
+      GRAPH_LOG: info
+  ipfs:
+    image: ipfs/kubo:v0.28.0
+    ports:
+      - "15001:5001"
+  postgres:
+    image: postgres:14
+    command: ["postgres", "-cshared_preload_libraries=pg_stat_statements"]
+    environment:
+      POSTGRES_USER: graph-node
+      POSTGRES_PASSWORD: let-me-in
+      POSTGRES_DB: graph-node
+      POSTGRES_INITDB_ARGS: "-E UTF8 --locale=C"
+"#;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SubgraphDeployment {
+    pub name: String,
+    pub deployment_id: Option<String>,
+    pub version_label: String,
+    pub deployed_at: u64,
+    pub query_url: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SubgraphHealth {
+    pub graph_node_running: bool,
+    pub deployment: Option<SubgraphDeployment>,
+    pub synced: Option<bool>,
+    // "healthy", "unhealthy" or "failed"
+    pub health: Option<String>,
+    pub latest_block: Option<u64>,
+    pub chain_head_block: Option<u64>,
+    pub fatal_error: Option<String>,
+}
+
+fn subgraph_dir(project_path: &Path) -> Result<PathBuf, String> {
+    let dir = project_path.join(SUBGRAPH_DIR);
+    if !dir.join("subgraph.yaml").is_file() {
+        return Err("No subgraph/subgraph.yaml found in the project".to_string());
+    }
+    Ok(dir)
+}
+
+pub fn has_subgraph(project_path: &Path) -> bool {
+    project_path.join(SUBGRAPH_DIR).join("subgraph.yaml").is_file()
+}
+
+// The manifest's `network:` is the name graph-node must know the chain by
+fn manifest_network(dir: &Path) -> String {
+    let manifest = fs::read_to_string(dir.join("subgraph.yaml")).unwrap_or_default();
+    Regex::new(r"(?m)^\s*network:\s*([\w-]+)")
+        .unwrap()
+        .captures(&manifest)
+        .map(|c| c[1].to_string())
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+// "user/name" as graph-node expects, from the subgraph's package.json or the project name
+fn default_name(project_path: &Path, dir: &Path) -> String {
+    let package = fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|package| package.get("name").and_then(Value::as_str).map(str::to_string));
+    let name = package.unwrap_or_else(|| {
+        project_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "subgraph".to_string())
+    });
+    let name: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '-' }).collect();
+    format!("hardhat-gui/{}", name.trim_matches('-'))
+}
+
+// Uses the subgraph's own graph-cli when installed, otherwise fetches it
+fn graph(dir: &Path) -> Command {
+    let mut cmd = resolver::npx();
+    if !dir.join("node_modules/.bin/graph").exists() {
+        cmd.args(["--yes", "--package", "@graphprotocol/graph-cli"]);
+    }
+    cmd.arg("graph").current_dir(dir);
+    cmd
+}
+
+async fn run(app: &AppHandle, execution_id: Option<String>, kind: &str, cmd: Command, step: &str) -> Result<String, AppError> {
+    let output = processes::run_tracked(app, execution_id, kind, cmd).await?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to {}: {}{}", step, stdout, stderr).into());
+    }
+    Ok(stdout)
+}
+
+fn compose(project_path: &Path) -> Command {
+    let mut cmd = resolver::command("docker");
+    cmd.args(["compose", "-p", COMPOSE_PROJECT, "-f"])
+        .arg(store::project_store_dir(project_path).join(COMPOSE_FILE));
+    cmd
+}
+
+// graph-node, IPFS and Postgres in Docker, indexing the GUI's local node
+#[tauri::command]
+pub async fn start_graph_node(app: AppHandle, project_path: String, execution_id: Option<String>) -> Result<String, AppError> {
+    let root = Path::new(&project_path);
+    let dir = subgraph_dir(root)?;
+    if resolver::locate("docker").is_none() {
+        return Err("Docker is required to run graph-node".to_string().into());
+    }
+    let port = rpc::default_url().rsplit(':').next().and_then(|p| p.trim_end_matches('/').parse::<u16>().ok());
+    let rpc_url = format!("http://host.docker.internal:{}", port.unwrap_or(8545));
+    let compose_file = store::project_store_dir(root).join(COMPOSE_FILE);
+    if let Some(parent) = compose_file.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let content = COMPOSE_TEMPLATE.replace("{network}", &manifest_network(&dir)).replace("{rpc_url}", &rpc_url);
+    fs::write(&compose_file, content).map_err(|e| format!("Failed to write {}: {}", compose_file.display(), e))?;
+
+    let mut cmd = compose(root);
+    cmd.args(["up", "-d"]).current_dir(root);
+    run(&app, execution_id, "graph-node", cmd, "start graph-node").await?;
+    Ok(format!("graph-node started; queries at {}", QUERY_URL))
+}
+
+#[tauri::command]
+pub async fn stop_graph_node(app: AppHandle, project_path: String) -> Result<String, AppError> {
+    let root = Path::new(&project_path);
+    let mut cmd = compose(root);
+    cmd.arg("down").current_dir(root);
+    run(&app, None, "graph-node", cmd, "stop graph-node").await?;
+    Ok("graph-node stopped".to_string())
+}
+
+// `graph codegen` then `graph build`
+#[tauri::command]
+pub async fn build_subgraph(app: AppHandle, project_path: String, execution_id: Option<String>) -> Result<String, AppError> {
+    let dir = subgraph_dir(Path::new(&project_path))?;
+    let mut codegen = graph(&dir);
+    codegen.arg("codegen");
+    let mut output = run(&app, execution_id, "subgraph", codegen, "generate subgraph types").await?;
+    let mut build = graph(&dir);
+    build.arg("build");
+    output.push_str(&run(&app, None, "subgraph", build, "build subgraph").await?);
+    Ok(output)
+}
+
+#[tauri::command]
+pub async fn deploy_subgraph(
+    app: AppHandle,
+    project_path: String,
+    name: Option<String>,
+    version_label: Option<String>,
+    execution_id: Option<String>,
+) -> Result<SubgraphDeployment, AppError> {
+    let root = Path::new(&project_path);
+    let dir = subgraph_dir(root)?;
+    let name = name.unwrap_or_else(|| default_name(root, &dir));
+    let version_label = version_label.unwrap_or_else(|| format!("v0.0.{}", store::timestamp_millis() / 1000));
+
+    // Creating an existing name fails harmlessly; deploy then adds a version
+    let mut create = graph(&dir);
+    create.args(["create", "--node", ADMIN_URL, &name]);
+    let _ = run(&app, None, "subgraph", create, "create subgraph").await;
+
+    let mut deploy = graph(&dir);
+    deploy.args(["deploy", "--node", ADMIN_URL, "--ipfs", IPFS_URL, "--version-label", &version_label, &name]);
+    let output = run(&app, execution_id, "subgraph", deploy, "deploy subgraph").await?;
+
+    let deployment = SubgraphDeployment {
+        deployment_id: Regex::new(r"\b(Qm[1-9A-HJ-NP-Za-km-z]{44})\b")
+            .unwrap()
+            .captures(&output)
+            .map(|c| c[1].to_string()),
+        query_url: format!("{}/{}", QUERY_URL, name),
+        name,
+        version_label,
+        deployed_at: store::timestamp_millis(),
+    };
+    store::save_json(root, SUBGRAPH_FILE, &deployment)?;
+    Ok(deployment)
+}
+
+pub async fn health(project_path: &Path) -> SubgraphHealth {
+    let deployment = store::load_json::<Option<SubgraphDeployment>>(project_path, SUBGRAPH_FILE).ok().flatten();
+    let mut health = SubgraphHealth {
+        graph_node_running: false,
+        deployment: deployment.clone(),
+        synced: None,
+        health: None,
+        latest_block: None,
+        chain_head_block: None,
+        fatal_error: None,
+    };
+    let query = "query($name: String!) { indexingStatusForCurrentVersion(subgraphName: $name) { synced health fatalError { message } chains { chainHeadBlock { number } latestBlock { number } } } }";
+    let name = deployment.map(|d| d.name).unwrap_or_default();
+    let response = rpc::client()
+        .post(STATUS_URL)
+        .json(&json!({ "query": query, "variables": { "name": name } }))
+        .send()
+        .await;
+    let Ok(response) = response else {
+        return health;
+    };
+    health.graph_node_running = true;
+    let Ok(body) = response.json::<Value>().await else {
+        return health;
+    };
+    let Some(status) = body.pointer("/data/indexingStatusForCurrentVersion").filter(|s| !s.is_null()) else {
+        return health;
+    };
+    let block = |pointer: &str| {
+        status.pointer(pointer).and_then(|n| n.as_str().and_then(|s| s.parse().ok()).or_else(|| n.as_u64()))
+    };
+    health.synced = status.get("synced").and_then(Value::as_bool);
+    health.health = status.get("health").and_then(Value::as_str).map(str::to_string);
+    health.latest_block = block("/chains/0/latestBlock/number");
+    health.chain_head_block = block("/chains/0/chainHeadBlock/number");
+    health.fatal_error = status.pointer("/fatalError/message").and_then(Value::as_str).map(str::to_string);
+    health
+}
+
+#[tauri::command]
+pub async fn get_subgraph_status(project_path: String) -> Result<SubgraphHealth, AppError> {
+    Ok(health(Path::new(&project_path)).await)
+}
@@ -0,0 +1,711 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+
+use crate::error::AppError;
+use crate::gas_oracle;
+use crate::messages::Message;
+use crate::networks;
+use crate::processes;
+use crate::project;
+use crate::resolver;
+use crate::store;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TaskParam {
+    pub name: String,
+    pub description: Option<String>,
+    pub param_type: String,
+    pub default_value: Option<Value>,
+    pub optional: bool,
+    pub flag: bool,
+    pub variadic: bool,
+    pub positional: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HardhatTask {
+    pub name: String,
+    pub scope: Option<String>,
+    pub description: Option<String>,
+    pub subtask: bool,
+    pub params: Vec<TaskParam>,
+}
+
+const OUTPUT_MARKER: &str = "__HARDHAT_GUI_TASKS__";
+
+// Reads the task registry straight from the runtime environment, which is far more
+// reliable than scraping `--help` output and covers tasks added by plugins
+const TASKS_SHIM: &str = r#"
+const hre = require("hardhat");
+
+function describeParam(param, positional) {
+  let defaultValue = param.defaultValue;
+  if (typeof defaultValue === "bigint") {
+    defaultValue = defaultValue.toString();
+  }
+  return {
+    name: param.name,
+    description: param.description || null,
+    param_type: param.type ? param.type.name : "string",
+    default_value: defaultValue === undefined ? null : defaultValue,
+    optional: !!param.isOptional,
+    flag: !!param.isFlag,
+    variadic: !!param.isVariadic,
+    positional,
+  };
+}
+
+function describeTask(task, scope) {
+  const params = Object.values(task.paramDefinitions || {}).map((p) => describeParam(p, false));
+  for (const p of task.positionalParamDefinitions || []) {
+    params.push(describeParam(p, true));
+  }
+  return {
+    name: task.name,
+    scope,
+    description: task.description || null,
+    subtask: !!task.isSubtask,
+    params,
+  };
+}
+
+const tasks = Object.values(hre.tasks).map((task) => describeTask(task, null));
+for (const [scopeName, scope] of Object.entries(hre.scopes || {})) {
+  for (const task of Object.values(scope.tasks || {})) {
+    tasks.push(describeTask(task, scopeName));
+  }
+}
+
+console.log("__HARDHAT_GUI_TASKS__" + JSON.stringify(tasks));
+"#;
+
+fn list_failed(error: impl ToString) -> Message {
+    Message::new("task.list_failed", json!({ "error": error.to_string() }))
+}
+
+async fn read_task_registry(project_path: &str) -> Result<Vec<HardhatTask>, Message> {
+    let store_dir = store::project_store_dir(Path::new(project_path));
+    fs::create_dir_all(&store_dir)
+        .map_err(|e| list_failed(format!("Failed to create {} directory: {}", store::STORE_DIR, e)))?;
+
+    // .cjs keeps the shim working in projects with "type": "module"
+    let shim_path = store_dir.join("list-tasks.cjs");
+    fs::write(&shim_path, TASKS_SHIM)
+        .map_err(|e| list_failed(format!("Failed to create task listing script: {}", e)))?;
+
+    let mut command = resolver::hardhat(Path::new(project_path));
+    command.args(["run", "--no-compile", &shim_path.to_string_lossy()]);
+    let output = processes::output(command).await.map_err(list_failed);
+
+    let _ = fs::remove_file(&shim_path);
+    let output = output?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let Some(json) = stdout.lines().find_map(|line| line.strip_prefix(OUTPUT_MARKER)) else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(list_failed(format!("{}\n{}", stderr, stdout)));
+    };
+
+    let mut tasks: Vec<HardhatTask> = serde_json::from_str(json)
+        .map_err(|e| list_failed(format!("Failed to parse task list: {}", e)))?;
+    tasks.sort_by(|a, b| (&a.scope, &a.name).cmp(&(&b.scope, &b.name)));
+
+    Ok(tasks)
+}
+
+// Tasks can only change when the config, a tasks/ file or the installed plugins change
+fn registry_fingerprint(project_path: &Path) -> Option<SystemTime> {
+    let mut files = project::collect_files(&project_path.join("tasks"), "js");
+    files.extend(project::collect_files(&project_path.join("tasks"), "ts"));
+    files.extend(project::find_hardhat_config(project_path));
+    files.push(project_path.join("package.json"));
+    files
+        .iter()
+        .filter_map(|f| fs::metadata(f).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+// Loading the registry means booting hardhat, so results are cached per project
+struct CachedTasks {
+    fingerprint: Option<SystemTime>,
+    tasks: Vec<HardhatTask>,
+}
+
+#[derive(Default)]
+pub struct TaskRegistryCache(Mutex<HashMap<String, CachedTasks>>);
+
+pub async fn load_tasks(cache: &TaskRegistryCache, project_path: &str) -> Result<Vec<HardhatTask>, Message> {
+    let fingerprint = registry_fingerprint(Path::new(project_path));
+    if let Some(cached) = cache.0.lock().unwrap().get(project_path) {
+        if cached.fingerprint == fingerprint {
+            return Ok(cached.tasks.clone());
+        }
+    }
+
+    let tasks = read_task_registry(project_path).await?;
+
+    cache
+        .0
+        .lock()
+        .unwrap()
+        .insert(project_path.to_string(), CachedTasks { fingerprint, tasks: tasks.clone() });
+    Ok(tasks)
+}
+
+#[tauri::command]
+pub async fn list_hardhat_tasks(
+    cache: State<'_, TaskRegistryCache>,
+    project_path: String,
+    include_subtasks: Option<bool>,
+) -> Result<Vec<HardhatTask>, AppError> {
+    let mut tasks = load_tasks(&cache, &project_path).await?;
+    if !include_subtasks.unwrap_or(false) {
+        tasks.retain(|task| !task.subtask);
+    }
+    Ok(tasks)
+}
+
+fn to_kebab_case(name: &str) -> String {
+    let mut kebab = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            kebab.push('-');
+            kebab.push(c.to_ascii_lowercase());
+        } else {
+            kebab.push(c);
+        }
+    }
+    kebab
+}
+
+// Converts a JSON value to the CLI string hardhat will parse for the given param type
+// At most one leading minus sign, then digits only
+fn is_integer(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+fn render_arg(param: &TaskParam, value: &Value) -> Result<String, Message> {
+    let invalid = || {
+        Message::new(
+            "task.invalid_value",
+            json!({ "param": param.name, "param_type": param.param_type, "value": value.to_string() }),
+        )
+    };
+
+    match (param.param_type.as_str(), value) {
+        ("int" | "bigint", Value::Number(n)) if n.is_i64() || n.is_u64() => Ok(n.to_string()),
+        ("int" | "bigint", Value::String(s)) if is_integer(s) => Ok(s.clone()),
+        ("float", Value::Number(n)) => Ok(n.to_string()),
+        ("float", Value::String(s)) if s.parse::<f64>().is_ok() => Ok(s.clone()),
+        ("boolean", Value::Bool(b)) => Ok(b.to_string()),
+        ("json", value) => Ok(value.to_string()),
+        ("int" | "bigint" | "float" | "boolean", _) => Err(invalid()),
+        (_, Value::String(s)) => Ok(s.clone()),
+        (_, Value::Number(n)) => Ok(n.to_string()),
+        (_, Value::Bool(b)) => Ok(b.to_string()),
+        _ => Err(invalid()),
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TaskInvocation {
+    pub task: String,
+    pub scope: Option<String>,
+    pub network: Option<String>,
+    // Keyed by the param name as declared in the task (camelCase); flags take booleans
+    // and variadic positionals take arrays
+    #[serde(default)]
+    pub args: BTreeMap<String, Value>,
+    // Extra environment for this run only, e.g. REPORT_GAS=true
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+pub fn build_task_args(task: &HardhatTask, invocation: &TaskInvocation) -> Result<Vec<String>, Message> {
+    let mut cli = Vec::new();
+    if let Some(network) = &invocation.network {
+        cli.push("--network".to_string());
+        cli.push(network.clone());
+    }
+    if let Some(scope) = &task.scope {
+        cli.push(scope.clone());
+    }
+    cli.push(task.name.clone());
+
+    if let Some(unknown) = invocation
+        .args
+        .keys()
+        .find(|key| !task.params.iter().any(|p| &p.name == *key))
+    {
+        return Err(Message::new("task.unknown_param", json!({ "task": task.name, "param": unknown })));
+    }
+
+    for param in task.params.iter().filter(|p| !p.positional) {
+        let value = invocation.args.get(&param.name).filter(|v| !v.is_null());
+        let flag_name = format!("--{}", to_kebab_case(&param.name));
+
+        if param.flag {
+            match value {
+                Some(Value::Bool(true)) => cli.push(flag_name),
+                Some(Value::Bool(false)) | None => {}
+                Some(other) => {
+                    return Err(Message::new(
+                        "task.flag_value",
+                        json!({ "param": param.name, "value": other.to_string() }),
+                    ))
+                }
+            }
+            continue;
+        }
+
+        match value {
+            Some(value) => {
+                cli.push(flag_name);
+                cli.push(render_arg(param, value)?);
+            }
+            None if !param.optional => return Err(Message::new("task.missing_param", json!({ "param": param.name }))),
+            None => {}
+        }
+    }
+
+    // Positionals are order-sensitive: once one is omitted, later ones can't be given
+    let mut omitted: Option<&str> = None;
+    for param in task.params.iter().filter(|p| p.positional) {
+        let Some(value) = invocation.args.get(&param.name).filter(|v| !v.is_null()) else {
+            if !param.optional {
+                return Err(Message::new("task.missing_argument", json!({ "param": param.name })));
+            }
+            omitted.get_or_insert(&param.name);
+            continue;
+        };
+
+        if let Some(previous) = omitted {
+            return Err(Message::new("task.argument_gap", json!({ "param": param.name, "previous": previous })));
+        }
+
+        match (param.variadic, value) {
+            (true, Value::Array(items)) => {
+                for item in items {
+                    cli.push(render_arg(param, item)?);
+                }
+            }
+            _ => cli.push(render_arg(param, value)?),
+        }
+    }
+
+    Ok(cli)
+}
+
+#[tauri::command]
+pub async fn run_hardhat_task_with_args(
+    app: AppHandle,
+    cache: State<'_, TaskRegistryCache>,
+    project_path: String,
+    invocation: TaskInvocation,
+    execution_id: Option<String>,
+) -> Result<Message, AppError> {
+    let tasks = load_tasks(&cache, &project_path).await?;
+    let task = tasks
+        .iter()
+        .find(|t| t.name == invocation.task && t.scope == invocation.scope)
+        .ok_or_else(|| Message::new("task.unknown", json!({ "task": invocation.task })))?;
+
+    let args = build_task_args(task, &invocation)?;
+
+    let mut cmd = resolver::hardhat(Path::new(&project_path));
+    cmd.args(&args);
+    if let Some(network) = &invocation.network {
+        gas_oracle::prefetch(network).await;
+        cmd.envs(networks::profile_env(network));
+    }
+    processes::apply_env(&mut cmd, &invocation.env)?;
+    let kind = if invocation.task == "verify" { "verify" } else { "task" };
+    let output = processes::run_tracked(&app, execution_id, kind, cmd).await?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if output.status.success() {
+        Ok(Message::new("task.succeeded", json!({ "task": invocation.task, "output": stdout })))
+    } else {
+        Err(Message::new("task.failed", json!({ "stderr": stderr, "stdout": stdout })).into())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NewParamKind {
+    Param,
+    OptionalParam,
+    Flag,
+    Positional,
+    OptionalPositional,
+    Variadic,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NewTaskParam {
+    pub name: String,
+    pub description: Option<String>,
+    pub kind: NewParamKind,
+    // One of Hardhat's argument types: string, int, bigint, float, boolean, json, inputFile
+    pub param_type: Option<String>,
+    pub default_value: Option<serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NewTaskDefinition {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub params: Vec<NewTaskParam>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreatedTask {
+    pub file_path: String,
+    pub config_path: String,
+    pub config_updated: bool,
+}
+
+const PARAM_TYPES: [&str; 7] = ["string", "int", "bigint", "float", "boolean", "json", "inputFile"];
+
+fn js_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+fn render_param(param: &NewTaskParam) -> Result<String, Message> {
+    let valid_name = param.name.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+        && param.name.chars().all(|c| c.is_ascii_alphanumeric());
+    if !valid_name {
+        return Err(Message::new("task.invalid_param_name", json!({ "param": param.name })));
+    }
+
+    let description = js_string(param.description.as_deref().unwrap_or(""));
+    let name = js_string(&param.name);
+
+    if param.kind == NewParamKind::Flag {
+        return Ok(format!("  .addFlag({}, {})", name, description));
+    }
+
+    let param_type = param.param_type.as_deref().unwrap_or("string");
+    if !PARAM_TYPES.contains(&param_type) {
+        return Err(Message::new("task.unknown_param_type", json!({ "param_type": param_type, "param": param.name })));
+    }
+    let default_value = match &param.default_value {
+        Some(value) => value.to_string(),
+        None => "undefined".to_string(),
+    };
+
+    let method = match param.kind {
+        NewParamKind::Param => "addParam",
+        NewParamKind::OptionalParam => "addOptionalParam",
+        NewParamKind::Positional => "addPositionalParam",
+        NewParamKind::OptionalPositional => "addOptionalPositionalParam",
+        NewParamKind::Variadic => "addOptionalVariadicPositionalParam",
+        NewParamKind::Flag => unreachable!(),
+    };
+
+    Ok(format!(
+        "  .{}({}, {}, {}, types.{})",
+        method, name, description, default_value, param_type
+    ))
+}
+
+#[tauri::command]
+pub async fn create_hardhat_task(
+    project_path: String,
+    definition: NewTaskDefinition,
+) -> Result<CreatedTask, AppError> {
+    let root = Path::new(&project_path);
+    let config_path = project::find_hardhat_config(root).ok_or_else(|| Message::new("task.no_config", json!({})))?;
+
+    let valid_name = definition.name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && definition
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ':');
+    if !valid_name {
+        return Err(Message::new("task.invalid_name", json!({ "name": definition.name })).into());
+    }
+
+    // The task file follows the config's module flavour. An .mjs config, or a .js one in a
+    // "type": "module" package, is ESM and can't `require`
+    let extension = config_path.extension().and_then(|e| e.to_str()).unwrap_or("js");
+    let typescript = extension == "ts";
+    let esm = extension == "mjs"
+        || (extension == "js"
+            && project::read_package_json(root)
+                .ok()
+                .flatten()
+                .is_some_and(|json| json.get("type").and_then(|kind| kind.as_str()) == Some("module")));
+
+    let file_stem = definition.name.replace(':', "-");
+    let tasks_dir = root.join("tasks");
+    let task_file = tasks_dir.join(format!("{}.{}", file_stem, extension));
+    if task_file.exists() {
+        return Err(Message::new("task.file_exists", json!({ "path": task_file.display().to_string() })).into());
+    }
+
+    let params = definition
+        .params
+        .iter()
+        .map(render_param)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let header = if typescript || esm {
+        "import { task, types } from \"hardhat/config\";"
+    } else {
+        "const { task, types } = require(\"hardhat/config\");"
+    };
+    let action_args = if typescript { "taskArgs: any, hre" } else { "taskArgs, hre" };
+
+    let mut content = format!(
+        "{}\n\ntask({}, {})\n",
+        header,
+        js_string(&definition.name),
+        js_string(definition.description.as_deref().unwrap_or(""))
+    );
+    for param in &params {
+        content.push_str(param);
+        content.push('\n');
+    }
+    content.push_str(&format!(
+        "  .setAction(async ({}) => {{\n    console.log(\"Running {} with\", taskArgs);\n  }});\n",
+        action_args,
+        definition.name.replace('"', "")
+    ));
+
+    let io_failed = |key: &str, path: &Path, error: std::io::Error| {
+        Message::new(key, json!({ "path": path.display().to_string(), "error": error.to_string() }))
+    };
+    fs::create_dir_all(&tasks_dir).map_err(|e| io_failed("task.write_failed", &tasks_dir, e))?;
+    fs::write(&task_file, content).map_err(|e| io_failed("task.write_failed", &task_file, e))?;
+
+    let config = fs::read_to_string(&config_path).map_err(|e| io_failed("task.read_failed", &config_path, e))?;
+    let import_path = if typescript {
+        format!("./tasks/{}", file_stem)
+    } else {
+        format!("./tasks/{}.{}", file_stem, extension)
+    };
+    let import_line = if typescript || esm {
+        format!("import \"{}\";", import_path)
+    } else {
+        format!("require(\"{}\");", import_path)
+    };
+
+    let config_updated = !config.contains(&import_path);
+    if config_updated {
+        fs::write(&config_path, insert_import(&config, &import_line))
+            .map_err(|e| io_failed("task.write_failed", &config_path, e))?;
+    }
+
+    Ok(CreatedTask {
+        file_path: task_file.to_string_lossy().to_string(),
+        config_path: config_path.to_string_lossy().to_string(),
+        config_updated,
+    })
+}
+
+// Whether an import/require statement is complete by the end of this line
+fn ends_statement(line: &str) -> bool {
+    line.ends_with(';')
+        || line.contains("from \"")
+        || line.contains("from '")
+        || line.starts_with("import \"")
+        || line.starts_with("import '")
+        || line.ends_with(')')
+}
+
+// Places the new import right after the config's existing top-level imports/requires.
+// Statements can span lines (`import {\n  a,\n} from "x";`), so it goes after the line
+// that ends the last one
+fn insert_import(config: &str, import_line: &str) -> String {
+    let lines: Vec<&str> = config.lines().collect();
+    let mut insert_at = 0;
+    let mut in_statement = false;
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        let starts = (line.starts_with("import ") && !line.starts_with("import type")) || line.starts_with("require(");
+        if in_statement || starts {
+            in_statement = !ends_statement(line);
+            if !in_statement {
+                insert_at = i + 1;
+            }
+        }
+    }
+
+    let mut result: Vec<&str> = lines[..insert_at].to_vec();
+    result.push(import_line);
+    result.extend_from_slice(&lines[insert_at..]);
+
+    let mut updated = result.join("\n");
+    if config.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_import_goes_after_the_last_require() {
+        let config = "require(\"@nomicfoundation/hardhat-toolbox\");\nrequire(\"dotenv\").config();\n\nmodule.exports = {};\n";
+        assert_eq!(
+            insert_import(config, "require(\"./tasks/a.js\");"),
+            "require(\"@nomicfoundation/hardhat-toolbox\");\nrequire(\"dotenv\").config();\nrequire(\"./tasks/a.js\");\n\nmodule.exports = {};\n"
+        );
+    }
+
+    #[test]
+    fn insert_import_skips_past_multi_line_imports() {
+        let config = "import { HardhatUserConfig } from \"hardhat/config\";\nimport {\n  a,\n  b\n} from \"./lib\";\n\nexport default {};\n";
+        assert_eq!(
+            insert_import(config, "import \"./tasks/a\";"),
+            "import { HardhatUserConfig } from \"hardhat/config\";\nimport {\n  a,\n  b\n} from \"./lib\";\nimport \"./tasks/a\";\n\nexport default {};\n"
+        );
+    }
+
+    #[test]
+    fn insert_import_handles_imports_without_semicolons() {
+        let config = "import \"@nomicfoundation/hardhat-toolbox\"\nimport {\n  task\n} from 'hardhat/config'\nexport default {}";
+        assert_eq!(
+            insert_import(config, "import \"./tasks/a.mjs\";"),
+            "import \"@nomicfoundation/hardhat-toolbox\"\nimport {\n  task\n} from 'hardhat/config'\nimport \"./tasks/a.mjs\";\nexport default {}"
+        );
+    }
+
+    #[test]
+    fn insert_import_ignores_type_imports() {
+        let config = "import \"@nomicfoundation/hardhat-toolbox\";\nimport type {\n  HardhatUserConfig\n} from \"hardhat/config\";\n";
+        assert_eq!(
+            insert_import(config, "import \"./tasks/a\";"),
+            "import \"@nomicfoundation/hardhat-toolbox\";\nimport \"./tasks/a\";\nimport type {\n  HardhatUserConfig\n} from \"hardhat/config\";\n"
+        );
+    }
+
+    #[test]
+    fn insert_import_goes_first_without_imports() {
+        assert_eq!(insert_import("module.exports = {};\n", "require(\"./tasks/a.js\");"), "require(\"./tasks/a.js\");\nmodule.exports = {};\n");
+    }
+
+    fn param(name: &str, param_type: &str) -> TaskParam {
+        TaskParam {
+            name: name.to_string(),
+            description: None,
+            param_type: param_type.to_string(),
+            default_value: None,
+            optional: false,
+            flag: false,
+            variadic: false,
+            positional: false,
+        }
+    }
+
+    fn task(params: Vec<TaskParam>) -> HardhatTask {
+        HardhatTask { name: "mint".to_string(), scope: None, description: None, subtask: false, params }
+    }
+
+    fn invocation(args: Value) -> TaskInvocation {
+        TaskInvocation {
+            task: "mint".to_string(),
+            scope: None,
+            network: Some("sepolia".to_string()),
+            args: serde_json::from_value(args).unwrap(),
+            env: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn build_task_args_orders_options_before_positionals() {
+        let task = task(vec![
+            TaskParam { variadic: true, positional: true, optional: true, ..param("recipients", "string") },
+            param("tokenId", "int"),
+            TaskParam { flag: true, ..param("dryRun", "boolean") },
+            TaskParam { optional: true, ..param("gasLimit", "int") },
+        ]);
+        let args = build_task_args(
+            &task,
+            &invocation(serde_json::json!({ "tokenId": "7", "dryRun": true, "recipients": ["0xa", "0xb"] })),
+        )
+        .unwrap();
+        assert_eq!(args, ["--network", "sepolia", "mint", "--token-id", "7", "--dry-run", "0xa", "0xb"]);
+    }
+
+    #[test]
+    fn build_task_args_rejects_bad_input() {
+        let task = task(vec![
+            param("tokenId", "int"),
+            TaskParam { flag: true, optional: true, ..param("dryRun", "boolean") },
+        ]);
+        let error = |args| build_task_args(&task, &invocation(args)).unwrap_err().text;
+        assert_eq!(error(serde_json::json!({})), "Missing required parameter: tokenId");
+        assert_eq!(error(serde_json::json!({ "tokenId": 1, "amount": 2 })), "Task mint has no parameter named amount");
+        assert_eq!(error(serde_json::json!({ "tokenId": 1, "dryRun": "yes" })), "Flag dryRun expects true or false, got \"yes\"");
+        assert_eq!(error(serde_json::json!({ "tokenId": "seven" })), "Invalid value for tokenId (expected int): \"seven\"");
+    }
+
+    #[test]
+    fn int_params_take_at_most_one_minus_sign() {
+        let task = task(vec![param("amount", "int")]);
+        let arg = |value| build_task_args(&task, &invocation(serde_json::json!({ "amount": value }))).map(|args| args.last().cloned().unwrap_or_default());
+        assert_eq!(arg(serde_json::json!("-5")).unwrap(), "-5");
+        assert_eq!(arg(serde_json::json!("12")).unwrap(), "12");
+        for bad in ["--5", "---x", "-", "", "5-"] {
+            assert!(arg(serde_json::json!(bad)).is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn build_task_args_keeps_positionals_contiguous() {
+        let task = task(vec![
+            TaskParam { positional: true, optional: true, ..param("from", "string") },
+            TaskParam { positional: true, optional: true, ..param("to", "string") },
+        ]);
+        assert_eq!(
+            build_task_args(&task, &invocation(serde_json::json!({ "to": "0xb" }))).unwrap_err().text,
+            "to cannot be set while the earlier argument from is empty"
+        );
+    }
+
+    fn new_param(name: &str, kind: NewParamKind) -> NewTaskParam {
+        NewTaskParam { name: name.to_string(), description: None, kind, param_type: None, default_value: None }
+    }
+
+    #[test]
+    fn render_param_emits_hardhat_builder_calls() {
+        assert_eq!(render_param(&new_param("dryRun", NewParamKind::Flag)).unwrap(), "  .addFlag(\"dryRun\", \"\")");
+        let amount = NewTaskParam {
+            description: Some("Amount in \"wei\"".to_string()),
+            param_type: Some("bigint".to_string()),
+            default_value: Some(serde_json::json!("1000")),
+            ..new_param("amount", NewParamKind::OptionalParam)
+        };
+        assert_eq!(
+            render_param(&amount).unwrap(),
+            "  .addOptionalParam(\"amount\", \"Amount in \\\"wei\\\"\", \"1000\", types.bigint)"
+        );
+        assert_eq!(
+            render_param(&new_param("files", NewParamKind::Variadic)).unwrap(),
+            "  .addOptionalVariadicPositionalParam(\"files\", \"\", undefined, types.string)"
+        );
+    }
+
+    #[test]
+    fn render_param_rejects_invalid_names_and_types() {
+        assert_eq!(render_param(&new_param("token-id", NewParamKind::Param)).unwrap_err().key, "task.invalid_param_name");
+        assert_eq!(render_param(&new_param("TokenId", NewParamKind::Param)).unwrap_err().key, "task.invalid_param_name");
+        let unknown = NewTaskParam { param_type: Some("address".to_string()), ..new_param("to", NewParamKind::Param) };
+        assert_eq!(render_param(&unknown).unwrap_err().text, "Unknown parameter type \"address\" for to");
+    }
+}
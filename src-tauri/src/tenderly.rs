@@ -0,0 +1,190 @@
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::AppError;
+use crate::networks;
+use crate::provider_keys::{self, KEYRING_SERVICE};
+use crate::rpc;
+use crate::simulate::{self, SimulationBase};
+
+const API_URL: &str = "https://api.tenderly.co/api/v1";
+const DASHBOARD_URL: &str = "https://dashboard.tenderly.co";
+const SHARED_URL: &str = "https://www.tdly.co/shared/simulation";
+// Tenderly requires a gas limit; used when the draft or transaction has none
+const DEFAULT_GAS: u64 = 8_000_000;
+
+// Stored whole in the keychain; account and project aren't secret but belong to the key
+#[derive(Serialize, Deserialize, Clone)]
+struct TenderlySettings {
+    account: String,
+    project: String,
+    access_key: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TenderlyInfo {
+    pub configured: bool,
+    pub account: Option<String>,
+    pub project: Option<String>,
+    pub key_hint: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TenderlySimulation {
+    pub simulation_id: String,
+    pub network_id: u64,
+    pub block_number: Option<u64>,
+    pub success: bool,
+    pub gas_used: Option<u64>,
+    pub error_message: Option<String>,
+    // Needs a Tenderly login with access to the project
+    pub dashboard_url: String,
+    // Set when the simulation was shared publicly
+    pub public_url: Option<String>,
+}
+
+fn entry() -> Result<Entry, String> {
+    Entry::new(KEYRING_SERVICE, "tenderly").map_err(|e| format!("Failed to open keychain: {}", e))
+}
+
+fn settings() -> Result<Option<TenderlySettings>, String> {
+    match entry()?.get_password() {
+        Ok(stored) => serde_json::from_str(&stored)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse Tenderly settings: {}", e)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read Tenderly settings from keychain: {}", e)),
+    }
+}
+
+fn info(settings: Option<&TenderlySettings>) -> TenderlyInfo {
+    TenderlyInfo {
+        configured: settings.is_some(),
+        account: settings.map(|s| s.account.clone()),
+        project: settings.map(|s| s.project.clone()),
+        key_hint: settings.map(|s| provider_keys::hint(&s.access_key)),
+    }
+}
+
+// Tenderly simulates against public chains, so a local fork is sent as the chain it
+// forked, at the fork block. Changes made on the local node after forking aren't there
+async fn target_chain(network: Option<&str>, url: &str) -> Result<(u64, Option<u64>), String> {
+    if let Some(network) = network {
+        let profile = networks::find_profile(network).ok_or_else(|| format!("Unknown network profile: {}", network))?;
+        return Ok((profile.chain_id, None));
+    }
+    let metadata = rpc::call(url, "hardhat_metadata", json!([])).await.unwrap_or(Value::Null);
+    let fork = metadata.get("forkedNetwork").filter(|fork| !fork.is_null());
+    match fork.and_then(|fork| fork.get("chainId")).and_then(Value::as_u64) {
+        Some(chain_id) => Ok((chain_id, fork.and_then(|f| f.get("forkBlockNumber")).and_then(Value::as_u64))),
+        None => Err("Tenderly can't simulate on a plain local chain; run the node as a fork or pick a network".to_string()),
+    }
+}
+
+async fn post(settings: &TenderlySettings, path: &str, body: &Value) -> Result<Value, String> {
+    let response = rpc::client()
+        .post(format!("{}/account/{}/project/{}/{}", API_URL, settings.account, settings.project, path))
+        .header("X-Access-Key", &settings.access_key)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Tenderly: {}", e))?;
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    if !status.is_success() {
+        let message = body.pointer("/error/message").and_then(Value::as_str).unwrap_or_else(|| status.as_str());
+        return Err(format!("Tenderly request failed: {}", message));
+    }
+    Ok(body)
+}
+
+#[tauri::command]
+pub async fn get_tenderly_settings() -> Result<TenderlyInfo, AppError> {
+    Ok(info(settings()?.as_ref()))
+}
+
+#[tauri::command]
+pub async fn set_tenderly_settings(account: String, project: String, access_key: String) -> Result<TenderlyInfo, AppError> {
+    let settings = TenderlySettings {
+        account: account.trim().to_string(),
+        project: project.trim().to_string(),
+        access_key: access_key.trim().to_string(),
+    };
+    let slug = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !slug(&settings.account) || !slug(&settings.project) || settings.access_key.is_empty() {
+        return Err("Tenderly needs an account slug, a project slug and an access key".to_string().into());
+    }
+    let stored = serde_json::to_string(&settings).map_err(|e| format!("Failed to serialize Tenderly settings: {}", e))?;
+    entry()?
+        .set_password(&stored)
+        .map_err(|e| format!("Failed to store Tenderly settings in keychain: {}", e))?;
+    Ok(info(Some(&settings)))
+}
+
+#[tauri::command]
+pub async fn remove_tenderly_settings() -> Result<bool, AppError> {
+    match entry()?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(format!("Failed to remove Tenderly settings from keychain: {}", e).into()),
+    }
+}
+
+// Sends a local transaction or a draft to Tenderly's simulator and returns where to
+// open it. `network` targets a profile's chain instead of the local fork
+#[tauri::command]
+pub async fn export_to_tenderly(
+    base: SimulationBase,
+    network: Option<String>,
+    share: Option<bool>,
+    rpc_url: Option<String>,
+) -> Result<TenderlySimulation, AppError> {
+    let settings = settings()?.ok_or("Tenderly is not configured")?;
+    let url = &rpc_url.unwrap_or_else(rpc::default_url);
+    let request = simulate::base_request(url, &base).await?;
+    let (network_id, block_number) = target_chain(network.as_deref(), url).await?;
+
+    let mut body = json!({
+        "network_id": network_id.to_string(),
+        "from": request.from,
+        "to": request.to,
+        "input": request.data,
+        "value": request.value,
+        "gas": request.gas.unwrap_or(DEFAULT_GAS),
+        "gas_price": "0",
+        "save": true,
+        "save_if_fails": true,
+        "simulation_type": "full",
+    });
+    if let Some(block) = block_number {
+        body["block_number"] = json!(block);
+    }
+    let response = post(&settings, "simulate", &body).await?;
+    let simulation_id = response
+        .pointer("/simulation/id")
+        .and_then(Value::as_str)
+        .ok_or("Tenderly returned no simulation id")?
+        .to_string();
+
+    let public_url = if share.unwrap_or(false) {
+        post(&settings, &format!("simulations/{}/share", simulation_id), &json!({})).await?;
+        Some(format!("{}/{}", SHARED_URL, simulation_id))
+    } else {
+        None
+    };
+    Ok(TenderlySimulation {
+        dashboard_url: format!("{}/{}/{}/simulator/{}", DASHBOARD_URL, settings.account, settings.project, simulation_id),
+        simulation_id,
+        network_id,
+        block_number,
+        success: response.pointer("/transaction/status").and_then(Value::as_bool).unwrap_or(false),
+        gas_used: response.pointer("/transaction/gas_used").and_then(Value::as_u64),
+        error_message: response
+            .pointer("/transaction/error_message")
+            .and_then(Value::as_str)
+            .filter(|message| !message.is_empty())
+            .map(str::to_string),
+        public_url,
+    })
+}
@@ -0,0 +1,195 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+use crate::error::AppError;
+use crate::exclusion::{self, JobGroup};
+use crate::rpc;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TimeTravelTarget {
+    // Unix seconds; the next block is mined at exactly this time
+    Timestamp { timestamp: u64 },
+    // "3600", "90m", "1d 12h", "2w": moves that far past the latest block
+    Duration { duration: String },
+    // Mines up to this height. With `timestamp` the last block lands on it and the ones
+    // before are spread evenly; otherwise blocks are `interval` seconds apart (default 1)
+    Block {
+        number: u64,
+        timestamp: Option<u64>,
+        interval: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TimeTravelResult {
+    pub from_block: u64,
+    pub from_timestamp: u64,
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub blocks_mined: u64,
+    // The RPC calls that were made, e.g. "evm_setNextBlockTimestamp(1700000000)"
+    pub calls: Vec<String>,
+}
+
+fn parse_duration(duration: &str) -> Result<u64, String> {
+    let invalid = || format!("Invalid duration {:?}: use e.g. 3600, 90m, 1d 12h", duration);
+    let mut total: u64 = 0;
+    for part in duration.split_whitespace() {
+        let split = part.find(|c: char| !c.is_ascii_digit()).unwrap_or(part.len());
+        let (amount, unit) = part.split_at(split);
+        let amount: u64 = amount.parse().map_err(|_| invalid())?;
+        let unit_secs = match unit {
+            "" | "s" => 1,
+            "m" => 60,
+            "h" => 3_600,
+            "d" => 86_400,
+            "w" => 604_800,
+            _ => return Err(invalid()),
+        };
+        total = amount
+            .checked_mul(unit_secs)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(invalid)?;
+    }
+    if total == 0 {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+// Durations and block counts come from the user and can be close to u64::MAX
+fn offset(timestamp: u64, seconds: u64) -> Result<u64, AppError> {
+    timestamp
+        .checked_add(seconds)
+        .ok_or_else(|| format!("{} seconds after {} is past the largest timestamp the node accepts", seconds, timestamp).into())
+}
+
+async fn latest_block(url: &str) -> Result<(u64, u64), AppError> {
+    let block = rpc::call(url, "eth_getBlockByNumber", json!(["latest", false])).await?;
+    let field = |key: &str| block.get(key).and_then(rpc::parse_quantity);
+    match (field("number"), field("timestamp")) {
+        (Some(number), Some(timestamp)) => Ok((number, timestamp)),
+        _ => Err("Failed to read the latest block".to_string().into()),
+    }
+}
+
+struct Travel<'a> {
+    url: &'a str,
+    calls: Vec<String>,
+}
+
+impl Travel<'_> {
+    async fn set_next_timestamp(&mut self, timestamp: u64) -> Result<(), AppError> {
+        rpc::call(self.url, "evm_setNextBlockTimestamp", json!([timestamp])).await?;
+        self.calls.push(format!("evm_setNextBlockTimestamp({})", timestamp));
+        Ok(())
+    }
+
+    async fn mine(&mut self, blocks: u64, interval: Option<u64>) -> Result<(), AppError> {
+        let mut params = vec![Value::String(format!("0x{:x}", blocks))];
+        params.extend(interval.map(|interval| Value::String(format!("0x{:x}", interval))));
+        rpc::call(self.url, "hardhat_mine", Value::Array(params)).await?;
+        match interval {
+            Some(interval) => self.calls.push(format!("hardhat_mine({}, {})", blocks, interval)),
+            None => self.calls.push(format!("hardhat_mine({})", blocks)),
+        }
+        Ok(())
+    }
+
+    // One block, mined at `timestamp`
+    async fn jump_to(&mut self, latest: u64, timestamp: u64) -> Result<(), AppError> {
+        if timestamp <= latest {
+            return Err(format!(
+                "Timestamp {} is not after the latest block ({}); the node's clock only moves forward",
+                timestamp, latest
+            ).into());
+        }
+        self.set_next_timestamp(timestamp).await?;
+        self.mine(1, None).await
+    }
+}
+
+async fn travel(url: &str, target: TimeTravelTarget) -> Result<TimeTravelResult, AppError> {
+    let (from_block, from_timestamp) = latest_block(url).await?;
+    let mut travel = Travel { url, calls: Vec::new() };
+    match target {
+        TimeTravelTarget::Timestamp { timestamp } => travel.jump_to(from_timestamp, timestamp).await?,
+        TimeTravelTarget::Duration { duration } => {
+            let seconds = parse_duration(&duration)?;
+            travel.jump_to(from_timestamp, offset(from_timestamp, seconds)?).await?
+        }
+        TimeTravelTarget::Block { number, timestamp, interval } => {
+            if number <= from_block {
+                return Err(format!("Block {} is not after the latest block ({})", number, from_block).into());
+            }
+            let blocks = number - from_block;
+            match timestamp {
+                Some(timestamp) => {
+                    // Every block needs a later timestamp than the one before
+                    if timestamp < offset(from_timestamp, blocks)? {
+                        return Err(format!(
+                            "Timestamp {} leaves less than a second per block for the {} blocks after {}",
+                            timestamp, blocks, from_timestamp
+                        ).into());
+                    }
+                    if blocks > 1 {
+                        let spacing = (timestamp - from_timestamp) / blocks;
+                        travel.set_next_timestamp(offset(from_timestamp, spacing)?).await?;
+                        travel.mine(blocks - 1, Some(spacing)).await?;
+                    }
+                    travel.set_next_timestamp(timestamp).await?;
+                    travel.mine(1, None).await?
+                }
+                None => travel.mine(blocks, interval).await?,
+            }
+        }
+    }
+
+    let (block_number, timestamp) = latest_block(url).await?;
+    Ok(TimeTravelResult {
+        from_block,
+        from_timestamp,
+        block_number,
+        timestamp,
+        blocks_mined: block_number.saturating_sub(from_block),
+        calls: travel.calls,
+    })
+}
+
+// Moves the local node to a point in time or a block height in one step, picking the
+// evm_setNextBlockTimestamp / hardhat_mine calls that get it there
+#[tauri::command]
+pub async fn time_travel(
+    app: AppHandle,
+    project_path: String,
+    target: TimeTravelTarget,
+    rpc_url: Option<String>,
+) -> Result<TimeTravelResult, AppError> {
+    let _slot = exclusion::acquire(&app, Path::new(&project_path), "time_travel", "time-travel", &[JobGroup::Rpc]).await;
+    travel(&rpc_url.unwrap_or_else(rpc::default_url), target).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("3600").unwrap(), 3_600);
+        assert_eq!(parse_duration("1d 12h").unwrap(), 129_600);
+        assert!(parse_duration("0").is_err());
+        assert!(parse_duration("5y").is_err());
+        assert!(parse_duration("99999999999999999w").is_err());
+    }
+
+    #[test]
+    fn offsets_that_overflow_are_errors() {
+        assert_eq!(offset(1_700_000_000, 60).unwrap(), 1_700_000_060);
+        let seconds = parse_duration(&format!("{}", u64::MAX - 10)).unwrap();
+        assert!(offset(1_700_000_000, seconds).is_err());
+    }
+}
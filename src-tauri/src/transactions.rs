@@ -0,0 +1,494 @@
+use hmac::{Hmac, Mac};
+use k256::ecdsa::SigningKey;
+use k256::elliptic_curve::PrimeField;
+use k256::Scalar;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha512;
+
+use crate::decode;
+use crate::error::AppError;
+use crate::gas_oracle;
+use crate::networks::{self, SignerSource};
+use crate::rpc;
+
+// Hardhat's and Anvil's default dev accounts come from this mnemonic
+pub const DEV_MNEMONIC: &str = "test test test test test test test test test test test junk";
+const DEFAULT_GAS: u64 = 3_000_000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TxType {
+    Legacy,
+    Eip1559,
+}
+
+// Where the key for a raw transaction comes from
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RawTxSigner {
+    // One of the local node's default accounts
+    DevAccount { index: u32 },
+    // A network profile's env private key or mnemonic, read from the GUI's environment
+    Network {
+        name: String,
+        #[serde(default)]
+        index: u32,
+    },
+    PrivateKey { key: String },
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct RawTxRequest {
+    pub to: Option<String>,
+    pub data: Option<String>,
+    // Wei amounts are decimal strings
+    pub value: Option<String>,
+    // Missing fields are filled in from the node
+    pub gas: Option<u64>,
+    pub nonce: Option<u64>,
+    pub chain_id: Option<u64>,
+    pub tx_type: Option<TxType>,
+    pub gas_price: Option<String>,
+    pub max_fee_per_gas: Option<String>,
+    pub max_priority_fee_per_gas: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SignedTransaction {
+    pub raw: String,
+    pub hash: String,
+    pub from: String,
+    pub tx_type: TxType,
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub gas: u64,
+}
+
+// The type of a transaction as the node reports it ("0x0", "0x2")
+pub fn tx_type_of(tx: &Value) -> Option<TxType> {
+    match tx.get("type").and_then(rpc::parse_quantity)? {
+        0 => Some(TxType::Legacy),
+        2 => Some(TxType::Eip1559),
+        _ => None,
+    }
+}
+
+// Nodes pick the type from the fee fields present, so choosing one means filling in its
+// fees from the oracle and dropping the other kind
+pub async fn apply_tx_type(url: &str, tx: &mut Value, tx_type: Option<TxType>) -> Result<(), AppError> {
+    let Some(tx_type) = tx_type else {
+        return Ok(());
+    };
+    let fees = gas_oracle::suggest(url).await?;
+    let object = tx.as_object_mut().ok_or("Transaction must be an object")?;
+    match tx_type {
+        TxType::Legacy => {
+            object.remove("maxFeePerGas");
+            object.remove("maxPriorityFeePerGas");
+            object.insert("gasPrice".to_string(), json!(decode::decimal_to_quantity(&fees.standard.max_fee_per_gas)?));
+        }
+        TxType::Eip1559 => {
+            if !fees.eip1559 {
+                return Err(format!("Chain {} doesn't support EIP-1559 transactions", fees.chain_id).into());
+            }
+            object.remove("gasPrice");
+            object.insert("maxFeePerGas".to_string(), json!(decode::decimal_to_quantity(&fees.standard.max_fee_per_gas)?));
+            object.insert(
+                "maxPriorityFeePerGas".to_string(),
+                json!(decode::decimal_to_quantity(&fees.standard.max_priority_fee_per_gas)?),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+// BIP-39 seed and BIP-32 derivation along m/44'/60'/0'/0/index, as wallets and Hardhat do
+fn derive_key(mnemonic: &str, index: u32) -> Result<[u8; 32], String> {
+    let phrase = mnemonic.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut seed = [0u8; 64];
+    pbkdf2::pbkdf2_hmac::<Sha512>(phrase.as_bytes(), b"mnemonic", 2048, &mut seed);
+    let master = hmac_sha512(b"Bitcoin seed", &seed);
+    let (mut key, mut chain_code): ([u8; 32], [u8; 32]) = (master[..32].try_into().unwrap(), master[32..].try_into().unwrap());
+
+    for (child, hardened) in [(44, true), (60, true), (0, true), (0, false), (index, false)] {
+        let mut data = Vec::with_capacity(37);
+        if hardened {
+            data.push(0);
+            data.extend_from_slice(&key);
+        } else {
+            let signing = SigningKey::from_slice(&key).map_err(|e| format!("Failed to derive key: {}", e))?;
+            data.extend_from_slice(signing.verifying_key().to_encoded_point(true).as_bytes());
+        }
+        data.extend_from_slice(&(if hardened { child | 0x8000_0000 } else { child }).to_be_bytes());
+        let out = hmac_sha512(&chain_code, &data);
+        let tweak: Option<Scalar> = Scalar::from_repr(<[u8; 32]>::try_from(&out[..32]).unwrap().into()).into();
+        let parent: Option<Scalar> = Scalar::from_repr(key.into()).into();
+        let (Some(tweak), Some(parent)) = (tweak, parent) else {
+            return Err("Failed to derive key: invalid child".to_string());
+        };
+        key = (tweak + parent).to_bytes().into();
+        chain_code = out[32..].try_into().unwrap();
+    }
+    Ok(key)
+}
+
+fn env_secret(variable: &str) -> Result<String, String> {
+    std::env::var(variable).map_err(|_| format!("{} is not set in the GUI's environment", variable))
+}
+
+fn signing_key(signer: &RawTxSigner) -> Result<SigningKey, String> {
+    let key = match signer {
+        RawTxSigner::DevAccount { index } => derive_key(DEV_MNEMONIC, *index)?.to_vec(),
+        RawTxSigner::PrivateKey { key } => decode::from_hex(key.trim()).ok_or("Invalid private key")?,
+        RawTxSigner::Network { name, index } => {
+            let profile = networks::find_profile(name).ok_or_else(|| format!("Unknown network profile: {}", name))?;
+            match profile.signer {
+                SignerSource::EnvPrivateKey { variable } => {
+                    decode::from_hex(env_secret(&variable)?.trim()).ok_or_else(|| format!("{} is not a hex private key", variable))?
+                }
+                SignerSource::EnvMnemonic { variable } => derive_key(&env_secret(&variable)?, *index)?.to_vec(),
+                SignerSource::NodeAccounts => derive_key(DEV_MNEMONIC, *index)?.to_vec(),
+                SignerSource::WalletConnect => {
+                    return Err(format!("{} signs through WalletConnect; the wallet holds the key", name))
+                }
+            }
+        }
+    };
+    SigningKey::from_slice(&key).map_err(|e| format!("Invalid private key: {}", e))
+}
+
+pub fn address_of(key: &SigningKey) -> String {
+    let point = key.verifying_key().to_encoded_point(false);
+    decode::to_hex(&decode::keccak256(&point.as_bytes()[1..])[12..])
+}
+
+pub fn dev_account_address(index: u32) -> Result<String, String> {
+    let key = SigningKey::from_slice(&derive_key(DEV_MNEMONIC, index)?).map_err(|e| format!("Failed to derive key: {}", e))?;
+    Ok(address_of(&key))
+}
+
+// RLP, just enough for transactions: byte strings and lists of them
+enum Rlp {
+    Bytes(Vec<u8>),
+    List(Vec<Rlp>),
+}
+
+fn rlp_length(length: usize, offset: u8) -> Vec<u8> {
+    if length < 56 {
+        return vec![offset + length as u8];
+    }
+    let bytes: Vec<u8> = length.to_be_bytes().into_iter().skip_while(|b| *b == 0).collect();
+    let mut out = vec![offset + 55 + bytes.len() as u8];
+    out.extend(bytes);
+    out
+}
+
+fn rlp_encode(item: &Rlp) -> Vec<u8> {
+    match item {
+        Rlp::Bytes(bytes) if bytes.len() == 1 && bytes[0] < 0x80 => bytes.clone(),
+        Rlp::Bytes(bytes) => {
+            let mut out = rlp_length(bytes.len(), 0x80);
+            out.extend_from_slice(bytes);
+            out
+        }
+        Rlp::List(items) => {
+            let payload: Vec<u8> = items.iter().flat_map(rlp_encode).collect();
+            let mut out = rlp_length(payload.len(), 0xc0);
+            out.extend(payload);
+            out
+        }
+    }
+}
+
+// Integers are big-endian without leading zeros; zero is the empty string
+fn rlp_uint(value: u64) -> Rlp {
+    Rlp::Bytes(value.to_be_bytes().into_iter().skip_while(|b| *b == 0).collect())
+}
+
+fn rlp_wei(decimal: &str) -> Result<Rlp, String> {
+    let quantity = decode::decimal_to_quantity(decimal)?;
+    let digits = quantity.trim_start_matches("0x");
+    let padded = if digits.len() % 2 == 1 { format!("0{}", digits) } else { digits.to_string() };
+    let bytes = decode::from_hex(&padded).unwrap_or_default();
+    Ok(Rlp::Bytes(bytes.into_iter().skip_while(|b| *b == 0).collect()))
+}
+
+// What the signature covers, once the node has filled in what the request left out.
+// Amounts are decimal wei; legacy transactions use `gas_price`, EIP-1559 ones the max fees
+struct UnsignedTx {
+    tx_type: TxType,
+    chain_id: u64,
+    nonce: u64,
+    gas: u64,
+    gas_price: String,
+    max_fee_per_gas: String,
+    max_priority_fee_per_gas: String,
+    to: Vec<u8>,
+    value: String,
+    data: Vec<u8>,
+}
+
+impl UnsignedTx {
+    fn encode(&self, signature: Option<[Rlp; 3]>) -> Result<Vec<u8>, String> {
+        let mut fields = match self.tx_type {
+            TxType::Legacy => vec![rlp_uint(self.nonce), rlp_wei(&self.gas_price)?],
+            TxType::Eip1559 => vec![
+                rlp_uint(self.chain_id),
+                rlp_uint(self.nonce),
+                rlp_wei(&self.max_priority_fee_per_gas)?,
+                rlp_wei(&self.max_fee_per_gas)?,
+            ],
+        };
+        fields.extend([rlp_uint(self.gas), Rlp::Bytes(self.to.clone()), rlp_wei(&self.value)?, Rlp::Bytes(self.data.clone())]);
+        let mut bytes = Vec::new();
+        if self.tx_type == TxType::Eip1559 {
+            // Empty access list, then the EIP-2718 type byte in front
+            fields.push(Rlp::List(Vec::new()));
+            bytes.push(2);
+        }
+        match signature {
+            Some(signature) => fields.extend(signature),
+            // EIP-155 replay protection: chain id, 0, 0 in place of the signature
+            None if self.tx_type == TxType::Legacy => fields.extend([rlp_uint(self.chain_id), rlp_uint(0), rlp_uint(0)]),
+            None => {}
+        }
+        bytes.extend(rlp_encode(&Rlp::List(fields)));
+        Ok(bytes)
+    }
+
+    // The raw signed transaction
+    fn sign(&self, key: &SigningKey) -> Result<Vec<u8>, String> {
+        let digest = decode::keccak256(&self.encode(None)?);
+        let (signature, recovery) = key
+            .sign_prehash_recoverable(&digest)
+            .map_err(|e| format!("Failed to sign transaction: {}", e))?;
+        let v = match self.tx_type {
+            TxType::Legacy => self.chain_id * 2 + 35 + recovery.to_byte() as u64,
+            TxType::Eip1559 => recovery.to_byte() as u64,
+        };
+        let (r, s) = signature.split_bytes();
+        let strip = |bytes: &[u8]| Rlp::Bytes(bytes.iter().copied().skip_while(|b| *b == 0).collect());
+        self.encode(Some([rlp_uint(v), strip(&r), strip(&s)]))
+    }
+}
+
+// Fills nonce, chain id, gas and fees from the node, signs, and returns the raw bytes
+pub async fn sign(url: &str, request: RawTxRequest, key: &SigningKey) -> Result<SignedTransaction, AppError> {
+    let from = address_of(key);
+    let to = match request.to.as_deref().filter(|to| !to.is_empty()) {
+        Some(to) => decode::from_hex(to).filter(|bytes| bytes.len() == 20).ok_or_else(|| format!("Invalid address: {}", to))?,
+        None => Vec::new(),
+    };
+    let data = decode::from_hex(request.data.as_deref().unwrap_or("0x")).ok_or("Calldata must be 0x-prefixed hex")?;
+    let value = request.value.clone().unwrap_or_else(|| "0".to_string());
+
+    let chain_id = match request.chain_id {
+        Some(chain_id) => chain_id,
+        None => rpc::chain_id(url).await?,
+    };
+    let nonce = match request.nonce {
+        Some(nonce) => nonce,
+        None => rpc::parse_quantity(&rpc::call(url, "eth_getTransactionCount", json!([from, "pending"])).await?)
+            .ok_or("Node returned an invalid nonce")?,
+    };
+    let gas = match request.gas {
+        Some(gas) => gas,
+        None => {
+            let mut estimate = json!({ "from": from, "data": decode::to_hex(&data), "value": decode::decimal_to_quantity(&value)? });
+            if !to.is_empty() {
+                estimate["to"] = json!(decode::to_hex(&to));
+            }
+            rpc::call(url, "eth_estimateGas", json!([estimate])).await.ok().and_then(|gas| rpc::parse_quantity(&gas)).unwrap_or(DEFAULT_GAS)
+        }
+    };
+
+    let mut fees = json!({});
+    if let Some(price) = &request.gas_price {
+        fees["gasPrice"] = json!(decode::decimal_to_quantity(price)?);
+    }
+    if let (Some(max_fee), Some(priority)) = (&request.max_fee_per_gas, &request.max_priority_fee_per_gas) {
+        fees["maxFeePerGas"] = json!(decode::decimal_to_quantity(max_fee)?);
+        fees["maxPriorityFeePerGas"] = json!(decode::decimal_to_quantity(priority)?);
+    }
+    let tx_type = request.tx_type.unwrap_or(if fees.get("gasPrice").is_some() { TxType::Legacy } else { TxType::Eip1559 });
+    let missing = match tx_type {
+        TxType::Legacy => fees.get("gasPrice").is_none(),
+        TxType::Eip1559 => fees.get("maxFeePerGas").is_none(),
+    };
+    if missing {
+        apply_tx_type(url, &mut fees, Some(tx_type)).await?;
+    }
+
+    let fee = |field: &str| decode::quantity_to_decimal(fees[field].as_str().unwrap_or("0x0"));
+    let unsigned = UnsignedTx {
+        tx_type,
+        chain_id,
+        nonce,
+        gas,
+        gas_price: fee("gasPrice"),
+        max_fee_per_gas: fee("maxFeePerGas"),
+        max_priority_fee_per_gas: fee("maxPriorityFeePerGas"),
+        to,
+        value,
+        data,
+    };
+    let raw = unsigned.sign(key)?;
+
+    Ok(SignedTransaction {
+        hash: decode::to_hex(&decode::keccak256(&raw)),
+        raw: decode::to_hex(&raw),
+        from,
+        tx_type,
+        chain_id,
+        nonce,
+        gas,
+    })
+}
+
+// Signs without broadcasting, for feeding relayers, bundlers or send_raw_transaction
+#[tauri::command]
+pub async fn build_raw_transaction(
+    request: RawTxRequest,
+    signer: RawTxSigner,
+    rpc_url: Option<String>,
+) -> Result<SignedTransaction, AppError> {
+    let key = signing_key(&signer)?;
+    sign(&rpc_url.unwrap_or_else(rpc::default_url), request, &key).await
+}
+
+#[tauri::command]
+pub async fn send_raw_transaction(raw: String, rpc_url: Option<String>) -> Result<String, AppError> {
+    let raw = raw.trim();
+    if decode::from_hex(raw).is_none_or(|bytes| bytes.is_empty()) {
+        return Err("Raw transaction must be 0x-prefixed hex".to_string().into());
+    }
+    let hash = rpc::call(&rpc_url.unwrap_or_else(rpc::default_url), "eth_sendRawTransaction", json!([raw])).await?;
+    Ok(hash.as_str().map(str::to_string).ok_or_else(|| "The node returned no transaction hash".to_string())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        decode::to_hex(bytes)
+    }
+
+    fn transfer(tx_type: TxType) -> UnsignedTx {
+        UnsignedTx {
+            tx_type,
+            chain_id: 1,
+            nonce: 9,
+            gas: 21_000,
+            gas_price: "20000000000".to_string(),
+            max_fee_per_gas: "100000000000".to_string(),
+            max_priority_fee_per_gas: "1000000000".to_string(),
+            to: vec![0x35; 20],
+            value: "1000000000000000000".to_string(),
+            data: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn rlp_matches_the_spec_examples() {
+        let bytes = |text: &str| Rlp::Bytes(text.as_bytes().to_vec());
+        assert_eq!(hex(&rlp_encode(&bytes("dog"))), "0x83646f67");
+        assert_eq!(hex(&rlp_encode(&Rlp::List(vec![bytes("cat"), bytes("dog")]))), "0xc88363617483646f67");
+        assert_eq!(hex(&rlp_encode(&bytes(""))), "0x80");
+        assert_eq!(hex(&rlp_encode(&Rlp::List(Vec::new()))), "0xc0");
+        assert_eq!(hex(&rlp_encode(&rlp_uint(0))), "0x80");
+        assert_eq!(hex(&rlp_encode(&rlp_uint(15))), "0x0f");
+        assert_eq!(hex(&rlp_encode(&rlp_uint(1024))), "0x820400");
+        let long = rlp_encode(&bytes("Lorem ipsum dolor sit amet, consectetur adipisicing elit"));
+        assert_eq!(hex(&long[..2]), "0xb838");
+        assert_eq!(long.len(), 58);
+    }
+
+    #[test]
+    fn rlp_wei_strips_leading_zeros() {
+        assert_eq!(hex(&rlp_encode(&rlp_wei("0").unwrap())), "0x80");
+        assert_eq!(hex(&rlp_encode(&rlp_wei("1000000000000000000").unwrap())), "0x880de0b6b3a7640000");
+    }
+
+    // The example from EIP-155
+    #[test]
+    fn signs_eip155_legacy_transactions() {
+        let tx = transfer(TxType::Legacy);
+        assert_eq!(
+            hex(&tx.encode(None).unwrap()),
+            "0xec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080"
+        );
+        let key = SigningKey::from_slice(&[0x46; 32]).unwrap();
+        assert_eq!(
+            hex(&tx.sign(&key).unwrap()),
+            "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+        );
+    }
+
+    #[test]
+    fn signs_eip1559_transactions() {
+        let mut tx = transfer(TxType::Eip1559);
+        tx.nonce = 0;
+        let unsigned = tx.encode(None).unwrap();
+        assert_eq!(
+            hex(&unsigned),
+            "0x02f00180843b9aca0085174876e8008252089435353535353535353535353535353535353535358\
+             80de0b6b3a764000080c0"
+        );
+
+        let key = SigningKey::from_slice(&[0x46; 32]).unwrap();
+        let signed = tx.sign(&key).unwrap();
+        // Same fields with y-parity, r and s appended, so the list header grows by 67 bytes
+        assert_eq!(signed[..2], [0x02, 0xf8]);
+        assert_eq!(signed[2] as usize, 0x30 + 67);
+        assert_eq!(signed[3..signed.len() - 67], unsigned[2..]);
+        let tail = &signed[signed.len() - 67..];
+        let parity = match tail[0] {
+            0x80 => 0,
+            byte => byte,
+        };
+        assert_eq!((tail[1], tail[34]), (0xa0, 0xa0));
+        let signature = Signature::from_scalars(
+            <[u8; 32]>::try_from(&tail[2..34]).unwrap(),
+            <[u8; 32]>::try_from(&tail[35..67]).unwrap(),
+        )
+        .unwrap();
+        let recovered = VerifyingKey::recover_from_prehash(
+            &decode::keccak256(&unsigned),
+            &signature,
+            RecoveryId::from_byte(parity).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(&recovered, key.verifying_key());
+    }
+
+    // Hardhat's first two default accounts
+    #[test]
+    fn derives_the_dev_accounts() {
+        assert_eq!(
+            hex(&derive_key(DEV_MNEMONIC, 0).unwrap()),
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+        );
+        assert_eq!(
+            hex(&derive_key(DEV_MNEMONIC, 1).unwrap()),
+            "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+        );
+        assert_eq!(dev_account_address(0).unwrap().to_lowercase(), "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+        assert_eq!(dev_account_address(1).unwrap().to_lowercase(), "0x70997970c51812dc3a010c7d01b50e0d17dc79c8");
+    }
+
+    #[test]
+    fn derive_key_ignores_extra_whitespace() {
+        let spaced = format!("  {}  ", DEV_MNEMONIC.replace(' ', "   "));
+        assert_eq!(derive_key(&spaced, 0).unwrap(), derive_key(DEV_MNEMONIC, 0).unwrap());
+    }
+}
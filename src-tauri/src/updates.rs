@@ -0,0 +1,249 @@
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::advisories;
+use crate::error::AppError;
+use crate::project;
+use crate::rpc;
+use crate::solidity::{self, SolcVersion};
+use crate::status;
+use crate::store;
+
+const APP_RELEASES_URL: &str = "https://api.github.com/repos/gap579137/hardhat_gui/releases/latest";
+const HARDHAT_LATEST_URL: &str = "https://registry.npmjs.org/hardhat/latest";
+const SOLC_LIST_URL: &str = "https://binaries.soliditylang.org/bin/list.json";
+// Leaves startup alone; the first check runs once the app has settled
+const STARTUP_DELAY: Duration = Duration::from_secs(60);
+const CHECK_INTERVAL: Duration = Duration::from_secs(12 * 60 * 60);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateComponent {
+    App,
+    Hardhat,
+    Solc,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct UpdateInfo {
+    pub component: UpdateComponent,
+    // None when the project doesn't have it installed or pinned
+    pub current: Option<String>,
+    pub latest: String,
+    pub update_available: bool,
+    // A new major version, or a new minor one for 0.x releases like solc's, which may
+    // need changes to the project
+    pub breaking: bool,
+    pub release_notes_url: String,
+    pub published_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UpdateReport {
+    pub checked_at: u64,
+    pub project_path: Option<String>,
+    pub updates: Vec<UpdateInfo>,
+    // Sources that couldn't be reached; the rest of the report still stands
+    pub errors: Vec<String>,
+}
+
+impl UpdateReport {
+    pub fn available(&self) -> Vec<&UpdateInfo> {
+        self.updates.iter().filter(|update| update.update_available).collect()
+    }
+}
+
+struct UpdateState {
+    last_report: Option<UpdateReport>,
+    // What the last `updates-available` event announced, so the same releases aren't
+    // announced again on every periodic check
+    announced: Vec<UpdateInfo>,
+}
+
+fn state() -> &'static Mutex<UpdateState> {
+    static STATE: OnceLock<Mutex<UpdateState>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(UpdateState { last_report: None, announced: Vec::new() }))
+}
+
+fn breaking(current: SolcVersion, latest: SolcVersion) -> bool {
+    if current.major == 0 {
+        latest.major != 0 || latest.minor != current.minor
+    } else {
+        latest.major != current.major
+    }
+}
+
+fn update_info(
+    component: UpdateComponent,
+    current: Option<&str>,
+    latest: &str,
+    release_notes_url: String,
+    published_at: Option<String>,
+) -> UpdateInfo {
+    let (current_version, latest_version) = (current.and_then(SolcVersion::parse), SolcVersion::parse(latest));
+    let (update_available, breaking) = match (current_version, latest_version) {
+        (Some(current), Some(latest)) => (latest > current, latest > current && breaking(current, latest)),
+        _ => (false, false),
+    };
+    UpdateInfo {
+        component,
+        current: current.map(str::to_string),
+        latest: latest.to_string(),
+        update_available,
+        breaking,
+        release_notes_url,
+        published_at,
+    }
+}
+
+async fn fetch_json(url: &str, source: &str) -> Result<Value, String> {
+    let response = rpc::client()
+        .get(url)
+        // GitHub's API rejects requests without one
+        .header("User-Agent", "hardhat-gui")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to check {}: {}", source, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to check {}: HTTP {}", source, response.status()));
+    }
+    response.json().await.map_err(|e| format!("Failed to parse {} release info: {}", source, e))
+}
+
+async fn check_app(app: &AppHandle) -> Result<UpdateInfo, String> {
+    let release = fetch_json(APP_RELEASES_URL, "Hardhat GUI").await?;
+    let tag = release.get("tag_name").and_then(Value::as_str).ok_or("Release has no tag")?;
+    let current = app.package_info().version.to_string();
+    let release_notes_url = release.get("html_url").and_then(Value::as_str).unwrap_or_default().to_string();
+    let published_at = release.get("published_at").and_then(Value::as_str).map(str::to_string);
+    Ok(update_info(
+        UpdateComponent::App,
+        Some(&current),
+        tag.trim_start_matches('v'),
+        release_notes_url,
+        published_at,
+    ))
+}
+
+async fn check_hardhat(root: &Path) -> Result<UpdateInfo, String> {
+    let latest = fetch_json(HARDHAT_LATEST_URL, "Hardhat").await?;
+    let latest = latest.get("version").and_then(Value::as_str).ok_or("npm returned no Hardhat version")?;
+    let current = advisories::installed_version(root, "hardhat");
+    let release_notes_url = format!("https://github.com/NomicFoundation/hardhat/releases/tag/hardhat@{}", latest);
+    Ok(update_info(UpdateComponent::Hardhat, current.as_deref(), latest, release_notes_url, None))
+}
+
+// One entry per compiler version pinned in the config. The newest release has to satisfy
+// every pragma the pinned version satisfies today, so the same sources still compile
+async fn check_solc(root: &Path) -> Result<Vec<UpdateInfo>, String> {
+    let configured = project::read_hardhat_config(root)
+        .map(|config| solidity::configured_compiler_versions(&config))
+        .unwrap_or_default();
+    if configured.is_empty() {
+        return Ok(Vec::new());
+    }
+    let pragmas: Vec<String> = project::collect_files(&root.join("contracts"), "sol")
+        .iter()
+        .filter_map(|file| fs::read_to_string(file).ok())
+        .filter_map(|source| solidity::extract_pragma(&source))
+        .collect();
+
+    let list = fetch_json(SOLC_LIST_URL, "solc").await?;
+    let releases: Vec<SolcVersion> = list
+        .get("releases")
+        .and_then(Value::as_object)
+        .map(|releases| releases.keys().filter_map(|version| SolcVersion::parse(version)).collect())
+        .unwrap_or_default();
+
+    let mut updates = Vec::new();
+    for current in configured {
+        let compiled: Vec<&String> = pragmas.iter().filter(|pragma| solidity::pragma_matches(pragma, current)).collect();
+        if compiled.is_empty() {
+            continue;
+        }
+        let latest = releases
+            .iter()
+            .copied()
+            .filter(|release| compiled.iter().all(|pragma| solidity::pragma_matches(pragma, *release)))
+            .max()
+            .map_or(current, |latest| latest.max(current));
+        let release_notes_url = format!("https://github.com/ethereum/solidity/releases/tag/v{}", latest);
+        updates.push(update_info(
+            UpdateComponent::Solc,
+            Some(&current.to_string()),
+            &latest.to_string(),
+            release_notes_url,
+            None,
+        ));
+    }
+    Ok(updates)
+}
+
+pub async fn check(app: &AppHandle, project_path: Option<String>) -> UpdateReport {
+    let mut updates = Vec::new();
+    let mut errors = Vec::new();
+    match check_app(app).await {
+        Ok(update) => updates.push(update),
+        Err(e) => errors.push(e),
+    }
+    let project_path = project_path.filter(|path| project::find_hardhat_config(Path::new(path)).is_some());
+    if let Some(root) = project_path.as_deref().map(Path::new) {
+        match check_hardhat(root).await {
+            Ok(update) => updates.push(update),
+            Err(e) => errors.push(e),
+        }
+        match check_solc(root).await {
+            Ok(solc) => updates.extend(solc),
+            Err(e) => errors.push(e),
+        }
+    }
+    let report = UpdateReport {
+        checked_at: store::timestamp_millis(),
+        project_path,
+        updates,
+        errors,
+    };
+
+    let mut state = state().lock().unwrap();
+    let available: Vec<UpdateInfo> = report.available().into_iter().cloned().collect();
+    if !available.is_empty() && available != state.announced {
+        let _ = app.emit("updates-available", report.clone());
+    }
+    state.announced = available;
+    state.last_report = Some(report.clone());
+    report
+}
+
+// Checks in the background for the project the status monitor is watching and emits
+// `updates-available` when a check turns up releases it hasn't announced yet
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let report = check(&app, status::project_path()).await;
+            for error in &report.errors {
+                tracing::debug!(error = %error, "update check incomplete");
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle, project_path: Option<String>) -> Result<UpdateReport, AppError> {
+    Ok(check(&app, project_path).await)
+}
+
+// The most recent check, periodic or on demand; None until the first one finishes
+#[tauri::command]
+pub async fn get_last_update_check() -> Result<Option<UpdateReport>, AppError> {
+    Ok(state().lock().unwrap().last_report.clone())
+}
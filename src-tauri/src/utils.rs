@@ -0,0 +1,220 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::abi::AbiParam;
+use crate::address_book::is_address;
+use crate::decode::{self, DecodedParam};
+use crate::error::AppError;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EtherUnit {
+    Wei,
+    Gwei,
+    Ether,
+}
+
+impl EtherUnit {
+    fn decimals(self) -> u8 {
+        match self {
+            EtherUnit::Wei => 0,
+            EtherUnit::Gwei => 9,
+            EtherUnit::Ether => 18,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InputEncoding {
+    #[default]
+    Utf8,
+    Hex,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UnitConversion {
+    pub wei: String,
+    pub gwei: String,
+    pub ether: String,
+    pub hex: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SignatureHashes {
+    // Canonical form, the one that actually gets hashed
+    pub signature: String,
+    pub selector: String,
+    pub topic: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AddressCheck {
+    pub valid: bool,
+    pub checksummed: Option<String>,
+    // None for all-lowercase or all-uppercase input, which carries no checksum
+    pub checksum_valid: Option<bool>,
+}
+
+// "1500000000000000000", 18 -> "1.5"
+pub fn format_units(raw: &str, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let padded = format!("{:0>width$}", raw, width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+// "1.5", 18 -> "1500000000000000000"
+pub fn parse_units(amount: &str, decimals: u8) -> Result<String, String> {
+    let amount = amount.trim().replace('_', "");
+    let (whole, fraction) = amount.split_once('.').unwrap_or((&amount, ""));
+    let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !digits(whole) || !digits(fraction) {
+        return Err(format!("Invalid amount: {}", amount));
+    }
+    if fraction.len() > decimals as usize {
+        return Err(format!("{} has more than {} decimal places", amount, decimals));
+    }
+    let raw = format!("{}{:0<width$}", whole, fraction, width = decimals as usize);
+    let raw = raw.trim_start_matches('0');
+    Ok(if raw.is_empty() { "0".to_string() } else { raw.to_string() })
+}
+
+// EIP-55: a hex letter is uppercase when the matching nibble of keccak256(lowercase) is >= 8
+pub fn to_checksum_address(address: &str) -> String {
+    let lower = address.trim_start_matches("0x").to_lowercase();
+    let hash = decode::keccak256(lower.as_bytes());
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+// uint/int are aliases that mustn't reach a signature hash
+fn canonical_param(mut param: AbiParam) -> AbiParam {
+    for alias in ["uint", "int"] {
+        let full = format!("{}256", alias);
+        if param.kind == alias || param.kind.strip_prefix(alias).is_some_and(|rest| rest.starts_with('[')) {
+            param.kind = param.kind.replacen(alias, &full, 1);
+        }
+    }
+    param.components = param.components.into_iter().map(canonical_param).collect();
+    param
+}
+
+// Accepts what people paste from Solidity: "function transfer(address to, uint amount)"
+fn parse_declaration(text: &str) -> Result<(String, Vec<AbiParam>), String> {
+    let text = text.trim().trim_end_matches(';');
+    let text = ["function ", "event ", "error "].iter().fold(text, |t, prefix| t.strip_prefix(prefix).unwrap_or(t));
+    // Up to the parameter list's closing parenthesis, dropping modifiers and returns (...)
+    let mut depth = 0;
+    let close = text.char_indices().find_map(|(i, c)| {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => return None,
+        }
+        (depth == 0).then_some(i)
+    });
+    let text = close.map_or(text, |close| &text[..=close]);
+    // Drops parameter names and data locations, keeping only the types
+    let names = Regex::new(r"([\w\]\)])\s+(?:(?:indexed|memory|calldata|storage|payable)\s+)*[A-Za-z_$][\w$]*\s*([,)])").unwrap();
+    let mut stripped = text.to_string();
+    while names.is_match(&stripped) {
+        stripped = names.replace_all(&stripped, "$1$2").to_string();
+    }
+    let stripped: String = stripped.split_whitespace().collect::<Vec<_>>().join("");
+    let (name, params) = decode::parse_signature(&stripped).ok_or_else(|| format!("Invalid signature: {}", text))?;
+    Ok((name, params.into_iter().map(canonical_param).collect()))
+}
+
+fn parse_types(types: &[String]) -> Result<Vec<AbiParam>, String> {
+    let (_, params) = parse_declaration(&format!("f({})", types.join(",")))?;
+    Ok(params)
+}
+
+#[tauri::command]
+pub async fn convert_units(amount: String, unit: EtherUnit) -> Result<UnitConversion, AppError> {
+    let wei = parse_units(&amount, unit.decimals())?;
+    Ok(UnitConversion {
+        hex: decode::decimal_to_quantity(&wei)?,
+        gwei: format_units(&wei, EtherUnit::Gwei.decimals()),
+        ether: format_units(&wei, EtherUnit::Ether.decimals()),
+        wei,
+    })
+}
+
+#[tauri::command]
+pub async fn keccak256_hash(input: String, encoding: Option<InputEncoding>) -> Result<String, AppError> {
+    let bytes = match encoding.unwrap_or_default() {
+        InputEncoding::Utf8 => input.into_bytes(),
+        InputEncoding::Hex => decode::from_hex(input.trim()).ok_or_else(|| format!("Invalid hex: {}", input))?,
+    };
+    Ok(decode::to_hex(&decode::keccak256(&bytes)))
+}
+
+// Function selector and event topic for a signature or a pasted declaration
+#[tauri::command]
+pub async fn compute_signature_hashes(signature: String) -> Result<SignatureHashes, AppError> {
+    let (name, params) = parse_declaration(&signature)?;
+    let types: Vec<String> = params.iter().map(AbiParam::canonical_type).collect();
+    let canonical = format!("{}({})", name, types.join(","));
+    Ok(SignatureHashes {
+        selector: decode::selector(&canonical),
+        topic: decode::event_topic(&canonical),
+        signature: canonical,
+    })
+}
+
+// abi.encode(values...) for the given types; with a signature, the calldata for that
+// function instead
+#[tauri::command]
+pub async fn abi_encode(types: Vec<String>, values: Vec<Value>, signature: Option<String>) -> Result<String, AppError> {
+    let (selector, params) = match signature {
+        Some(signature) => {
+            let (name, params) = parse_declaration(&signature)?;
+            let types: Vec<String> = params.iter().map(AbiParam::canonical_type).collect();
+            (decode::selector(&format!("{}({})", name, types.join(","))), params)
+        }
+        None => ("0x".to_string(), parse_types(&types)?),
+    };
+    if params.len() != values.len() {
+        return Err(format!("Expected {} values, got {}", params.len(), values.len()).into());
+    }
+    let encoded = decode::encode_params(&params, &values)?;
+    Ok(format!("{}{}", selector, &decode::to_hex(&encoded)[2..]))
+}
+
+#[tauri::command]
+pub async fn abi_decode(types: Vec<String>, data: String) -> Result<Vec<DecodedParam>, AppError> {
+    let params = parse_types(&types)?;
+    let bytes = decode::from_hex(data.trim()).ok_or_else(|| format!("Invalid hex: {}", data))?;
+    Ok(decode::decode_params(&params, &bytes).ok_or_else(|| "Data doesn't match the given types".to_string())?)
+}
+
+#[tauri::command]
+pub async fn check_address(address: String) -> Result<AddressCheck, AppError> {
+    let address = address.trim();
+    if !is_address(address) {
+        return Ok(AddressCheck { valid: false, checksummed: None, checksum_valid: None });
+    }
+    let checksummed = to_checksum_address(address);
+    let digits = &address[2..];
+    let mixed_case = digits != digits.to_lowercase() && digits != digits.to_uppercase();
+    Ok(AddressCheck {
+        valid: !mixed_case || address == checksummed,
+        checksum_valid: mixed_case.then(|| address == checksummed),
+        checksummed: Some(checksummed),
+    })
+}
@@ -0,0 +1,654 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use ed25519_dalek::{Signer, SigningKey};
+use futures_util::{SinkExt, StreamExt};
+use hkdf::Hkdf;
+use rand::rngs::OsRng;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::Message;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::artifacts;
+use crate::decode;
+use crate::deployments;
+use crate::error::AppError;
+use crate::networks::{self, NetworkProfile, SignerSource};
+use crate::provider_keys::{self, Provider};
+use crate::rpc;
+use crate::store;
+use crate::transactions::{self, TxType};
+
+const RELAY_URL: &str = "wss://relay.walletconnect.com";
+const PAIRING_TTL_SECS: u64 = 300;
+// The user has to confirm on their phone or in the browser extension
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(180);
+const METHODS: [&str; 3] = ["eth_sendTransaction", "personal_sign", "eth_signTypedData_v4"];
+const EVENTS: [&str; 2] = ["chainChanged", "accountsChanged"];
+
+// Relay tags from the Sign API spec; a response is tagged with its request's tag + 1
+const TAG_SESSION_PROPOSE: u32 = 1100;
+const TAG_SESSION_REQUEST: u32 = 1108;
+const TAG_SESSION_DELETE: u32 = 1112;
+
+#[derive(Serialize, Deserialize)]
+pub struct WalletConnectPairing {
+    // wc:...@2?relay-protocol=irn&symKey=..., shown as a QR code or pasted into the wallet
+    pub uri: String,
+    pub chain_ids: Vec<u64>,
+    pub expires_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WalletSession {
+    pub wallet_name: Option<String>,
+    pub wallet_url: Option<String>,
+    pub accounts: Vec<String>,
+    // Chains the wallet approved; requests for others are refused before reaching it
+    pub chain_ids: Vec<u64>,
+    pub expires_at: u64,
+    pub connected_at: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WalletTransaction {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub data: Option<String>,
+    // Wei, decimal
+    pub value: Option<String>,
+    pub gas: Option<u64>,
+    // None lets the wallet choose the fees
+    pub tx_type: Option<TxType>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WalletDeployment {
+    pub contract_name: String,
+    pub network: String,
+    pub chain_id: u64,
+    pub transaction_hash: String,
+    // None if the transaction wasn't mined before the GUI stopped waiting
+    pub address: Option<String>,
+}
+
+struct Pairing {
+    secret: StaticSecret,
+    proposal_id: u64,
+    rejected: Option<String>,
+}
+
+#[derive(Default)]
+struct Client {
+    // None while disconnected from the relay; reconnecting resubscribes every topic
+    outgoing: Option<mpsc::UnboundedSender<Message>>,
+    // Symmetric key per subscribed topic
+    keys: HashMap<String, [u8; 32]>,
+    pairing: Option<Pairing>,
+    session: Option<(String, WalletSession)>,
+    pending: HashMap<u64, oneshot::Sender<Result<Value, String>>>,
+}
+
+static CLIENT: Mutex<Option<Client>> = Mutex::new(None);
+
+// Every change to the client is a single map or field update, so state left behind by a
+// panicking holder is still usable and the relay task must not die with it
+fn with_client<T>(f: impl FnOnce(&mut Client) -> T) -> T {
+    let mut client = CLIENT.lock().unwrap_or_else(PoisonError::into_inner);
+    f(client.get_or_insert_with(Client::default))
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+fn hex(bytes: &[u8]) -> String {
+    decode::to_hex(bytes)[2..].to_string()
+}
+
+// JSON-RPC ids must be unique across both peers, hence time plus randomness
+fn message_id() -> u64 {
+    store::timestamp_millis() * 1000 + rand::thread_rng().gen_range(0..1000)
+}
+
+fn topic_for(key: &[u8; 32]) -> String {
+    hex(&Sha256::digest(key))
+}
+
+// Type 0 envelope: 0x00 || iv || ChaCha20-Poly1305(payload)
+fn seal(key: &[u8; 32], payload: &Value) -> Result<String, String> {
+    let iv: [u8; 12] = random_bytes();
+    let sealed = ChaCha20Poly1305::new(key.into())
+        .encrypt(Nonce::from_slice(&iv), payload.to_string().as_bytes())
+        .map_err(|_| "Failed to encrypt WalletConnect message".to_string())?;
+    let mut envelope = vec![0u8];
+    envelope.extend_from_slice(&iv);
+    envelope.extend(sealed);
+    Ok(STANDARD.encode(envelope))
+}
+
+// Type 1 envelopes carry the sender's public key before the iv
+fn open(key: &[u8; 32], message: &str) -> Result<Value, String> {
+    let envelope = STANDARD
+        .decode(message)
+        .map_err(|e| format!("Failed to decode WalletConnect message: {}", e))?;
+    let start = match envelope.first() {
+        Some(0) => 1,
+        Some(1) => 33,
+        _ => return Err("Unknown WalletConnect envelope type".to_string()),
+    };
+    if envelope.len() < start + 12 {
+        return Err("Truncated WalletConnect message".to_string());
+    }
+    let plain = ChaCha20Poly1305::new(key.into())
+        .decrypt(Nonce::from_slice(&envelope[start..start + 12]), &envelope[start + 12..])
+        .map_err(|_| "Failed to decrypt WalletConnect message".to_string())?;
+    serde_json::from_slice(&plain).map_err(|e| format!("Failed to parse WalletConnect message: {}", e))
+}
+
+// The relay authenticates clients with an EdDSA JWT issued by a did:key
+fn auth_token() -> String {
+    let key = SigningKey::generate(&mut OsRng);
+    let mut multicodec = vec![0xed, 0x01];
+    multicodec.extend_from_slice(key.verifying_key().as_bytes());
+    let issued_at = store::timestamp_millis() / 1000;
+    let header = URL_SAFE_NO_PAD.encode(json!({ "alg": "EdDSA", "typ": "JWT" }).to_string());
+    let claims = URL_SAFE_NO_PAD.encode(
+        json!({
+            "iss": format!("did:key:z{}", bs58::encode(multicodec).into_string()),
+            "sub": hex(&random_bytes::<32>()),
+            "aud": RELAY_URL,
+            "iat": issued_at,
+            "exp": issued_at + 86_400,
+        })
+        .to_string(),
+    );
+    let signed = format!("{}.{}", header, claims);
+    let signature = URL_SAFE_NO_PAD.encode(key.sign(signed.as_bytes()).to_bytes());
+    format!("{}.{}", signed, signature)
+}
+
+fn send(client: &Client, frame: Value) -> Result<(), String> {
+    client
+        .outgoing
+        .as_ref()
+        .and_then(|outgoing| outgoing.send(Message::Text(frame.to_string())).ok())
+        .ok_or_else(|| "Not connected to the WalletConnect relay".to_string())
+}
+
+fn subscribe(client: &Client, topic: &str) -> Result<(), String> {
+    send(client, json!({ "id": message_id(), "jsonrpc": "2.0", "method": "irn_subscribe", "params": { "topic": topic } }))
+}
+
+fn publish(client: &Client, topic: &str, payload: &Value, tag: u32, prompt: bool) -> Result<(), String> {
+    let key = client.keys.get(topic).ok_or("Unknown WalletConnect topic")?;
+    send(
+        client,
+        json!({
+            "id": message_id(),
+            "jsonrpc": "2.0",
+            "method": "irn_publish",
+            "params": { "topic": topic, "message": seal(key, payload)?, "ttl": PAIRING_TTL_SECS, "tag": tag, "prompt": prompt },
+        }),
+    )
+}
+
+fn emit_session(app: &AppHandle, session: Option<&WalletSession>) {
+    let _ = app.emit("walletconnect-session", session);
+}
+
+// "eip155:1:0xabc..." accounts, as namespaces list them
+fn session_from(params: &Value, connected_at: u64) -> WalletSession {
+    let accounts: Vec<(u64, String)> = params
+        .pointer("/namespaces/eip155/accounts")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter_map(|account| {
+            let mut parts = account.split(':').skip(1);
+            Some((parts.next()?.parse().ok()?, parts.next()?.to_lowercase()))
+        })
+        .collect();
+    let mut chain_ids: Vec<u64> = accounts.iter().map(|(chain, _)| *chain).collect();
+    chain_ids.sort();
+    chain_ids.dedup();
+    let mut addresses: Vec<String> = Vec::new();
+    for (_, address) in accounts {
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+    let metadata = |key: &str| params.pointer(&format!("/controller/metadata/{}", key)).and_then(Value::as_str).map(str::to_string);
+    WalletSession {
+        wallet_name: metadata("name"),
+        wallet_url: metadata("url"),
+        accounts: addresses,
+        chain_ids,
+        expires_at: params.get("expiry").and_then(Value::as_u64).unwrap_or_default() * 1000,
+        connected_at,
+    }
+}
+
+// Requests from the wallet: settle, update, extend, delete, ping and events
+fn handle_request(app: &AppHandle, client: &mut Client, topic: &str, tag: u32, payload: &Value) {
+    let params = payload.get("params").cloned().unwrap_or(Value::Null);
+    match payload.get("method").and_then(Value::as_str).unwrap_or_default() {
+        "wc_sessionSettle" => {
+            let session = session_from(&params, store::timestamp_millis());
+            emit_session(app, Some(&session));
+            client.session = Some((topic.to_string(), session));
+            client.pairing = None;
+        }
+        "wc_sessionUpdate" => {
+            if let Some((_, session)) = client.session.as_mut().filter(|(t, _)| t == topic) {
+                let updated = session_from(&params, session.connected_at);
+                session.accounts = updated.accounts;
+                session.chain_ids = updated.chain_ids;
+                emit_session(app, Some(session));
+            }
+        }
+        "wc_sessionExtend" => {
+            if let Some((_, session)) = client.session.as_mut().filter(|(t, _)| t == topic) {
+                session.expires_at = params.get("expiry").and_then(Value::as_u64).unwrap_or_default() * 1000;
+            }
+        }
+        "wc_sessionDelete" if client.session.as_ref().is_some_and(|(t, _)| t == topic) => {
+            client.session = None;
+            emit_session(app, None);
+        }
+        _ => {}
+    }
+    if let Some(id) = payload.get("id") {
+        let _ = publish(client, topic, &json!({ "id": id, "jsonrpc": "2.0", "result": true }), tag + 1, false);
+    }
+}
+
+// The wallet's answer to the proposal carries its public key; the session topic and key
+// are derived from it with X25519 and HKDF-SHA256
+fn handle_proposal_response(client: &mut Client, payload: &Value) {
+    let Some(pairing) = client.pairing.as_mut() else {
+        return;
+    };
+    if let Some(error) = payload.get("error") {
+        let message = error.get("message").and_then(Value::as_str).unwrap_or("rejected");
+        pairing.rejected = Some(format!("The wallet rejected the connection: {}", message));
+        return;
+    }
+    let responder = payload
+        .pointer("/result/responderPublicKey")
+        .and_then(Value::as_str)
+        .and_then(decode::from_hex)
+        .and_then(|key| <[u8; 32]>::try_from(key).ok());
+    let Some(responder) = responder else {
+        pairing.rejected = Some("The wallet sent an invalid session key".to_string());
+        return;
+    };
+    let shared = pairing.secret.diffie_hellman(&PublicKey::from(responder));
+    let mut key = [0u8; 32];
+    let _ = Hkdf::<Sha256>::new(None, shared.as_bytes()).expand(&[], &mut key);
+    let topic = topic_for(&key);
+    client.keys.insert(topic.clone(), key);
+    let _ = subscribe(client, &topic);
+}
+
+fn handle_message(app: &AppHandle, topic: &str, message: &str, tag: u32) {
+    with_client(|client| {
+        let Some(payload) = client.keys.get(topic).and_then(|key| open(key, message).ok()) else {
+            return;
+        };
+        let id = payload.get("id").and_then(Value::as_u64).unwrap_or_default();
+        if payload.get("method").is_some() {
+            handle_request(app, client, topic, tag, &payload);
+        } else if client.pairing.as_ref().is_some_and(|p| p.proposal_id == id) {
+            handle_proposal_response(client, &payload);
+        } else if let Some(waiter) = client.pending.remove(&id) {
+            let result = match payload.get("error") {
+                Some(error) => Err(format!(
+                    "Wallet refused the request: {}",
+                    error.get("message").and_then(Value::as_str).unwrap_or("unknown error")
+                )),
+                None => Ok(payload.get("result").cloned().unwrap_or(Value::Null)),
+            };
+            let _ = waiter.send(result);
+        }
+    });
+}
+
+// Frames from the relay: deliveries on subscribed topics, and acks of our own calls
+fn handle_frame(app: &AppHandle, text: &str) {
+    let Ok(frame) = serde_json::from_str::<Value>(text) else {
+        return;
+    };
+    if frame.get("method").and_then(Value::as_str) != Some("irn_subscription") {
+        if let Some(error) = frame.get("error") {
+            tracing::warn!(error = %error, "walletconnect relay error");
+        }
+        return;
+    }
+    with_client(|client| {
+        let _ = send(client, json!({ "id": frame["id"], "jsonrpc": "2.0", "result": true }));
+    });
+    let data = &frame["params"]["data"];
+    let topic = data.get("topic").and_then(Value::as_str).unwrap_or_default();
+    let message = data.get("message").and_then(Value::as_str).unwrap_or_default();
+    let tag = data.get("tag").and_then(Value::as_u64).unwrap_or_default() as u32;
+    handle_message(app, topic, message, tag);
+}
+
+async fn ensure_connected(app: &AppHandle) -> Result<(), String> {
+    if with_client(|client| client.outgoing.as_ref().is_some_and(|o| !o.is_closed())) {
+        return Ok(());
+    }
+    let project_id = Provider::WalletConnect
+        .key()?
+        .ok_or("No WALLETCONNECT_PROJECT_ID is configured (create one at cloud.reown.com)")?;
+    let url = format!("{}/?auth={}&projectId={}", RELAY_URL, auth_token(), project_id);
+    let (socket, _) = tokio_tungstenite::connect_async(url)
+        .await
+        .map_err(|e| format!("Failed to connect to the WalletConnect relay: {}", e))?;
+    let (mut write, mut read) = socket.split();
+    let (outgoing, mut queued) = mpsc::unbounded_channel();
+    with_client(|client| {
+        client.outgoing = Some(outgoing);
+        for topic in client.keys.keys() {
+            let _ = subscribe(client, topic);
+        }
+    });
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::select! {
+                message = queued.recv() => match message {
+                    Some(message) => if write.send(message).await.is_err() { break },
+                    None => break,
+                },
+                frame = read.next() => match frame {
+                    Some(Ok(Message::Text(text))) => handle_frame(&app, &text),
+                    Some(Ok(_)) => {}
+                    _ => break,
+                },
+            }
+        }
+        tracing::info!("walletconnect relay disconnected");
+        // Requests in flight are lost; topics and keys stay for the next connection
+        with_client(|client| {
+            client.outgoing = None;
+            client.pending.clear();
+        });
+    });
+    Ok(())
+}
+
+async fn session_request(app: &AppHandle, chain_id: u64, method: &str, params: Value) -> Result<Value, String> {
+    ensure_connected(app).await?;
+    let id = message_id();
+    let (waiter, response) = oneshot::channel();
+    with_client(|client| {
+        let (topic, session) = client.session.as_ref().ok_or("No wallet is connected")?;
+        if !session.chain_ids.contains(&chain_id) {
+            return Err(format!("The wallet didn't approve chain {}; reconnect it with that chain", chain_id));
+        }
+        let payload = json!({
+            "id": id,
+            "jsonrpc": "2.0",
+            "method": "wc_sessionRequest",
+            "params": { "request": { "method": method, "params": params }, "chainId": format!("eip155:{}", chain_id) },
+        });
+        publish(client, &topic.clone(), &payload, TAG_SESSION_REQUEST, true)?;
+        client.pending.insert(id, waiter);
+        Ok(())
+    })?;
+    match tokio::time::timeout(REQUEST_TIMEOUT, response).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(_)) => Err("Lost the connection to the WalletConnect relay".to_string()),
+        Err(_) => {
+            with_client(|client| client.pending.remove(&id));
+            Err(format!("The wallet didn't answer within {}s", REQUEST_TIMEOUT.as_secs()))
+        }
+    }
+}
+
+fn network_profile(network: &str) -> Result<NetworkProfile, String> {
+    networks::find_profile(network).ok_or_else(|| format!("Unknown network profile: {}", network))
+}
+
+fn default_account(from: Option<String>) -> Result<String, String> {
+    match from {
+        Some(from) => Ok(from),
+        None => with_client(|client| client.session.as_ref().and_then(|(_, s)| s.accounts.first().cloned()))
+            .ok_or_else(|| "No wallet is connected".to_string()),
+    }
+}
+
+async fn send_transaction(app: &AppHandle, profile: &NetworkProfile, tx: WalletTransaction) -> Result<String, AppError> {
+    let mut request = json!({ "from": default_account(tx.from)?, "data": tx.data.unwrap_or_else(|| "0x".to_string()) });
+    if let Some(to) = tx.to {
+        request["to"] = json!(to);
+    }
+    if let Some(value) = tx.value {
+        request["value"] = json!(decode::decimal_to_quantity(&value)?);
+    }
+    if let Some(gas) = tx.gas {
+        request["gas"] = json!(format!("{:#x}", gas));
+    }
+    transactions::apply_tx_type(&provider_keys::expand(&profile.rpc_url), &mut request, tx.tx_type).await?;
+    let hash = session_request(app, profile.chain_id, "eth_sendTransaction", json!([request])).await?;
+    hash.as_str().map(str::to_string).ok_or_else(|| "The wallet returned no transaction hash".into())
+}
+
+// Starts a pairing and returns its URI; the session arrives once the wallet approves.
+// Chains default to the profiles that sign through WalletConnect
+#[tauri::command]
+pub async fn walletconnect_pair(app: AppHandle, chain_ids: Option<Vec<u64>>) -> Result<WalletConnectPairing, AppError> {
+    let chain_ids = chain_ids.unwrap_or_else(|| {
+        let mut ids: Vec<u64> = networks::load_profiles()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|profile| profile.signer == SignerSource::WalletConnect)
+            .map(|profile| profile.chain_id)
+            .collect();
+        ids.sort();
+        ids.dedup();
+        if ids.is_empty() { vec![1] } else { ids }
+    });
+    ensure_connected(&app).await?;
+
+    let key: [u8; 32] = random_bytes();
+    let topic = topic_for(&key);
+    let secret = StaticSecret::random_from_rng(OsRng);
+    let proposal_id = message_id();
+    let expires_at = store::timestamp_millis() / 1000 + PAIRING_TTL_SECS;
+    let proposal = json!({
+        "id": proposal_id,
+        "jsonrpc": "2.0",
+        "method": "wc_sessionPropose",
+        "params": {
+            "relays": [{ "protocol": "irn" }],
+            "requiredNamespaces": {},
+            "optionalNamespaces": {
+                "eip155": {
+                    "chains": chain_ids.iter().map(|id| format!("eip155:{}", id)).collect::<Vec<_>>(),
+                    "methods": METHODS,
+                    "events": EVENTS,
+                },
+            },
+            "proposer": {
+                "publicKey": hex(PublicKey::from(&secret).as_bytes()),
+                "metadata": {
+                    "name": "Hardhat GUI",
+                    "description": "Sign transactions and deployments from Hardhat GUI",
+                    "url": "https://github.com/gap579137/hardhat_gui",
+                    "icons": [],
+                },
+            },
+            "expiryTimestamp": expires_at,
+        },
+    });
+    with_client(|client| {
+        client.keys.insert(topic.clone(), key);
+        subscribe(client, &topic)?;
+        publish(client, &topic, &proposal, TAG_SESSION_PROPOSE, true)?;
+        client.pairing = Some(Pairing { secret, proposal_id, rejected: None });
+        Ok::<(), String>(())
+    })?;
+
+    Ok(WalletConnectPairing {
+        uri: format!("wc:{}@2?relay-protocol=irn&symKey={}&expiryTimestamp={}", topic, hex(&key), expires_at),
+        chain_ids,
+        expires_at: expires_at * 1000,
+    })
+}
+
+// Resolves once the wallet approves the pending pairing
+#[tauri::command]
+pub async fn walletconnect_wait_for_session(timeout_secs: Option<u64>) -> Result<WalletSession, AppError> {
+    let deadline = store::timestamp_millis() + timeout_secs.unwrap_or(PAIRING_TTL_SECS) * 1000;
+    loop {
+        let state = with_client(|client| match (&client.pairing, &client.session) {
+            (Some(Pairing { rejected: Some(error), .. }), _) => Some(Err(error.clone())),
+            (None, Some((_, session))) => Some(Ok(session.clone())),
+            (None, None) => Some(Err("No WalletConnect pairing is in progress".to_string())),
+            _ => None,
+        });
+        if let Some(state) = state {
+            return Ok(state?);
+        }
+        if store::timestamp_millis() > deadline {
+            return Err("The wallet didn't connect in time".to_string().into());
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+#[tauri::command]
+pub async fn get_walletconnect_session() -> Result<Option<WalletSession>, AppError> {
+    Ok(with_client(|client| client.session.as_ref().map(|(_, session)| session.clone())))
+}
+
+#[tauri::command]
+pub async fn walletconnect_disconnect(app: AppHandle) -> Result<bool, AppError> {
+    let _ = ensure_connected(&app).await;
+    let disconnected = with_client(|client| {
+        let Some((topic, _)) = client.session.take() else {
+            return false;
+        };
+        let payload = json!({
+            "id": message_id(),
+            "jsonrpc": "2.0",
+            "method": "wc_sessionDelete",
+            "params": { "code": 6000, "message": "User disconnected." },
+        });
+        let _ = publish(client, &topic, &payload, TAG_SESSION_DELETE, false);
+        client.keys.remove(&topic);
+        true
+    });
+    if disconnected {
+        emit_session(&app, None);
+    }
+    Ok(disconnected)
+}
+
+#[tauri::command]
+pub async fn walletconnect_send_transaction(app: AppHandle, network: String, tx: WalletTransaction) -> Result<String, AppError> {
+    let profile = network_profile(&network)?;
+    send_transaction(&app, &profile, tx).await
+}
+
+// personal_sign of a UTF-8 message
+#[tauri::command]
+pub async fn walletconnect_sign_message(
+    app: AppHandle,
+    network: String,
+    message: String,
+    address: Option<String>,
+) -> Result<String, AppError> {
+    let profile = network_profile(&network)?;
+    let params = json!([decode::to_hex(message.as_bytes()), default_account(address)?]);
+    let signature = session_request(&app, profile.chain_id, "personal_sign", params).await?;
+    Ok(signature.as_str().map(str::to_string).ok_or_else(|| "The wallet returned no signature".to_string())?)
+}
+
+// Deploys a compiled contract with the wallet as deployer, then waits for the receipt
+// on the profile's RPC to report the address
+#[tauri::command]
+pub async fn walletconnect_deploy(
+    app: AppHandle,
+    project_path: String,
+    network: String,
+    contract_name: String,
+    args: Option<Vec<Value>>,
+    tx_type: Option<TxType>,
+) -> Result<WalletDeployment, AppError> {
+    let profile = network_profile(&network)?;
+    let artifact = artifacts::index(Path::new(&project_path))
+        .get(&contract_name)
+        .cloned()
+        .ok_or_else(|| format!("No artifact for {}; compile first", contract_name))?;
+    if artifact.bytecode_size == 0 {
+        return Err(format!("{} is abstract or an interface and can't be deployed", contract_name).into());
+    }
+    let inputs = artifact
+        .abi
+        .iter()
+        .find(|item| item.kind == "constructor")
+        .map(|item| item.inputs.clone())
+        .unwrap_or_default();
+    let encoded = decode::encode_params(&inputs, &args.unwrap_or_default())?;
+    let bytecode = artifact.code()?.bytecode;
+    let data = format!("{}{}", bytecode, &decode::to_hex(&encoded)[2..]);
+
+    let transaction_hash = send_transaction(&app, &profile, WalletTransaction {
+        from: None,
+        to: None,
+        data: Some(data),
+        value: None,
+        gas: None,
+        tx_type,
+    })
+    .await?;
+
+    let url = provider_keys::expand(&profile.rpc_url);
+    let deadline = store::timestamp_millis() + RECEIPT_TIMEOUT.as_millis() as u64;
+    let mut address = None;
+    while store::timestamp_millis() < deadline {
+        let receipt = rpc::call(&url, "eth_getTransactionReceipt", json!([transaction_hash])).await.unwrap_or(Value::Null);
+        if !receipt.is_null() {
+            if receipt.get("status").and_then(rpc::parse_quantity) == Some(0) {
+                return Err(format!("Deployment transaction {} reverted", transaction_hash).into());
+            }
+            address = receipt.get("contractAddress").and_then(Value::as_str).map(str::to_string);
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+    if let Some(address) = &address {
+        let deployment_id = format!("chain-{}-walletconnect", profile.chain_id);
+        let future_id = format!("WalletConnect#{}", contract_name);
+        if let Err(e) = deployments::record_external(Path::new(&project_path), &deployment_id, &future_id, address) {
+            tracing::warn!(error = %e, "failed to record walletconnect deployment");
+        }
+    }
+    Ok(WalletDeployment {
+        contract_name,
+        network,
+        chain_id: profile.chain_id,
+        transaction_hash,
+        address,
+    })
+}
@@ -28,7 +28,9 @@ import {
   NavLink,
   TextInput,
   Modal,
-  Textarea
+  Textarea,
+  Progress,
+  Select
 } from '@mantine/core';
 import { useDisclosure } from '@mantine/hooks';
 import { notifications } from '@mantine/notifications';
@@ -38,6 +40,75 @@ import './App.css';
 // Check if we're running in Tauri
 const isTauri = typeof window !== 'undefined' && window.__TAURI_INTERNALS__;
 
+// Commands reject with `{ kind, message, key, params, ... }`; other failures are strings or Errors
+const errorMessage = (err) => (typeof err === 'string' ? err : err?.message ?? String(err));
+
+// Folds one `execution-event` into the per-execution state shown by ExecutionStream
+const applyExecutionEvent = (executions, event) => {
+  const current = executions[event.execution_id] ?? { kind: event.kind, output: [], progress: null, finished: null };
+  const next = { ...current };
+  switch (event.type) {
+    case 'started':
+      next.output = [];
+      break;
+    case 'stdout':
+    case 'stderr':
+      next.output = [...current.output, ...event.segments.map((segment) => ({ ...segment, stream: event.type }))];
+      break;
+    case 'progress':
+      next.progress = event;
+      break;
+    case 'finished':
+      next.finished = event;
+      break;
+    default:
+      return executions;
+  }
+  return { ...executions, [event.execution_id]: next };
+};
+
+const FINISH_COLORS = { succeeded: 'green', failed: 'red', cancelled: 'gray', timed_out: 'orange' };
+
+// One view for every long-running command (compile, test, coverage, deploy, script, install)
+function ExecutionStream({ executions }) {
+  const entries = Object.entries(executions).slice(-5).reverse();
+  if (entries.length === 0) return null;
+  return (
+    <Card shadow="sm" padding="md" radius="md" withBorder>
+      <Title order={5} mb="sm">⏱️ Executions</Title>
+      <Stack gap="sm">
+        {entries.map(([id, execution]) => (
+          <Paper key={id} p="xs" withBorder>
+            <Group justify="space-between" mb="xs">
+              <Text size="sm" fw={600}>{execution.kind} <Text span size="xs" c="dimmed">{id}</Text></Text>
+              {execution.finished ? (
+                <Badge color={FINISH_COLORS[execution.finished.status]}>{execution.finished.status.replace('_', ' ')}</Badge>
+              ) : (
+                <Loader size="xs" />
+              )}
+            </Group>
+            <Text size="xs" c="dimmed" mb="xs">
+              {execution.finished?.summary ?? execution.progress?.message ?? 'Running...'}
+              {!execution.finished && execution.progress?.completed != null &&
+                ` (${execution.progress.completed}${execution.progress.total ? `/${execution.progress.total}` : ''})`}
+            </Text>
+            {!execution.finished && execution.progress?.percent != null && (
+              <Progress value={execution.progress.percent} size="sm" mb="xs" />
+            )}
+            <Code block size="xs" style={{ maxHeight: '200px', overflow: 'auto' }}>
+              {execution.output.map((segment, i) => (
+                <span key={i} style={{ color: segment.stream === 'stderr' ? 'var(--mantine-color-red-6)' : undefined }}>
+                  {segment.text}
+                </span>
+              ))}
+            </Code>
+          </Paper>
+        ))}
+      </Stack>
+    </Card>
+  );
+}
+
 // Theme toggle component
 function ThemeToggle() {
   const { colorScheme, toggleColorScheme } = useMantineColorScheme();
@@ -60,7 +131,12 @@ function HardhatApp() {
   const [error, setError] = useState(null);
   const [networkInfo, setNetworkInfo] = useState(null);
   const [hardhatStatus, setHardhatStatus] = useState(null);
+  const [environment, setEnvironment] = useState(null);
   const [selectedProjectPath, setSelectedProjectPath] = useState('');
+  const [projectTemplate, setProjectTemplate] = useState('javascript');
+  const [creationExecutionId, setCreationExecutionId] = useState(null);
+  const [projectCandidates, setProjectCandidates] = useState([]);
+  const [mockPresets, setMockPresets] = useState([]);
   const [currentProjectPath, setCurrentProjectPath] = useState(''); // Track the active project path
   const [isManaging, setIsManaging] = useState(false);
   const [managementMessage, setManagementMessage] = useState('');
@@ -71,6 +147,7 @@ function HardhatApp() {
   const [compilationStatus, setCompilationStatus] = useState(null);
   const [testResults, setTestResults] = useState(null);
   const [deploymentStatus, setDeploymentStatus] = useState(null);
+  const [executions, setExecutions] = useState({});
   const [consoleOutput, setConsoleOutput] = useState([]);
   const [consoleInput, setConsoleInput] = useState('');
   const [autoRefresh, setAutoRefresh] = useState(false);
@@ -126,7 +203,7 @@ function HardhatApp() {
       return status;
     } catch (err) {
       console.error('Error checking Hardhat status:', err);
-      setError(`Failed to check Hardhat status: ${err}`);
+      setError(`Failed to check Hardhat status: ${errorMessage(err)}`);
       return null;
     }
   };
@@ -162,27 +239,55 @@ function HardhatApp() {
       
       setAccounts(accountsWithBalances);
     } catch (err) {
-      setError(err.message);
+      setError(errorMessage(err));
       console.error('Error loading blockchain data:', err);
     } finally {
       setLoading(false);
     }
   };
 
+  const checkEnvironment = async () => {
+    if (!isTauri || !tauriApis.invoke) return;
+    try {
+      setEnvironment(await tauriApis.invoke('check_environment'));
+    } catch (err) {
+      console.error('Error checking environment:', errorMessage(err));
+    }
+  };
+
+  // Installs Node.js, npm or git through the platform's package manager or fnm
+  const handleInstallTool = async (tool, installer) => {
+    setIsManaging(true);
+    setManagementMessage(`Installing ${tool} with ${installer}...`);
+    try {
+      const report = await tauriApis.invoke('install_tool', { tool, installer });
+      setEnvironment(report);
+      notifications.show({ title: 'Installation Complete', message: `${tool} is installed`, color: 'green' });
+      checkHardhatStatus();
+    } catch (err) {
+      notifications.show({ title: 'Installation Failed', message: errorMessage(err), color: 'red' });
+    } finally {
+      setIsManaging(false);
+      setManagementMessage('');
+    }
+  };
+
   const handleInstallHardhat = async () => {
     if (!isTauri || !tauriApis.invoke) {
       setError('Hardhat installation requires the desktop app');
       return;
     }
 
+    // Installs into the selected project; only without one does it fall back to a global install
+    const projectPath = selectedProjectPath || currentProjectPath || null;
     setIsManaging(true);
-    setManagementMessage('Installing Hardhat globally...');
+    setManagementMessage(projectPath ? 'Installing Hardhat in the project...' : 'Installing Hardhat globally...');
     
     try {
-      const result = await tauriApis.invoke('install_hardhat');
+      const result = await tauriApis.invoke('install_hardhat', { projectPath });
       notifications.show({
         title: 'Installation Complete',
-        message: result,
+        message: result.text,
         color: 'green',
       });
       setTimeout(() => {
@@ -192,10 +297,10 @@ function HardhatApp() {
     } catch (err) {
       notifications.show({
         title: 'Installation Failed',
-        message: err.toString(),
+        message: errorMessage(err),
         color: 'red',
       });
-      setManagementMessage(`Installation failed: ${err}`);
+      setManagementMessage(`Installation failed: ${errorMessage(err)}`);
     } finally {
       setIsManaging(false);
     }
@@ -214,15 +319,24 @@ function HardhatApp() {
       });
       
       if (selected) {
-        setSelectedProjectPath(selected);
-        const status = await checkHardhatStatus(selected);
-        // If project is detected, keep the selected path for future operations
-        if (status?.project_detected) {
-          console.log('Project detected at:', selected);
+        // The folder picked may sit inside a project or hold several (a monorepo)
+        const discovery = await tauriApis.invoke('discover_hardhat_projects', { path: selected });
+        const candidates = discovery.candidates;
+        setProjectCandidates(candidates.length > 1 ? candidates : []);
+        const onlyCandidate = candidates.length === 1 ? candidates[0].path : null;
+        const projectPath = candidates[0]?.relation === 'selected' ? selected : (onlyCandidate ?? selected);
+        setSelectedProjectPath(projectPath);
+        await checkHardhatStatus(projectPath);
+        if (projectPath !== selected) {
+          notifications.show({
+            title: 'Project Found',
+            message: `Using the Hardhat project at ${projectPath}`,
+            color: 'blue',
+          });
         }
       }
     } catch (err) {
-      setError(`Failed to select directory: ${err}`);
+      setError(`Failed to select directory: ${errorMessage(err)}`);
     }
   };
 
@@ -238,15 +352,17 @@ function HardhatApp() {
     }
 
     setIsManaging(true);
+    setCreationExecutionId(null);
     setManagementMessage('Creating new Hardhat project...');
     
     try {
       const result = await tauriApis.invoke('create_hardhat_project', { 
-        projectPath: selectedProjectPath 
+        projectPath: selectedProjectPath,
+        template: projectTemplate
       });
       notifications.show({
         title: 'Project Created',
-        message: result,
+        message: result.text,
         color: 'green',
       });
       setTimeout(async () => {
@@ -256,10 +372,10 @@ function HardhatApp() {
     } catch (err) {
       notifications.show({
         title: 'Project Creation Failed',
-        message: err.toString(),
+        message: errorMessage(err),
         color: 'red',
       });
-      setManagementMessage(`Project creation failed: ${err}`);
+      setManagementMessage(`Project creation failed: ${errorMessage(err)}`);
     } finally {
       setIsManaging(false);
     }
@@ -287,7 +403,7 @@ function HardhatApp() {
       });
       notifications.show({
         title: 'Network Started',
-        message: result,
+        message: result.text,
         color: 'green',
       });
       setTimeout(async () => {
@@ -299,10 +415,10 @@ function HardhatApp() {
     } catch (err) {
       notifications.show({
         title: 'Network Start Failed',
-        message: err.toString(),
+        message: errorMessage(err),
         color: 'red',
       });
-      setManagementMessage(`Network start failed: ${err}`);
+      setManagementMessage(`Network start failed: ${errorMessage(err)}`);
     } finally {
       setIsManaging(false);
     }
@@ -360,7 +476,7 @@ function HardhatApp() {
       const result = await tauriApis.invoke('compile_contracts', { 
         projectPath: currentProjectPath 
       });
-      setCompilationStatus({ success: true, message: result });
+      setCompilationStatus({ success: true, message: result.text });
       notifications.show({
         title: 'Compilation Successful',
         message: 'All contracts compiled successfully',
@@ -379,10 +495,10 @@ function HardhatApp() {
       });
       
     } catch (err) {
-      setCompilationStatus({ success: false, message: err.toString() });
+      setCompilationStatus({ success: false, message: errorMessage(err) });
       notifications.show({
         title: 'Compilation Failed',
-        message: err.toString(),
+        message: errorMessage(err),
         color: 'red',
       });
     } finally {
@@ -401,14 +517,14 @@ function HardhatApp() {
       const result = await tauriApis.invoke('run_tests', { 
         projectPath: currentProjectPath 
       });
-      setTestResults({ success: true, message: result });
+      setTestResults({ success: true, message: result.text });
       notifications.show({
         title: 'Tests Completed',
         message: 'All tests passed successfully',
         color: 'green',
       });
     } catch (err) {
-      setTestResults({ success: false, message: err.toString() });
+      setTestResults({ success: false, message: errorMessage(err) });
       notifications.show({
         title: 'Tests Failed',
         message: 'Some tests failed',
@@ -430,17 +546,46 @@ function HardhatApp() {
       const result = await tauriApis.invoke('deploy_contracts', { 
         projectPath: currentProjectPath 
       });
-      setDeploymentStatus({ success: true, message: result });
+      setDeploymentStatus({ success: true, message: result.text });
       notifications.show({
         title: 'Deployment Successful',
         message: 'Contracts deployed successfully',
         color: 'green',
       });
     } catch (err) {
-      setDeploymentStatus({ success: false, message: err.toString() });
+      setDeploymentStatus({ success: false, message: errorMessage(err) });
       notifications.show({
         title: 'Deployment Failed',
-        message: err.toString(),
+        message: errorMessage(err),
+        color: 'red',
+      });
+    } finally {
+      setIsManaging(false);
+      setManagementMessage('');
+    }
+  };
+
+  // Mocks go to the local node and into the deployment registry
+  const handleDeployMock = async (preset) => {
+    if (!currentProjectPath || !isTauri || !tauriApis.invoke) return;
+
+    setIsManaging(true);
+    setManagementMessage(`Deploying mock ${preset.label}...`);
+
+    try {
+      const deployment = await tauriApis.invoke('deploy_mock', {
+        projectPath: currentProjectPath,
+        spec: preset.spec
+      });
+      notifications.show({
+        title: 'Mock Deployed',
+        message: `${deployment.label} at ${deployment.address}`,
+        color: 'green',
+      });
+    } catch (err) {
+      notifications.show({
+        title: 'Mock Deployment Failed',
+        message: errorMessage(err),
         color: 'red',
       });
     } finally {
@@ -458,6 +603,45 @@ function HardhatApp() {
     };
   }, [refreshInterval]);
 
+  // The backend pushes status changes (install, config, node up/down) and queue positions
+  useEffect(() => {
+    if (!isTauri) return;
+    let unlisten = [];
+    import('@tauri-apps/api/event').then(({ listen }) => Promise.all([
+      listen('status-changed', (event) => setHardhatStatus(event.payload)),
+      // Conflicting operations on the same project queue in the backend instead of racing
+      listen('job-waiting', ({ payload }) => setManagementMessage(
+        `Waiting for ${payload.blocked_by_kind} to finish (position ${payload.position} in queue)...`
+      )),
+      listen('execution-event', ({ payload }) => setExecutions((executions) => applyExecutionEvent(executions, payload))),
+      listen('onboarding-progress', ({ payload }) => setManagementMessage(
+        `Step ${payload.step}/${payload.steps}: ${payload.description}...`
+      )),
+      // Phases are scaffold, install and verify; steps running a command stream their output
+      listen('project-creation-progress', ({ payload }) => {
+        setManagementMessage(`[${payload.phase}] ${payload.message}...`);
+        if (payload.execution_id) setCreationExecutionId(payload.execution_id);
+      }),
+      // Periodic checks only announce releases they haven't reported before
+      listen('updates-available', ({ payload }) => {
+        const available = payload.updates.filter((update) => update.update_available);
+        notifications.show({
+          title: 'Updates Available',
+          message: available.map((update) => `${update.component} ${update.current ?? ''} → ${update.latest}`).join(', '),
+          color: 'blue',
+        });
+      }),
+    ])).then((stops) => { unlisten = stops; });
+    return () => unlisten.forEach((stop) => stop());
+  }, []);
+
+  useEffect(() => {
+    checkEnvironment();
+    if (tauriApis.invoke) {
+      tauriApis.invoke('list_mock_presets').then(setMockPresets).catch(() => setMockPresets([]));
+    }
+  }, [tauriApis.invoke]);
+
   // Load contracts when project changes
   useEffect(() => {
     if (currentProjectPath) {
@@ -465,6 +649,17 @@ function HardhatApp() {
     }
   }, [currentProjectPath, tauriApis.invoke]);
 
+  // Switching away from a project stops its background work (processes, syncs, schedules)
+  useEffect(() => {
+    if (!currentProjectPath || !tauriApis.invoke) return;
+    const invoke = tauriApis.invoke;
+    return () => {
+      invoke('close_project', { projectPath: currentProjectPath }).catch((err) =>
+        console.error('Error closing project:', errorMessage(err))
+      );
+    };
+  }, [currentProjectPath, tauriApis.invoke]);
+
   useEffect(() => {
     const initializeApp = async () => {
       if (isTauri) {
@@ -675,6 +870,38 @@ function HardhatApp() {
     return (
       <Card shadow="sm" padding="md" radius="md" withBorder mb="lg">
         <Title order={4} c="orange" mb="sm">🔨 Hardhat Management</Title>
+
+        {environment && !environment.ready && (
+          <Alert variant="light" color="yellow" title="Environment setup" mb="sm">
+            <Stack gap="xs">
+              {environment.checks.filter((check) => !check.ok).map((check) => (
+                <div key={check.tool}>
+                  <Text size="sm" mb={4}>{check.problem}</Text>
+                  <Group gap="xs">
+                    {check.install_options.map((option) => (
+                      <Button
+                        key={option.installer}
+                        size="xs"
+                        variant="light"
+                        disabled={isManaging}
+                        title={option.commands.join('\n')}
+                        onClick={() => handleInstallTool(check.tool, option.installer)}
+                      >
+                        Install with {option.installer}{option.requires_admin ? ' (admin)' : ''}
+                      </Button>
+                    ))}
+                    {check.install_options.length === 0 && (
+                      <Text size="xs" c="dimmed">No supported installer found; install {check.tool} manually</Text>
+                    )}
+                  </Group>
+                </div>
+              ))}
+              <Group>
+                <Button size="xs" variant="subtle" onClick={checkEnvironment} disabled={isManaging}>Check again</Button>
+              </Group>
+            </Stack>
+          </Alert>
+        )}
         
         <SimpleGrid cols={{ base: 1, sm: 3 }} spacing="xs" mb="sm">
           <Paper 
@@ -686,7 +913,9 @@ function HardhatApp() {
           >
             <Text size="xs" fw={600} tt="uppercase" c="dimmed" mb={4}>Installation</Text>
             <Text size="xs" ff="monospace">
-              {hardhatStatus?.installed ? `✅ Installed ${hardhatStatus.version || ''}` : '❌ Not Installed'}
+              {hardhatStatus?.installed
+                ? `✅ Installed ${hardhatStatus.version || ''}${hardhatStatus.local ? ' (project)' : ' (global)'}`
+                : '❌ Not Installed'}
             </Text>
           </Paper>
           
@@ -726,6 +955,12 @@ function HardhatApp() {
           </Notification>
         )}
 
+        {creationExecutionId && executions[creationExecutionId] && (
+          <Box mb="sm">
+            <ExecutionStream executions={{ [creationExecutionId]: executions[creationExecutionId] }} />
+          </Box>
+        )}
+
         <Group gap="xs" mb="sm">
           {!hardhatStatus?.installed && (
             <Button 
@@ -750,15 +985,30 @@ function HardhatApp() {
           </Button>
 
           {selectedProjectPath && !hardhatStatus?.project_detected && (
-            <Button 
-              onClick={handleCreateProject} 
-              disabled={isManaging}
-              color="cyan"
-              leftSection="🆕"
-              size="xs"
-            >
-              Create Hardhat Project
-            </Button>
+            <Group gap="xs">
+              <Select
+                size="xs"
+                value={projectTemplate}
+                onChange={(value) => value && setProjectTemplate(value)}
+                disabled={isManaging}
+                allowDeselect={false}
+                data={[
+                  { value: 'javascript', label: 'JavaScript' },
+                  { value: 'typescript', label: 'TypeScript' },
+                  { value: 'typescript_viem', label: 'TypeScript + viem' },
+                  { value: 'empty', label: 'Empty config' },
+                ]}
+              />
+              <Button 
+                onClick={handleCreateProject} 
+                disabled={isManaging}
+                color="cyan"
+                leftSection="🆕"
+                size="xs"
+              >
+                Create Hardhat Project
+              </Button>
+            </Group>
           )}
 
           {hardhatStatus?.project_detected && !hardhatStatus?.network_running && (
@@ -774,6 +1024,44 @@ function HardhatApp() {
           )}
         </Group>
 
+        {currentProjectPath && hardhatStatus?.network_running && mockPresets.length > 0 && (
+          <Group gap="xs" mb="sm">
+            <Text size="xs" fw={600}>Deploy mock:</Text>
+            {mockPresets.map((preset) => (
+              <Button
+                key={preset.label}
+                onClick={() => handleDeployMock(preset)}
+                disabled={isManaging}
+                variant="light"
+                color="grape"
+                size="xs"
+              >
+                {preset.label}
+              </Button>
+            ))}
+          </Group>
+        )}
+
+        {projectCandidates.length > 1 && (
+          <Select
+            size="xs"
+            mb="sm"
+            label={`${projectCandidates.length} Hardhat projects found`}
+            value={selectedProjectPath}
+            allowDeselect={false}
+            disabled={isManaging}
+            onChange={async (value) => {
+              if (!value) return;
+              setSelectedProjectPath(value);
+              await checkHardhatStatus(value);
+            }}
+            data={projectCandidates.map((candidate) => ({
+              value: candidate.path,
+              label: `${candidate.package_name ?? candidate.path} (${candidate.relation}, ${candidate.config_file})`,
+            }))}
+          />
+        )}
+
         {selectedProjectPath && (
           <Paper p="sm" withBorder>
             <Text fw={600} mb={4} size="xs">Selected Path:</Text>
@@ -951,6 +1239,8 @@ function HardhatApp() {
             </Code>
           </Card>
         )}
+
+        <ExecutionStream executions={executions} />
       </Stack>
     );
   };
@@ -1017,9 +1307,16 @@ function HardhatApp() {
           projectPath: currentProjectPath,
           command: command
         });
-        setConsoleOutput(prev => [...prev, { type: 'output', content: result }]);
+        const logged = result.output.join('\n');
+        if (result.success) {
+          const content = [logged, result.result].filter(Boolean).join('\n');
+          setConsoleOutput(prev => [...prev, { type: 'output', content: content || 'undefined' }]);
+        } else {
+          const content = [logged, `Error: ${result.error}`].filter(Boolean).join('\n');
+          setConsoleOutput(prev => [...prev, { type: 'error', content }]);
+        }
       } catch (err) {
-        setConsoleOutput(prev => [...prev, { type: 'error', content: err.toString() }]);
+        setConsoleOutput(prev => [...prev, { type: 'error', content: errorMessage(err) }]);
       }
     };
 
@@ -1187,13 +1484,13 @@ function HardhatApp() {
       });
       notifications.show({
         title: `${taskName} Task Completed`,
-        message: result.substring(0, 100) + (result.length > 100 ? '...' : ''),
+        message: result.text.substring(0, 100) + (result.text.length > 100 ? '...' : ''),
         color: 'green',
       });
     } catch (err) {
       notifications.show({
         title: `${taskName} Task Failed`,
-        message: err.toString(),
+        message: errorMessage(err),
         color: 'red',
       });
     }
@@ -1251,7 +1548,7 @@ function HardhatApp() {
     } catch (err) {
       notifications.show({
         title: 'Verification Failed',
-        message: err.toString(),
+        message: errorMessage(err),
         color: 'red',
       });
     } finally {